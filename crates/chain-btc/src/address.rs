@@ -1,9 +1,129 @@
+use std::fmt;
+
 use bitcoin::address::Address;
-use bitcoin::CompressedPublicKey;
+use bitcoin::{CompressedPublicKey, ScriptBuf};
 
 use crate::error::BtcError;
 use crate::network::BtcNetwork;
 
+/// Output type encoded by a Bitcoin address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddressType {
+    /// Legacy pay-to-pubkey-hash (`1...` / `m...`, `n...`).
+    P2pkh,
+    /// Pay-to-script-hash (`3...` / `2...`).
+    P2sh,
+    /// Native SegWit v0 key hash (`bc1q...`, 42 chars on mainnet).
+    P2wpkh,
+    /// Native SegWit v0 script hash (`bc1q...`, 62 chars on mainnet).
+    P2wsh,
+    /// Taproot, SegWit v1 (`bc1p...`).
+    P2tr,
+}
+
+impl fmt::Display for AddressType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            AddressType::P2pkh => "P2PKH",
+            AddressType::P2sh => "P2SH",
+            AddressType::P2wpkh => "P2WPKH",
+            AddressType::P2wsh => "P2WSH",
+            AddressType::P2tr => "P2TR",
+        })
+    }
+}
+
+/// Detect which network an address belongs to and what kind of output it pays to.
+///
+/// Testnet and signet share address prefixes, so any test-network address is
+/// reported as [`BtcNetwork::Testnet`]. Regtest shares the base58 prefixes too,
+/// so legacy regtest addresses are indistinguishable from testnet ones and also
+/// come back as `Testnet`; only bech32 regtest addresses (`bcrt1...`) can be
+/// told apart, and those are rejected, as are output types without a standard
+/// name (e.g. future witness versions).
+pub fn classify_address(address: &str) -> Result<(BtcNetwork, AddressType), BtcError> {
+    let parsed = address
+        .parse::<Address<bitcoin::address::NetworkUnchecked>>()
+        .map_err(|e| BtcError::InvalidAddress(format!("failed to parse address: {e}")))?;
+
+    let network = [BtcNetwork::Mainnet, BtcNetwork::Testnet]
+        .into_iter()
+        .find(|n| parsed.is_valid_for_network(n.to_bitcoin_network()))
+        .ok_or_else(|| {
+            BtcError::InvalidNetwork(format!("{address} is not a mainnet or testnet address"))
+        })?;
+
+    let address_type = match parsed.assume_checked().address_type() {
+        Some(bitcoin::AddressType::P2pkh) => AddressType::P2pkh,
+        Some(bitcoin::AddressType::P2sh) => AddressType::P2sh,
+        Some(bitcoin::AddressType::P2wpkh) => AddressType::P2wpkh,
+        Some(bitcoin::AddressType::P2wsh) => AddressType::P2wsh,
+        Some(bitcoin::AddressType::P2tr) => AddressType::P2tr,
+        _ => {
+            return Err(BtcError::InvalidAddress(format!(
+                "{address} is not a standard address type"
+            )))
+        }
+    };
+
+    Ok((network, address_type))
+}
+
+/// A Bitcoin address that has passed [`validate_address`] for a network.
+///
+/// Transaction builders take this instead of `&str`, so a string meant for
+/// another chain or network is rejected where it enters the wallet rather
+/// than while outputs are being assembled.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BtcAddress {
+    text: String,
+    address: Address,
+    network: BtcNetwork,
+}
+
+impl BtcAddress {
+    /// Parse an address and require it to belong to `network`.
+    pub fn parse(address: &str, network: BtcNetwork) -> Result<Self, BtcError> {
+        if !validate_address(address, network)? {
+            return Err(BtcError::InvalidAddress(format!(
+                "address {address} is not valid for {network}"
+            )));
+        }
+
+        let checked = address
+            .parse::<Address<bitcoin::address::NetworkUnchecked>>()
+            .map_err(|e| BtcError::InvalidAddress(format!("failed to parse address: {e}")))?
+            .assume_checked();
+
+        Ok(Self {
+            text: address.to_string(),
+            address: checked,
+            network,
+        })
+    }
+
+    /// The address as it was parsed.
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    /// The network the address was validated for.
+    pub fn network(&self) -> BtcNetwork {
+        self.network
+    }
+
+    /// The output script paying to this address.
+    pub fn script_pubkey(&self) -> ScriptBuf {
+        self.address.script_pubkey()
+    }
+}
+
+impl fmt::Display for BtcAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
 /// Derive a P2WPKH (native SegWit bech32) address from a compressed public key.
 ///
 /// Takes a 33-byte compressed secp256k1 public key and returns a bech32 address
@@ -135,4 +255,85 @@ mod tests {
         .unwrap();
         assert!(valid);
     }
+
+    #[test]
+    fn btc_address_parses_for_matching_network() {
+        let addr = BtcAddress::parse(
+            "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4",
+            BtcNetwork::Mainnet,
+        )
+        .unwrap();
+        assert_eq!(addr.as_str(), "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4");
+        assert_eq!(addr.to_string(), addr.as_str());
+        assert_eq!(addr.network(), BtcNetwork::Mainnet);
+        assert_eq!(
+            hex::encode(addr.script_pubkey().as_bytes()),
+            "0014751e76e8199196d454941c45d1b3a323f1433bd6"
+        );
+    }
+
+    #[test]
+    fn btc_address_rejects_wrong_network_and_other_chains() {
+        assert!(BtcAddress::parse(
+            "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4",
+            BtcNetwork::Testnet
+        )
+        .is_err());
+        assert!(BtcAddress::parse(
+            "0x000000000000000000000000000000000000dEaD",
+            BtcNetwork::Mainnet
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn classify_mainnet_address_types() {
+        let cases = [
+            ("1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa", AddressType::P2pkh),
+            ("3J98t1WpEZ73CNmQviecrnyiWrnqRhWNLy", AddressType::P2sh),
+            (
+                "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4",
+                AddressType::P2wpkh,
+            ),
+            (
+                "bc1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3qccfmv3",
+                AddressType::P2wsh,
+            ),
+            (
+                "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqzk5jj0",
+                AddressType::P2tr,
+            ),
+        ];
+        for (address, expected) in cases {
+            assert_eq!(
+                classify_address(address).unwrap(),
+                (BtcNetwork::Mainnet, expected),
+                "{address}"
+            );
+        }
+    }
+
+    #[test]
+    fn classify_testnet_address_types() {
+        assert_eq!(
+            classify_address("mipcBbFg9gMiCh81Kj8tqqdgoZub1ZJRfn").unwrap(),
+            (BtcNetwork::Testnet, AddressType::P2pkh)
+        );
+        assert_eq!(
+            classify_address("tb1qw508d6qejxtdg4y5r3zarvary0c5xw7kxpjzsx").unwrap(),
+            (BtcNetwork::Testnet, AddressType::P2wpkh)
+        );
+    }
+
+    #[test]
+    fn classify_rejects_garbage_and_bech32_regtest() {
+        assert!(classify_address("notanaddress!!!").is_err());
+        assert!(classify_address("bcrt1qw508d6qejxtdg4y5r3zarvary0c5xw7kygt080").is_err());
+    }
+
+    #[test]
+    fn address_type_display() {
+        assert_eq!(AddressType::P2tr.to_string(), "P2TR");
+        assert_eq!(AddressType::P2wpkh.to_string(), "P2WPKH");
+    }
 }
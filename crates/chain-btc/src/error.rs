@@ -20,6 +20,9 @@ pub enum BtcError {
 
     #[error("invalid network: {0}")]
     InvalidNetwork(String),
+
+    #[error("insufficient funds: need {needed} sat, have {available}")]
+    InsufficientFunds { needed: u64, available: u64 },
 }
 
 #[cfg(test)]
@@ -65,6 +68,18 @@ mod tests {
         assert_eq!(err.to_string(), "invalid network: regtest not supported");
     }
 
+    #[test]
+    fn display_insufficient_funds() {
+        let err = BtcError::InsufficientFunds {
+            needed: 50_000,
+            available: 1_000,
+        };
+        assert_eq!(
+            err.to_string(),
+            "insufficient funds: need 50000 sat, have 1000"
+        );
+    }
+
     #[test]
     fn error_trait_is_implemented() {
         let err: Box<dyn std::error::Error> =
@@ -6,5 +6,7 @@
 pub mod address;
 pub mod error;
 pub mod network;
+pub mod schnorr;
+pub mod taproot;
 pub mod transaction;
 pub mod utxo;
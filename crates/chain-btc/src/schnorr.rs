@@ -0,0 +1,148 @@
+//! BIP-340 Schnorr signatures over 32-byte messages.
+//!
+//! Thin wrappers over libsecp256k1 for Taproot key-path spends and other
+//! protocols that sign raw 32-byte digests. Public keys are 32-byte x-only
+//! keys; signatures are the 64-byte `r || s` encoding.
+
+use bitcoin::key::{Keypair, XOnlyPublicKey};
+use bitcoin::secp256k1::{schnorr, Message, Secp256k1};
+
+use crate::error::BtcError;
+
+/// Sign `msg32` with BIP-340 Schnorr.
+///
+/// `aux_rand` is the auxiliary randomness of BIP-340; `None` is the same as
+/// 32 zero bytes, which is deterministic but still safe. Callers with a
+/// source of randomness should pass fresh bytes as BIP-340 recommends.
+pub fn sign(
+    msg32: &[u8; 32],
+    private_key: &[u8; 32],
+    aux_rand: Option<[u8; 32]>,
+) -> Result<[u8; 64], BtcError> {
+    let secp = Secp256k1::signing_only();
+    let keypair = Keypair::from_seckey_slice(&secp, private_key)
+        .map_err(|e| BtcError::InvalidPrivateKey(e.to_string()))?;
+    let msg = Message::from_digest(*msg32);
+
+    let sig = secp.sign_schnorr_with_aux_rand(&msg, &keypair, &aux_rand.unwrap_or([0u8; 32]));
+    Ok(sig.serialize())
+}
+
+/// Verify a BIP-340 signature over `msg32` against an x-only public key.
+///
+/// Returns `false` for a bad signature as well as for a malformed key or
+/// signature encoding.
+pub fn verify(msg32: &[u8; 32], xonly_pubkey: &[u8; 32], sig: &[u8; 64]) -> bool {
+    let (Ok(pubkey), Ok(sig)) = (
+        XOnlyPublicKey::from_slice(xonly_pubkey),
+        schnorr::Signature::from_slice(sig),
+    ) else {
+        return false;
+    };
+
+    Secp256k1::verification_only()
+        .verify_schnorr(&sig, &Message::from_digest(*msg32), &pubkey)
+        .is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn h32(hex_str: &str) -> [u8; 32] {
+        hex::decode(hex_str).unwrap().try_into().unwrap()
+    }
+
+    fn h64(hex_str: &str) -> [u8; 64] {
+        hex::decode(hex_str).unwrap().try_into().unwrap()
+    }
+
+    /// BIP-340 signing vector: (seckey, pubkey, aux_rand, msg, sig).
+    fn check_vector(seckey: &str, pubkey: &str, aux: &str, msg: &str, sig: &str) {
+        let (msg, sig) = (h32(msg), h64(sig));
+
+        assert_eq!(sign(&msg, &h32(seckey), Some(h32(aux))).unwrap(), sig);
+        assert!(verify(&msg, &h32(pubkey), &sig));
+    }
+
+    // BIP-340 test-vectors.csv, indices 0-3.
+
+    #[test]
+    fn bip340_vector_0() {
+        check_vector(
+            "0000000000000000000000000000000000000000000000000000000000000003",
+            "f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9",
+            "0000000000000000000000000000000000000000000000000000000000000000",
+            "0000000000000000000000000000000000000000000000000000000000000000",
+            "e907831f80848d1069a5371b402410364bdf1c5f8307b0084c55f1ce2dca8215\
+             25f66a4a85ea8b71e482a74f382d2ce5ebeee8fdb2172f477df4900d310536c0",
+        );
+    }
+
+    #[test]
+    fn bip340_vector_1() {
+        check_vector(
+            "b7e151628aed2a6abf7158809cf4f3c762e7160f38b4da56a784d9045190cfef",
+            "dff1d77f2a671c5f36183726db2341be58feae1da2deced843240f7b502ba659",
+            "0000000000000000000000000000000000000000000000000000000000000001",
+            "243f6a8885a308d313198a2e03707344a4093822299f31d0082efa98ec4e6c89",
+            "6896bd60eeae296db48a229ff71dfe071bde413e6d43f917dc8dcf8c78de3341\
+             8906d11ac976abccb20b091292bff4ea897efcb639ea871cfa95f6de339e4b0a",
+        );
+    }
+
+    #[test]
+    fn bip340_vector_2() {
+        check_vector(
+            "c90fdaa22168c234c4c6628b80dc1cd129024e088a67cc74020bbea63b14e5c9",
+            "dd308afec5777e13121fa72b9cc1b7cc0139715309b086c960e18fd969774eb8",
+            "c87aa53824b4d7ae2eb035a2b5bbbccc080e76cdc6d1692c4b0b62d798e6d906",
+            "7e2d58d8b3bcdf1abadec7829054f90dda9805aab56c77333024b9d0a508b75c",
+            "5831aaeed7b44bb74e5eab94ba9d4294c49bcf2a60728d8b4c200f50dd313c1b\
+             ab745879a5ad954a72c45a91c3a51d3c7adea98d82f8481e0e1e03674a6f3fb7",
+        );
+    }
+
+    #[test]
+    fn bip340_vector_3() {
+        check_vector(
+            "0b432b2677937381aef05bb02a66ecd012773062cf3fa2549e44f58ed2401710",
+            "25d1dff95105f5253c4022f628a996ad3a0d95fbf21d468a1b33f8c160d8f517",
+            "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+            "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+            "7eb0509757e246f19449885651611cb965ecc1a187dd51b64fda1edc9637d5ec\
+             97582b9cb13db3933705b32ba982af5af25fd78881ebb32771fc5922efc66ea3",
+        );
+    }
+
+    #[test]
+    fn no_aux_rand_matches_zero_aux() {
+        let key = h32("0000000000000000000000000000000000000000000000000000000000000003");
+        let msg = [0x42u8; 32];
+        assert_eq!(
+            sign(&msg, &key, None).unwrap(),
+            sign(&msg, &key, Some([0u8; 32])).unwrap()
+        );
+    }
+
+    #[test]
+    fn verify_rejects_tampering_and_bad_key() {
+        let key = h32("0000000000000000000000000000000000000000000000000000000000000003");
+        let pubkey = h32("f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9");
+        let msg = [0x42u8; 32];
+        let sig = sign(&msg, &key, None).unwrap();
+
+        let mut other_msg = msg;
+        other_msg[0] ^= 1;
+        assert!(!verify(&other_msg, &pubkey, &sig));
+
+        // BIP-340 vector 5: public key not on the curve.
+        let off_curve = h32("eefdea4cdb677750a420fee807eacf21eb9898ae79b9768766e4faa04a2d4a34");
+        assert!(!verify(&msg, &off_curve, &sig));
+    }
+
+    #[test]
+    fn sign_rejects_invalid_private_key() {
+        assert!(sign(&[0u8; 32], &[0u8; 32], None).is_err());
+    }
+}
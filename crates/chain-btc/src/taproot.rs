@@ -0,0 +1,140 @@
+//! BIP-341 Taproot hashing primitives.
+//!
+//! Pure functions for building and committing to Taproot script trees: the
+//! tagged leaf/branch/tweak hashes and the output key tweak. They are the
+//! groundwork for script-path spends (e.g. inscriptions); nothing here signs.
+//!
+//! Keys are 32-byte x-only public keys and hashes are in their natural
+//! (non-reversed) byte order, matching the BIP-341 test vectors.
+
+use bitcoin::consensus::encode::serialize;
+use bitcoin::hashes::{Hash, HashEngine};
+use bitcoin::key::{TapTweak, XOnlyPublicKey};
+use bitcoin::secp256k1::Secp256k1;
+use bitcoin::taproot::{TapLeafHash, TapNodeHash, TapTweakHash};
+use bitcoin::ScriptBuf;
+
+use crate::error::BtcError;
+
+/// Leaf version for Tapscript (BIP-342).
+pub const TAPSCRIPT_LEAF_VERSION: u8 = 0xC0;
+
+/// `TapLeaf` tagged hash: `H_TapLeaf(version || compact_size(len) || script)`.
+///
+/// `version` is used as given; BIP-341 requires its low bit to be clear, and
+/// [`TAPSCRIPT_LEAF_VERSION`] is the only version defined today.
+pub fn tap_leaf_hash(version: u8, script: &[u8]) -> [u8; 32] {
+    let mut engine = TapLeafHash::engine();
+    engine.input(&[version]);
+    engine.input(&serialize(&ScriptBuf::from_bytes(script.to_vec())));
+    TapLeafHash::from_engine(engine).to_byte_array()
+}
+
+/// `TapBranch` tagged hash of two child nodes.
+///
+/// The children are sorted lexicographically before hashing, so the result
+/// does not depend on argument order.
+pub fn tap_branch_hash(a: [u8; 32], b: [u8; 32]) -> [u8; 32] {
+    TapNodeHash::from_node_hashes(
+        TapNodeHash::from_byte_array(a),
+        TapNodeHash::from_byte_array(b),
+    )
+    .to_byte_array()
+}
+
+/// `TapTweak` tagged hash: `H_TapTweak(internal_key || merkle_root)`.
+///
+/// For a key-path-only output (`merkle_root` is `None`) only the internal
+/// key is hashed.
+pub fn tap_tweak_hash(internal_key: [u8; 32], merkle_root: Option<[u8; 32]>) -> [u8; 32] {
+    let mut engine = TapTweakHash::engine();
+    engine.input(&internal_key);
+    if let Some(root) = merkle_root {
+        engine.input(&root);
+    }
+    TapTweakHash::from_engine(engine).to_byte_array()
+}
+
+/// Tweak an internal x-only key into the Taproot output key (BIP-341):
+/// `Q = P + tap_tweak_hash(P, merkle_root) * G`.
+///
+/// Returns the x-only output key, i.e. the witness program of the P2TR
+/// output. Fails if `internal_key` is not a valid x-only point.
+pub fn tweak_pubkey(
+    internal_key: [u8; 32],
+    merkle_root: Option<[u8; 32]>,
+) -> Result<[u8; 32], BtcError> {
+    let internal = XOnlyPublicKey::from_slice(&internal_key)
+        .map_err(|e| BtcError::InvalidPublicKey(format!("invalid x-only key: {e}")))?;
+
+    let secp = Secp256k1::verification_only();
+    let (output_key, _parity) =
+        internal.tap_tweak(&secp, merkle_root.map(TapNodeHash::from_byte_array));
+
+    Ok(output_key.to_x_only_public_key().serialize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn h32(hex_str: &str) -> [u8; 32] {
+        hex::decode(hex_str).unwrap().try_into().unwrap()
+    }
+
+    // BIP-341 wallet test vectors, `scriptPubKey` section.
+
+    #[test]
+    fn bip341_key_path_only() {
+        let internal = h32("d6889cb081036e0faefa3a35157ad71086b123b2b144b649798b494c300a961d");
+
+        assert_eq!(
+            tap_tweak_hash(internal, None),
+            h32("b86e7be8f39bab32a6f2c0443abbc210f0edac0e2c53d501b36b64437d9c6c70")
+        );
+        assert_eq!(
+            tweak_pubkey(internal, None).unwrap(),
+            h32("53a1f6e454df1aa2776a2814a721372d6258050de330b3c6d10ee8f4e0dda343")
+        );
+    }
+
+    #[test]
+    fn bip341_single_leaf() {
+        let internal = h32("187791b6f712a8ea41c8ecdd0ee77fab3e85263b37e1ec18a3651926b3a6cf27");
+        let script =
+            hex::decode("20d85a959b0290bf19bb89ed43c916be835475d013da4b362117393e25a48229b8ac")
+                .unwrap();
+
+        let leaf = tap_leaf_hash(TAPSCRIPT_LEAF_VERSION, &script);
+        assert_eq!(
+            leaf,
+            h32("5b75adecf53548f3ec6ad7d78383bf84cc57b55a3127c72b9a2481752dd88b21")
+        );
+
+        // A single leaf is its own merkle root.
+        assert_eq!(
+            tap_tweak_hash(internal, Some(leaf)),
+            h32("cbd8679ba636c1110ea247542cfbd964131a6be84f873f7f3b62a777528ed001")
+        );
+        assert_eq!(
+            tweak_pubkey(internal, Some(leaf)).unwrap(),
+            h32("147c9c57132f6e7ecddba9800bb0c4449251c92a1e60371ee77557b6620f3ea3")
+        );
+    }
+
+    #[test]
+    fn tap_branch_hash_is_order_independent() {
+        let a = tap_leaf_hash(TAPSCRIPT_LEAF_VERSION, &[0x51]);
+        let b = tap_leaf_hash(TAPSCRIPT_LEAF_VERSION, &[0x52]);
+
+        assert_eq!(tap_branch_hash(a, b), tap_branch_hash(b, a));
+        assert_ne!(tap_branch_hash(a, b), a);
+    }
+
+    #[test]
+    fn tweak_pubkey_rejects_invalid_point() {
+        // x = p (field prime) is not a valid x coordinate.
+        let invalid = h32("fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f");
+        assert!(tweak_pubkey(invalid, None).is_err());
+    }
+}
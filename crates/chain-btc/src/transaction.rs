@@ -1,21 +1,30 @@
 use bitcoin::absolute::LockTime;
 use bitcoin::address::Address;
 use bitcoin::hashes::Hash;
-use bitcoin::script::ScriptBuf;
+use bitcoin::script::{Builder, PushBytesBuf, ScriptBuf};
 use bitcoin::secp256k1::{Message, Secp256k1, SecretKey};
 use bitcoin::sighash::{EcdsaSighashType, SighashCache};
 use bitcoin::transaction::Version;
 use bitcoin::{
     Amount, CompressedPublicKey, OutPoint, Sequence, Transaction, TxIn, TxOut, Txid, Witness,
 };
+use serde::{Deserialize, Serialize};
 
+use crate::address::BtcAddress;
 use crate::error::BtcError;
 use crate::network::BtcNetwork;
-use crate::utxo::Utxo;
+use crate::utxo::{Utxo, UtxoSelection};
+
+pub use crypto_utils::signer::InputSigner;
 
 /// Estimated virtual size of a P2WPKH input (in vbytes).
 /// Breakdown: 41 bytes non-witness + ~27 witness bytes / 4 = ~68 vbytes per input.
-const P2WPKH_INPUT_VBYTES: u64 = 68;
+pub(crate) const P2WPKH_INPUT_VBYTES: u64 = 68;
+
+/// Estimated virtual size of a P2PKH input (in vbytes).
+/// Breakdown: 41 bytes outpoint/sequence/length + ~107 byte scriptSig (signature
+/// and pubkey), none of it witness-discounted = ~148 vbytes per input.
+pub(crate) const P2PKH_INPUT_VBYTES: u64 = 148;
 
 /// Estimated virtual size of any output (in vbytes).
 const OUTPUT_VBYTES: u64 = 31;
@@ -23,14 +32,125 @@ const OUTPUT_VBYTES: u64 = 31;
 /// Fixed transaction overhead (in vbytes): version + locktime + segwit marker/flag + counts.
 const TX_OVERHEAD_VBYTES: u64 = 11;
 
+/// Fee rate (sat/vbyte) a replacement must add over the original for its own
+/// size (BIP-125 rule 4). Bitcoin Core's long-standing `-incrementalrelayfee`
+/// default; nodes configured lower still accept it.
+pub const INCREMENTAL_RELAY_FEE_SAT_VBYTE: u64 = 1;
+
+/// Change at or below this value (in sats) is not worth an output and goes to the fee.
+pub const DUST_THRESHOLD_SAT: u64 = 546;
+
+/// Relay-policy dust limit of a P2WPKH output. A caller-supplied dust
+/// threshold is never allowed below it, so change is never made dust.
+pub const P2WPKH_DUST_THRESHOLD_SAT: u64 = 294;
+
 /// An unsigned Bitcoin transaction ready for signing.
-#[derive(Debug, Clone)]
+///
+/// Serializes to JSON with the transaction as consensus-encoded hex and each
+/// prevout's value and scriptPubKey (see [`UnsignedBtcTx::to_json`]).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(into = "UnsignedBtcTxJson", try_from = "UnsignedBtcTxJson")]
 pub struct UnsignedBtcTx {
     /// The bitcoin transaction with empty witnesses.
     pub tx: Transaction,
     /// The UTXOs being spent (in the same order as the transaction inputs).
     /// Needed for computing sighashes during signing.
     pub prevouts: Vec<TxOut>,
+    /// Address the change output pays to, or `None` if the change was below
+    /// the dust threshold and no change output was added.
+    pub change_address: Option<String>,
+}
+
+impl UnsignedBtcTx {
+    /// Serialize the transaction to JSON for offline signing.
+    pub fn to_json(&self) -> Result<String, BtcError> {
+        serde_json::to_string(self).map_err(|e| BtcError::TransactionBuildError(e.to_string()))
+    }
+
+    /// Parse a transaction written by [`UnsignedBtcTx::to_json`].
+    ///
+    /// Fails unless there is exactly one prevout per input.
+    pub fn from_json(json: &str) -> Result<Self, BtcError> {
+        serde_json::from_str(json)
+            .map_err(|e| BtcError::TransactionBuildError(format!("invalid transaction JSON: {e}")))
+    }
+}
+
+/// JSON form of [`UnsignedBtcTx`].
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UnsignedBtcTxJson {
+    /// Consensus-encoded transaction, hex.
+    tx_hex: String,
+    prevouts: Vec<PrevoutJson>,
+    change_address: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PrevoutJson {
+    value_sat: u64,
+    /// scriptPubKey, hex.
+    #[serde(rename = "scriptPubKey")]
+    script_pubkey: String,
+}
+
+impl From<UnsignedBtcTx> for UnsignedBtcTxJson {
+    fn from(unsigned: UnsignedBtcTx) -> Self {
+        Self {
+            tx_hex: bitcoin::consensus::encode::serialize_hex(&unsigned.tx),
+            prevouts: unsigned
+                .prevouts
+                .iter()
+                .map(|prevout| PrevoutJson {
+                    value_sat: prevout.value.to_sat(),
+                    script_pubkey: hex::encode(prevout.script_pubkey.as_bytes()),
+                })
+                .collect(),
+            change_address: unsigned.change_address,
+        }
+    }
+}
+
+impl TryFrom<UnsignedBtcTxJson> for UnsignedBtcTx {
+    type Error = BtcError;
+
+    fn try_from(json: UnsignedBtcTxJson) -> Result<Self, BtcError> {
+        let invalid = |what: &str, e: &dyn std::fmt::Display| {
+            BtcError::TransactionBuildError(format!("invalid {what}: {e}"))
+        };
+
+        let raw_tx = hex::decode(&json.tx_hex).map_err(|e| invalid("transaction hex", &e))?;
+        let tx: Transaction =
+            bitcoin::consensus::deserialize(&raw_tx).map_err(|e| invalid("transaction", &e))?;
+
+        let prevouts = json
+            .prevouts
+            .into_iter()
+            .map(|prevout| {
+                let script = hex::decode(&prevout.script_pubkey)
+                    .map_err(|e| invalid("prevout scriptPubKey", &e))?;
+                Ok(TxOut {
+                    value: Amount::from_sat(prevout.value_sat),
+                    script_pubkey: ScriptBuf::from_bytes(script),
+                })
+            })
+            .collect::<Result<Vec<_>, BtcError>>()?;
+
+        if prevouts.len() != tx.input.len() {
+            return Err(BtcError::TransactionBuildError(format!(
+                "{} prevouts for {} inputs",
+                prevouts.len(),
+                tx.input.len()
+            )));
+        }
+
+        Ok(Self {
+            tx,
+            prevouts,
+            change_address: json.change_address,
+        })
+    }
 }
 
 /// Estimate the fee for a P2WPKH transaction.
@@ -43,43 +163,323 @@ pub fn estimate_fee(num_inputs: usize, num_outputs: usize, fee_rate_sat_vbyte: u
     vsize * fee_rate_sat_vbyte
 }
 
+/// Estimate the fee for a transaction spending outputs locked by `input_scripts`.
+///
+/// Like [`estimate_fee`], but sizes each input by its scriptPubKey: P2PKH
+/// inputs at ~148 vbytes, everything else as P2WPKH.
+pub fn estimate_fee_for_scripts<'a>(
+    input_scripts: impl IntoIterator<Item = &'a [u8]>,
+    num_outputs: usize,
+    fee_rate_sat_vbyte: u64,
+) -> u64 {
+    estimate_vsize(input_scripts, num_outputs) * fee_rate_sat_vbyte
+}
+
+/// Estimated vsize of a transaction spending outputs locked by `input_scripts`.
+fn estimate_vsize<'a>(
+    input_scripts: impl IntoIterator<Item = &'a [u8]>,
+    num_outputs: usize,
+) -> u64 {
+    let inputs_vsize: u64 = input_scripts.into_iter().map(input_vbytes).sum();
+    TX_OVERHEAD_VBYTES + inputs_vsize + num_outputs as u64 * OUTPUT_VBYTES
+}
+
+/// Estimated vsize of an input spending an output locked by `script_pubkey`.
+pub(crate) fn input_vbytes(script_pubkey: &[u8]) -> u64 {
+    if bitcoin::Script::from_bytes(script_pubkey).is_p2pkh() {
+        P2PKH_INPUT_VBYTES
+    } else {
+        P2WPKH_INPUT_VBYTES
+    }
+}
+
+/// scriptPubKeys of `utxos`, for [`estimate_fee_for_scripts`].
+pub(crate) fn utxo_scripts(utxos: &[Utxo]) -> impl Iterator<Item = &[u8]> {
+    utxos.iter().map(|utxo| utxo.script_pubkey.as_slice())
+}
+
+/// Inputs, fee and change of a P2WPKH spend, before any outputs are built.
+#[derive(Debug, Clone)]
+pub struct SpendPlan {
+    /// UTXOs the spend would consume.
+    pub selection: UtxoSelection,
+    /// Total fee, including any dust change left to the miner.
+    pub fee_sat: u64,
+    /// Value of the change output, or 0 if the change is dust and dropped.
+    pub change_sat: u64,
+}
+
+/// Work out which UTXOs a P2WPKH spend uses and what it pays in fee and
+/// change, exactly as [`build_p2wpkh_transaction`] would, without needing
+/// any addresses. Used for fee quotes before the user confirms.
+pub fn plan_p2wpkh_spend(
+    utxos: &[Utxo],
+    amount_sat: u64,
+    fee_rate_sat_vbyte: u64,
+    dust_threshold: Option<u64>,
+) -> Result<SpendPlan, BtcError> {
+    let selection = crate::utxo::select_utxos(utxos, amount_sat, fee_rate_sat_vbyte, &[])?;
+
+    // Change is sized assuming two outputs; when it is dropped as dust the
+    // whole remainder (the two-output fee plus the change) goes to the miner.
+    let fee_2_outputs =
+        estimate_fee_for_scripts(utxo_scripts(&selection.selected), 2, fee_rate_sat_vbyte);
+    let change_sat = selection
+        .total_sat
+        .saturating_sub(amount_sat + fee_2_outputs);
+
+    let (fee_sat, change_sat) = if change_sat > effective_dust_threshold(dust_threshold) {
+        (fee_2_outputs, change_sat)
+    } else {
+        (fee_2_outputs + change_sat, 0)
+    };
+
+    Ok(SpendPlan {
+        selection,
+        fee_sat,
+        change_sat,
+    })
+}
+
+/// `dust_threshold`, defaulting to [`DUST_THRESHOLD_SAT`] and never below
+/// [`P2WPKH_DUST_THRESHOLD_SAT`].
+fn effective_dust_threshold(dust_threshold: Option<u64>) -> u64 {
+    dust_threshold
+        .unwrap_or(DUST_THRESHOLD_SAT)
+        .max(P2WPKH_DUST_THRESHOLD_SAT)
+}
+
 /// Build an unsigned P2WPKH Bitcoin transaction.
 ///
 /// Selects UTXOs, constructs inputs/outputs, and returns an `UnsignedBtcTx`
-/// ready for signing. A change output is added if the change exceeds the dust
-/// threshold (546 sats).
+/// ready for signing. A change output is added if the change exceeds
+/// `dust_threshold`, which defaults to [`DUST_THRESHOLD_SAT`] (546 sats);
+/// anything at or below it is left to the fee. Relay policy puts P2WPKH dust
+/// as low as 294 sats, so callers may pass a lower value to keep small change;
+/// values below [`P2WPKH_DUST_THRESHOLD_SAT`] are raised to it.
+///
+/// `lock_time_height`, usually the current tip height, sets `nLockTime` for
+/// anti-fee-sniping, as Bitcoin Core does. The inputs' sequence
+/// (`0xFFFFFFFD`) is below `0xFFFFFFFF`, so the locktime is enforced and
+/// RBF stays signalled for [`bump_fee`].
+///
+/// A zero `amount_sat` is rejected unless `allow_zero_amount` is set.
+#[allow(clippy::too_many_arguments)]
 pub fn build_p2wpkh_transaction(
     utxos: &[Utxo],
-    recipient: &str,
+    recipient: &BtcAddress,
     amount_sat: u64,
-    change_address: &str,
+    change_address: &BtcAddress,
     fee_rate_sat_vbyte: u64,
     network: BtcNetwork,
+    dust_threshold: Option<u64>,
+    lock_time_height: Option<u32>,
+    allow_zero_amount: bool,
 ) -> Result<UnsignedBtcTx, BtcError> {
-    let net = network.to_bitcoin_network();
+    if amount_sat == 0 && !allow_zero_amount {
+        return Err(BtcError::TransactionBuildError(
+            "amount must be greater than zero".into(),
+        ));
+    }
+    require_network(recipient, "recipient", network)?;
+    require_network(change_address, "change", network)?;
+
+    let lock_time = match lock_time_height {
+        Some(height) => LockTime::from_height(height).map_err(|e| {
+            BtcError::TransactionBuildError(format!("invalid lock time height: {e}"))
+        })?,
+        None => LockTime::ZERO,
+    };
 
-    // Parse and validate the recipient address.
-    let recipient_addr: Address = recipient
-        .parse::<Address<bitcoin::address::NetworkUnchecked>>()
-        .map_err(|e| BtcError::InvalidAddress(format!("invalid recipient address: {e}")))?
-        .require_network(net)
-        .map_err(|e| BtcError::InvalidAddress(format!("recipient address wrong network: {e}")))?;
+    let plan = plan_p2wpkh_spend(utxos, amount_sat, fee_rate_sat_vbyte, dust_threshold)?;
+    let (inputs, prevouts) = build_inputs(&plan.selection.selected)?;
 
-    // Parse and validate the change address.
-    let change_addr: Address = change_address
-        .parse::<Address<bitcoin::address::NetworkUnchecked>>()
-        .map_err(|e| BtcError::InvalidAddress(format!("invalid change address: {e}")))?
-        .require_network(net)
-        .map_err(|e| BtcError::InvalidAddress(format!("change address wrong network: {e}")))?;
+    let change_sat = plan.change_sat;
+    let has_change = change_sat > 0;
+    let outputs = if has_change {
+        // Two outputs: recipient + change.
+        vec![
+            TxOut {
+                value: Amount::from_sat(amount_sat),
+                script_pubkey: recipient.script_pubkey(),
+            },
+            TxOut {
+                value: Amount::from_sat(change_sat),
+                script_pubkey: change_address.script_pubkey(),
+            },
+        ]
+    } else {
+        // One output: no change (dust goes to fee).
+        vec![TxOut {
+            value: Amount::from_sat(amount_sat),
+            script_pubkey: recipient.script_pubkey(),
+        }]
+    };
+
+    let tx = Transaction {
+        version: Version::TWO,
+        lock_time,
+        input: inputs,
+        output: outputs,
+    };
+
+    Ok(UnsignedBtcTx {
+        tx,
+        prevouts,
+        change_address: has_change.then(|| change_address.to_string()),
+    })
+}
+
+/// Build an unsigned P2WPKH transaction spending exactly `selected`.
+///
+/// For manual coin control: no selection is done, every given UTXO becomes an
+/// input, in order. `recipients` are paid in order, followed by a change
+/// output when the change exceeds [`DUST_THRESHOLD_SAT`]; smaller change goes
+/// to the fee. A recipient amount at or below the dust threshold is rejected.
+/// Fails with [`BtcError::InsufficientFunds`] if the inputs do not cover the
+/// amounts plus the fee.
+pub fn build_with_inputs(
+    selected: &[Utxo],
+    recipients: &[(BtcAddress, u64)],
+    change_address: &BtcAddress,
+    fee_rate_sat_vbyte: u64,
+    network: BtcNetwork,
+) -> Result<UnsignedBtcTx, BtcError> {
+    if selected.is_empty() {
+        return Err(BtcError::TransactionBuildError("no inputs selected".into()));
+    }
+    if recipients.is_empty() {
+        return Err(BtcError::TransactionBuildError("no recipients".into()));
+    }
+    for (recipient, amount) in recipients {
+        require_network(recipient, "recipient", network)?;
+        if *amount <= DUST_THRESHOLD_SAT {
+            return Err(BtcError::TransactionBuildError(format!(
+                "amount {amount} sat to {recipient} is at or below the dust threshold"
+            )));
+        }
+    }
+    require_network(change_address, "change", network)?;
+
+    let overflow = || BtcError::TransactionBuildError("amounts overflow u64".into());
+    let total_in = selected
+        .iter()
+        .try_fold(0u64, |total, u| total.checked_add(u.amount_sat))
+        .ok_or_else(overflow)?;
+    let total_out = recipients
+        .iter()
+        .try_fold(0u64, |total, (_, amount)| total.checked_add(*amount))
+        .ok_or_else(overflow)?;
+    let fee_with_change = estimate_fee_for_scripts(
+        utxo_scripts(selected),
+        recipients.len() + 1,
+        fee_rate_sat_vbyte,
+    );
+    let fee_without_change =
+        estimate_fee_for_scripts(utxo_scripts(selected), recipients.len(), fee_rate_sat_vbyte);
+    let needed = total_out
+        .checked_add(fee_without_change)
+        .ok_or_else(overflow)?;
+    if total_in < needed {
+        return Err(BtcError::InsufficientFunds {
+            needed,
+            available: total_in,
+        });
+    }
+
+    let change_sat = (total_in - total_out).saturating_sub(fee_with_change);
+    let has_change = change_sat > DUST_THRESHOLD_SAT;
+
+    let (inputs, prevouts) = build_inputs(selected)?;
+    let mut outputs: Vec<TxOut> = recipients
+        .iter()
+        .map(|(recipient, amount)| TxOut {
+            value: Amount::from_sat(*amount),
+            script_pubkey: recipient.script_pubkey(),
+        })
+        .collect();
+    if has_change {
+        outputs.push(TxOut {
+            value: Amount::from_sat(change_sat),
+            script_pubkey: change_address.script_pubkey(),
+        });
+    }
+
+    let tx = Transaction {
+        version: Version::TWO,
+        lock_time: LockTime::ZERO,
+        input: inputs,
+        output: outputs,
+    };
+
+    Ok(UnsignedBtcTx {
+        tx,
+        prevouts,
+        change_address: has_change.then(|| change_address.to_string()),
+    })
+}
+
+/// Build an unsigned P2WPKH consolidation spending every UTXO in `utxos`.
+///
+/// For shrinking the UTXO set while fees are low: all inputs go to a single
+/// output paying `destination` (normally the wallet's own address) the total
+/// minus the fee for that shape. There is no selection and no change.
+/// `destination` is reported as the change address, so the result can still
+/// be fee-bumped with [`bump_fee`]. Fails if the output would be at or below
+/// [`DUST_THRESHOLD_SAT`].
+pub fn build_consolidation(
+    utxos: &[Utxo],
+    destination: &BtcAddress,
+    fee_rate_sat_vbyte: u64,
+    network: BtcNetwork,
+) -> Result<UnsignedBtcTx, BtcError> {
+    if utxos.is_empty() {
+        return Err(BtcError::TransactionBuildError(
+            "no UTXOs to consolidate".into(),
+        ));
+    }
+    require_network(destination, "destination", network)?;
+
+    let overflow = || BtcError::TransactionBuildError("amounts overflow u64".into());
+    let total_in = utxos
+        .iter()
+        .try_fold(0u64, |total, u| total.checked_add(u.amount_sat))
+        .ok_or_else(overflow)?;
+    let fee = estimate_fee_for_scripts(utxo_scripts(utxos), 1, fee_rate_sat_vbyte);
+    let needed = fee
+        .checked_add(DUST_THRESHOLD_SAT + 1)
+        .ok_or_else(overflow)?;
+    if total_in < needed {
+        return Err(BtcError::InsufficientFunds {
+            needed,
+            available: total_in,
+        });
+    }
+
+    let (inputs, prevouts) = build_inputs(utxos)?;
+    let tx = Transaction {
+        version: Version::TWO,
+        lock_time: LockTime::ZERO,
+        input: inputs,
+        output: vec![TxOut {
+            value: Amount::from_sat(total_in - fee),
+            script_pubkey: destination.script_pubkey(),
+        }],
+    };
 
-    // Select UTXOs.
-    let selection = crate::utxo::select_utxos(utxos, amount_sat, fee_rate_sat_vbyte)?;
+    Ok(UnsignedBtcTx {
+        tx,
+        prevouts,
+        change_address: Some(destination.to_string()),
+    })
+}
 
-    // Build inputs.
-    let mut inputs = Vec::with_capacity(selection.selected.len());
-    let mut prevouts = Vec::with_capacity(selection.selected.len());
+/// RBF-signalling segwit inputs for `utxos`, with the matching prevouts.
+fn build_inputs(utxos: &[Utxo]) -> Result<(Vec<TxIn>, Vec<TxOut>), BtcError> {
+    let mut inputs = Vec::with_capacity(utxos.len());
+    let mut prevouts = Vec::with_capacity(utxos.len());
 
-    for utxo in &selection.selected {
+    for utxo in utxos {
         let txid: Txid = utxo
             .txid
             .parse()
@@ -98,100 +498,420 @@ pub fn build_p2wpkh_transaction(
         });
     }
 
-    // Determine number of outputs (1 or 2) to compute the fee accurately.
-    let fee_2_outputs = estimate_fee(selection.selected.len(), 2, fee_rate_sat_vbyte);
-    let fee_1_output = estimate_fee(selection.selected.len(), 1, fee_rate_sat_vbyte);
+    Ok((inputs, prevouts))
+}
 
-    let change_sat = selection.total_sat.saturating_sub(amount_sat + fee_2_outputs);
-    let dust_threshold: u64 = 546;
+/// Build an unsigned P2WPKH transaction that returns change to the wallet itself.
+///
+/// Identical to [`build_p2wpkh_transaction`], except that the change address is
+/// derived from `change_pubkey` — the compressed public key at the wallet's
+/// internal chain (`m/84'/coin'/account'/1/index`) — instead of being supplied
+/// by the caller. The derived address is reported in
+/// [`UnsignedBtcTx::change_address`].
+pub fn build_p2wpkh_transaction_with_change_key(
+    utxos: &[Utxo],
+    recipient: &BtcAddress,
+    amount_sat: u64,
+    change_pubkey: &[u8; 33],
+    fee_rate_sat_vbyte: u64,
+    network: BtcNetwork,
+) -> Result<UnsignedBtcTx, BtcError> {
+    let change_address = crate::address::pubkey_to_p2wpkh_address(change_pubkey, network)?;
+    let change_address = BtcAddress::parse(&change_address, network)?;
+    build_p2wpkh_transaction(
+        utxos,
+        recipient,
+        amount_sat,
+        &change_address,
+        fee_rate_sat_vbyte,
+        network,
+        None,
+        None,
+        false,
+    )
+}
 
-    let (outputs, _fee) = if change_sat > dust_threshold {
-        // Two outputs: recipient + change.
-        let outs = vec![
-            TxOut {
-                value: Amount::from_sat(amount_sat),
-                script_pubkey: recipient_addr.script_pubkey(),
-            },
-            TxOut {
-                value: Amount::from_sat(change_sat),
-                script_pubkey: change_addr.script_pubkey(),
-            },
-        ];
-        (outs, fee_2_outputs)
-    } else {
-        // One output: no change (dust goes to fee).
-        let outs = vec![TxOut {
-            value: Amount::from_sat(amount_sat),
-            script_pubkey: recipient_addr.script_pubkey(),
-        }];
-        (outs, fee_1_output + change_sat)
-    };
+/// Rebuild a transaction at a higher fee rate for replace-by-fee (BIP-125).
+///
+/// Keeps the same inputs and recipient output and pays the extra fee out of
+/// the change output, so `unsigned` must have been built with change. All
+/// inputs must already signal RBF, the new fee must exceed the current one by
+/// at least [`INCREMENTAL_RELAY_FEE_SAT_VBYTE`] times the new vsize (BIP-125
+/// rule 4), and the reduced change must stay above `dust_threshold`, the same
+/// threshold [`build_p2wpkh_transaction`] takes. The result has to be signed
+/// again before broadcast.
+pub fn bump_fee(
+    unsigned: &UnsignedBtcTx,
+    new_fee_rate: u64,
+    dust_threshold: Option<u64>,
+) -> Result<UnsignedBtcTx, BtcError> {
+    if !unsigned
+        .tx
+        .input
+        .iter()
+        .all(|input| input.sequence.is_rbf())
+    {
+        return Err(BtcError::TransactionBuildError(
+            "transaction does not signal replace-by-fee".into(),
+        ));
+    }
+
+    let change_address = unsigned.change_address.as_deref().ok_or_else(|| {
+        BtcError::TransactionBuildError("transaction has no change output to reduce".into())
+    })?;
+    let change_script = change_address
+        .parse::<Address<bitcoin::address::NetworkUnchecked>>()
+        .map_err(|e| BtcError::InvalidAddress(format!("invalid change address: {e}")))?
+        .assume_checked()
+        .script_pubkey();
+    // The change output comes last, even when paying to the same address.
+    let change_index = unsigned
+        .tx
+        .output
+        .iter()
+        .rposition(|out| out.script_pubkey == change_script)
+        .ok_or_else(|| {
+            BtcError::TransactionBuildError("change output not found in transaction".into())
+        })?;
+
+    let total_in: u64 = unsigned.prevouts.iter().map(|p| p.value.to_sat()).sum();
+    let total_out: u64 = unsigned.tx.output.iter().map(|o| o.value.to_sat()).sum();
+    let old_fee = total_in.saturating_sub(total_out);
+    let new_vsize = estimate_vsize(
+        unsigned.prevouts.iter().map(|p| p.script_pubkey.as_bytes()),
+        unsigned.tx.output.len(),
+    );
+    let fee_overflow =
+        || BtcError::TransactionBuildError("bumped fee overflows u64 at this fee rate".into());
+    let new_fee = new_vsize
+        .checked_mul(new_fee_rate)
+        .ok_or_else(fee_overflow)?;
+    if new_fee <= old_fee {
+        return Err(BtcError::TransactionBuildError(format!(
+            "new fee {new_fee} sat does not exceed current fee {old_fee} sat"
+        )));
+    }
+    // The replacement must also pay for its own relay (BIP-125 rule 4).
+    let min_increase = INCREMENTAL_RELAY_FEE_SAT_VBYTE
+        .checked_mul(new_vsize)
+        .ok_or_else(fee_overflow)?;
+    if new_fee - old_fee < min_increase {
+        return Err(BtcError::TransactionBuildError(format!(
+            "fee increase of {} sat is below the {min_increase} sat incremental relay fee",
+            new_fee - old_fee
+        )));
+    }
+
+    let other_outputs = total_out - unsigned.tx.output[change_index].value.to_sat();
+    let needed = other_outputs
+        .checked_add(new_fee)
+        .ok_or_else(fee_overflow)?;
+    let new_change = total_in
+        .checked_sub(needed)
+        .ok_or(BtcError::InsufficientFunds {
+            needed,
+            available: total_in,
+        })?;
+    if new_change <= effective_dust_threshold(dust_threshold) {
+        return Err(BtcError::TransactionBuildError(format!(
+            "change of {new_change} sat after fee bump would be dust"
+        )));
+    }
+
+    let mut bumped = unsigned.clone();
+    bumped.tx.output[change_index].value = Amount::from_sat(new_change);
+    for input in &mut bumped.tx.input {
+        input.witness = Witness::default();
+    }
+    Ok(bumped)
+}
+
+/// Build a child-pays-for-parent (CPFP) transaction.
+///
+/// Spends the wallet's unconfirmed output `parent_txid:parent_vout` (usually
+/// its change) in a single-input, single-output child paying to `recipient`.
+/// The child's fee is set so that parent and child together pay
+/// `combined_fee_rate` over their combined vsize:
+///
+/// `child_fee = combined_fee_rate * (parent_vsize + child_vsize) - parent_fee_sat`
+///
+/// `parent_vsize` and `parent_fee_sat` describe the stuck parent and must be
+/// supplied by the caller (no network access here). Pass `0` for
+/// `parent_fee_sat` to ignore what the parent already pays.
+#[allow(clippy::too_many_arguments)]
+pub fn build_cpfp_transaction(
+    parent_txid: &str,
+    parent_vout: u32,
+    parent_amount_sat: u64,
+    parent_script: &[u8],
+    parent_vsize: u64,
+    parent_fee_sat: u64,
+    recipient: &BtcAddress,
+    combined_fee_rate: u64,
+    network: BtcNetwork,
+) -> Result<UnsignedBtcTx, BtcError> {
+    require_network(recipient, "recipient", network)?;
+
+    let txid: Txid = parent_txid
+        .parse()
+        .map_err(|e| BtcError::TransactionBuildError(format!("invalid txid: {e}")))?;
+
+    let child_vsize = TX_OVERHEAD_VBYTES + input_vbytes(parent_script) + OUTPUT_VBYTES;
+    let fee_overflow =
+        || BtcError::TransactionBuildError("CPFP fee overflows u64 at this fee rate".into());
+    let package_fee = parent_vsize
+        .checked_add(child_vsize)
+        .and_then(|vsize| vsize.checked_mul(combined_fee_rate))
+        .ok_or_else(fee_overflow)?;
+    // Never drop below the child's own fee at the target rate.
+    let child_fee = package_fee.saturating_sub(parent_fee_sat).max(
+        child_vsize
+            .checked_mul(combined_fee_rate)
+            .ok_or_else(fee_overflow)?,
+    );
+
+    let output_sat =
+        parent_amount_sat
+            .checked_sub(child_fee)
+            .ok_or(BtcError::InsufficientFunds {
+                needed: child_fee,
+                available: parent_amount_sat,
+            })?;
+    if output_sat <= DUST_THRESHOLD_SAT {
+        return Err(BtcError::TransactionBuildError(format!(
+            "child output of {output_sat} sat after CPFP fee would be dust"
+        )));
+    }
 
     let tx = Transaction {
         version: Version::TWO,
         lock_time: LockTime::ZERO,
-        input: inputs,
-        output: outputs,
+        input: vec![TxIn {
+            previous_output: OutPoint::new(txid, parent_vout),
+            script_sig: ScriptBuf::new(),
+            sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+            witness: Witness::default(),
+        }],
+        output: vec![TxOut {
+            value: Amount::from_sat(output_sat),
+            script_pubkey: recipient.script_pubkey(),
+        }],
     };
 
-    Ok(UnsignedBtcTx { tx, prevouts })
+    Ok(UnsignedBtcTx {
+        tx,
+        prevouts: vec![TxOut {
+            value: Amount::from_sat(parent_amount_sat),
+            script_pubkey: ScriptBuf::from(parent_script.to_vec()),
+        }],
+        change_address: None,
+    })
+}
+
+/// Reject an address that was validated for a different network.
+fn require_network(address: &BtcAddress, role: &str, network: BtcNetwork) -> Result<(), BtcError> {
+    if address.network() != network {
+        return Err(BtcError::InvalidAddress(format!(
+            "{role} address is for {}, not {network}",
+            address.network()
+        )));
+    }
+    Ok(())
+}
+
+/// Re-validates the outputs of `unsigned` right before signing.
+///
+/// `UnsignedBtcTx` has public fields and round-trips through JSON, so the
+/// outputs may have changed since it was built. Every output must pay a
+/// standard address type, except zero-value OP_RETURN data outputs, and the
+/// recorded `change_address`, if any, must be valid for `network` and paid by
+/// one of the outputs.
+pub fn assert_recipient(unsigned: &UnsignedBtcTx, network: BtcNetwork) -> Result<(), BtcError> {
+    for (index, output) in unsigned.tx.output.iter().enumerate() {
+        if output.script_pubkey.is_op_return() {
+            // Data carriers pay no one; value sent to one is burned.
+            if output.value != Amount::ZERO {
+                return Err(BtcError::InvalidAddress(format!(
+                    "OP_RETURN output {index} carries {} sat",
+                    output.value.to_sat()
+                )));
+            }
+            continue;
+        }
+        Address::from_script(&output.script_pubkey, network.to_bitcoin_network()).map_err(|e| {
+            BtcError::InvalidAddress(format!(
+                "output {index} does not pay a standard address ({e}): {}",
+                output.script_pubkey.to_hex_string()
+            ))
+        })?;
+    }
+
+    if let Some(change) = &unsigned.change_address {
+        let change = BtcAddress::parse(change, network).map_err(|e| {
+            BtcError::InvalidAddress(format!("change address failed re-validation: {e}"))
+        })?;
+        let script = change.script_pubkey();
+        if !unsigned.tx.output.iter().any(|o| o.script_pubkey == script) {
+            return Err(BtcError::InvalidAddress(format!(
+                "change address {change} is not paid by any output"
+            )));
+        }
+    }
+
+    Ok(())
 }
 
-/// Sign an unsigned P2WPKH transaction with the given private key.
+/// Sign an unsigned transaction spending P2WPKH and/or P2PKH outputs.
+///
+/// Each input is signed according to its prevout's scriptPubKey: P2WPKH
+/// (`0014...`) with the BIP-143 sighash and a witness, P2PKH
+/// (`76a914...88ac`) with the legacy sighash and a scriptSig. Any other
+/// script type is an error.
 ///
-/// All inputs are assumed to be controlled by the same key. The private key
-/// must be a 32-byte secp256k1 scalar. Returns the serialized signed
-/// transaction ready for broadcast.
+/// `signer` gives the key for each input: one key for all of them, or one
+/// per input in input order (which coin selection may have changed from the
+/// order the UTXOs were given in). Keys must be 32-byte secp256k1 scalars.
+/// The outputs are checked with [`assert_recipient`] first. Returns the
+/// serialized signed transaction ready for broadcast.
 pub fn sign_transaction(
     unsigned_tx: &UnsignedBtcTx,
-    private_key: &[u8; 32],
-    _network: BtcNetwork,
+    signer: &InputSigner,
+    network: BtcNetwork,
 ) -> Result<Vec<u8>, BtcError> {
-    let secp = Secp256k1::new();
-    let secret_key = SecretKey::from_slice(private_key)
-        .map_err(|e| BtcError::InvalidPrivateKey(format!("invalid secret key: {e}")))?;
-    let public_key = bitcoin::secp256k1::PublicKey::from_secret_key(&secp, &secret_key);
-    let compressed_pk = CompressedPublicKey(public_key);
+    assert_recipient(unsigned_tx, network)?;
+
+    // Hand-built transactions may not line up; indexing below would panic.
+    if unsigned_tx.prevouts.len() != unsigned_tx.tx.input.len() {
+        return Err(BtcError::SigningError(
+            "prevouts/inputs length mismatch".into(),
+        ));
+    }
 
+    let keys = signer
+        .keys(unsigned_tx.tx.input.len())
+        .map_err(|e| BtcError::SigningError(e.to_string()))?;
+
+    let secp = Secp256k1::new();
     let mut signed_tx = unsigned_tx.tx.clone();
 
     // We need to sign each input.
-    for input_index in 0..signed_tx.input.len() {
-        let script_code = ScriptBuf::new_p2wpkh(&compressed_pk.wpubkey_hash());
-
+    for (input_index, key) in keys.into_iter().enumerate() {
+        let secret_key = SecretKey::from_slice(key)
+            .map_err(|e| BtcError::InvalidPrivateKey(format!("invalid secret key: {e}")))?;
+        let public_key = bitcoin::secp256k1::PublicKey::from_secret_key(&secp, &secret_key);
+        let prevout = &unsigned_tx.prevouts[input_index];
         let mut sighash_cache = SighashCache::new(&unsigned_tx.tx);
-        let sighash = sighash_cache
-            .p2wpkh_signature_hash(
-                input_index,
-                &script_code,
-                unsigned_tx.prevouts[input_index].value,
-                EcdsaSighashType::All,
-            )
-            .map_err(|e| BtcError::SigningError(format!("sighash computation failed: {e}")))?;
 
-        let msg = Message::from_digest(sighash.to_byte_array());
+        // The prevout's script decides the sighash algorithm and where the
+        // signature goes: BIP-143 into the witness for P2WPKH, the legacy
+        // algorithm into the scriptSig for P2PKH.
+        let segwit = if prevout.script_pubkey.is_p2wpkh() {
+            true
+        } else if prevout.script_pubkey.is_p2pkh() {
+            false
+        } else {
+            return Err(BtcError::SigningError(format!(
+                "unsupported scriptPubKey for input {input_index}: {}",
+                prevout.script_pubkey.to_hex_string()
+            )));
+        };
+
+        // The legacy sighash commits to the prevout script as-is, so a key
+        // that doesn't own it would sign a transaction the network rejects.
+        if !segwit
+            && prevout.script_pubkey
+                != ScriptBuf::new_p2pkh(&bitcoin::PublicKey::new(public_key).pubkey_hash())
+        {
+            return Err(BtcError::SigningError(format!(
+                "private key does not match scriptPubKey of input {input_index}"
+            )));
+        }
+
+        let sighash_error =
+            |e: String| BtcError::SigningError(format!("sighash computation failed: {e}"));
+        let sighash = if segwit {
+            let compressed_pk = CompressedPublicKey(public_key);
+            let script_code = ScriptBuf::new_p2wpkh(&compressed_pk.wpubkey_hash());
+            sighash_cache
+                .p2wpkh_signature_hash(
+                    input_index,
+                    &script_code,
+                    prevout.value,
+                    EcdsaSighashType::All,
+                )
+                .map_err(|e| sighash_error(e.to_string()))?
+                .to_byte_array()
+        } else {
+            sighash_cache
+                .legacy_signature_hash(
+                    input_index,
+                    &prevout.script_pubkey,
+                    EcdsaSighashType::All.to_u32(),
+                )
+                .map_err(|e| sighash_error(e.to_string()))?
+                .to_byte_array()
+        };
+
+        let msg = Message::from_digest(sighash);
         let signature = secp.sign_ecdsa(&msg, &secret_key);
 
         // Serialize signature in DER + sighash type byte.
         let mut sig_bytes = signature.serialize_der().to_vec();
         sig_bytes.push(EcdsaSighashType::All as u8);
 
-        // Build witness: [signature, pubkey].
-        let mut witness = Witness::new();
-        witness.push(&sig_bytes);
-        witness.push(&public_key.serialize());
-
-        signed_tx.input[input_index].witness = witness;
+        if segwit {
+            // Build witness: [signature, pubkey].
+            let mut witness = Witness::new();
+            witness.push(&sig_bytes);
+            witness.push(public_key.serialize());
+            signed_tx.input[input_index].witness = witness;
+        } else {
+            // Build scriptSig: <signature> <pubkey>.
+            let sig_push = PushBytesBuf::try_from(sig_bytes)
+                .map_err(|e| BtcError::SigningError(format!("signature push failed: {e}")))?;
+            signed_tx.input[input_index].script_sig = Builder::new()
+                .push_slice(sig_push)
+                .push_slice(public_key.serialize())
+                .into_script();
+        }
     }
 
     Ok(bitcoin::consensus::serialize(&signed_tx))
 }
 
+/// The txid of a serialized transaction, in the usual reversed-hex display
+/// order.
+///
+/// The txid is the double SHA-256 of the serialization without witness data,
+/// so it is the same before and after signing a SegWit transaction.
+pub fn txid(raw_tx: &[u8]) -> Result<String, BtcError> {
+    let tx: Transaction = bitcoin::consensus::deserialize(raw_tx)
+        .map_err(|e| BtcError::TransactionBuildError(format!("invalid transaction: {e}")))?;
+    Ok(tx.compute_txid().to_string())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::utxo::Utxo;
 
+    #[test]
+    fn txid_of_genesis_coinbase() {
+        let raw = hex::decode(
+            "01000000010000000000000000000000000000000000000000000000000000000000000000ffffffff\
+             4d04ffff001d0104455468652054696d65732030332f4a616e2f32303039204368616e63656c6c6f72\
+             206f6e206272696e6b206f66207365636f6e64206261696c6f757420666f722062616e6b73ffffffff\
+             0100f2052a01000000434104678afdb0fe5548271967f1a67130b7105cd6a828e03909a67962e0ea1f\
+             61deb649f6bc3f4cef38c4f35504e51ec112de5c384df7ba0b8d578a4c702b6bf11d5fac00000000",
+        )
+        .unwrap();
+        assert_eq!(
+            txid(&raw).unwrap(),
+            "4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b"
+        );
+        assert!(txid(&raw[..raw.len() - 1]).is_err());
+    }
+
     #[test]
     fn estimate_fee_basic() {
         // 1 input, 2 outputs: 11 + 68 + 62 = 141 vbytes at 1 sat/vbyte = 141
@@ -212,6 +932,10 @@ mod tests {
         assert_eq!(estimate_fee(5, 5, 0), 0);
     }
 
+    fn mainnet(address: &str) -> BtcAddress {
+        BtcAddress::parse(address, BtcNetwork::Mainnet).unwrap()
+    }
+
     fn make_test_utxo(txid: &str, vout: u32, amount_sat: u64, script_hex: &str) -> Utxo {
         Utxo {
             txid: txid.to_string(),
@@ -233,11 +957,14 @@ mod tests {
 
         let result = build_p2wpkh_transaction(
             &utxos,
-            "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4",
+            &mainnet("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"),
             50_000,
-            "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4",
+            &mainnet("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"),
             1,
             BtcNetwork::Mainnet,
+            None,
+            None,
+            false,
         );
 
         assert!(result.is_ok());
@@ -248,6 +975,18 @@ mod tests {
         assert_eq!(unsigned.tx.output[0].value.to_sat(), 50_000);
     }
 
+    #[test]
+    fn build_transaction_rejects_zero_amount() {
+        let script_hex = format!("0014{}", "ab".repeat(20));
+        let utxos = vec![make_test_utxo(&"a".repeat(64), 0, 100_000, &script_hex)];
+        let addr = mainnet("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4");
+
+        let network = BtcNetwork::Mainnet;
+        let result =
+            build_p2wpkh_transaction(&utxos, &addr, 0, &addr, 1, network, None, None, false);
+        assert!(matches!(result, Err(BtcError::TransactionBuildError(_))));
+    }
+
     #[test]
     fn build_transaction_dust_change_omitted() {
         let txid = "b".repeat(64);
@@ -260,11 +999,14 @@ mod tests {
 
         let result = build_p2wpkh_transaction(
             &utxos,
-            "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4",
+            &mainnet("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"),
             99_700,
-            "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4",
+            &mainnet("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"),
             1,
             BtcNetwork::Mainnet,
+            None,
+            None,
+            false,
         );
 
         assert!(result.is_ok());
@@ -273,84 +1015,679 @@ mod tests {
         assert_eq!(unsigned.tx.output.len(), 1);
     }
 
-    #[test]
-    fn build_transaction_insufficient_funds() {
-        let txid = "c".repeat(64);
-        let script_hex = format!("0014{}", "ef".repeat(20));
-
-        let utxos = vec![make_test_utxo(&txid, 0, 1_000, &script_hex)];
+    fn build_with_dust_threshold(amount_sat: u64, dust_threshold: Option<u64>) -> UnsignedBtcTx {
+        let script_hex = format!("0014{}", "cd".repeat(20));
+        let utxos = vec![make_test_utxo(&"b".repeat(64), 0, 100_000, &script_hex)];
+        let addr = mainnet("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4");
 
-        let result = build_p2wpkh_transaction(
+        build_p2wpkh_transaction(
             &utxos,
-            "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4",
-            500_000,
-            "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4",
+            &addr,
+            amount_sat,
+            &addr,
             1,
             BtcNetwork::Mainnet,
-        );
-
-        assert!(result.is_err());
+            dust_threshold,
+            None,
+            false,
+        )
+        .unwrap()
     }
 
     #[test]
-    fn build_transaction_invalid_recipient() {
-        let txid = "d".repeat(64);
-        let script_hex = format!("0014{}", "11".repeat(20));
-
-        let utxos = vec![make_test_utxo(&txid, 0, 100_000, &script_hex)];
+    fn build_transaction_lower_dust_threshold_keeps_change() {
+        // 100_000 in, 141 sat fee for two outputs: 300 sat of change, which is
+        // above P2WPKH relay dust (294) but below the 546 default.
+        let amount = 100_000 - estimate_fee(1, 2, 1) - 300;
 
-        let result = build_p2wpkh_transaction(
-            &utxos,
-            "not_a_valid_address",
-            50_000,
-            "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4",
-            1,
-            BtcNetwork::Mainnet,
-        );
+        assert_eq!(build_with_dust_threshold(amount, None).tx.output.len(), 1);
 
-        assert!(result.is_err());
+        let unsigned = build_with_dust_threshold(amount, Some(294));
+        assert_eq!(unsigned.tx.output.len(), 2);
+        assert_eq!(unsigned.tx.output[1].value.to_sat(), 300);
+        assert!(unsigned.change_address.is_some());
     }
 
     #[test]
-    fn build_transaction_wrong_network() {
-        let txid = "e".repeat(64);
-        let script_hex = format!("0014{}", "22".repeat(20));
-
-        let utxos = vec![make_test_utxo(&txid, 0, 100_000, &script_hex)];
+    fn build_transaction_higher_dust_threshold_drops_change() {
+        let amount = 100_000 - estimate_fee(1, 2, 1) - 600;
 
-        // Use a mainnet address but specify testnet.
-        let result = build_p2wpkh_transaction(
-            &utxos,
-            "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4",
-            50_000,
-            "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4",
-            1,
-            BtcNetwork::Testnet,
-        );
+        assert_eq!(build_with_dust_threshold(amount, None).tx.output.len(), 2);
 
-        assert!(result.is_err());
+        let unsigned = build_with_dust_threshold(amount, Some(1_000));
+        assert_eq!(unsigned.tx.output.len(), 1);
+        assert!(unsigned.change_address.is_none());
     }
 
     #[test]
-    fn sign_transaction_produces_valid_bytes() {
-        let txid = "a".repeat(64);
-        let script_hex = format!("0014{}", "ab".repeat(20));
+    fn build_transaction_dust_threshold_never_below_relay_dust() {
+        // 200 sat of change is dust even for P2WPKH, whatever the caller asks.
+        let amount = 100_000 - estimate_fee(1, 2, 1) - 200;
 
-        let utxos = vec![make_test_utxo(&txid, 0, 100_000, &script_hex)];
+        assert_eq!(
+            build_with_dust_threshold(amount, Some(0)).tx.output.len(),
+            1
+        );
+        assert_eq!(
+            build_with_dust_threshold(amount, Some(100)).tx.output.len(),
+            1
+        );
+    }
 
-        let unsigned = build_p2wpkh_transaction(
+    #[test]
+    fn plan_matches_built_transaction() {
+        let script_hex = format!("0014{}", "ab".repeat(20));
+        let utxos = vec![
+            make_test_utxo(&"a".repeat(64), 0, 60_000, &script_hex),
+            make_test_utxo(&"b".repeat(64), 1, 50_000, &script_hex),
+        ];
+        let addr = mainnet("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4");
+
+        // With change, and with the change dropped as dust.
+        for amount in [80_000, 110_000 - estimate_fee(2, 2, 3) - 300] {
+            let plan = plan_p2wpkh_spend(&utxos, amount, 3, None).unwrap();
+            let unsigned = build_p2wpkh_transaction(
+                &utxos,
+                &addr,
+                amount,
+                &addr,
+                3,
+                BtcNetwork::Mainnet,
+                None,
+                None,
+                false,
+            )
+            .unwrap();
+
+            let total_in: u64 = unsigned.prevouts.iter().map(|p| p.value.to_sat()).sum();
+            let total_out: u64 = unsigned.tx.output.iter().map(|o| o.value.to_sat()).sum();
+            assert_eq!(plan.fee_sat, total_in - total_out);
+            assert_eq!(plan.change_sat, total_out - amount);
+            assert_eq!(plan.selection.selected.len(), unsigned.tx.input.len());
+            assert_eq!(plan.change_sat == 0, unsigned.tx.output.len() == 1);
+        }
+    }
+
+    // ─── build_with_inputs ───
+
+    fn coin_control_utxos() -> Vec<Utxo> {
+        let script_hex = format!("0014{}", "ab".repeat(20));
+        vec![
+            make_test_utxo(&"a".repeat(64), 0, 10_000, &script_hex),
+            make_test_utxo(&"b".repeat(64), 1, 500_000, &script_hex),
+            make_test_utxo(&"c".repeat(64), 2, 40_000, &script_hex),
+        ]
+    }
+
+    #[test]
+    fn build_with_inputs_uses_exactly_the_supplied_utxos() {
+        // Largest-first would take only the 500k coin; the user picked the other two.
+        let utxos = coin_control_utxos();
+        let picked = [utxos[0].clone(), utxos[2].clone()];
+        let recipient = mainnet("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4");
+        let change = mainnet("bc1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3qccfmv3");
+
+        let unsigned = build_with_inputs(
+            &picked,
+            &[(recipient, 30_000)],
+            &change,
+            5,
+            BtcNetwork::Mainnet,
+        )
+        .unwrap();
+
+        let outpoints: Vec<(String, u32)> = unsigned
+            .tx
+            .input
+            .iter()
+            .map(|i| (i.previous_output.txid.to_string(), i.previous_output.vout))
+            .collect();
+        assert_eq!(outpoints, [("a".repeat(64), 0), ("c".repeat(64), 2)]);
+
+        let expected_change = 50_000 - 30_000 - estimate_fee(2, 2, 5);
+        assert_eq!(unsigned.tx.output.len(), 2);
+        assert_eq!(unsigned.tx.output[0].value.to_sat(), 30_000);
+        assert_eq!(unsigned.tx.output[1].value.to_sat(), expected_change);
+        assert_eq!(unsigned.tx.output[1].script_pubkey, change.script_pubkey());
+        assert_eq!(unsigned.change_address, Some(change.to_string()));
+        assert_eq!(fee_of(&unsigned), estimate_fee(2, 2, 5));
+    }
+
+    #[test]
+    fn build_with_inputs_pays_several_recipients_and_drops_dust_change() {
+        let utxos = coin_control_utxos();
+        let a = mainnet("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4");
+        let b = mainnet("bc1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3qccfmv3");
+        // Leave 300 sats over the three-output fee: dust, so no change output.
+        let second = 40_000 - 20_000 - estimate_fee(1, 3, 2) - 300;
+
+        let unsigned = build_with_inputs(
+            &utxos[2..],
+            &[(a, 20_000), (b.clone(), second)],
+            &b,
+            2,
+            BtcNetwork::Mainnet,
+        )
+        .unwrap();
+
+        assert_eq!(unsigned.tx.input.len(), 1);
+        assert_eq!(unsigned.tx.output.len(), 2);
+        assert!(unsigned.change_address.is_none());
+        assert_eq!(fee_of(&unsigned), estimate_fee(1, 3, 2) + 300);
+    }
+
+    #[test]
+    fn build_with_inputs_rejects_insufficient_selection() {
+        let utxos = coin_control_utxos();
+        let addr = mainnet("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4");
+
+        let recipients = [(addr.clone(), 10_000)];
+        let err =
+            build_with_inputs(&utxos[..1], &recipients, &addr, 1, BtcNetwork::Mainnet).unwrap_err();
+        assert!(matches!(
+            err,
+            BtcError::InsufficientFunds { needed, available: 10_000 }
+                if needed == 10_000 + estimate_fee(1, 1, 1)
+        ));
+        assert!(build_with_inputs(&[], &recipients, &addr, 1, BtcNetwork::Mainnet).is_err());
+        assert!(build_with_inputs(&utxos, &[], &addr, 1, BtcNetwork::Mainnet).is_err());
+    }
+
+    #[test]
+    fn build_with_inputs_rejects_dust_recipient() {
+        let utxos = coin_control_utxos();
+        let addr = mainnet("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4");
+
+        let recipients = [(addr.clone(), 5_000), (addr.clone(), DUST_THRESHOLD_SAT)];
+        let err =
+            build_with_inputs(&utxos, &recipients, &addr, 1, BtcNetwork::Mainnet).unwrap_err();
+        assert!(err.to_string().contains("dust"), "{err}");
+    }
+
+    #[test]
+    fn build_with_inputs_rejects_overflowing_amounts() {
+        let script_hex = format!("0014{}", "ab".repeat(20));
+        let addr = mainnet("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4");
+
+        let huge = vec![
+            make_test_utxo(&"a".repeat(64), 0, u64::MAX, &script_hex),
+            make_test_utxo(&"b".repeat(64), 0, 1, &script_hex),
+        ];
+        let err = build_with_inputs(
+            &huge,
+            &[(addr.clone(), 10_000)],
+            &addr,
+            1,
+            BtcNetwork::Mainnet,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("overflow"), "{err}");
+
+        let utxos = coin_control_utxos();
+        let recipients = [(addr.clone(), u64::MAX), (addr.clone(), 1_000)];
+        let err =
+            build_with_inputs(&utxos, &recipients, &addr, 1, BtcNetwork::Mainnet).unwrap_err();
+        assert!(err.to_string().contains("overflow"), "{err}");
+    }
+
+    // ─── build_consolidation ───
+
+    #[test]
+    fn build_consolidation_spends_everything_into_one_output() {
+        let utxos = coin_control_utxos();
+        let destination = mainnet("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4");
+
+        let unsigned = build_consolidation(&utxos, &destination, 3, BtcNetwork::Mainnet).unwrap();
+
+        let outpoints: Vec<(String, u32)> = unsigned
+            .tx
+            .input
+            .iter()
+            .map(|i| (i.previous_output.txid.to_string(), i.previous_output.vout))
+            .collect();
+        let expected: Vec<(String, u32)> = utxos.iter().map(|u| (u.txid.clone(), u.vout)).collect();
+        assert_eq!(outpoints, expected);
+
+        let total: u64 = utxos.iter().map(|u| u.amount_sat).sum();
+        let fee = estimate_fee(utxos.len(), 1, 3);
+        assert_eq!(unsigned.tx.output.len(), 1);
+        assert_eq!(unsigned.tx.output[0].value.to_sat(), total - fee);
+        assert_eq!(
+            unsigned.tx.output[0].script_pubkey,
+            destination.script_pubkey()
+        );
+        assert_eq!(fee_of(&unsigned), estimate_fee(utxos.len(), 1, 3));
+    }
+
+    #[test]
+    fn build_consolidation_can_be_fee_bumped() {
+        let utxos = coin_control_utxos();
+        let destination = mainnet("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4");
+        let unsigned = build_consolidation(&utxos, &destination, 1, BtcNetwork::Mainnet).unwrap();
+
+        let bumped = bump_fee(&unsigned, 4, None).unwrap();
+        assert_eq!(bumped.tx.output.len(), 1);
+        assert_eq!(fee_of(&bumped), estimate_fee(utxos.len(), 1, 4));
+    }
+
+    #[test]
+    fn build_consolidation_rejects_empty_and_dust_results() {
+        let destination = mainnet("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4");
+        assert!(build_consolidation(&[], &destination, 1, BtcNetwork::Mainnet).is_err());
+
+        let script_hex = format!("0014{}", "ab".repeat(20));
+        let tiny = [make_test_utxo(&"a".repeat(64), 0, 600, &script_hex)];
+        let err = build_consolidation(&tiny, &destination, 1, BtcNetwork::Mainnet).unwrap_err();
+        assert!(matches!(
+            err,
+            BtcError::InsufficientFunds { available: 600, .. }
+        ));
+
+        let testnet = BtcAddress::parse(
+            "tb1qw508d6qejxtdg4y5r3zarvary0c5xw7kxpjzsx",
+            BtcNetwork::Testnet,
+        )
+        .unwrap();
+        let utxos = coin_control_utxos();
+        assert!(build_consolidation(&utxos, &testnet, 1, BtcNetwork::Mainnet).is_err());
+    }
+
+    #[test]
+    fn build_consolidation_rejects_overflowing_amounts() {
+        let script_hex = format!("0014{}", "ab".repeat(20));
+        let destination = mainnet("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4");
+        let huge = [
+            make_test_utxo(&"a".repeat(64), 0, u64::MAX, &script_hex),
+            make_test_utxo(&"b".repeat(64), 0, 1, &script_hex),
+        ];
+        let err = build_consolidation(&huge, &destination, 1, BtcNetwork::Mainnet).unwrap_err();
+        assert!(err.to_string().contains("overflow"), "{err}");
+    }
+
+    // ─── bump_fee ───
+
+    fn fee_of(unsigned: &UnsignedBtcTx) -> u64 {
+        let total_in: u64 = unsigned.prevouts.iter().map(|p| p.value.to_sat()).sum();
+        let total_out: u64 = unsigned.tx.output.iter().map(|o| o.value.to_sat()).sum();
+        total_in - total_out
+    }
+
+    fn build_with_change(fee_rate: u64) -> UnsignedBtcTx {
+        let script_hex = format!("0014{}", "ab".repeat(20));
+        let utxos = vec![
+            make_test_utxo(&"a".repeat(64), 0, 60_000, &script_hex),
+            make_test_utxo(&"b".repeat(64), 1, 50_000, &script_hex),
+        ];
+        build_p2wpkh_transaction(
             &utxos,
-            "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4",
+            &mainnet("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"),
+            80_000,
+            &mainnet("bc1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3qccfmv3"),
+            fee_rate,
+            BtcNetwork::Mainnet,
+            None,
+            None,
+            false,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn bump_fee_keeps_inputs_and_recipient() {
+        let original = build_with_change(2);
+        let bumped = bump_fee(&original, 10, None).unwrap();
+
+        let outpoints = |u: &UnsignedBtcTx| {
+            u.tx.input
+                .iter()
+                .map(|i| i.previous_output)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(outpoints(&bumped), outpoints(&original));
+        assert_eq!(bumped.prevouts, original.prevouts);
+        assert_eq!(bumped.tx.output.len(), original.tx.output.len());
+        assert_eq!(bumped.tx.output[0], original.tx.output[0]);
+        assert_eq!(bumped.change_address, original.change_address);
+    }
+
+    #[test]
+    fn bump_fee_reduces_change_and_raises_fee() {
+        let original = build_with_change(2);
+        let bumped = bump_fee(&original, 10, None).unwrap();
+
+        assert!(bumped.tx.output[1].value < original.tx.output[1].value);
+        assert!(fee_of(&bumped) > fee_of(&original));
+        assert_eq!(fee_of(&bumped), estimate_fee(2, 2, 10));
+    }
+
+    #[test]
+    fn bump_fee_with_change_to_recipient_address_reduces_last_output() {
+        let script_hex = format!("0014{}", "ab".repeat(20));
+        let addr = mainnet("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4");
+        let utxos = vec![make_test_utxo(&"c".repeat(64), 0, 100_000, &script_hex)];
+        let original = build_p2wpkh_transaction(
+            &utxos,
+            &addr,
+            50_000,
+            &addr,
+            1,
+            BtcNetwork::Mainnet,
+            None,
+            None,
+            false,
+        )
+        .unwrap();
+
+        let bumped = bump_fee(&original, 5, None).unwrap();
+        assert_eq!(bumped.tx.output[0].value.to_sat(), 50_000);
+        assert!(bumped.tx.output[1].value < original.tx.output[1].value);
+    }
+
+    #[test]
+    fn bump_fee_requires_rbf_signal() {
+        let mut original = build_with_change(2);
+        original.tx.input[1].sequence = Sequence::MAX;
+
+        let result = bump_fee(&original, 10, None);
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("replace-by-fee"));
+    }
+
+    #[test]
+    fn bump_fee_rejects_lower_or_equal_rate() {
+        let original = build_with_change(10);
+        assert!(bump_fee(&original, 10, None).is_err());
+        assert!(bump_fee(&original, 5, None).is_err());
+    }
+
+    #[test]
+    fn bump_fee_requires_incremental_relay_fee() {
+        let mut original = build_with_change(2);
+        let vsize = fee_of(&original) / 2;
+        // Half a sat/vbyte more than the rate already left in the fee.
+        original.tx.output[1].value -= Amount::from_sat(vsize / 2);
+
+        let err = bump_fee(&original, 3, None).unwrap_err();
+        assert!(err.to_string().contains("incremental relay fee"), "{err}");
+        assert!(bump_fee(&original, 4, None).is_ok());
+    }
+
+    #[test]
+    fn bump_fee_rejects_change_below_dust() {
+        let original = build_with_change(2);
+        // 110_000 in, 80_000 to the recipient: 2 inputs + 2 outputs = 209 vbytes,
+        // so 141 sat/vbyte leaves 531 sat of change, below the dust threshold.
+        let result = bump_fee(&original, 141, None);
+        assert!(result.unwrap_err().to_string().contains("dust"));
+    }
+
+    #[test]
+    fn bump_fee_uses_custom_dust_threshold() {
+        let original = build_with_change(2);
+        // 531 sat of change: kept above relay dust, dropped by the default.
+        let bumped = bump_fee(&original, 141, Some(P2WPKH_DUST_THRESHOLD_SAT)).unwrap();
+        assert_eq!(bumped.tx.output[1].value.to_sat(), 531);
+
+        // 138 sat/vbyte leaves 1_158 sat, dust under a 2_000 sat threshold.
+        assert!(bump_fee(&original, 138, None).is_ok());
+        let err = bump_fee(&original, 138, Some(2_000)).unwrap_err();
+        assert!(err.to_string().contains("dust"), "{err}");
+
+        // A threshold below relay dust is raised to it.
+        let err = bump_fee(&original, 143, Some(0)).unwrap_err();
+        assert!(err.to_string().contains("dust"), "{err}");
+    }
+
+    #[test]
+    fn bump_fee_rejects_fee_above_inputs() {
+        let original = build_with_change(2);
+        let result = bump_fee(&original, 1_000, None);
+        assert!(matches!(result, Err(BtcError::InsufficientFunds { .. })));
+    }
+
+    #[test]
+    fn bump_fee_overflow_fails() {
+        let original = build_with_change(2);
+        let err = bump_fee(&original, u64::MAX, None).unwrap_err();
+        assert!(err.to_string().contains("overflows"), "{err}");
+    }
+
+    #[test]
+    fn bump_fee_without_change_fails() {
+        let mut original = build_with_change(2);
+        original.tx.output.truncate(1);
+        original.change_address = None;
+
+        assert!(bump_fee(&original, 10, None).is_err());
+    }
+
+    // ─── build_cpfp_transaction ───
+
+    const CHILD_VSIZE: u64 = TX_OVERHEAD_VBYTES + P2WPKH_INPUT_VBYTES + OUTPUT_VBYTES;
+
+    fn build_cpfp(
+        parent_vsize: u64,
+        parent_fee: u64,
+        rate: u64,
+    ) -> Result<UnsignedBtcTx, BtcError> {
+        let script = hex::decode(format!("0014{}", "ab".repeat(20))).unwrap();
+        build_cpfp_transaction(
+            &"d".repeat(64),
+            1,
             50_000,
+            &script,
+            parent_vsize,
+            parent_fee,
+            &mainnet("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"),
+            rate,
+            BtcNetwork::Mainnet,
+        )
+    }
+
+    #[test]
+    fn cpfp_spends_parent_output() {
+        let child = build_cpfp(200, 0, 5).unwrap();
+
+        assert_eq!(child.tx.input.len(), 1);
+        assert_eq!(child.tx.output.len(), 1);
+        assert_eq!(child.tx.input[0].previous_output.vout, 1);
+        assert_eq!(
+            child.tx.input[0].previous_output.txid.to_string(),
+            "d".repeat(64)
+        );
+        assert_eq!(child.prevouts[0].value.to_sat(), 50_000);
+        assert_eq!(child.change_address, None);
+    }
+
+    #[test]
+    fn cpfp_fee_covers_parent_and_child_at_target_rate() {
+        let parent_vsize = 200;
+        let rate = 20;
+        let child = build_cpfp(parent_vsize, 0, rate).unwrap();
+
+        let child_fee = fee_of(&child);
+        assert!(child_fee >= (parent_vsize + CHILD_VSIZE) * rate);
+        assert_eq!(child_fee, (200 + CHILD_VSIZE) * 20);
+    }
+
+    #[test]
+    fn cpfp_credits_fee_already_paid_by_parent() {
+        let child = build_cpfp(200, 1_000, 20).unwrap();
+
+        let package_fee = fee_of(&child) + 1_000;
+        assert_eq!(package_fee, (200 + CHILD_VSIZE) * 20);
+    }
+
+    #[test]
+    fn cpfp_child_pays_at_least_its_own_size() {
+        // Parent already overpays; the child still pays for itself.
+        let child = build_cpfp(200, 100_000, 10).unwrap();
+        assert_eq!(fee_of(&child), CHILD_VSIZE * 10);
+    }
+
+    #[test]
+    fn cpfp_fee_exceeding_parent_output_fails() {
+        let result = build_cpfp(10_000, 0, 100);
+        assert!(matches!(result, Err(BtcError::InsufficientFunds { .. })));
+    }
+
+    #[test]
+    fn cpfp_fee_overflow_fails() {
+        let result = build_cpfp(200, 0, u64::MAX);
+        assert!(matches!(result, Err(BtcError::TransactionBuildError(_))));
+
+        let result = build_cpfp(u64::MAX, 0, 1);
+        assert!(matches!(result, Err(BtcError::TransactionBuildError(_))));
+    }
+
+    #[test]
+    fn cpfp_dust_output_fails() {
+        // 50_000 - (200 + 110) * 160 = 400 sat left over, below dust.
+        let result = build_cpfp(200, 0, 160);
+        assert!(result.unwrap_err().to_string().contains("dust"));
+    }
+
+    #[test]
+    fn cpfp_invalid_txid_fails() {
+        let result = build_cpfp_transaction(
+            "not-a-txid",
+            0,
+            50_000,
+            &[],
+            200,
+            0,
+            &mainnet("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"),
+            5,
+            BtcNetwork::Mainnet,
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn build_transaction_insufficient_funds() {
+        let txid = "c".repeat(64);
+        let script_hex = format!("0014{}", "ef".repeat(20));
+
+        let utxos = vec![make_test_utxo(&txid, 0, 1_000, &script_hex)];
+
+        let result = build_p2wpkh_transaction(
+            &utxos,
+            &mainnet("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"),
+            500_000,
+            &mainnet("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"),
+            1,
+            BtcNetwork::Mainnet,
+            None,
+            None,
+            false,
+        );
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn build_transaction_invalid_recipient() {
+        // Recipients are typed, so malformed input never reaches the builder.
+        assert!(BtcAddress::parse("not_a_valid_address", BtcNetwork::Mainnet).is_err());
+    }
+
+    #[test]
+    fn build_transaction_wrong_network() {
+        let txid = "e".repeat(64);
+        let script_hex = format!("0014{}", "22".repeat(20));
+
+        let utxos = vec![make_test_utxo(&txid, 0, 100_000, &script_hex)];
+
+        assert!(BtcAddress::parse(
             "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4",
+            BtcNetwork::Testnet
+        )
+        .is_err());
+
+        // A mainnet address handed to a testnet build.
+        let result = build_p2wpkh_transaction(
+            &utxos,
+            &mainnet("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"),
+            50_000,
+            &mainnet("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"),
+            1,
+            BtcNetwork::Testnet,
+            None,
+            None,
+            false,
+        );
+
+        assert!(result.is_err());
+    }
+
+    fn build_with_lock_time(height: Option<u32>) -> Result<UnsignedBtcTx, BtcError> {
+        let script_hex = format!("0014{}", "ab".repeat(20));
+        let utxos = vec![
+            make_test_utxo(&"a".repeat(64), 0, 60_000, &script_hex),
+            make_test_utxo(&"b".repeat(64), 1, 60_000, &script_hex),
+        ];
+        let addr = mainnet("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4");
+        let network = BtcNetwork::Mainnet;
+        build_p2wpkh_transaction(
+            &utxos, &addr, 90_000, &addr, 1, network, None, height, false,
+        )
+    }
+
+    #[test]
+    fn lock_time_height_is_set_and_enforced() {
+        let unsigned = build_with_lock_time(Some(850_000)).unwrap();
+
+        assert_eq!(
+            unsigned.tx.lock_time,
+            LockTime::from_height(850_000).unwrap()
+        );
+        let raw = bitcoin::consensus::encode::serialize(&unsigned.tx);
+        assert_eq!(raw[raw.len() - 4..], 850_000u32.to_le_bytes());
+
+        // Every input must opt in to the locktime; RBF stays signalled.
+        assert_eq!(unsigned.tx.input.len(), 2);
+        for input in &unsigned.tx.input {
+            assert!(input.sequence.enables_absolute_lock_time());
+            assert!(input.sequence.is_rbf());
+        }
+    }
+
+    #[test]
+    fn lock_time_defaults_to_zero() {
+        let unsigned = build_with_lock_time(None).unwrap();
+        assert_eq!(unsigned.tx.lock_time, LockTime::ZERO);
+    }
+
+    #[test]
+    fn lock_time_rejects_timestamp_range() {
+        // 500_000_000 and above is a Unix time, not a height.
+        assert!(build_with_lock_time(Some(500_000_000)).is_err());
+    }
+
+    #[test]
+    fn sign_transaction_produces_valid_bytes() {
+        let txid = "a".repeat(64);
+        let script_hex = format!("0014{}", "ab".repeat(20));
+
+        let utxos = vec![make_test_utxo(&txid, 0, 100_000, &script_hex)];
+
+        let unsigned = build_p2wpkh_transaction(
+            &utxos,
+            &mainnet("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"),
+            50_000,
+            &mainnet("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"),
             1,
             BtcNetwork::Mainnet,
+            None,
+            None,
+            false,
         )
         .unwrap();
 
         // Use a known valid private key.
         let privkey = [0xcd; 32];
-        let result = sign_transaction(&unsigned, &privkey, BtcNetwork::Mainnet);
+        let signer = InputSigner::SingleKey(privkey);
+        let result = sign_transaction(&unsigned, &signer, BtcNetwork::Mainnet);
 
         assert!(result.is_ok());
         let signed_bytes = result.unwrap();
@@ -369,17 +1706,507 @@ mod tests {
 
         let unsigned = build_p2wpkh_transaction(
             &utxos,
-            "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4",
+            &mainnet("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"),
             50_000,
-            "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4",
+            &mainnet("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"),
             1,
             BtcNetwork::Mainnet,
+            None,
+            None,
+            false,
         )
         .unwrap();
 
         // All-zero is not a valid secp256k1 private key.
         let bad_key = [0u8; 32];
-        let result = sign_transaction(&unsigned, &bad_key, BtcNetwork::Mainnet);
+        let signer = InputSigner::SingleKey(bad_key);
+        let result = sign_transaction(&unsigned, &signer, BtcNetwork::Mainnet);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn sign_transaction_prevouts_mismatch_is_an_error() {
+        let script_hex = format!("0014{}", "ab".repeat(20));
+        let utxos = vec![make_test_utxo(&"a".repeat(64), 0, 100_000, &script_hex)];
+        let addr = mainnet("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4");
+        let unsigned = build_p2wpkh_transaction(
+            &utxos,
+            &addr,
+            50_000,
+            &addr,
+            1,
+            BtcNetwork::Mainnet,
+            None,
+            None,
+            false,
+        )
+        .unwrap();
+
+        let mut missing = unsigned.clone();
+        missing.prevouts.clear();
+        let signer = InputSigner::SingleKey([0xcd; 32]);
+        let err = sign_transaction(&missing, &signer, BtcNetwork::Mainnet).unwrap_err();
+        assert!(matches!(err, BtcError::SigningError(ref m) if m.contains("length mismatch")));
+
+        let mut extra = unsigned;
+        extra.prevouts.push(extra.prevouts[0].clone());
+        assert!(sign_transaction(&extra, &signer, BtcNetwork::Mainnet).is_err());
+    }
+
+    #[test]
+    fn sign_transaction_rejects_corrupted_outputs() {
+        let unsigned = build_with_change(2);
+        let signer = InputSigner::SingleKey([0xcd; 32]);
+        assert!(assert_recipient(&unsigned, BtcNetwork::Mainnet).is_ok());
+
+        // Recipient script replaced with junk after the build.
+        let mut junk = unsigned.clone();
+        junk.tx.output[0].script_pubkey = ScriptBuf::from(vec![0xde, 0xad]);
+        let err = sign_transaction(&junk, &signer, BtcNetwork::Mainnet).unwrap_err();
+        assert!(
+            matches!(err, BtcError::InvalidAddress(ref m) if m.contains("output 0")),
+            "{err}"
+        );
+
+        // Change address swapped for one on another network.
+        let mut testnet_change = unsigned.clone();
+        testnet_change.change_address = Some("tb1qw508d6qejxtdg4y5r3zarvary0c5xw7kxpjzsx".into());
+        let err = sign_transaction(&testnet_change, &signer, BtcNetwork::Mainnet).unwrap_err();
+        assert!(
+            matches!(err, BtcError::InvalidAddress(ref m) if m.contains("change")),
+            "{err}"
+        );
+
+        // Change output dropped while the change address is still recorded.
+        let mut unpaid = unsigned;
+        unpaid.tx.output.truncate(1);
+        assert!(sign_transaction(&unpaid, &signer, BtcNetwork::Mainnet).is_err());
+    }
+
+    #[test]
+    fn assert_recipient_allows_op_return_data_outputs() {
+        let mut unsigned = build_with_change(2);
+        let data = ScriptBuf::new_op_return(b"anvil");
+        unsigned.tx.output.push(TxOut {
+            value: Amount::ZERO,
+            script_pubkey: data,
+        });
+        assert!(assert_recipient(&unsigned, BtcNetwork::Mainnet).is_ok());
+
+        // Value sent to a data carrier is burned.
+        unsigned.tx.output.last_mut().unwrap().value = Amount::from_sat(1_000);
+        let err = assert_recipient(&unsigned, BtcNetwork::Mainnet).unwrap_err();
+        assert!(
+            matches!(err, BtcError::InvalidAddress(ref m) if m.contains("OP_RETURN")),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn sign_transaction_key_per_input() {
+        let script_hex = format!("0014{}", "ab".repeat(20));
+        let utxos = vec![
+            make_test_utxo(&"a".repeat(64), 0, 60_000, &script_hex),
+            make_test_utxo(&"b".repeat(64), 1, 50_000, &script_hex),
+        ];
+        let addr = mainnet("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4");
+        let unsigned = build_p2wpkh_transaction(
+            &utxos,
+            &addr,
+            90_000,
+            &addr,
+            1,
+            BtcNetwork::Mainnet,
+            None,
+            None,
+            false,
+        )
+        .unwrap();
+        assert_eq!(unsigned.tx.input.len(), 2);
+
+        let keys = [[0x11; 32], [0x22; 32]];
+        let signer = InputSigner::KeyPerInput(keys.to_vec());
+        let raw = sign_transaction(&unsigned, &signer, BtcNetwork::Mainnet).unwrap();
+        let signed: Transaction = bitcoin::consensus::deserialize(&raw).unwrap();
+
+        let secp = Secp256k1::new();
+        for (input, key) in signed.input.iter().zip(&keys) {
+            let secret = SecretKey::from_slice(key).unwrap();
+            let pubkey = bitcoin::secp256k1::PublicKey::from_secret_key(&secp, &secret);
+            assert_eq!(input.witness.nth(1).unwrap(), pubkey.serialize());
+        }
+
+        // The same key for every input matches a single-key signer.
+        assert_eq!(
+            sign_transaction(
+                &unsigned,
+                &InputSigner::KeyPerInput(vec![[0xcd; 32]; 2]),
+                BtcNetwork::Mainnet
+            )
+            .unwrap(),
+            sign_transaction(
+                &unsigned,
+                &InputSigner::SingleKey([0xcd; 32]),
+                BtcNetwork::Mainnet
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn sign_transaction_mixed_segwit_and_legacy_inputs() {
+        let secp = Secp256k1::new();
+        let keys = [[0x11; 32], [0x22; 32]];
+        let pubkeys = keys.map(|k| {
+            CompressedPublicKey(bitcoin::secp256k1::PublicKey::from_secret_key(
+                &secp,
+                &SecretKey::from_slice(&k).unwrap(),
+            ))
+        });
+        let p2wpkh = ScriptBuf::new_p2wpkh(&pubkeys[0].wpubkey_hash());
+        let p2pkh = ScriptBuf::new_p2pkh(&pubkeys[1].pubkey_hash());
+        // Largest first, so input 0 is the P2WPKH output and input 1 the P2PKH one.
+        let utxos = vec![
+            make_test_utxo(&"a".repeat(64), 0, 60_000, &p2wpkh.to_hex_string()),
+            make_test_utxo(&"b".repeat(64), 0, 50_000, &p2pkh.to_hex_string()),
+        ];
+        let addr = mainnet("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4");
+        let unsigned = build_p2wpkh_transaction(
+            &utxos,
+            &addr,
+            90_000,
+            &addr,
+            1,
+            BtcNetwork::Mainnet,
+            None,
+            None,
+            false,
+        )
+        .unwrap();
+
+        let signer = InputSigner::KeyPerInput(keys.to_vec());
+        let raw = sign_transaction(&unsigned, &signer, BtcNetwork::Mainnet).unwrap();
+        let signed: Transaction = bitcoin::consensus::deserialize(&raw).unwrap();
+
+        assert_eq!(signed.input[0].witness.len(), 2);
+        assert!(signed.input[0].script_sig.is_empty());
+        assert!(signed.input[1].witness.is_empty());
+
+        // scriptSig is <sig> <pubkey>, and the signature covers the legacy sighash.
+        let pushes: Vec<_> = signed.input[1]
+            .script_sig
+            .instructions()
+            .map(|i| i.unwrap().push_bytes().unwrap().as_bytes().to_vec())
+            .collect();
+        assert_eq!(pushes.len(), 2);
+        assert_eq!(pushes[1], pubkeys[1].to_bytes());
+        let sighash = SighashCache::new(&unsigned.tx)
+            .legacy_signature_hash(1, &p2pkh, EcdsaSighashType::All.to_u32())
+            .unwrap();
+        let (der, hash_type) = pushes[0].split_at(pushes[0].len() - 1);
+        assert_eq!(hash_type, [EcdsaSighashType::All as u8]);
+        let sig = bitcoin::secp256k1::ecdsa::Signature::from_der(der).unwrap();
+        secp.verify_ecdsa(
+            &Message::from_digest(sighash.to_byte_array()),
+            &sig,
+            &pubkeys[1].0,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn sign_transaction_rejects_p2pkh_key_mismatch() {
+        let secp = Secp256k1::new();
+        let owner = CompressedPublicKey(bitcoin::secp256k1::PublicKey::from_secret_key(
+            &secp,
+            &SecretKey::from_slice(&[0x22; 32]).unwrap(),
+        ));
+        let p2pkh = ScriptBuf::new_p2pkh(&owner.pubkey_hash());
+        let utxos = vec![make_test_utxo(
+            &"b".repeat(64),
+            0,
+            50_000,
+            &p2pkh.to_hex_string(),
+        )];
+        let addr = mainnet("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4");
+        let unsigned = build_p2wpkh_transaction(
+            &utxos,
+            &addr,
+            20_000,
+            &addr,
+            1,
+            BtcNetwork::Mainnet,
+            None,
+            None,
+            false,
+        )
+        .unwrap();
+
+        let sign_with =
+            |key| sign_transaction(&unsigned, &InputSigner::SingleKey(key), BtcNetwork::Mainnet);
+        let err = sign_with([0x11; 32]).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("does not match scriptPubKey of input 0"));
+        assert!(sign_with([0x22; 32]).is_ok());
+    }
+
+    #[test]
+    fn p2pkh_inputs_are_sized_as_legacy() {
+        let p2pkh = ScriptBuf::new_p2pkh(&bitcoin::PubkeyHash::all_zeros());
+        let p2wpkh = ScriptBuf::new_p2wpkh(&bitcoin::WPubkeyHash::all_zeros());
+        assert_eq!(
+            estimate_fee_for_scripts([p2wpkh.as_bytes()], 2, 10),
+            estimate_fee(1, 2, 10)
+        );
+        assert_eq!(
+            estimate_fee_for_scripts([p2pkh.as_bytes()], 2, 10) - estimate_fee(1, 2, 10),
+            (P2PKH_INPUT_VBYTES - P2WPKH_INPUT_VBYTES) * 10
+        );
+
+        let utxos = vec![make_test_utxo(
+            &"b".repeat(64),
+            0,
+            50_000,
+            &p2pkh.to_hex_string(),
+        )];
+        let addr = mainnet("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4");
+        let unsigned = build_p2wpkh_transaction(
+            &utxos,
+            &addr,
+            20_000,
+            &addr,
+            10,
+            BtcNetwork::Mainnet,
+            None,
+            None,
+            false,
+        )
+        .unwrap();
+        assert_eq!(
+            fee_of(&unsigned),
+            estimate_fee_for_scripts([p2pkh.as_bytes()], 2, 10)
+        );
+    }
+
+    #[test]
+    fn json_roundtrip_signs_identically() {
+        let utxos = vec![
+            make_test_utxo(
+                &"a".repeat(64),
+                0,
+                60_000,
+                &format!("0014{}", "ab".repeat(20)),
+            ),
+            make_test_utxo(
+                &"b".repeat(64),
+                1,
+                50_000,
+                &format!("0014{}", "cd".repeat(20)),
+            ),
+        ];
+        let addr = mainnet("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4");
+        let unsigned = build_p2wpkh_transaction(
+            &utxos,
+            &addr,
+            90_000,
+            &addr,
+            1,
+            BtcNetwork::Mainnet,
+            None,
+            None,
+            false,
+        )
+        .unwrap();
+
+        let json = unsigned.to_json().unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["prevouts"][1]["valueSat"], 50_000);
+
+        let restored = UnsignedBtcTx::from_json(&json).unwrap();
+        assert_eq!(restored.tx, unsigned.tx);
+        assert_eq!(restored.prevouts, unsigned.prevouts);
+        assert_eq!(restored.change_address, unsigned.change_address);
+
+        let signer = InputSigner::KeyPerInput(vec![[0x11; 32], [0x22; 32]]);
+        assert_eq!(
+            sign_transaction(&restored, &signer, BtcNetwork::Mainnet).unwrap(),
+            sign_transaction(&unsigned, &signer, BtcNetwork::Mainnet).unwrap()
+        );
+    }
+
+    #[test]
+    fn from_json_rejects_malformed_input() {
+        let utxos = vec![make_test_utxo(&"a".repeat(64), 0, 100_000, "0014ab")];
+        let addr = mainnet("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4");
+        let unsigned = build_p2wpkh_transaction(
+            &utxos,
+            &addr,
+            50_000,
+            &addr,
+            1,
+            BtcNetwork::Mainnet,
+            None,
+            None,
+            false,
+        )
+        .unwrap();
+        let mut value: serde_json::Value =
+            serde_json::from_str(&unsigned.to_json().unwrap()).unwrap();
+
+        let mut no_prevouts = value.clone();
+        no_prevouts["prevouts"] = serde_json::json!([]);
+        let mut bad_script = value.clone();
+        bad_script["prevouts"][0]["scriptPubKey"] = "zz".into();
+        value["txHex"] = "0200".into();
+
+        for bad in [no_prevouts, bad_script, value] {
+            let result = UnsignedBtcTx::from_json(&bad.to_string());
+            assert!(
+                matches!(result, Err(BtcError::TransactionBuildError(_))),
+                "{bad}"
+            );
+        }
+        assert!(UnsignedBtcTx::from_json("{}").is_err());
+    }
+
+    #[test]
+    fn sign_transaction_rejects_unsupported_script() {
+        // P2SH is neither P2WPKH nor P2PKH.
+        let script_hex = format!("a914{}87", "ab".repeat(20));
+        let utxos = vec![make_test_utxo(&"a".repeat(64), 0, 100_000, &script_hex)];
+        let addr = mainnet("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4");
+        let unsigned = build_p2wpkh_transaction(
+            &utxos,
+            &addr,
+            50_000,
+            &addr,
+            1,
+            BtcNetwork::Mainnet,
+            None,
+            None,
+            false,
+        )
+        .unwrap();
+
+        let signer = InputSigner::SingleKey([0xcd; 32]);
+        let err = sign_transaction(&unsigned, &signer, BtcNetwork::Mainnet).unwrap_err();
+        assert!(matches!(err, BtcError::SigningError(ref m) if m.contains("unsupported")));
+    }
+
+    #[test]
+    fn sign_transaction_key_per_input_length_mismatch() {
+        let script_hex = format!("0014{}", "ab".repeat(20));
+        let utxos = vec![make_test_utxo(&"a".repeat(64), 0, 100_000, &script_hex)];
+        let addr = mainnet("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4");
+        let unsigned = build_p2wpkh_transaction(
+            &utxos,
+            &addr,
+            50_000,
+            &addr,
+            1,
+            BtcNetwork::Mainnet,
+            None,
+            None,
+            false,
+        )
+        .unwrap();
+
+        let signer = InputSigner::KeyPerInput(vec![[0x11; 32], [0x22; 32]]);
+        let err = sign_transaction(&unsigned, &signer, BtcNetwork::Mainnet).unwrap_err();
+        assert!(matches!(err, BtcError::SigningError(ref m) if m.contains("one key per input")));
+    }
+
+    #[test]
+    fn build_transaction_reports_explicit_change_address() {
+        let txid = "a".repeat(64);
+        let script_hex = format!("0014{}", "ab".repeat(20));
+        let utxos = vec![make_test_utxo(&txid, 0, 100_000, &script_hex)];
+        let change = "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4";
+
+        let unsigned = build_p2wpkh_transaction(
+            &utxos,
+            &mainnet("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"),
+            50_000,
+            &mainnet(change),
+            1,
+            BtcNetwork::Mainnet,
+            None,
+            None,
+            false,
+        )
+        .unwrap();
+
+        assert_eq!(unsigned.change_address.as_deref(), Some(change));
+    }
+
+    #[test]
+    fn build_transaction_dust_change_reports_no_change_address() {
+        let txid = "b".repeat(64);
+        let script_hex = format!("0014{}", "cd".repeat(20));
+        let utxos = vec![make_test_utxo(&txid, 0, 100_000, &script_hex)];
+
+        let unsigned = build_p2wpkh_transaction(
+            &utxos,
+            &mainnet("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"),
+            99_700,
+            &mainnet("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"),
+            1,
+            BtcNetwork::Mainnet,
+            None,
+            None,
+            false,
+        )
+        .unwrap();
+
+        assert!(unsigned.change_address.is_none());
+    }
+
+    #[test]
+    fn build_with_change_key_pays_change_to_derived_script() {
+        let txid = "c".repeat(64);
+        let script_hex = format!("0014{}", "ab".repeat(20));
+        let utxos = vec![make_test_utxo(&txid, 0, 100_000, &script_hex)];
+
+        let secp = Secp256k1::new();
+        let change_sk = SecretKey::from_slice(&[0x24; 32]).unwrap();
+        let change_pk = CompressedPublicKey(bitcoin::secp256k1::PublicKey::from_secret_key(
+            &secp, &change_sk,
+        ));
+
+        let unsigned = build_p2wpkh_transaction_with_change_key(
+            &utxos,
+            &mainnet("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"),
+            50_000,
+            &change_pk.to_bytes(),
+            1,
+            BtcNetwork::Mainnet,
+        )
+        .unwrap();
+
+        assert_eq!(unsigned.tx.output.len(), 2);
+        assert_eq!(
+            unsigned.tx.output[1].script_pubkey,
+            ScriptBuf::new_p2wpkh(&change_pk.wpubkey_hash())
+        );
+        let expected_addr = bitcoin::Address::p2wpkh(&change_pk, bitcoin::Network::Bitcoin);
+        assert_eq!(unsigned.change_address, Some(expected_addr.to_string()));
+    }
+
+    #[test]
+    fn build_with_change_key_invalid_pubkey_fails() {
+        let txid = "d".repeat(64);
+        let script_hex = format!("0014{}", "ab".repeat(20));
+        let utxos = vec![make_test_utxo(&txid, 0, 100_000, &script_hex)];
+
+        let result = build_p2wpkh_transaction_with_change_key(
+            &utxos,
+            &mainnet("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"),
+            50_000,
+            &[0u8; 33],
+            1,
+            BtcNetwork::Mainnet,
+        );
         assert!(result.is_err());
     }
 
@@ -396,19 +2223,23 @@ mod tests {
         let public_key = bitcoin::secp256k1::PublicKey::from_secret_key(&secp, &secret_key);
         let compressed = CompressedPublicKey(public_key);
         let addr = bitcoin::Address::p2wpkh(&compressed, bitcoin::Network::Testnet);
-        let addr_str = addr.to_string();
+        let addr = BtcAddress::parse(&addr.to_string(), BtcNetwork::Testnet).unwrap();
 
         let unsigned = build_p2wpkh_transaction(
             &utxos,
-            &addr_str,
+            &addr,
             100_000,
-            &addr_str,
+            &addr,
             2,
             BtcNetwork::Testnet,
+            None,
+            None,
+            false,
         )
         .unwrap();
 
-        let signed = sign_transaction(&unsigned, &[0x42; 32], BtcNetwork::Testnet);
+        let signer = InputSigner::SingleKey([0x42; 32]);
+        let signed = sign_transaction(&unsigned, &signer, BtcNetwork::Testnet);
         assert!(signed.is_ok());
         assert!(signed.unwrap().len() > 100);
     }
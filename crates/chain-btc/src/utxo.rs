@@ -25,12 +25,17 @@ pub struct UtxoSelection {
 /// Select UTXOs to cover `target_sat` plus estimated fees.
 ///
 /// Uses a simple largest-first (descending by value) coin selection strategy.
-/// The estimated fee is computed for a P2WPKH transaction with the number of
-/// selected inputs and two outputs (recipient + change).
+/// The estimated fee is computed for the selected inputs, each sized by its
+/// script type, and two outputs (recipient + change).
+///
+/// Outpoints listed in `locked` (`(txid, vout)`, e.g. UTXOs carrying
+/// inscriptions) are never selected; if the rest cannot cover the target the
+/// result is `InsufficientFunds`, with `available` counting only unlocked value.
 pub fn select_utxos(
     utxos: &[Utxo],
     target_sat: u64,
     fee_rate_sat_vbyte: u64,
+    locked: &[(String, u32)],
 ) -> Result<UtxoSelection, BtcError> {
     if utxos.is_empty() {
         return Err(BtcError::TransactionBuildError(
@@ -38,9 +43,16 @@ pub fn select_utxos(
         ));
     }
 
-    // Sort by value descending (largest first).
-    let mut sorted: Vec<&Utxo> = utxos.iter().collect();
-    sorted.sort_by(|a, b| b.amount_sat.cmp(&a.amount_sat));
+    // Drop locked outpoints, then sort by value descending (largest first).
+    let mut sorted: Vec<&Utxo> = utxos
+        .iter()
+        .filter(|u| {
+            !locked
+                .iter()
+                .any(|(txid, vout)| *txid == u.txid && *vout == u.vout)
+        })
+        .collect();
+    sorted.sort_by_key(|u| std::cmp::Reverse(u.amount_sat));
 
     let mut selected: Vec<Utxo> = Vec::new();
     let mut total_sat: u64 = 0;
@@ -49,26 +61,162 @@ pub fn select_utxos(
         selected.push((*utxo).clone());
         total_sat += utxo.amount_sat;
 
-        // Estimate fee with current selection count and 2 outputs (recipient + change).
-        let fee = crate::transaction::estimate_fee(selected.len(), 2, fee_rate_sat_vbyte);
+        // Estimate fee with current selection and 2 outputs (recipient + change).
+        let fee = estimate_selection_fee(&selected, fee_rate_sat_vbyte);
         if total_sat >= target_sat + fee {
             return Ok(UtxoSelection { selected, total_sat });
         }
     }
 
     // Even after selecting all UTXOs, check if we have enough.
-    let fee = crate::transaction::estimate_fee(selected.len(), 2, fee_rate_sat_vbyte);
+    let fee = estimate_selection_fee(&selected, fee_rate_sat_vbyte);
     if total_sat >= target_sat + fee {
         return Ok(UtxoSelection { selected, total_sat });
     }
 
-    Err(BtcError::TransactionBuildError(format!(
-        "insufficient funds: have {} sat, need {} sat (target {} + fee {})",
-        total_sat,
-        target_sat + fee,
+    Err(BtcError::InsufficientFunds {
+        needed: target_sat + fee,
+        available: total_sat,
+    })
+}
+
+/// Fee for spending `selected` into two outputs (recipient + change).
+fn estimate_selection_fee(selected: &[Utxo], fee_rate_sat_vbyte: u64) -> u64 {
+    use crate::transaction::{estimate_fee_for_scripts, utxo_scripts};
+    estimate_fee_for_scripts(utxo_scripts(selected), 2, fee_rate_sat_vbyte)
+}
+
+/// Upper bound on subsets visited by [`select_min_fee`] before it settles for
+/// the best selection found so far.
+const MIN_FEE_SEARCH_TRIES: usize = 100_000;
+
+/// Select the fewest UTXOs that cover `target_sat` plus fees, wasting the
+/// least value on change.
+///
+/// The fee grows with the input count, so the lowest-fee selections are
+/// those with the fewest inputs. That count is what [`select_utxos`] already
+/// reaches by going largest-first. This function keeps that count, but among
+/// such selections picks the one with the smallest total that still covers
+/// the target and its own fee (P2PKH inputs cost more than P2WPKH), leaving
+/// the large UTXOs for later sends.
+///
+/// The search is capped at `MIN_FEE_SEARCH_TRIES` subsets; past that, the best
+/// selection found so far (at worst the largest-first one) is returned.
+pub fn select_min_fee(
+    utxos: &[Utxo],
+    target_sat: u64,
+    fee_rate_sat_vbyte: u64,
+) -> Result<UtxoSelection, BtcError> {
+    let largest_first = select_utxos(utxos, target_sat, fee_rate_sat_vbyte, &[])?;
+    let count = largest_first.selected.len();
+    // Cheapest possible fee for `count` inputs (all P2WPKH), used for pruning.
+    let min_needed = target_sat + crate::transaction::estimate_fee(count, 2, fee_rate_sat_vbyte);
+
+    let mut sorted: Vec<&Utxo> = utxos.iter().collect();
+    sorted.sort_by_key(|u| std::cmp::Reverse(u.amount_sat));
+
+    let mut search = MinFeeSearch {
+        sorted: &sorted,
         target_sat,
-        fee,
-    )))
+        fee_rate_sat_vbyte,
+        min_needed,
+        tries: 0,
+        chosen: Vec::with_capacity(count),
+        best: (0..count).collect(),
+        best_total: largest_first.total_sat,
+    };
+    search.visit(0, count, 0);
+
+    let selected: Vec<Utxo> = search.best.iter().map(|&i| sorted[i].clone()).collect();
+    Ok(UtxoSelection {
+        total_sat: search.best_total,
+        selected,
+    })
+}
+
+/// Depth-first search state for [`select_min_fee`], over UTXOs sorted by
+/// value descending.
+struct MinFeeSearch<'a> {
+    sorted: &'a [&'a Utxo],
+    target_sat: u64,
+    fee_rate_sat_vbyte: u64,
+    min_needed: u64,
+    tries: usize,
+    chosen: Vec<usize>,
+    best: Vec<usize>,
+    best_total: u64,
+}
+
+impl MinFeeSearch<'_> {
+    /// Choose `remaining` more UTXOs from `sorted[start..]`, given `total` so far.
+    fn visit(&mut self, start: usize, remaining: usize, total: u64) {
+        self.tries += 1;
+        if self.tries > MIN_FEE_SEARCH_TRIES || total >= self.best_total {
+            return;
+        }
+        if remaining == 0 {
+            let chosen: Vec<Utxo> = self
+                .chosen
+                .iter()
+                .map(|&i| self.sorted[i].clone())
+                .collect();
+            if total >= self.target_sat + estimate_selection_fee(&chosen, self.fee_rate_sat_vbyte) {
+                self.best_total = total;
+                self.best.clone_from(&self.chosen);
+            }
+            return;
+        }
+        if start + remaining > self.sorted.len() {
+            return;
+        }
+        // The next `remaining` UTXOs are the largest left; if even they fall
+        // short, nothing further along can cover the target.
+        let reachable: u64 = self.sorted[start..start + remaining]
+            .iter()
+            .map(|u| u.amount_sat)
+            .sum();
+        if total + reachable < self.min_needed {
+            return;
+        }
+
+        for i in start..=self.sorted.len() - remaining {
+            self.chosen.push(i);
+            self.visit(i + 1, remaining - 1, total + self.sorted[i].amount_sat);
+            self.chosen.pop();
+        }
+    }
+}
+
+/// Keep only the UTXOs worth spending at `fee_rate_sat_vbyte`.
+///
+/// A P2WPKH input adds `P2WPKH_INPUT_VBYTES` (68) vbytes and a P2PKH input
+/// `P2PKH_INPUT_VBYTES` (148), so a UTXO worth no more than that times
+/// `fee_rate` sats costs at least as much in fee as it adds to the
+/// transaction. Such UTXOs are dropped; spending them is better left for a
+/// low-fee consolidation.
+pub fn economical_utxos(utxos: &[Utxo], fee_rate_sat_vbyte: u64) -> Vec<Utxo> {
+    utxos
+        .iter()
+        .filter(|u| {
+            let spend_cost = crate::transaction::input_vbytes(&u.script_pubkey)
+                .saturating_mul(fee_rate_sat_vbyte);
+            u.amount_sat > spend_cost
+        })
+        .cloned()
+        .collect()
+}
+
+/// Same as [`select_utxos`], but only considers [`economical_utxos`].
+///
+/// On failure, `InsufficientFunds::available` counts only the economical
+/// UTXOs, since that is what can be spent at this fee rate without loss.
+pub fn select_economical_utxos(
+    utxos: &[Utxo],
+    target_sat: u64,
+    fee_rate_sat_vbyte: u64,
+) -> Result<UtxoSelection, BtcError> {
+    let economical = economical_utxos(utxos, fee_rate_sat_vbyte);
+    select_utxos(&economical, target_sat, fee_rate_sat_vbyte, &[])
 }
 
 #[cfg(test)]
@@ -90,7 +238,7 @@ mod tests {
             make_utxo("aaaa", 0, 100_000),
             make_utxo("bbbb", 0, 50_000),
         ];
-        let selection = select_utxos(&utxos, 40_000, 1).unwrap();
+        let selection = select_utxos(&utxos, 40_000, 1, &[]).unwrap();
         assert_eq!(selection.selected.len(), 1);
         assert_eq!(selection.total_sat, 100_000);
     }
@@ -102,7 +250,7 @@ mod tests {
             make_utxo("bbbb", 0, 30_000),
             make_utxo("cccc", 0, 30_000),
         ];
-        let selection = select_utxos(&utxos, 55_000, 1).unwrap();
+        let selection = select_utxos(&utxos, 55_000, 1, &[]).unwrap();
         assert!(selection.selected.len() >= 2);
         assert!(selection.total_sat >= 55_000);
     }
@@ -110,15 +258,23 @@ mod tests {
     #[test]
     fn insufficient_funds_returns_error() {
         let utxos = vec![make_utxo("aaaa", 0, 1_000)];
-        let result = select_utxos(&utxos, 500_000, 1);
+        let result = select_utxos(&utxos, 500_000, 1, &[]);
         assert!(result.is_err());
-        let msg = result.unwrap_err().to_string();
-        assert!(msg.contains("insufficient funds"));
+        let err = result.unwrap_err();
+        assert!(err.to_string().contains("insufficient funds"));
+        match err {
+            BtcError::InsufficientFunds { needed, available } => {
+                let fee = crate::transaction::estimate_fee(1, 2, 1);
+                assert_eq!(needed, 500_000 + fee);
+                assert_eq!(available, 1_000);
+            }
+            other => panic!("expected InsufficientFunds, got: {other}"),
+        }
     }
 
     #[test]
     fn empty_utxos_returns_error() {
-        let result = select_utxos(&[], 1_000, 1);
+        let result = select_utxos(&[], 1_000, 1, &[]);
         assert!(result.is_err());
     }
 
@@ -129,7 +285,7 @@ mod tests {
             make_utxo("large", 0, 100_000),
             make_utxo("medium", 0, 50_000),
         ];
-        let selection = select_utxos(&utxos, 10_000, 1).unwrap();
+        let selection = select_utxos(&utxos, 10_000, 1, &[]).unwrap();
         // Should pick the largest first, so only one UTXO needed.
         assert_eq!(selection.selected.len(), 1);
         assert_eq!(selection.selected[0].txid, "large");
@@ -142,8 +298,8 @@ mod tests {
             make_utxo("bbbb", 0, 50_000),
         ];
         // With a very high fee rate, one UTXO may not be enough.
-        let result_low = select_utxos(&utxos, 40_000, 1);
-        let result_high = select_utxos(&utxos, 40_000, 500);
+        let result_low = select_utxos(&utxos, 40_000, 1, &[]);
+        let result_high = select_utxos(&utxos, 40_000, 500, &[]);
 
         assert!(result_low.is_ok());
         // High fee rate may need more UTXOs or may fail.
@@ -151,4 +307,134 @@ mod tests {
             assert!(sel.selected.len() >= result_low.unwrap().selected.len());
         }
     }
+
+    #[test]
+    fn locked_utxo_is_never_selected() {
+        let utxos = vec![
+            make_utxo("inscription", 0, 500_000),
+            make_utxo("inscription", 1, 30_000),
+            make_utxo("plain", 0, 40_000),
+        ];
+        let locked = [("inscription".to_string(), 0)];
+
+        let selection = select_utxos(&utxos, 50_000, 1, &locked).unwrap();
+        assert!(!selection
+            .selected
+            .iter()
+            .any(|u| u.txid == "inscription" && u.vout == 0));
+        assert_eq!(selection.selected.len(), 2);
+        assert_eq!(selection.total_sat, 70_000);
+    }
+
+    #[test]
+    fn locking_too_much_is_insufficient_funds() {
+        let utxos = vec![make_utxo("aaaa", 0, 100_000), make_utxo("bbbb", 0, 20_000)];
+        let locked = [("aaaa".to_string(), 0)];
+
+        match select_utxos(&utxos, 40_000, 1, &locked) {
+            Err(BtcError::InsufficientFunds { available, .. }) => assert_eq!(available, 20_000),
+            other => panic!("expected InsufficientFunds, got {:?}", other),
+        }
+
+        let all = [("aaaa".to_string(), 0), ("bbbb".to_string(), 0)];
+        match select_utxos(&utxos, 1_000, 1, &all) {
+            Err(BtcError::InsufficientFunds { available, .. }) => assert_eq!(available, 0),
+            other => panic!("expected InsufficientFunds, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn min_fee_prefers_smallest_covering_utxo() {
+        let utxos = vec![
+            make_utxo("large", 0, 100_000),
+            make_utxo("medium", 0, 30_000),
+            make_utxo("small", 0, 5_000),
+        ];
+        let largest = select_utxos(&utxos, 20_000, 1, &[]).unwrap();
+        let min_fee = select_min_fee(&utxos, 20_000, 1).unwrap();
+
+        assert_eq!(largest.selected[0].txid, "large");
+        assert_eq!(min_fee.selected.len(), 1);
+        assert_eq!(min_fee.selected[0].txid, "medium");
+        let fee = crate::transaction::estimate_fee(1, 2, 1);
+        assert!(min_fee.total_sat >= 20_000 + fee);
+    }
+
+    #[test]
+    fn min_fee_keeps_input_count_but_shrinks_change() {
+        let utxos = vec![
+            make_utxo("a", 0, 60_000),
+            make_utxo("b", 0, 50_000),
+            make_utxo("c", 0, 45_000),
+            make_utxo("d", 0, 20_000),
+        ];
+        let largest = select_utxos(&utxos, 90_000, 1, &[]).unwrap();
+        let min_fee = select_min_fee(&utxos, 90_000, 1).unwrap();
+
+        // Same number of inputs, hence the same fee; both cover the target.
+        assert_eq!(largest.selected.len(), 2);
+        assert_eq!(min_fee.selected.len(), 2);
+        let fee = crate::transaction::estimate_fee(2, 2, 1);
+        assert!(largest.total_sat >= 90_000 + fee);
+        assert!(min_fee.total_sat >= 90_000 + fee);
+
+        assert_eq!(largest.total_sat, 110_000);
+        assert_eq!(min_fee.total_sat, 95_000);
+        let mut txids: Vec<_> = min_fee.selected.iter().map(|u| u.txid.as_str()).collect();
+        txids.sort();
+        assert_eq!(txids, ["b", "c"]);
+    }
+
+    #[test]
+    fn min_fee_insufficient_funds_matches_largest_first() {
+        let utxos = vec![make_utxo("aaaa", 0, 1_000)];
+        assert!(matches!(
+            select_min_fee(&utxos, 500_000, 1),
+            Err(BtcError::InsufficientFunds {
+                available: 1_000,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn economical_utxos_depends_on_fee_rate() {
+        let utxos = vec![make_utxo("aaaa", 0, 500), make_utxo("bbbb", 0, 50_000)];
+
+        // 68 vB * 10 sat/vB = 680 sat to spend: the 500-sat UTXO is a loss.
+        let at_10 = economical_utxos(&utxos, 10);
+        assert_eq!(at_10.len(), 1);
+        assert_eq!(at_10[0].amount_sat, 50_000);
+
+        // 68 vB * 1 sat/vB = 68 sat to spend: worth including.
+        assert_eq!(economical_utxos(&utxos, 1).len(), 2);
+    }
+
+    #[test]
+    fn select_economical_utxos_skips_dust() {
+        let utxos = vec![
+            make_utxo("aaaa", 0, 20_000),
+            make_utxo("bbbb", 0, 500),
+            make_utxo("cccc", 0, 600),
+        ];
+
+        // Adding dust never makes a selection succeed; it only inflates the
+        // reported balance. Economical selection reports what is spendable.
+        let available = |result: Result<UtxoSelection, BtcError>| match result {
+            Err(BtcError::InsufficientFunds { available, .. }) => available,
+            other => panic!("expected InsufficientFunds, got: {other:?}"),
+        };
+        assert_eq!(available(select_utxos(&utxos, 19_000, 10, &[])), 21_100);
+        assert_eq!(
+            available(select_economical_utxos(&utxos, 19_000, 10)),
+            20_000
+        );
+        assert_eq!(
+            select_economical_utxos(&utxos, 10_000, 10)
+                .unwrap()
+                .selected
+                .len(),
+            1
+        );
+    }
 }
@@ -1,7 +1,10 @@
-/// Minimal ABI encoding for EVM function calls.
-///
-/// This module provides just enough ABI encoding to build ERC-20 and similar
-/// contract call data without pulling in a full ABI parser.
+//! Minimal ABI encoding for EVM function calls.
+//!
+//! This module provides just enough ABI encoding to build ERC-20 and similar
+//! contract call data without pulling in a full ABI parser. It only needs
+//! `alloc`, so it is also available when the `std` feature is disabled.
+
+use alloc::vec::Vec;
 
 /// A single ABI-encoded parameter.
 #[derive(Debug, Clone)]
@@ -1,9 +1,57 @@
+use core::fmt;
+
+use crypto_utils::hashes::keccak256;
 use k256::elliptic_curve::sec1::{FromEncodedPoint, ToEncodedPoint};
 use k256::{EncodedPoint, PublicKey};
-use sha3::{Digest, Keccak256};
 
 use crate::error::EthError;
 
+/// An Ethereum address that has passed [`validate_address`].
+///
+/// Transaction builders take this instead of `&str`, so a string meant for
+/// another chain is rejected where it enters the wallet rather than deep in
+/// encoding. The original spelling is kept for display.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EthAddress {
+    text: String,
+    bytes: [u8; 20],
+}
+
+impl EthAddress {
+    /// Parses and validates a 0x-prefixed hex address. Mixed-case input must
+    /// carry a correct EIP-55 checksum.
+    pub fn parse(address: &str) -> Result<Self, EthError> {
+        if !validate_address(address)? {
+            return Err(EthError::InvalidAddress("EIP-55 checksum mismatch".into()));
+        }
+
+        let mut bytes = [0u8; 20];
+        hex::decode_to_slice(&address[2..], &mut bytes)
+            .map_err(|e| EthError::InvalidAddress(format!("invalid hex: {e}")))?;
+
+        Ok(Self {
+            text: address.to_string(),
+            bytes,
+        })
+    }
+
+    /// The address as it was parsed.
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    /// The raw 20-byte address.
+    pub fn as_bytes(&self) -> &[u8; 20] {
+        &self.bytes
+    }
+}
+
+impl fmt::Display for EthAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
 /// Derives an EIP-55 checksummed Ethereum address from an uncompressed secp256k1
 /// public key (65 bytes, starting with 0x04).
 ///
@@ -17,7 +65,7 @@ pub fn pubkey_to_eth_address(uncompressed_pubkey: &[u8; 65]) -> Result<String, E
     }
 
     // Keccak-256 of the 64-byte key (skip the 0x04 prefix).
-    let hash = Keccak256::digest(&uncompressed_pubkey[1..]);
+    let hash = keccak256(&uncompressed_pubkey[1..]);
 
     // Last 20 bytes are the raw address.
     let mut addr_bytes = [0u8; 20];
@@ -33,30 +81,71 @@ pub fn pubkey_to_eth_address(uncompressed_pubkey: &[u8; 65]) -> Result<String, E
 /// The compressed key is first decompressed via k256, then the standard
 /// derivation path is followed.
 pub fn pubkey_bytes_to_eth_address(pubkey_33_bytes: &[u8; 33]) -> Result<String, EthError> {
-    let encoded = EncodedPoint::from_bytes(pubkey_33_bytes).map_err(|e| {
-        EthError::InvalidPublicKey(format!("invalid compressed key encoding: {e}"))
-    })?;
+    pubkey_to_eth_address(&decompress_pubkey(pubkey_33_bytes)?)
+}
 
-    let pubkey: Option<PublicKey> = PublicKey::from_encoded_point(&encoded).into();
-    let pubkey = pubkey.ok_or_else(|| {
-        EthError::InvalidPublicKey("point is not on the secp256k1 curve".into())
-    })?;
+/// Converts an uncompressed secp256k1 public key (65 bytes, 0x04 prefix) to
+/// its compressed SEC1 form (33 bytes, 0x02/0x03 prefix).
+///
+/// Fails if the point is not on the curve.
+pub fn compress_pubkey(uncompressed: &[u8; 65]) -> Result<[u8; 33], EthError> {
+    if uncompressed[0] != 0x04 {
+        return Err(EthError::InvalidPublicKey(
+            "uncompressed key must start with 0x04".into(),
+        ));
+    }
+
+    let mut key_33 = [0u8; 33];
+    key_33.copy_from_slice(
+        parse_sec1_pubkey(uncompressed)?
+            .to_encoded_point(true)
+            .as_bytes(),
+    );
+    Ok(key_33)
+}
 
-    // Decompress to uncompressed form (65 bytes with 0x04 prefix).
-    let uncompressed = pubkey.to_encoded_point(false);
-    let uncompressed_bytes: &[u8] = uncompressed.as_bytes();
+/// Converts a compressed secp256k1 public key (33 bytes, 0x02/0x03 prefix) to
+/// its uncompressed form (65 bytes, 0x04 prefix).
+///
+/// Fails if no curve point has the given x coordinate.
+pub fn decompress_pubkey(compressed: &[u8; 33]) -> Result<[u8; 65], EthError> {
+    if !matches!(compressed[0], 0x02 | 0x03) {
+        return Err(EthError::InvalidPublicKey(
+            "compressed key must start with 0x02 or 0x03".into(),
+        ));
+    }
 
     let mut key_65 = [0u8; 65];
-    key_65.copy_from_slice(uncompressed_bytes);
+    key_65.copy_from_slice(
+        parse_sec1_pubkey(compressed)?
+            .to_encoded_point(false)
+            .as_bytes(),
+    );
+    Ok(key_65)
+}
+
+/// Parses a SEC1-encoded public key, checking that the point is on the curve.
+fn parse_sec1_pubkey(bytes: &[u8]) -> Result<PublicKey, EthError> {
+    let encoded = EncodedPoint::from_bytes(bytes)
+        .map_err(|e| EthError::InvalidPublicKey(format!("invalid key encoding: {e}")))?;
 
-    pubkey_to_eth_address(&key_65)
+    let pubkey: Option<PublicKey> = PublicKey::from_encoded_point(&encoded).into();
+    pubkey.ok_or_else(|| EthError::InvalidPublicKey("point is not on the secp256k1 curve".into()))
 }
 
 /// Validates an Ethereum address string.
 ///
 /// Checks that the address has the correct format (0x + 40 hex characters).
 /// If the address contains mixed case, the EIP-55 checksum is verified.
+/// Avalanche X/P-chain addresses get a dedicated error, since funds sent to
+/// one from the C-chain would be lost.
 pub fn validate_address(address: &str) -> Result<bool, EthError> {
+    if is_avalanche_bech32(address) {
+        return Err(EthError::InvalidAddress(
+            "Avalanche X/P-chain address; EVM transfers need a 0x C-chain address".into(),
+        ));
+    }
+
     if !address.starts_with("0x") && !address.starts_with("0X") {
         return Err(EthError::InvalidAddress(
             "address must start with 0x".into(),
@@ -93,6 +182,17 @@ pub fn validate_address(address: &str) -> Result<bool, EthError> {
     Ok(checksummed == address)
 }
 
+/// Whether `address` looks like an Avalanche X/P-chain bech32 address, e.g.
+/// `X-avax1...`, `P-fuji1...` or a bare `avax1...`.
+fn is_avalanche_bech32(address: &str) -> bool {
+    let lower = address.trim().to_ascii_lowercase();
+    let bech32 = lower
+        .strip_prefix("x-")
+        .or_else(|| lower.strip_prefix("p-"))
+        .unwrap_or(&lower);
+    bech32.starts_with("avax1") || bech32.starts_with("fuji1")
+}
+
 /// Applies EIP-55 mixed-case checksum encoding to an Ethereum address.
 ///
 /// The input should be a lowercase 0x-prefixed address. Returns the
@@ -120,7 +220,7 @@ pub fn checksum_address(address: &str) -> Result<String, EthError> {
     }
 
     // EIP-55: hash the lowercase hex address (without 0x).
-    let hash = Keccak256::digest(hex_part.as_bytes());
+    let hash = keccak256(hex_part.as_bytes());
     let hash_hex = hex::encode(hash);
 
     let mut checksummed = String::with_capacity(42);
@@ -144,6 +244,19 @@ pub fn checksum_address(address: &str) -> Result<String, EthError> {
     Ok(checksummed)
 }
 
+/// Validates an address and returns its canonical EIP-55 form.
+///
+/// Unlike [`checksum_address`], mixed-case input must already carry a
+/// correct checksum: a mistyped character in a checksummed address is an
+/// error rather than being silently "fixed". All-lowercase and all-uppercase
+/// input carries no checksum and is normalized.
+pub fn to_checksummed(address: &str) -> Result<String, EthError> {
+    if !validate_address(address)? {
+        return Err(EthError::InvalidAddress("EIP-55 checksum mismatch".into()));
+    }
+    checksum_address(address)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -263,6 +376,45 @@ mod tests {
         assert_eq!(address, "0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf");
     }
 
+    /// secp256k1 generator point G, the public key of private key 1.
+    const G_COMPRESSED: &str = "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798";
+    const G_UNCOMPRESSED: &str = "\
+        0479be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798\
+        483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8";
+
+    #[test]
+    fn compress_decompress_roundtrip() {
+        let compressed: [u8; 33] = hex::decode(G_COMPRESSED).unwrap().try_into().unwrap();
+        let uncompressed: [u8; 65] = hex::decode(G_UNCOMPRESSED).unwrap().try_into().unwrap();
+
+        assert_eq!(decompress_pubkey(&compressed).unwrap(), uncompressed);
+        assert_eq!(compress_pubkey(&uncompressed).unwrap(), compressed);
+        assert_eq!(
+            pubkey_to_eth_address(&decompress_pubkey(&compressed).unwrap()).unwrap(),
+            "0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf"
+        );
+    }
+
+    #[test]
+    fn decompress_rejects_off_curve_point() {
+        // x = 5: 5^3 + 7 is not a square mod p, so no curve point has this x.
+        let mut key = [0u8; 33];
+        key[0] = 0x02;
+        key[32] = 5;
+        assert!(matches!(
+            decompress_pubkey(&key),
+            Err(EthError::InvalidPublicKey(_))
+        ));
+    }
+
+    #[test]
+    fn compress_rejects_wrong_prefix() {
+        let mut key = [0u8; 65];
+        key[0] = 0x02;
+        assert!(compress_pubkey(&key).is_err());
+        assert!(decompress_pubkey(&[0x04; 33]).is_err());
+    }
+
     #[test]
     fn invalid_uncompressed_prefix_errors() {
         let mut key = [0u8; 65];
@@ -281,4 +433,58 @@ mod tests {
         let result = checksum_address("0xdeadbeef");
         assert!(result.is_err());
     }
+
+    #[test]
+    fn to_checksummed_normalizes_lowercase() {
+        let result = to_checksummed("0xfb6916095ca1df60bb79ce92ce3ea74c37c5d359").unwrap();
+        assert_eq!(result, "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359");
+    }
+
+    #[test]
+    fn to_checksummed_is_idempotent() {
+        let checksummed = "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB";
+        assert_eq!(to_checksummed(checksummed).unwrap(), checksummed);
+    }
+
+    #[test]
+    fn to_checksummed_rejects_bad_checksum_and_malformed() {
+        // Last letter's case flipped.
+        assert!(to_checksummed("0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6Fb").is_err());
+        assert!(to_checksummed("0xdeadbeef").is_err());
+        assert!(to_checksummed("0xzz6916095ca1df60bb79ce92ce3ea74c37c5d359").is_err());
+    }
+
+    #[test]
+    fn eth_address_parses_valid_hex() {
+        let addr = EthAddress::parse("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed").unwrap();
+        assert_eq!(addr.as_str(), "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed");
+        assert_eq!(addr.to_string(), addr.as_str());
+        assert_eq!(addr.as_bytes()[0], 0x5a);
+        assert_eq!(addr.as_bytes()[19], 0xed);
+    }
+
+    #[test]
+    fn eth_address_rejects_bech32() {
+        assert!(EthAddress::parse("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4").is_err());
+    }
+
+    #[test]
+    fn eth_address_rejects_avalanche_x_chain() {
+        for addr in [
+            "X-avax1tzdcgj4ehsvhhgpl7zylwpw0gl2rxcg4r5afk5",
+            "P-avax1tzdcgj4ehsvhhgpl7zylwpw0gl2rxcg4r5afk5",
+            "x-fuji1tzdcgj4ehsvhhgpl7zylwpw0gl2rxcg4r5afk5",
+        ] {
+            let err = EthAddress::parse(addr).unwrap_err();
+            assert!(err.to_string().contains("C-chain"), "{addr}: {err}");
+        }
+        // Plain malformed input keeps the generic error.
+        let err = EthAddress::parse("avalanche").unwrap_err();
+        assert!(!err.to_string().contains("C-chain"));
+    }
+
+    #[test]
+    fn eth_address_rejects_bad_checksum() {
+        assert!(EthAddress::parse("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD").is_err());
+    }
 }
@@ -0,0 +1,185 @@
+//! Parsing of 256-bit amounts (token amounts, wei values, fee caps).
+//!
+//! Every parser returns a big-endian `[u8; 32]`, the byte order of an ABI
+//! `uint256` word, and rejects values wider than 256 bits while parsing
+//! rather than after decoding. [`u256_to_u128`] narrows a parsed value for
+//! fields that are `u128` in the transaction model (value, fee caps).
+
+use alloc::format;
+
+use crate::error::EthError;
+
+/// Hex digits in a full 256-bit value.
+const MAX_HEX_DIGITS: usize = 64;
+
+/// Parses a decimal or `0x`-prefixed hex amount into a big-endian uint256.
+///
+/// Hex digits may be odd in number (`"0xf4240"`) and leading zeros are
+/// ignored. Signs, whitespace and separators are rejected.
+pub fn parse_u256(input: &str) -> Result<[u8; 32], EthError> {
+    match strip_hex_prefix(input) {
+        Some(digits) => parse_hex_digits(digits),
+        None => parse_decimal_digits(input),
+    }
+}
+
+/// Parses a hex amount, with or without a `0x` prefix, into a big-endian
+/// uint256.
+///
+/// For inputs that are hex by contract (e.g. `*_hex` FFI parameters), where
+/// `"10"` means sixteen rather than ten.
+pub fn parse_u256_hex(input: &str) -> Result<[u8; 32], EthError> {
+    parse_hex_digits(strip_hex_prefix(input).unwrap_or(input))
+}
+
+/// Narrows a big-endian uint256 to `u128`, failing if it does not fit.
+pub fn u256_to_u128(value: &[u8; 32]) -> Result<u128, EthError> {
+    let (high, low) = value.split_at(16);
+    if high.iter().any(|&b| b != 0) {
+        return Err(EthError::EncodingError("amount exceeds 128 bits".into()));
+    }
+    let mut bytes = [0u8; 16];
+    bytes.copy_from_slice(low);
+    Ok(u128::from_be_bytes(bytes))
+}
+
+fn strip_hex_prefix(input: &str) -> Option<&str> {
+    input
+        .strip_prefix("0x")
+        .or_else(|| input.strip_prefix("0X"))
+}
+
+fn parse_hex_digits(digits: &str) -> Result<[u8; 32], EthError> {
+    if digits.is_empty() {
+        return Err(EthError::EncodingError("empty hex amount".into()));
+    }
+    let significant = digits.trim_start_matches('0');
+    if significant.len() > MAX_HEX_DIGITS {
+        return Err(overflow());
+    }
+
+    // Fill nibbles from the least significant end.
+    let mut out = [0u8; 32];
+    for (i, c) in significant.bytes().rev().enumerate() {
+        let nibble = (c as char)
+            .to_digit(16)
+            .ok_or_else(|| EthError::EncodingError(format!("invalid hex digit {:?}", c as char)))?
+            as u8;
+        out[31 - i / 2] |= if i % 2 == 0 { nibble } else { nibble << 4 };
+    }
+    Ok(out)
+}
+
+fn parse_decimal_digits(digits: &str) -> Result<[u8; 32], EthError> {
+    if digits.is_empty() {
+        return Err(EthError::EncodingError("empty amount".into()));
+    }
+
+    let mut out = [0u8; 32];
+    for c in digits.chars() {
+        let digit = c
+            .to_digit(10)
+            .ok_or_else(|| EthError::EncodingError(format!("invalid decimal digit {c:?}")))?;
+        // out = out * 10 + digit, from the least significant byte up.
+        let mut carry = digit;
+        for byte in out.iter_mut().rev() {
+            let v = u32::from(*byte) * 10 + carry;
+            *byte = v as u8;
+            carry = v >> 8;
+        }
+        if carry != 0 {
+            return Err(overflow());
+        }
+    }
+    Ok(out)
+}
+
+fn overflow() -> EthError {
+    EthError::EncodingError("amount exceeds 256 bits".into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAX_DECIMAL: &str =
+        "115792089237316195423570985008687907853269984665640564039457584007913129639935";
+    const MAX_PLUS_ONE_DECIMAL: &str =
+        "115792089237316195423570985008687907853269984665640564039457584007913129639936";
+
+    fn word(low: u128) -> [u8; 32] {
+        let mut out = [0u8; 32];
+        out[16..].copy_from_slice(&low.to_be_bytes());
+        out
+    }
+
+    #[test]
+    fn max_uint256_parses_as_decimal_and_hex() {
+        assert_eq!(parse_u256(MAX_DECIMAL).unwrap(), [0xff; 32]);
+        let hex = format!("0x{}", "f".repeat(64));
+        assert_eq!(parse_u256(&hex).unwrap(), [0xff; 32]);
+        assert_eq!(parse_u256_hex(&hex[2..]).unwrap(), [0xff; 32]);
+    }
+
+    #[test]
+    fn one_over_max_is_rejected() {
+        let err = parse_u256(MAX_PLUS_ONE_DECIMAL).unwrap_err();
+        assert_eq!(err.to_string(), "encoding error: amount exceeds 256 bits");
+
+        let hex = format!("0x1{}", "0".repeat(64));
+        assert_eq!(
+            parse_u256(&hex).unwrap_err().to_string(),
+            "encoding error: amount exceeds 256 bits"
+        );
+    }
+
+    #[test]
+    fn leading_zeros_do_not_count_towards_width() {
+        let hex = format!("0x{}1", "0".repeat(80));
+        assert_eq!(parse_u256(&hex).unwrap(), word(1));
+        assert_eq!(
+            parse_u256(&format!("{}7", "0".repeat(90))).unwrap(),
+            word(7)
+        );
+    }
+
+    #[test]
+    fn odd_length_hex() {
+        assert_eq!(parse_u256("0xf4240").unwrap(), word(1_000_000));
+        assert_eq!(parse_u256_hex("f4240").unwrap(), word(1_000_000));
+        assert_eq!(parse_u256("0XABC").unwrap(), word(0xabc));
+    }
+
+    #[test]
+    fn decimal_input() {
+        assert_eq!(parse_u256("1000000").unwrap(), word(1_000_000));
+        assert_eq!(parse_u256("0").unwrap(), word(0));
+        assert_eq!(parse_u256(&u128::MAX.to_string()).unwrap(), word(u128::MAX));
+    }
+
+    #[test]
+    fn unprefixed_input_is_decimal_unless_hex_is_requested() {
+        assert_eq!(parse_u256("10").unwrap(), word(10));
+        assert_eq!(parse_u256_hex("10").unwrap(), word(16));
+        assert!(parse_u256("f4240").is_err());
+    }
+
+    #[test]
+    fn rejects_malformed_input() {
+        for bad in [
+            "", "0x", "-1", "+1", " 1", "1_000", "1.5", "0xzz", "0x-1", "1e18",
+        ] {
+            assert!(parse_u256(bad).is_err(), "{bad:?} should be rejected");
+        }
+        assert!(parse_u256_hex("").is_err());
+        assert!(parse_u256_hex("not-hex").is_err());
+    }
+
+    #[test]
+    fn narrows_to_u128_only_when_it_fits() {
+        assert_eq!(u256_to_u128(&word(u128::MAX)).unwrap(), u128::MAX);
+        let mut wide = word(0);
+        wide[15] = 1;
+        assert!(u256_to_u128(&wide).is_err());
+    }
+}
@@ -1,15 +1,31 @@
 use crate::abi::{encode_function_call, AbiParam};
+use crate::address::EthAddress;
 use crate::error::EthError;
 
 /// Function selector for `transfer(address,uint256)`: `0xa9059cbb`.
 const TRANSFER_SELECTOR: [u8; 4] = [0xa9, 0x05, 0x9c, 0xbb];
 
+/// Function selector for `transferFrom(address,address,uint256)`: `0x23b872dd`.
+const TRANSFER_FROM_SELECTOR: [u8; 4] = [0x23, 0xb8, 0x72, 0xdd];
+
 /// Function selector for `balanceOf(address)`: `0x70a08231`.
 const BALANCE_OF_SELECTOR: [u8; 4] = [0x70, 0xa0, 0x82, 0x31];
 
 /// Function selector for `approve(address,uint256)`: `0x095ea7b3`.
 const APPROVE_SELECTOR: [u8; 4] = [0x09, 0x5e, 0xa7, 0xb3];
 
+/// `2^256 - 1`, the amount used for an unlimited ("infinite") approval.
+pub const MAX_UINT256: [u8; 32] = [0xff; 32];
+
+/// Function selector for `allowance(address,address)`: `0xdd62ed3e`.
+const ALLOWANCE_SELECTOR: [u8; 4] = [0xdd, 0x62, 0xed, 0x3e];
+
+/// Function selector for `decimals()`: `0x313ce567`.
+const DECIMALS_SELECTOR: [u8; 4] = [0x31, 0x3c, 0xe5, 0x67];
+
+/// Function selector for `symbol()`: `0x95d89b41`.
+const SYMBOL_SELECTOR: [u8; 4] = [0x95, 0xd8, 0x9b, 0x41];
+
 /// Parses a 0x-prefixed hex address string into a 20-byte array.
 fn parse_address(address: &str) -> Result<[u8; 20], EthError> {
     let hex_str = address.strip_prefix("0x").or_else(|| address.strip_prefix("0X")).ok_or_else(
@@ -35,16 +51,38 @@ fn parse_address(address: &str) -> Result<[u8; 20], EthError> {
 ///
 /// # Parameters
 ///
-/// - `to`: The recipient address (0x-prefixed hex string).
+/// - `to`: The recipient address.
 /// - `amount`: The transfer amount as a big-endian 32-byte uint256.
 ///
 /// # Returns
 ///
 /// The complete calldata (4-byte selector + 64 bytes of ABI-encoded params).
-pub fn encode_transfer(to: &str, amount: [u8; 32]) -> Result<Vec<u8>, EthError> {
-    let addr = parse_address(to)?;
-    let params = [AbiParam::Address(addr), AbiParam::Uint256(amount)];
-    Ok(encode_function_call(TRANSFER_SELECTOR, &params))
+pub fn encode_transfer(to: &EthAddress, amount: [u8; 32]) -> Vec<u8> {
+    let params = [AbiParam::Address(*to.as_bytes()), AbiParam::Uint256(amount)];
+    encode_function_call(TRANSFER_SELECTOR, &params)
+}
+
+/// Encodes an ERC-20 `transferFrom(address,address,uint256)` call.
+///
+/// Moves tokens out of `from` on the caller's allowance, as used by DEX and
+/// other delegated-spend flows.
+///
+/// # Parameters
+///
+/// - `from`: The token holder whose allowance is spent.
+/// - `to`: The recipient address.
+/// - `amount`: The transfer amount as a big-endian 32-byte uint256.
+///
+/// # Returns
+///
+/// The complete calldata (4-byte selector + 96 bytes of ABI-encoded params).
+pub fn encode_transfer_from(from: &EthAddress, to: &EthAddress, amount: [u8; 32]) -> Vec<u8> {
+    let params = [
+        AbiParam::Address(*from.as_bytes()),
+        AbiParam::Address(*to.as_bytes()),
+        AbiParam::Uint256(amount),
+    ];
+    encode_function_call(TRANSFER_FROM_SELECTOR, &params)
 }
 
 /// Encodes an ERC-20 `balanceOf(address)` call.
@@ -78,6 +116,128 @@ pub fn encode_approve(spender: &str, amount: [u8; 32]) -> Result<Vec<u8>, EthErr
     Ok(encode_function_call(APPROVE_SELECTOR, &params))
 }
 
+/// Encodes an ERC-20 `approve(spender, 2^256 - 1)` call, an unlimited
+/// allowance.
+///
+/// # Security
+///
+/// The spender can move the owner's entire balance of this token, now and
+/// for any tokens received later, until the approval is revoked with
+/// `approve(spender, 0)`. If the spender contract is malicious, upgradeable
+/// or later compromised, every token is at risk. Prefer approving the exact
+/// amount needed unless the user explicitly asks for an unlimited approval.
+pub fn encode_approve_max(spender: &str) -> Result<Vec<u8>, EthError> {
+    encode_approve(spender, MAX_UINT256)
+}
+
+/// Encodes the two-step `approve(spender, 0)` then `approve(spender, amount)`
+/// sequence.
+///
+/// Some tokens (notably USDT) revert when an existing non-zero allowance is
+/// changed to another non-zero value, so the allowance is reset to zero
+/// first. The calls must be mined in order, i.e. sent with consecutive nonces.
+///
+/// # Returns
+///
+/// `[reset, approve]` calldata, each as returned by [`encode_approve`].
+pub fn encode_safe_approve(spender: &str, amount: [u8; 32]) -> Result<[Vec<u8>; 2], EthError> {
+    Ok([
+        encode_approve(spender, [0u8; 32])?,
+        encode_approve(spender, amount)?,
+    ])
+}
+
+/// Encodes an ERC-20 `allowance(address,address)` call.
+///
+/// # Parameters
+///
+/// - `owner`: The token holder (0x-prefixed hex string).
+/// - `spender`: The approved spender (0x-prefixed hex string).
+///
+/// # Returns
+///
+/// The complete calldata (4-byte selector + 64 bytes of ABI-encoded params).
+pub fn encode_allowance(owner: &str, spender: &str) -> Result<Vec<u8>, EthError> {
+    let owner = parse_address(owner)?;
+    let spender = parse_address(spender)?;
+    let params = [AbiParam::Address(owner), AbiParam::Address(spender)];
+    Ok(encode_function_call(ALLOWANCE_SELECTOR, &params))
+}
+
+/// Encodes an ERC-20 `decimals()` call (selector only, no params).
+pub fn encode_decimals() -> Vec<u8> {
+    encode_function_call(DECIMALS_SELECTOR, &[])
+}
+
+/// Encodes an ERC-20 `symbol()` call (selector only, no params).
+pub fn encode_symbol() -> Vec<u8> {
+    encode_function_call(SYMBOL_SELECTOR, &[])
+}
+
+/// Decodes the `uint8` return value of `decimals()`.
+///
+/// The value occupies a full 32-byte word; anything above 255 is rejected
+/// rather than truncated.
+pub fn decode_decimals(data: &[u8]) -> Result<u8, EthError> {
+    let word = decode_uint256(data)?;
+    if word[..31].iter().any(|&b| b != 0) {
+        return Err(EthError::EncodingError(
+            "decimals value does not fit in uint8".into(),
+        ));
+    }
+    Ok(word[31])
+}
+
+/// Decodes a dynamic `string` return value, e.g. from `symbol()` or `name()`.
+///
+/// Layout: a 32-byte offset word, then at that offset a 32-byte length word
+/// followed by the UTF-8 bytes. Some older tokens (e.g. MKR) return a
+/// `bytes32` instead; a response of exactly 32 bytes is decoded that way,
+/// with trailing zero bytes trimmed.
+pub fn decode_string(data: &[u8]) -> Result<String, EthError> {
+    if data.len() == 32 {
+        let end = data.iter().rposition(|&b| b != 0).map_or(0, |i| i + 1);
+        return utf8(&data[..end]);
+    }
+
+    let offset = read_usize_word(data, 0)?;
+    let len = read_usize_word(data, offset)?;
+    let start = offset + 32;
+    let bytes = start
+        .checked_add(len)
+        .and_then(|end| data.get(start..end))
+        .ok_or_else(|| {
+            EthError::EncodingError(format!(
+                "string of {len} bytes at offset {start} exceeds {} bytes of data",
+                data.len()
+            ))
+        })?;
+    utf8(bytes)
+}
+
+/// Reads the 32-byte word at `pos` as a `usize`, rejecting values that
+/// cannot possibly index into `data`.
+fn read_usize_word(data: &[u8], pos: usize) -> Result<usize, EthError> {
+    let word = pos
+        .checked_add(32)
+        .and_then(|end| data.get(pos..end))
+        .ok_or_else(|| EthError::EncodingError(format!("no 32-byte word at offset {pos}")))?;
+    if word[..24].iter().any(|&b| b != 0) {
+        return Err(EthError::EncodingError(format!(
+            "word at offset {pos} is too large"
+        )));
+    }
+    let mut buf = [0u8; 8];
+    buf.copy_from_slice(&word[24..]);
+    usize::try_from(u64::from_be_bytes(buf))
+        .map_err(|_| EthError::EncodingError(format!("word at offset {pos} is too large")))
+}
+
+fn utf8(bytes: &[u8]) -> Result<String, EthError> {
+    String::from_utf8(bytes.to_vec())
+        .map_err(|e| EthError::EncodingError(format!("string is not valid UTF-8: {e}")))
+}
+
 /// Decodes a single uint256 return value from ABI-encoded data.
 ///
 /// Useful for decoding the return value of `balanceOf` and similar view
@@ -99,12 +259,16 @@ pub fn decode_uint256(data: &[u8]) -> Result<[u8; 32], EthError> {
 mod tests {
     use super::*;
 
+    fn dead() -> EthAddress {
+        EthAddress::parse("0x000000000000000000000000000000000000dEaD").unwrap()
+    }
+
     #[test]
     fn encode_transfer_correct_selector() {
-        let to = "0x000000000000000000000000000000000000dEaD";
+        let to = dead();
         let amount = [0u8; 32];
 
-        let data = encode_transfer(to, amount).unwrap();
+        let data = encode_transfer(&to, amount);
 
         // First 4 bytes should be the transfer selector.
         assert_eq!(&data[..4], &TRANSFER_SELECTOR);
@@ -112,10 +276,10 @@ mod tests {
 
     #[test]
     fn encode_transfer_correct_length() {
-        let to = "0x000000000000000000000000000000000000dEaD";
+        let to = dead();
         let amount = [0u8; 32];
 
-        let data = encode_transfer(to, amount).unwrap();
+        let data = encode_transfer(&to, amount);
 
         // 4 (selector) + 32 (address) + 32 (amount) = 68 bytes.
         assert_eq!(data.len(), 68);
@@ -123,24 +287,24 @@ mod tests {
 
     #[test]
     fn encode_transfer_encodes_address() {
-        let to = "0x000000000000000000000000000000000000dEaD";
+        let to = dead();
         let amount = [0u8; 32];
 
-        let data = encode_transfer(to, amount).unwrap();
+        let data = encode_transfer(&to, amount);
 
         // Address is left-padded to 32 bytes starting at offset 4.
         assert_eq!(&data[4..16], &[0u8; 12]); // 12 zero-pad bytes
-        assert_eq!(data[34], 0xdE);
-        assert_eq!(data[35], 0xaD);
+        assert_eq!(data[34], 0xDE);
+        assert_eq!(data[35], 0xAD);
     }
 
     #[test]
     fn encode_transfer_encodes_amount() {
-        let to = "0x000000000000000000000000000000000000dEaD";
+        let to = dead();
         let mut amount = [0u8; 32];
         amount[31] = 0x64; // 100
 
-        let data = encode_transfer(to, amount).unwrap();
+        let data = encode_transfer(&to, amount);
 
         // Amount is at bytes 36..68.
         assert_eq!(data[67], 0x64);
@@ -148,9 +312,34 @@ mod tests {
     }
 
     #[test]
-    fn encode_transfer_invalid_address() {
-        let result = encode_transfer("not-an-address", [0u8; 32]);
-        assert!(result.is_err());
+    fn encode_transfer_ignores_address_spelling() {
+        let lower = EthAddress::parse("0x000000000000000000000000000000000000dead").unwrap();
+        assert_eq!(
+            encode_transfer(&lower, [1u8; 32]),
+            encode_transfer(&dead(), [1u8; 32])
+        );
+    }
+
+    #[test]
+    fn encode_transfer_from_layout() {
+        let from = EthAddress::parse("0x1111111111111111111111111111111111111111").unwrap();
+        let to = dead();
+        let mut amount = [0u8; 32];
+        amount[31] = 0x64;
+
+        let data = encode_transfer_from(&from, &to, amount);
+
+        // 4 (selector) + 32 (from) + 32 (to) + 32 (amount) = 100 bytes.
+        assert_eq!(data.len(), 100);
+        assert_eq!(&data[..4], &[0x23, 0xb8, 0x72, 0xdd]);
+        assert_eq!(&data[4..16], &[0u8; 12]);
+        assert_eq!(&data[16..36], &[0x11; 20]);
+        assert_eq!(&data[36..48], &[0u8; 12]);
+        assert_eq!(
+            hex::encode(&data[48..68]),
+            "000000000000000000000000000000000000dead"
+        );
+        assert_eq!(&data[68..100], &amount);
     }
 
     #[test]
@@ -191,6 +380,37 @@ mod tests {
         assert_eq!(data.len(), 68);
     }
 
+    #[test]
+    fn encode_approve_max_uses_all_ones_amount() {
+        let spender = "0x000000000000000000000000000000000000dEaD";
+
+        let data = encode_approve_max(spender).unwrap();
+
+        assert_eq!(data.len(), 68);
+        assert_eq!(&data[..4], &APPROVE_SELECTOR);
+        assert_eq!(&data[36..], &[0xff; 32]);
+        assert_eq!(data, encode_approve(spender, MAX_UINT256).unwrap());
+    }
+
+    #[test]
+    fn encode_safe_approve_resets_to_zero_first() {
+        let spender = "0x000000000000000000000000000000000000dEaD";
+        let mut amount = [0u8; 32];
+        amount[31] = 0x64;
+
+        let [reset, approve] = encode_safe_approve(spender, amount).unwrap();
+
+        assert_eq!(reset, encode_approve(spender, [0u8; 32]).unwrap());
+        assert_eq!(&reset[36..], &[0u8; 32]);
+        assert_eq!(approve, encode_approve(spender, amount).unwrap());
+        assert_eq!(&approve[36..], &amount);
+    }
+
+    #[test]
+    fn encode_safe_approve_invalid_spender() {
+        assert!(encode_safe_approve("0x1234", [0u8; 32]).is_err());
+    }
+
     #[test]
     fn decode_uint256_valid() {
         let mut data = [0u8; 32];
@@ -219,7 +439,7 @@ mod tests {
     #[test]
     fn encode_transfer_full_calldata_matches_expected() {
         // Known test vector: transfer 1 token (1e18 wei) to a specific address.
-        let to = "0xdead000000000000000000000000000000000000";
+        let to = EthAddress::parse("0xdead000000000000000000000000000000000000").unwrap();
         let mut amount = [0u8; 32];
         // 1e18 = 0x0de0b6b3a7640000
         amount[24] = 0x0d;
@@ -231,7 +451,7 @@ mod tests {
         amount[30] = 0x00;
         amount[31] = 0x00;
 
-        let data = encode_transfer(to, amount).unwrap();
+        let data = encode_transfer(&to, amount);
 
         // Selector: a9059cbb
         assert_eq!(hex::encode(&data[..4]), "a9059cbb");
@@ -256,4 +476,86 @@ mod tests {
         let result = parse_address("dead000000000000000000000000000000000000");
         assert!(result.is_err());
     }
+
+    #[test]
+    fn encode_allowance_selector_and_layout() {
+        let owner = "0x000000000000000000000000000000000000dEaD";
+        let spender = "0x000000000000000000000000000000000000bEEF";
+
+        let data = encode_allowance(owner, spender).unwrap();
+
+        assert_eq!(hex::encode(&data[..4]), "dd62ed3e");
+        assert_eq!(data.len(), 68);
+        assert_eq!(&data[34..36], &[0xde, 0xad]);
+        assert_eq!(&data[66..68], &[0xbe, 0xef]);
+    }
+
+    #[test]
+    fn encode_allowance_invalid_spender() {
+        let owner = "0x000000000000000000000000000000000000dEaD";
+        assert!(encode_allowance(owner, "0xdead").is_err());
+    }
+
+    #[test]
+    fn encode_decimals_is_bare_selector() {
+        assert_eq!(hex::encode(encode_decimals()), "313ce567");
+    }
+
+    #[test]
+    fn encode_symbol_is_bare_selector() {
+        assert_eq!(hex::encode(encode_symbol()), "95d89b41");
+    }
+
+    #[test]
+    fn decode_decimals_valid() {
+        let mut data = [0u8; 32];
+        data[31] = 18;
+        assert_eq!(decode_decimals(&data).unwrap(), 18);
+    }
+
+    #[test]
+    fn decode_decimals_rejects_overflow() {
+        let mut data = [0u8; 32];
+        data[30] = 1;
+        assert!(decode_decimals(&data).is_err());
+    }
+
+    #[test]
+    fn decode_string_dynamic_symbol() {
+        // symbol() return of USDC: offset 0x20, length 4, "USDC" right-padded.
+        let data = hex::decode(concat!(
+            "0000000000000000000000000000000000000000000000000000000000000020",
+            "0000000000000000000000000000000000000000000000000000000000000004",
+            "5553444300000000000000000000000000000000000000000000000000000000",
+        ))
+        .unwrap();
+
+        assert_eq!(decode_string(&data).unwrap(), "USDC");
+    }
+
+    #[test]
+    fn decode_string_bytes32_fallback() {
+        let mut data = [0u8; 32];
+        data[..3].copy_from_slice(b"MKR");
+        assert_eq!(decode_string(&data).unwrap(), "MKR");
+    }
+
+    #[test]
+    fn decode_string_length_out_of_bounds() {
+        let data = hex::decode(concat!(
+            "0000000000000000000000000000000000000000000000000000000000000020",
+            "0000000000000000000000000000000000000000000000000000000000000040",
+            "5553444300000000000000000000000000000000000000000000000000000000",
+        ))
+        .unwrap();
+
+        assert!(decode_string(&data).is_err());
+    }
+
+    #[test]
+    fn decode_string_offset_out_of_bounds() {
+        let mut data = vec![0u8; 64];
+        data[31] = 0x80;
+        assert!(decode_string(&data).is_err());
+    }
 }
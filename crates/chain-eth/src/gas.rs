@@ -0,0 +1,226 @@
+//! Offline gas-limit heuristics for common EVM operations.
+//!
+//! These are starting points for when `eth_estimateGas` is unavailable or
+//! as a sanity check on its result. Token contracts with hooks, fee-on-transfer
+//! logic or cold storage slots can use more, so pad with [`with_buffer`].
+//!
+//! [`effective_fee`] turns EIP-1559 fee caps into what a transaction actually
+//! pays at a given base fee, and [`calldata_gas`] gives the intrinsic floor for
+//! arbitrary calldata. [`l1_data_gas`] approximates the L1 data component of
+//! an OP-stack L2 fee.
+
+/// Kind of transaction a gas limit is being estimated for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TxKind {
+    /// Plain ETH (or native token) transfer to an externally owned account.
+    NativeTransfer,
+    /// ERC-20 `transfer(address,uint256)`.
+    Erc20Transfer,
+    /// ERC-20 `approve(address,uint256)`.
+    Erc20Approve,
+    /// ERC-721 `safeTransferFrom(address,address,uint256)`.
+    Erc721Transfer,
+}
+
+/// Baseline gas limit for `kind`, without any safety buffer.
+///
+/// The native transfer cost is exact (the intrinsic 21,000 gas); the token
+/// figures are typical costs for standard OpenZeppelin-style contracts.
+pub fn estimate_gas_limit(kind: TxKind) -> u64 {
+    match kind {
+        TxKind::NativeTransfer => 21_000,
+        TxKind::Erc20Transfer => 65_000,
+        TxKind::Erc20Approve => 46_000,
+        TxKind::Erc721Transfer => 85_000,
+    }
+}
+
+/// Add `pct` percent on top of `limit`, rounding up.
+///
+/// E.g. `with_buffer(estimate_gas_limit(TxKind::Erc20Transfer), 20)` gives
+/// 78,000. Saturates at `u64::MAX`.
+pub fn with_buffer(limit: u64, pct: u64) -> u64 {
+    let extra = (limit as u128 * pct as u128).div_ceil(100);
+    (limit as u128 + extra).min(u64::MAX as u128) as u64
+}
+
+/// Intrinsic gas of a call carrying `data`: the 21,000 base plus 4 gas per
+/// zero byte and 16 per non-zero byte (EIP-2028, Istanbul onwards).
+///
+/// Exact for a call to an account without code; for contract calls it is a
+/// floor, with execution cost on top.
+pub fn calldata_gas(data: &[u8]) -> u64 {
+    estimate_gas_limit(TxKind::NativeTransfer) + byte_gas(data)
+}
+
+/// L1 calldata gas for posting a signed L2 transaction: 16 per non-zero and
+/// 4 per zero byte of `raw_tx` (the full `0x02 || rlp(...)` encoding).
+///
+/// On Optimism, Base and other OP-stack chains the L1 data fee is roughly
+/// this times the L1 gas price and the chain's fee scalar. It ignores the
+/// compression newer OP-stack versions apply, so it overestimates; use it for
+/// fee previews, not exact quotes.
+pub fn l1_data_gas(raw_tx: &[u8]) -> u64 {
+    byte_gas(raw_tx)
+}
+
+/// 4 gas per zero byte and 16 per non-zero byte (EIP-2028).
+fn byte_gas(data: &[u8]) -> u64 {
+    let zeros = data.iter().filter(|&&b| b == 0).count() as u64;
+    let non_zeros = data.len() as u64 - zeros;
+    zeros * 4 + non_zeros * 16
+}
+
+/// Fee in wei an EIP-1559 transaction pays for `gas_used` at `base_fee`:
+/// `min(max_fee, base_fee + max_priority_fee) * gas_used`.
+///
+/// `max_fee` is only a ceiling; the price per gas is the base fee plus the
+/// tip, capped by it. Saturates at `u128::MAX`.
+pub fn effective_fee(base_fee: u128, max_priority_fee: u128, max_fee: u128, gas_used: u64) -> u128 {
+    let price = base_fee.saturating_add(max_priority_fee).min(max_fee);
+    price.saturating_mul(u128::from(gas_used))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn native_transfer_is_intrinsic_gas() {
+        assert_eq!(estimate_gas_limit(TxKind::NativeTransfer), 21_000);
+    }
+
+    #[test]
+    fn token_baselines() {
+        assert_eq!(estimate_gas_limit(TxKind::Erc20Transfer), 65_000);
+        assert_eq!(estimate_gas_limit(TxKind::Erc20Approve), 46_000);
+        assert_eq!(estimate_gas_limit(TxKind::Erc721Transfer), 85_000);
+    }
+
+    #[test]
+    fn buffer_adds_percentage() {
+        assert_eq!(with_buffer(21_000, 20), 25_200);
+        assert_eq!(with_buffer(65_000, 20), 78_000);
+    }
+
+    #[test]
+    fn buffer_rounds_up() {
+        // 20% of 21_001 is 4_200.2, which rounds up to 4_201.
+        assert_eq!(with_buffer(21_001, 20), 25_202);
+        assert_eq!(with_buffer(1, 1), 2);
+    }
+
+    #[test]
+    fn zero_buffer_is_identity() {
+        assert_eq!(with_buffer(46_000, 0), 46_000);
+    }
+
+    #[test]
+    fn buffer_saturates() {
+        assert_eq!(with_buffer(u64::MAX, 20), u64::MAX);
+    }
+
+    #[test]
+    fn calldata_gas_empty_is_base() {
+        assert_eq!(calldata_gas(&[]), 21_000);
+    }
+
+    #[test]
+    fn calldata_gas_all_zero_bytes() {
+        assert_eq!(calldata_gas(&[0u8; 100]), 21_000 + 100 * 4);
+    }
+
+    #[test]
+    fn calldata_gas_erc20_transfer() {
+        // transfer(0x...dead, 1_000_000): 9 non-zero bytes (selector, 0xdead,
+        // 0x0f4240) and 59 zero bytes of padding.
+        let mut data = vec![0xa9, 0x05, 0x9c, 0xbb];
+        let mut to = [0u8; 32];
+        to[30..].copy_from_slice(&[0xde, 0xad]);
+        let mut amount = [0u8; 32];
+        amount[29..].copy_from_slice(&[0x0f, 0x42, 0x40]);
+        data.extend_from_slice(&to);
+        data.extend_from_slice(&amount);
+
+        assert_eq!(calldata_gas(&data), 21_000 + 9 * 16 + 59 * 4);
+    }
+
+    #[test]
+    fn l1_data_gas_counts_bytes() {
+        assert_eq!(l1_data_gas(&[]), 0);
+        assert_eq!(l1_data_gas(&[0x02, 0x00, 0xff, 0x00]), 2 * 16 + 2 * 4);
+    }
+
+    #[cfg(feature = "std")]
+    fn signed_raw_tx(tx: &crate::transaction::EthTransaction) -> Vec<u8> {
+        let mut key = [0u8; 32];
+        key[31] = 1;
+        crate::transaction::sign_transaction(tx, &key)
+            .unwrap()
+            .raw_tx
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn l1_data_gas_native_transfer() {
+        use crate::address::EthAddress;
+        use crate::transaction::build_transfer;
+
+        // Base (8453), 0.01 ETH, 0.001 / 0.1 gwei fees.
+        let to = EthAddress::parse("0x000000000000000000000000000000000000dEaD").unwrap();
+        let value = 10_000_000_000_000_000;
+        let tx =
+            build_transfer(8453, 0, &to, value, 1_000_000, 100_000_000, 21_000, false).unwrap();
+        let raw = signed_raw_tx(&tx);
+
+        // 117 bytes, 21 of them zero (mostly the padded `to` address).
+        assert_eq!(raw.len(), 117);
+        assert_eq!(l1_data_gas(&raw), 96 * 16 + 21 * 4);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn l1_data_gas_erc20_transfer() {
+        use crate::address::EthAddress;
+        use crate::transaction::build_erc20_transfer;
+
+        // 1 USDC (6 decimals) on Base.
+        let token = EthAddress::parse("0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913").unwrap();
+        let to = EthAddress::parse("0x000000000000000000000000000000000000dEaD").unwrap();
+        let mut amount = [0u8; 32];
+        amount[29..].copy_from_slice(&[0x0f, 0x42, 0x40]);
+        let (tx, _) =
+            build_erc20_transfer(8453, 1, &token, &to, amount, 1_000_000, 100_000_000, 65_000)
+                .unwrap();
+        let raw = signed_raw_tx(&tx);
+
+        // 179 bytes; the 68-byte calldata adds 59 zeros of ABI padding.
+        assert_eq!(raw.len(), 179);
+        assert_eq!(l1_data_gas(&raw), 119 * 16 + 60 * 4);
+    }
+
+    #[test]
+    fn effective_fee_uncapped_pays_base_plus_tip() {
+        // 20 gwei base + 2 gwei tip, 50 gwei cap: pays 22 gwei per gas.
+        let gwei = 1_000_000_000u128;
+        assert_eq!(
+            effective_fee(20 * gwei, 2 * gwei, 50 * gwei, 21_000),
+            22 * gwei * 21_000
+        );
+    }
+
+    #[test]
+    fn effective_fee_capped_by_max_fee() {
+        // 30 gwei base + 2 gwei tip exceeds the 25 gwei cap.
+        let gwei = 1_000_000_000u128;
+        assert_eq!(
+            effective_fee(30 * gwei, 2 * gwei, 25 * gwei, 65_000),
+            25 * gwei * 65_000
+        );
+    }
+
+    #[test]
+    fn effective_fee_saturates() {
+        assert_eq!(effective_fee(u128::MAX, 1, u128::MAX, 2), u128::MAX);
+    }
+}
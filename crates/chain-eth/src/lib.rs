@@ -3,13 +3,37 @@
 //! This crate provides:
 //! - Ethereum address derivation from secp256k1 public keys (with EIP-55 checksums)
 //! - EIP-1559 transaction building and signing
+//! - EIP-712 typed data hashing for `eth_signTypedData_v4`
 //! - ERC-20 token interaction encoding (transfer, approve, balanceOf)
 //! - Multi-chain EVM network definitions
+//! - Offline gas-limit heuristics and EIP-1559 effective-fee math
 //! - Minimal ABI encoding utilities
+//! - Overflow-checked uint256 amount parsing (decimal or hex)
+//! - A small RLP encoder/decoder for ad hoc payloads
+//! - JSON persistence of unsigned transactions for offline signing
+//!
+//! With the default `std` feature disabled only the ABI encoder, amount parsing,
+//! the gas heuristics and the error type are built, using `alloc` alone.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
 
 pub mod abi;
+pub mod amount;
+#[cfg(feature = "std")]
 pub mod address;
+#[cfg(feature = "std")]
 pub mod chains;
+#[cfg(feature = "std")]
+pub mod eip712;
+#[cfg(feature = "std")]
 pub mod erc20;
 pub mod error;
+pub mod gas;
+#[cfg(feature = "std")]
+pub mod rlp;
+#[cfg(feature = "std")]
+mod serde_helpers;
+#[cfg(feature = "std")]
 pub mod transaction;
@@ -0,0 +1,181 @@
+//! Minimal RLP (Recursive Length Prefix) encoding for ad hoc payloads.
+//!
+//! A small, stable API over `alloy_rlp` for callers building custom payloads
+//! (e.g. typed-transaction bodies) without depending on it directly. Items
+//! are handled as raw encodings: [`encode_list`] takes already-encoded items
+//! and [`decode_list`] returns them, so nested lists compose by calling the
+//! functions again on an item.
+
+use alloy_rlp::{Encodable, Header};
+
+use crate::error::EthError;
+
+/// Encodes `bytes` as an RLP string.
+///
+/// A single byte below `0x80` encodes as itself; anything else gets a
+/// length prefix.
+pub fn encode_bytes(bytes: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(bytes.len() + 9);
+    bytes.encode(&mut out);
+    out
+}
+
+/// Encodes a list from already-encoded RLP `items`.
+///
+/// Each item must itself be a complete RLP encoding, from [`encode_bytes`]
+/// or a nested [`encode_list`].
+pub fn encode_list(items: &[Vec<u8>]) -> Vec<u8> {
+    let payload_length = items.iter().map(Vec::len).sum();
+    let mut out = Vec::with_capacity(payload_length + 9);
+    Header {
+        list: true,
+        payload_length,
+    }
+    .encode(&mut out);
+    for item in items {
+        out.extend_from_slice(item);
+    }
+    out
+}
+
+/// Decodes an RLP string, returning its payload.
+///
+/// Fails if `data` is a list, is not canonically encoded, or has bytes left
+/// over after the string.
+pub fn decode_bytes(data: &[u8]) -> Result<Vec<u8>, EthError> {
+    let mut buf = data;
+    let payload = take_payload(&mut buf, false)?;
+    require_consumed(buf)?;
+    Ok(payload.to_vec())
+}
+
+/// Decodes an RLP list, returning the raw encoding of each item.
+///
+/// Decode a string item with [`decode_bytes`] and a nested list with
+/// [`decode_list`]. Fails if `data` is not a single canonically encoded
+/// list.
+pub fn decode_list(data: &[u8]) -> Result<Vec<Vec<u8>>, EthError> {
+    let mut buf = data;
+    let mut payload = take_payload(&mut buf, true)?;
+    require_consumed(buf)?;
+
+    let mut items = Vec::new();
+    while !payload.is_empty() {
+        let start = payload;
+        let header = Header::decode(&mut payload).map_err(rlp_error)?;
+        if payload.len() < header.payload_length {
+            return Err(rlp_error(alloy_rlp::Error::InputTooShort));
+        }
+        payload = &payload[header.payload_length..];
+        items.push(start[..start.len() - payload.len()].to_vec());
+    }
+    Ok(items)
+}
+
+/// Reads one header of the expected kind from `buf` and splits off its
+/// payload.
+fn take_payload<'a>(buf: &mut &'a [u8], list: bool) -> Result<&'a [u8], EthError> {
+    let header = Header::decode(buf).map_err(rlp_error)?;
+    if header.list != list {
+        return Err(EthError::EncodingError(format!(
+            "expected an RLP {}",
+            if list { "list" } else { "string" }
+        )));
+    }
+    if buf.len() < header.payload_length {
+        return Err(rlp_error(alloy_rlp::Error::InputTooShort));
+    }
+    let (payload, rest) = buf.split_at(header.payload_length);
+    *buf = rest;
+    Ok(payload)
+}
+
+fn require_consumed(rest: &[u8]) -> Result<(), EthError> {
+    if rest.is_empty() {
+        Ok(())
+    } else {
+        Err(EthError::EncodingError(format!(
+            "{} trailing bytes after RLP item",
+            rest.len()
+        )))
+    }
+}
+
+fn rlp_error(e: alloy_rlp::Error) -> EthError {
+    EthError::EncodingError(format!("invalid RLP: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Vectors from the Ethereum wiki's RLP page.
+
+    #[test]
+    fn empty_list() {
+        assert_eq!(encode_list(&[]), vec![0xc0]);
+        assert!(decode_list(&[0xc0]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn short_string() {
+        let encoded = encode_bytes(b"dog");
+        assert_eq!(hex::encode(&encoded), "83646f67");
+        assert_eq!(decode_bytes(&encoded).unwrap(), b"dog");
+    }
+
+    #[test]
+    fn single_bytes_and_empty_string() {
+        assert_eq!(encode_bytes(&[0x0f]), vec![0x0f]);
+        assert_eq!(encode_bytes(&[0x80]), vec![0x81, 0x80]);
+        assert_eq!(encode_bytes(b""), vec![0x80]);
+        assert_eq!(decode_bytes(&[0x0f]).unwrap(), vec![0x0f]);
+        assert!(decode_bytes(&[0x80]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn list_of_strings() {
+        let encoded = encode_list(&[encode_bytes(b"cat"), encode_bytes(b"dog")]);
+        assert_eq!(hex::encode(&encoded), "c88363617483646f67");
+
+        let items = decode_list(&encoded).unwrap();
+        let strings: Vec<Vec<u8>> = items.iter().map(|i| decode_bytes(i).unwrap()).collect();
+        assert_eq!(strings, vec![b"cat".to_vec(), b"dog".to_vec()]);
+    }
+
+    #[test]
+    fn nested_lists() {
+        // The set-theoretic representation of three: [ [], [[]], [ [], [[]] ] ]
+        let empty = encode_list(&[]);
+        let one = encode_list(std::slice::from_ref(&empty));
+        let two = encode_list(&[empty.clone(), one.clone()]);
+        let items = vec![empty, one, two];
+        let three = encode_list(&items);
+        assert_eq!(hex::encode(&three), "c7c0c1c0c3c0c1c0");
+
+        assert_eq!(decode_list(&three).unwrap(), items);
+        assert_eq!(decode_list(&items[1]).unwrap(), vec![vec![0xc0]]);
+    }
+
+    #[test]
+    fn long_string_uses_length_of_length() {
+        let data = [0xaa; 56];
+        let encoded = encode_bytes(&data);
+        assert_eq!(&encoded[..2], &[0xb8, 56]);
+        assert_eq!(decode_bytes(&encoded).unwrap(), data);
+    }
+
+    #[test]
+    fn decode_rejects_malformed_input() {
+        // Wrong kind.
+        assert!(decode_list(&hex::decode("83646f67").unwrap()).is_err());
+        assert!(decode_bytes(&[0xc0]).is_err());
+        // Truncated payload, trailing bytes, truncated item inside a list.
+        assert!(decode_bytes(&hex::decode("83646f").unwrap()).is_err());
+        assert!(decode_bytes(&hex::decode("83646f6700").unwrap()).is_err());
+        assert!(decode_list(&hex::decode("c283646f").unwrap()).is_err());
+        // Non-canonical: a single byte below 0x80 must not carry a prefix.
+        assert!(decode_bytes(&[0x81, 0x0f]).is_err());
+        assert!(decode_list(&[]).is_err());
+    }
+}
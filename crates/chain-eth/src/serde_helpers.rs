@@ -0,0 +1,64 @@
+//! `#[serde(with = ...)]` adapters for persisting transactions as JSON.
+//!
+//! Byte strings are 0x-prefixed hex and `u128` amounts are decimal strings,
+//! so the JSON survives consumers whose numbers are IEEE doubles.
+
+/// `Vec<u8>` as a 0x-prefixed hex string.
+pub(crate) mod hex_bytes {
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format!("0x{}", hex::encode(bytes)))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        let digits = s.strip_prefix("0x").unwrap_or(&s);
+        hex::decode(digits).map_err(|e| D::Error::custom(format!("invalid hex: {e}")))
+    }
+}
+
+/// `Vec<[u8; 32]>` as a list of 0x-prefixed hex strings.
+pub(crate) mod hex_bytes32_vec {
+    use serde::{de::Error, ser::SerializeSeq, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(words: &[[u8; 32]], serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(words.len()))?;
+        for word in words {
+            seq.serialize_element(&format!("0x{}", hex::encode(word)))?;
+        }
+        seq.end()
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<[u8; 32]>, D::Error> {
+        Vec::<String>::deserialize(deserializer)?
+            .iter()
+            .map(|s| {
+                let mut word = [0u8; 32];
+                hex::decode_to_slice(s.strip_prefix("0x").unwrap_or(s), &mut word)
+                    .map_err(|e| D::Error::custom(format!("invalid 32-byte hex: {e}")))?;
+                Ok(word)
+            })
+            .collect()
+    }
+}
+
+/// `u128` as a decimal string; hex (`0x...`) is accepted when reading.
+pub(crate) mod u128_string {
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    use crate::amount;
+
+    pub fn serialize<S: Serializer>(value: &u128, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&value.to_string())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u128, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        amount::parse_u256(&s)
+            .and_then(|value| amount::u256_to_u128(&value))
+            .map_err(D::Error::custom)
+    }
+}
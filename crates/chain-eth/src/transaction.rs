@@ -1,26 +1,71 @@
-use alloy_rlp::{Encodable, RlpEncodable};
+use std::collections::HashSet;
+
+use alloy_rlp::{Decodable, Encodable, Header, RlpEncodable};
+use crypto_utils::hashes::keccak256;
 use k256::ecdsa::signature::hazmat::PrehashSigner;
-use k256::ecdsa::{RecoveryId, Signature, SigningKey};
+use k256::ecdsa::{RecoveryId, Signature, SigningKey, VerifyingKey};
+use serde::{Deserialize, Serialize};
 use sha3::{Digest, Keccak256};
 use zeroize::Zeroize;
 
+use crate::address::{pubkey_to_eth_address, EthAddress};
 use crate::erc20;
 use crate::error::EthError;
+use crate::serde_helpers::{hex_bytes, hex_bytes32_vec, u128_string};
 
 /// An unsigned EIP-1559 (type 2) Ethereum transaction.
-#[derive(Debug, Clone)]
+///
+/// Serializes to camelCase JSON with wei amounts as decimal strings and bytes
+/// as 0x-prefixed hex (see [`EthTransaction::to_json`]).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct EthTransaction {
     pub chain_id: u64,
     pub nonce: u64,
+    #[serde(with = "u128_string")]
     pub max_priority_fee_per_gas: u128,
+    #[serde(with = "u128_string")]
     pub max_fee_per_gas: u128,
     pub gas_limit: u64,
     /// Recipient address as a 0x-prefixed hex string.
     pub to: String,
     /// Transfer value in wei.
+    #[serde(with = "u128_string")]
     pub value: u128,
     /// Calldata (empty for simple ETH transfers).
+    #[serde(with = "hex_bytes")]
     pub data: Vec<u8>,
+    /// EIP-2930 access list (empty unless the caller pre-declares storage).
+    #[serde(default)]
+    pub access_list: Vec<AccessListEntry>,
+}
+
+impl EthTransaction {
+    /// Serializes the transaction to JSON for offline signing, so an online
+    /// device can build it and a cold device sign it.
+    pub fn to_json(&self) -> Result<String, EthError> {
+        serde_json::to_string(self).map_err(|e| EthError::EncodingError(e.to_string()))
+    }
+
+    /// Parses a transaction written by [`EthTransaction::to_json`].
+    ///
+    /// Only the JSON shape is checked here; the recipient and access-list
+    /// addresses are validated when the transaction is encoded or signed.
+    pub fn from_json(json: &str) -> Result<Self, EthError> {
+        serde_json::from_str(json)
+            .map_err(|e| EthError::EncodingError(format!("invalid transaction JSON: {e}")))
+    }
+}
+
+/// An EIP-2930 access list entry: a contract and the storage slots it touches.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessListEntry {
+    /// Contract address as a 0x-prefixed hex string.
+    pub address: String,
+    /// 32-byte storage slot keys.
+    #[serde(with = "hex_bytes32_vec")]
+    pub storage_keys: Vec<[u8; 32]>,
 }
 
 /// A signed EIP-1559 Ethereum transaction ready for broadcast.
@@ -32,16 +77,26 @@ pub struct SignedEthTransaction {
 }
 
 /// Builds an unsigned EIP-1559 ETH transfer transaction.
+///
+/// A zero `value_wei` is rejected unless `allow_zero_amount` is set: a plain
+/// transfer of nothing is almost always a mistake, while contract calls
+/// (calldata set on the result) legitimately carry no value.
+#[allow(clippy::too_many_arguments)]
 pub fn build_transfer(
     chain_id: u64,
     nonce: u64,
-    to: &str,
+    to: &EthAddress,
     value_wei: u128,
     max_priority_fee: u128,
     max_fee: u128,
     gas_limit: u64,
+    allow_zero_amount: bool,
 ) -> Result<EthTransaction, EthError> {
-    validate_to_address(to)?;
+    if value_wei == 0 && !allow_zero_amount {
+        return Err(EthError::TransactionBuildError(
+            "transfer value is zero; pass allow_zero_amount for contract calls".into(),
+        ));
+    }
 
     Ok(EthTransaction {
         chain_id,
@@ -52,25 +107,96 @@ pub fn build_transfer(
         to: to.to_string(),
         value: value_wei,
         data: Vec::new(),
+        access_list: Vec::new(),
+    })
+}
+
+/// Lowest gas limit that plausibly covers an ERC-20 `transfer`.
+///
+/// A plain token transfer costs roughly 35k-50k gas; anything below this is
+/// all but certain to run out of gas and revert (while still paying the fee).
+pub const MIN_ERC20_GAS_LIMIT: u64 = 45_000;
+
+/// A non-fatal problem with a built transaction, worth showing the user
+/// before they sign.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TxWarning {
+    /// The gas limit is below the minimum expected for this kind of call.
+    LowGasLimit { gas_limit: u64, minimum: u64 },
+}
+
+impl core::fmt::Display for TxWarning {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            TxWarning::LowGasLimit { gas_limit, minimum } => write!(
+                f,
+                "gas limit {gas_limit} is below the expected minimum of {minimum}; \
+                 the transaction will likely revert"
+            ),
+        }
+    }
+}
+
+/// Returns a [`TxWarning::LowGasLimit`] if `gas_limit` is under `min_gas_limit`.
+pub fn check_gas_limit(gas_limit: u64, min_gas_limit: u64) -> Option<TxWarning> {
+    (gas_limit < min_gas_limit).then_some(TxWarning::LowGasLimit {
+        gas_limit,
+        minimum: min_gas_limit,
     })
 }
 
 /// Builds an unsigned EIP-1559 ERC-20 token transfer transaction.
 ///
 /// The calldata is automatically encoded using `transfer(address,uint256)`.
+/// A gas limit below [`MIN_ERC20_GAS_LIMIT`] is still accepted, but reported
+/// in the returned warnings.
+#[allow(clippy::too_many_arguments)]
 pub fn build_erc20_transfer(
     chain_id: u64,
     nonce: u64,
-    token_contract: &str,
-    to: &str,
+    token_contract: &EthAddress,
+    to: &EthAddress,
     amount: [u8; 32],
     max_priority_fee: u128,
     max_fee: u128,
     gas_limit: u64,
-) -> Result<EthTransaction, EthError> {
-    validate_to_address(token_contract)?;
+) -> Result<(EthTransaction, Vec<TxWarning>), EthError> {
+    let calldata = erc20::encode_transfer(to, amount);
+    let warnings = check_gas_limit(gas_limit, MIN_ERC20_GAS_LIMIT)
+        .into_iter()
+        .collect();
 
-    let calldata = erc20::encode_transfer(to, amount)?;
+    let tx = EthTransaction {
+        chain_id,
+        nonce,
+        max_priority_fee_per_gas: max_priority_fee,
+        max_fee_per_gas: max_fee,
+        gas_limit,
+        to: token_contract.to_string(),
+        value: 0,
+        data: calldata,
+        access_list: Vec::new(),
+    };
+    Ok((tx, warnings))
+}
+
+/// Builds an unsigned EIP-1559 ERC-20 `transferFrom` transaction.
+///
+/// Spends the sender's allowance on `from`'s tokens; the calldata is encoded
+/// using `transferFrom(address,address,uint256)`.
+#[allow(clippy::too_many_arguments)]
+pub fn build_erc20_transfer_from(
+    chain_id: u64,
+    nonce: u64,
+    token_contract: &EthAddress,
+    from: &EthAddress,
+    to: &EthAddress,
+    amount: [u8; 32],
+    max_priority_fee: u128,
+    max_fee: u128,
+    gas_limit: u64,
+) -> Result<EthTransaction, EthError> {
+    let calldata = erc20::encode_transfer_from(from, to, amount);
 
     Ok(EthTransaction {
         chain_id,
@@ -81,12 +207,53 @@ pub fn build_erc20_transfer(
         to: token_contract.to_string(),
         value: 0,
         data: calldata,
+        access_list: Vec::new(),
     })
 }
 
+/// Returns a replacement for a stuck `tx` with both fees raised by at least
+/// `min_bump_pct` percent, rounded up.
+///
+/// Nodes only accept a same-nonce replacement if both the priority fee and
+/// the max fee go up (geth requires 10%), so each fee also rises by at least
+/// 1 wei even when it was zero. Nonce, recipient, value and data are kept.
+pub fn bump_fees(tx: &EthTransaction, min_bump_pct: u8) -> EthTransaction {
+    let bump = |fee: u128| {
+        let increase = fee
+            .saturating_mul(u128::from(min_bump_pct))
+            .div_ceil(100)
+            .max(1);
+        fee.saturating_add(increase)
+    };
+
+    EthTransaction {
+        max_priority_fee_per_gas: bump(tx.max_priority_fee_per_gas),
+        max_fee_per_gas: bump(tx.max_fee_per_gas),
+        ..tx.clone()
+    }
+}
+
+/// Re-validates `tx.to` right before signing.
+///
+/// The builders only take an [`EthAddress`], but `EthTransaction` has public
+/// fields and round-trips through JSON, so the recipient may have changed
+/// since it was built. It must still be a 0x-prefixed 20-byte hex address,
+/// with a correct EIP-55 checksum if it is mixed-case.
+pub fn assert_recipient(tx: &EthTransaction) -> Result<(), EthError> {
+    match EthAddress::parse(&tx.to) {
+        Ok(_) => Ok(()),
+        Err(EthError::InvalidAddress(reason)) => Err(EthError::InvalidAddress(format!(
+            "recipient {:?} failed re-validation: {reason}",
+            tx.to
+        ))),
+        Err(e) => Err(e),
+    }
+}
+
 /// Signs an EIP-1559 transaction with the given secp256k1 private key.
 ///
-/// The signing process:
+/// The recipient is checked with [`assert_recipient`] first. The signing
+/// process:
 /// 1. RLP-encode the unsigned transaction fields.
 /// 2. Prepend the type byte (0x02) to get the signing payload.
 /// 3. Keccak-256 hash the payload.
@@ -97,11 +264,13 @@ pub fn sign_transaction(
     tx: &EthTransaction,
     private_key: &[u8; 32],
 ) -> Result<SignedEthTransaction, EthError> {
+    assert_recipient(tx)?;
+
     // Build the unsigned payload: 0x02 || rlp(unsigned_fields).
     let unsigned_payload = encode_unsigned_tx(tx)?;
 
     // Keccak-256 of the unsigned payload for signing.
-    let msg_hash = Keccak256::digest(&unsigned_payload);
+    let msg_hash = keccak256(&unsigned_payload);
 
     // Create the signing key (zeroized on drop).
     let mut key_bytes = *private_key;
@@ -132,9 +301,8 @@ pub fn sign_transaction(
         gas_limit: tx.gas_limit,
         to: parse_to_bytes(&tx.to)?,
         value: tx.value,
-        data: tx.data.clone(),
-        // Empty access list.
-        access_list: Vec::new(),
+        data: RlpBytes(tx.data.clone()),
+        access_list: encode_access_list(&tx.access_list)?,
         signature_y_parity: y_parity,
         signature_r: r_bytes.into(),
         signature_s: s_bytes.into(),
@@ -148,7 +316,7 @@ pub fn sign_transaction(
     raw_tx.extend_from_slice(&rlp_buf);
 
     // Transaction hash is the Keccak-256 of the signed raw bytes.
-    let tx_hash = Keccak256::digest(&raw_tx);
+    let tx_hash = keccak256(&raw_tx);
     let tx_hash_hex = format!("0x{}", hex::encode(tx_hash));
 
     Ok(SignedEthTransaction {
@@ -157,6 +325,49 @@ pub fn sign_transaction(
     })
 }
 
+/// Extracts `(y_parity, r, s)` from a signed EIP-1559 transaction.
+///
+/// Decodes `raw_tx` rather than trusting any cached values, so it also works
+/// on transactions signed elsewhere. Useful when an endpoint or debugger wants
+/// the signature split out; the legacy-style `v` is `27 + y_parity`.
+pub fn signature_components(
+    signed: &SignedEthTransaction,
+) -> Result<(u8, [u8; 32], [u8; 32]), EthError> {
+    let malformed =
+        |e: alloy_rlp::Error| EthError::EncodingError(format!("malformed signed transaction: {e}"));
+
+    let mut rlp = signed
+        .raw_tx
+        .strip_prefix(&[0x02])
+        .ok_or_else(|| EthError::EncodingError("not an EIP-1559 (type 2) transaction".into()))?;
+    let mut fields = Header::decode_bytes(&mut rlp, true).map_err(malformed)?;
+    if !rlp.is_empty() {
+        return Err(EthError::EncodingError(
+            "trailing bytes after transaction".into(),
+        ));
+    }
+
+    // Skip chain_id .. access_list; the signature is the last three fields.
+    for _ in 0..9 {
+        Header::decode_raw(&mut fields).map_err(malformed)?;
+    }
+    let y_parity = u8::decode(&mut fields).map_err(malformed)?;
+    let r = decode_u256_bytes(&mut fields).map_err(malformed)?;
+    let s = decode_u256_bytes(&mut fields).map_err(malformed)?;
+
+    if y_parity > 1 {
+        return Err(EthError::EncodingError(format!(
+            "invalid y_parity {y_parity}"
+        )));
+    }
+    if !fields.is_empty() {
+        return Err(EthError::EncodingError(
+            "unexpected fields after signature".into(),
+        ));
+    }
+    Ok((y_parity, r, s))
+}
+
 /// Signs an arbitrary message using EIP-191 personal_sign.
 ///
 /// The message is hashed as: keccak256("\x19Ethereum Signed Message:\n" + len(message) + message)
@@ -188,6 +399,28 @@ pub fn sign_message(
     Ok(sig)
 }
 
+/// Signs data for an intended validator using EIP-191 version `0x00`.
+///
+/// The data is hashed as: keccak256(0x19 || 0x00 || validator_address || data)
+/// where `validator_address` is the 20-byte address of the contract that
+/// will verify the signature. Binding the validator stops the signature from
+/// being replayed against a different contract.
+///
+/// Returns the 65-byte signature (r[32] + s[32] + v[1]) where v is 27 or 28.
+pub fn sign_message_v0(
+    validator: &EthAddress,
+    data: &[u8],
+    private_key: &[u8; 32],
+) -> Result<Vec<u8>, EthError> {
+    let mut hasher = Keccak256::new();
+    hasher.update([0x19, 0x00]);
+    hasher.update(validator.as_bytes());
+    hasher.update(data);
+    let msg_hash: [u8; 32] = hasher.finalize().into();
+
+    sign_raw_hash(&msg_hash, private_key)
+}
+
 /// Signs a raw 32-byte hash without any prefix (no EIP-191).
 ///
 /// Used for EIP-712 typed data signing where the caller has already computed
@@ -198,20 +431,170 @@ pub fn sign_raw_hash(
     hash: &[u8; 32],
     private_key: &[u8; 32],
 ) -> Result<Vec<u8>, EthError> {
-    let mut key_bytes = *private_key;
+    let (r, s, recovery_id) = ecdsa_sign_recoverable(hash, private_key)?;
+
+    let mut sig = Vec::with_capacity(65);
+    sig.extend_from_slice(&r);
+    sig.extend_from_slice(&s);
+    sig.push(recovery_id + 27); // v = 27 or 28
+    Ok(sig)
+}
+
+/// Signs a 32-byte prehash with secp256k1 ECDSA, returning `(r, s, recovery_id)`.
+///
+/// The building block under every signer here, for custom protocols that
+/// need the raw parts. The nonce is derived per RFC 6979, so the same hash
+/// and key always give the same signature; `s` is low-s normalized and
+/// `recovery_id` is the 0/1 y-parity of the nonce point.
+pub fn ecdsa_sign_recoverable(
+    hash32: &[u8; 32],
+    key: &[u8; 32],
+) -> Result<([u8; 32], [u8; 32], u8), EthError> {
+    let mut key_bytes = *key;
     let signing_key = SigningKey::from_bytes((&key_bytes).into())
         .map_err(|e| EthError::InvalidPrivateKey(e.to_string()))?;
     key_bytes.zeroize();
 
     let (signature, recovery_id): (Signature, RecoveryId) = signing_key
-        .sign_prehash(hash.as_slice())
+        .sign_prehash(hash32.as_slice())
         .map_err(|e| EthError::SigningError(e.to_string()))?;
 
-    let mut sig = Vec::with_capacity(65);
-    sig.extend_from_slice(&signature.r().to_bytes());
-    sig.extend_from_slice(&signature.s().to_bytes());
-    sig.push(recovery_id.is_y_odd() as u8 + 27); // v = 27 or 28
-    Ok(sig)
+    Ok((
+        signature.r().to_bytes().into(),
+        signature.s().to_bytes().into(),
+        recovery_id.is_y_odd() as u8,
+    ))
+}
+
+/// Converts a signature `v` from any common encoding to the 0/1 y-parity.
+///
+/// Accepts the raw parity (0/1), the EIP-191 / pre-EIP-155 form (27/28) and
+/// EIP-155 `chain_id * 2 + 35 + parity`. When `chain_id` is given, an EIP-155
+/// `v` must belong to that chain; without it the parity is taken as-is.
+pub fn normalize_recovery_id(v: u64, chain_id: Option<u64>) -> Result<u8, EthError> {
+    match v {
+        0 | 1 => Ok(v as u8),
+        27 | 28 => Ok((v - 27) as u8),
+        35.. => {
+            let parity = (v - 35) % 2;
+            if let Some(chain_id) = chain_id {
+                let base = chain_id.checked_mul(2).and_then(|x| x.checked_add(35));
+                if base != Some(v - parity) {
+                    return Err(EthError::SigningError(format!(
+                        "v = {v} is not an EIP-155 value for chain {chain_id}"
+                    )));
+                }
+            }
+            Ok(parity as u8)
+        }
+        _ => Err(EthError::SigningError(format!(
+            "invalid recovery id v = {v}"
+        ))),
+    }
+}
+
+/// Chain id encoded in an EIP-155 legacy-transaction `v`, `(v - 35) / 2`.
+///
+/// Returns `None` for pre-EIP-155 `v` (27/28) and anything else below 35:
+/// such a signature carries no chain id and can be replayed on any EVM chain.
+pub fn extract_chain_id_from_v(v: u64) -> Option<u64> {
+    v.checked_sub(35).map(|offset| offset / 2)
+}
+
+/// Packs a 65-byte `r || s || v` signature into the 64-byte EIP-2098 compact
+/// form `r || yParityAndS`, storing the y-parity in the top bit of `s`.
+///
+/// `v` must be 27/28 or the raw parity 0/1; any other recovery byte is
+/// rejected rather than reduced to a parity. High-s signatures are rejected,
+/// as in [`der_to_compact`]: the top bit of a high `s` would collide with
+/// the parity bit. Signatures from this crate are always low-s.
+pub fn to_compact_signature(sig: &[u8; 65]) -> Result<[u8; 64], EthError> {
+    let signature = Signature::from_slice(&sig[..64])
+        .map_err(|e| EthError::EncodingError(format!("invalid signature: {e}")))?;
+    if signature.normalize_s().is_some() {
+        return Err(EthError::EncodingError(
+            "non-canonical signature: s is in the upper half of the curve order".into(),
+        ));
+    }
+
+    let y_parity = match sig[64] {
+        0 | 27 => 0,
+        1 | 28 => 1,
+        v => {
+            return Err(EthError::EncodingError(format!(
+                "invalid recovery byte v = {v}: expected 0, 1, 27 or 28"
+            )))
+        }
+    };
+
+    let mut compact = [0u8; 64];
+    compact.copy_from_slice(&sig[..64]);
+    compact[32] |= y_parity << 7;
+    Ok(compact)
+}
+
+/// Expands an EIP-2098 compact signature back to `r || s || v` with v = 27 or 28.
+pub fn from_compact_signature(compact: &[u8; 64]) -> [u8; 65] {
+    let mut sig = [0u8; 65];
+    sig[..64].copy_from_slice(compact);
+    sig[32] &= 0x7f;
+    sig[64] = 27 + (compact[32] >> 7);
+    sig
+}
+
+/// Converts a DER-encoded ECDSA signature (BTC/ZEC style) to the 64-byte
+/// `r || s` form taken by recovery.
+///
+/// The encoding must be strict DER, and high-s signatures are rejected, not
+/// normalized. Anyone can flip `s` to `n - s`, so a high-s signature is
+/// malleable and was not produced by a canonical signer. This is plain
+/// `r || s`, not the EIP-2098 form of [`to_compact_signature`].
+pub fn der_to_compact(der: &[u8]) -> Result<[u8; 64], EthError> {
+    let signature = Signature::from_der(der)
+        .map_err(|e| EthError::EncodingError(format!("invalid DER signature: {e}")))?;
+    if signature.normalize_s().is_some() {
+        return Err(EthError::EncodingError(
+            "non-canonical signature: s is in the upper half of the curve order".into(),
+        ));
+    }
+    Ok(signature.to_bytes().into())
+}
+
+/// Finds the recovery id (0 or 1) for a bare 64-byte `r || s` signature.
+///
+/// Tries both y-parities against `message_hash` and returns the one whose
+/// recovered signer is `expected_address`. Fails if neither matches, e.g.
+/// the hash or address is wrong.
+pub fn recover_with_search(
+    r_s: &[u8; 64],
+    message_hash: &[u8; 32],
+    expected_address: &str,
+) -> Result<u8, EthError> {
+    let expected = EthAddress::parse(expected_address)?;
+    let signature = Signature::from_slice(r_s)
+        .map_err(|e| EthError::EncodingError(format!("invalid signature: {e}")))?;
+
+    for recovery_id in 0..=1u8 {
+        let Some(id) = RecoveryId::from_byte(recovery_id) else {
+            continue;
+        };
+        let Ok(key) = VerifyingKey::recover_from_prehash(message_hash, &signature, id) else {
+            continue;
+        };
+        let uncompressed: [u8; 65] = key
+            .to_encoded_point(false)
+            .as_bytes()
+            .try_into()
+            .map_err(|_| EthError::SigningError("unexpected public key length".into()))?;
+        let recovered = EthAddress::parse(&pubkey_to_eth_address(&uncompressed)?)?;
+        if recovered.as_bytes() == expected.as_bytes() {
+            return Ok(recovery_id);
+        }
+    }
+
+    Err(EthError::SigningError(format!(
+        "signature does not recover to {expected_address}"
+    )))
 }
 
 /// Encodes the unsigned EIP-1559 transaction as `0x02 || rlp(fields)`.
@@ -228,8 +611,8 @@ pub fn encode_unsigned_tx(tx: &EthTransaction) -> Result<Vec<u8>, EthError> {
         gas_limit: tx.gas_limit,
         to: parse_to_bytes(&tx.to)?,
         value: tx.value,
-        data: tx.data.clone(),
-        access_list: Vec::new(),
+        data: RlpBytes(tx.data.clone()),
+        access_list: encode_access_list(&tx.access_list)?,
     };
 
     let mut rlp_buf = Vec::new();
@@ -242,6 +625,42 @@ pub fn encode_unsigned_tx(tx: &EthTransaction) -> Result<Vec<u8>, EthError> {
     Ok(payload)
 }
 
+/// Offline record of `(sender, chain_id, nonce)` tuples already signed.
+///
+/// A second transaction with the same sender, chain and nonce either replaces
+/// the first or is dropped by the network, so an app can [`reserve`] each
+/// nonce before signing to catch accidental reuse. Advisory only: it knows
+/// nothing about on-chain state and is lost when dropped.
+///
+/// [`reserve`]: NonceTracker::reserve
+#[derive(Debug, Clone, Default)]
+pub struct NonceTracker {
+    used: HashSet<([u8; 20], u64, u64)>,
+}
+
+impl NonceTracker {
+    /// Creates an empty tracker.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records `nonce` for `from` on `chain_id`; errors if it was already used.
+    pub fn reserve(
+        &mut self,
+        from: &EthAddress,
+        chain_id: u64,
+        nonce: u64,
+    ) -> Result<(), EthError> {
+        if self.used.insert((*from.as_bytes(), chain_id, nonce)) {
+            Ok(())
+        } else {
+            Err(EthError::TransactionBuildError(format!(
+                "nonce {nonce} already used by {from} on chain {chain_id}"
+            )))
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // RLP-encodable structures
 // ---------------------------------------------------------------------------
@@ -256,7 +675,7 @@ struct UnsignedTxFields {
     gas_limit: u64,
     to: RlpAddress,
     value: u128,
-    data: Vec<u8>,
+    data: RlpBytes,
     access_list: Vec<AccessListItem>,
 }
 
@@ -270,14 +689,14 @@ struct SignedTxFields {
     gas_limit: u64,
     to: RlpAddress,
     value: u128,
-    data: Vec<u8>,
+    data: RlpBytes,
     access_list: Vec<AccessListItem>,
     signature_y_parity: u8,
     signature_r: RlpU256,
     signature_s: RlpU256,
 }
 
-/// An EIP-2930 access list entry (kept empty for now).
+/// An EIP-2930 access list entry as encoded on the wire.
 #[derive(Debug, Clone, RlpEncodable)]
 struct AccessListItem {
     address: RlpAddress,
@@ -299,6 +718,22 @@ impl Encodable for RlpAddress {
     }
 }
 
+/// Wrapper for a variable-length byte string (calldata).
+///
+/// A bare `Vec<u8>` would RLP-encode as a list of integers, not a string.
+#[derive(Debug, Clone)]
+struct RlpBytes(Vec<u8>);
+
+impl Encodable for RlpBytes {
+    fn encode(&self, out: &mut dyn alloy_rlp::BufMut) {
+        self.0.as_slice().encode(out);
+    }
+
+    fn length(&self) -> usize {
+        self.0.as_slice().length()
+    }
+}
+
 /// Wrapper for a 256-bit integer (32 bytes) that encodes as minimal big-endian
 /// bytes with leading zeros stripped (standard RLP integer encoding).
 #[derive(Debug, Clone)]
@@ -343,6 +778,17 @@ impl<const N: usize> Encodable for RlpFixedBytes<N> {
 // Helpers
 // ---------------------------------------------------------------------------
 
+/// Decodes an RLP integer of at most 32 bytes into a left-padded uint256.
+fn decode_u256_bytes(buf: &mut &[u8]) -> Result<[u8; 32], alloy_rlp::Error> {
+    let bytes = Header::decode_bytes(buf, false)?;
+    if bytes.len() > 32 {
+        return Err(alloy_rlp::Error::Overflow);
+    }
+    let mut out = [0u8; 32];
+    out[32 - bytes.len()..].copy_from_slice(bytes);
+    Ok(out)
+}
+
 /// Parses a 0x-prefixed hex address string into the RLP wrapper.
 fn parse_to_bytes(address: &str) -> Result<RlpAddress, EthError> {
     let hex_str = address
@@ -367,10 +813,22 @@ fn parse_to_bytes(address: &str) -> Result<RlpAddress, EthError> {
     Ok(RlpAddress(addr))
 }
 
-/// Validates that a "to" address is well-formed.
-fn validate_to_address(address: &str) -> Result<(), EthError> {
-    let _ = parse_to_bytes(address)?;
-    Ok(())
+/// Converts the public access list into its RLP form, validating addresses.
+fn encode_access_list(entries: &[AccessListEntry]) -> Result<Vec<AccessListItem>, EthError> {
+    entries
+        .iter()
+        .map(|entry| {
+            Ok(AccessListItem {
+                address: parse_to_bytes(&entry.address)?,
+                storage_keys: entry
+                    .storage_keys
+                    .iter()
+                    .copied()
+                    .map(RlpFixedBytes)
+                    .collect(),
+            })
+        })
+        .collect()
 }
 
 #[cfg(test)]
@@ -386,16 +844,21 @@ mod tests {
 
     const TEST_ADDRESS: &str = "0x000000000000000000000000000000000000dEaD";
 
+    fn test_address() -> EthAddress {
+        EthAddress::parse(TEST_ADDRESS).unwrap()
+    }
+
     #[test]
     fn build_transfer_creates_valid_tx() {
         let tx = build_transfer(
             1,
             0,
-            TEST_ADDRESS,
+            &test_address(),
             1_000_000_000_000_000_000, // 1 ETH
             1_000_000_000,              // 1 gwei priority
             50_000_000_000,             // 50 gwei max
             21_000,
+            false,
         )
         .unwrap();
 
@@ -407,22 +870,38 @@ mod tests {
     }
 
     #[test]
-    fn build_transfer_invalid_address() {
-        let result = build_transfer(1, 0, "bad-address", 0, 0, 0, 21_000);
-        assert!(result.is_err());
+    fn malformed_recipient_is_not_an_eth_address() {
+        // Recipients are typed, so malformed input is rejected before a
+        // transaction can be built.
+        assert!(EthAddress::parse("bad-address").is_err());
+    }
+
+    #[test]
+    fn build_transfer_rejects_zero_value_by_default() {
+        let err = build_transfer(1, 0, &test_address(), 0, 100, 200, 21_000, false).unwrap_err();
+        assert!(matches!(err, EthError::TransactionBuildError(_)));
+    }
+
+    #[test]
+    fn build_transfer_allows_zero_value_contract_call() {
+        let mut tx = build_transfer(1, 0, &test_address(), 0, 100, 200, 60_000, true).unwrap();
+        tx.data = vec![0xa9, 0x05, 0x9c, 0xbb];
+
+        assert_eq!(tx.value, 0);
+        assert!(sign_transaction(&tx, &[1u8; 32]).is_ok());
     }
 
     #[test]
     fn build_erc20_transfer_creates_valid_tx() {
-        let token = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48"; // USDC
+        let token = EthAddress::parse("0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48").unwrap(); // USDC
         let mut amount = [0u8; 32];
         amount[31] = 100;
 
-        let tx = build_erc20_transfer(
+        let (tx, warnings) = build_erc20_transfer(
             1,
             5,
-            token,
-            TEST_ADDRESS,
+            &token,
+            &test_address(),
             amount,
             1_000_000_000,
             50_000_000_000,
@@ -430,6 +909,7 @@ mod tests {
         )
         .unwrap();
 
+        assert!(warnings.is_empty());
         assert_eq!(tx.chain_id, 1);
         assert_eq!(tx.nonce, 5);
         assert_eq!(tx.value, 0);
@@ -440,9 +920,42 @@ mod tests {
         assert_eq!(&tx.data[..4], &[0xa9, 0x05, 0x9c, 0xbb]);
     }
 
+    #[test]
+    fn build_erc20_transfer_warns_on_low_gas_limit() {
+        let (tx, warnings) = build_erc20_transfer(
+            1,
+            0,
+            &test_address(),
+            &test_address(),
+            [0; 32],
+            0,
+            0,
+            21_000,
+        )
+        .unwrap();
+
+        // Still built as requested; the caller decides whether to proceed.
+        assert_eq!(tx.gas_limit, 21_000);
+        assert_eq!(
+            warnings,
+            vec![TxWarning::LowGasLimit {
+                gas_limit: 21_000,
+                minimum: MIN_ERC20_GAS_LIMIT
+            }]
+        );
+        assert!(warnings[0].to_string().contains("21000"));
+    }
+
+    #[test]
+    fn check_gas_limit_boundary() {
+        assert!(check_gas_limit(45_000, MIN_ERC20_GAS_LIMIT).is_none());
+        assert!(check_gas_limit(44_999, MIN_ERC20_GAS_LIMIT).is_some());
+        assert!(check_gas_limit(100_000, 120_000).is_some());
+    }
+
     #[test]
     fn encode_unsigned_tx_starts_with_type_byte() {
-        let tx = build_transfer(1, 0, TEST_ADDRESS, 0, 0, 0, 21_000).unwrap();
+        let tx = build_transfer(1, 0, &test_address(), 0, 0, 0, 21_000, true).unwrap();
         let encoded = encode_unsigned_tx(&tx).unwrap();
 
         assert_eq!(encoded[0], 0x02, "EIP-1559 type byte must be 0x02");
@@ -454,11 +967,12 @@ mod tests {
         let tx = build_transfer(
             1,
             42,
-            TEST_ADDRESS,
+            &test_address(),
             1_000_000_000,
             100,
             200,
             21_000,
+            false,
         )
         .unwrap();
 
@@ -473,11 +987,12 @@ mod tests {
         let tx = build_transfer(
             1,     // Ethereum mainnet
             0,     // nonce
-            TEST_ADDRESS,
+            &test_address(),
             1_000_000_000_000_000_000, // 1 ETH
             1_000_000_000,              // 1 gwei
             50_000_000_000,             // 50 gwei
             21_000,
+            false,
         )
         .unwrap();
 
@@ -493,7 +1008,7 @@ mod tests {
 
     #[test]
     fn sign_transaction_is_deterministic() {
-        let tx = build_transfer(1, 0, TEST_ADDRESS, 0, 100, 200, 21_000).unwrap();
+        let tx = build_transfer(1, 0, &test_address(), 0, 100, 200, 21_000, true).unwrap();
 
         let signed1 = sign_transaction(&tx, &TEST_PRIVKEY).unwrap();
         let signed2 = sign_transaction(&tx, &TEST_PRIVKEY).unwrap();
@@ -504,8 +1019,8 @@ mod tests {
 
     #[test]
     fn sign_transaction_different_nonces_differ() {
-        let tx1 = build_transfer(1, 0, TEST_ADDRESS, 0, 100, 200, 21_000).unwrap();
-        let tx2 = build_transfer(1, 1, TEST_ADDRESS, 0, 100, 200, 21_000).unwrap();
+        let tx1 = build_transfer(1, 0, &test_address(), 0, 100, 200, 21_000, true).unwrap();
+        let tx2 = build_transfer(1, 1, &test_address(), 0, 100, 200, 21_000, true).unwrap();
 
         let signed1 = sign_transaction(&tx1, &TEST_PRIVKEY).unwrap();
         let signed2 = sign_transaction(&tx2, &TEST_PRIVKEY).unwrap();
@@ -516,8 +1031,8 @@ mod tests {
 
     #[test]
     fn sign_transaction_different_chains_differ() {
-        let tx1 = build_transfer(1, 0, TEST_ADDRESS, 0, 100, 200, 21_000).unwrap();
-        let tx2 = build_transfer(137, 0, TEST_ADDRESS, 0, 100, 200, 21_000).unwrap();
+        let tx1 = build_transfer(1, 0, &test_address(), 0, 100, 200, 21_000, true).unwrap();
+        let tx2 = build_transfer(137, 0, &test_address(), 0, 100, 200, 21_000, true).unwrap();
 
         let signed1 = sign_transaction(&tx1, &TEST_PRIVKEY).unwrap();
         let signed2 = sign_transaction(&tx2, &TEST_PRIVKEY).unwrap();
@@ -527,16 +1042,40 @@ mod tests {
 
     #[test]
     fn sign_transaction_invalid_private_key() {
-        let tx = build_transfer(1, 0, TEST_ADDRESS, 0, 0, 0, 21_000).unwrap();
+        let tx = build_transfer(1, 0, &test_address(), 0, 0, 0, 21_000, true).unwrap();
         let bad_key = [0u8; 32]; // All zeros is not a valid private key.
 
         let result = sign_transaction(&tx, &bad_key);
         assert!(result.is_err());
     }
 
+    #[test]
+    fn sign_transaction_rejects_corrupted_recipient() {
+        let mut tx = build_transfer(1, 0, &test_address(), 0, 0, 0, 21_000, true).unwrap();
+        assert!(assert_recipient(&tx).is_ok());
+
+        tx.to = "0xnot-an-address".into();
+        match sign_transaction(&tx, &TEST_PRIVKEY) {
+            Err(EthError::InvalidAddress(msg)) => {
+                assert!(msg.contains("recipient \"0xnot-an-address\""), "{msg}");
+            }
+            other => panic!(
+                "expected InvalidAddress, got {:?}",
+                other.map(|s| s.tx_hash)
+            ),
+        }
+
+        // Right length and hex, but a broken EIP-55 checksum.
+        tx.to = "0x000000000000000000000000000000000000DEad".into();
+        assert!(matches!(
+            sign_transaction(&tx, &TEST_PRIVKEY),
+            Err(EthError::InvalidAddress(_))
+        ));
+    }
+
     #[test]
     fn signed_tx_raw_bytes_are_nonempty() {
-        let tx = build_transfer(1, 0, TEST_ADDRESS, 0, 0, 0, 21_000).unwrap();
+        let tx = build_transfer(1, 0, &test_address(), 0, 0, 0, 21_000, true).unwrap();
         let signed = sign_transaction(&tx, &TEST_PRIVKEY).unwrap();
 
         // Should be at least type byte + some RLP + signature.
@@ -544,33 +1083,145 @@ mod tests {
     }
 
     #[test]
-    fn build_erc20_transfer_invalid_contract() {
-        let result = build_erc20_transfer(
-            1,
-            0,
-            "not-an-address",
-            TEST_ADDRESS,
-            [0u8; 32],
-            0,
-            0,
-            65_000,
-        );
-        assert!(result.is_err());
+    fn signature_components_recover_signer() {
+        use k256::ecdsa::VerifyingKey;
+
+        let tx = build_transfer(1, 7, &test_address(), 1_000, 100, 200, 21_000, false).unwrap();
+        let signed = sign_transaction(&tx, &TEST_PRIVKEY).unwrap();
+        let (y_parity, r, s) = signature_components(&signed).unwrap();
+
+        let signature = Signature::from_scalars(r, s).unwrap();
+        let recovery_id = RecoveryId::from_byte(y_parity).unwrap();
+        let hash = Keccak256::digest(encode_unsigned_tx(&tx).unwrap());
+        let recovered = VerifyingKey::recover_from_prehash(&hash, &signature, recovery_id).unwrap();
+
+        let signer = SigningKey::from_bytes((&TEST_PRIVKEY).into()).unwrap();
+        assert_eq!(&recovered, signer.verifying_key());
+
+        // The components are the tail of the encoded transaction.
+        assert_eq!(&signed.raw_tx[signed.raw_tx.len() - 32..], &s);
     }
 
     #[test]
-    fn build_erc20_transfer_invalid_recipient() {
-        let result = build_erc20_transfer(
-            1,
-            0,
-            TEST_ADDRESS,
-            "bad",
-            [0u8; 32],
-            0,
-            0,
-            65_000,
-        );
-        assert!(result.is_err());
+    fn signature_components_rejects_malformed() {
+        let tx = build_transfer(1, 0, &test_address(), 0, 0, 0, 21_000, true).unwrap();
+        let signed = sign_transaction(&tx, &TEST_PRIVKEY).unwrap();
+
+        let mut truncated = signed.raw_tx.clone();
+        truncated.pop();
+        let mut legacy = signed.raw_tx.clone();
+        legacy[0] = 0x01;
+        let mut trailing = signed.raw_tx.clone();
+        trailing.push(0x00);
+
+        for raw_tx in [truncated, legacy, trailing, vec![0x02]] {
+            let bad = SignedEthTransaction {
+                raw_tx,
+                tx_hash: signed.tx_hash.clone(),
+            };
+            assert!(signature_components(&bad).is_err());
+        }
+    }
+
+    #[test]
+    fn bump_fees_raises_both_fees_by_at_least_pct() {
+        let (priority, max) = (1_500_000_001, 30_000_000_007);
+        let tx =
+            build_transfer(1, 9, &test_address(), 1_000, priority, max, 21_000, false).unwrap();
+        let bumped = bump_fees(&tx, 10);
+
+        assert!(bumped.max_priority_fee_per_gas * 100 >= tx.max_priority_fee_per_gas * 110);
+        assert!(bumped.max_fee_per_gas * 100 >= tx.max_fee_per_gas * 110);
+        // Rounded up, not down: 10% of 1_500_000_001 is 150_000_000.1.
+        assert_eq!(bumped.max_priority_fee_per_gas, 1_650_000_002);
+        assert_eq!(bumped.max_fee_per_gas, 33_000_000_008);
+
+        assert_eq!(bumped.nonce, tx.nonce);
+        assert_eq!(bumped.to, tx.to);
+        assert_eq!(bumped.value, tx.value);
+        assert_eq!(bumped.data, tx.data);
+        assert_eq!(bumped.gas_limit, tx.gas_limit);
+    }
+
+    #[test]
+    fn bump_fees_always_increases() {
+        let tx = build_transfer(1, 0, &test_address(), 0, 0, 5, 21_000, true).unwrap();
+        let bumped = bump_fees(&tx, 10);
+        assert_eq!(bumped.max_priority_fee_per_gas, 1);
+        assert_eq!(bumped.max_fee_per_gas, 6);
+
+        let maxed =
+            build_transfer(1, 0, &test_address(), 0, u128::MAX, u128::MAX, 21_000, true).unwrap();
+        assert_eq!(bump_fees(&maxed, 10).max_fee_per_gas, u128::MAX);
+    }
+
+    #[test]
+    fn nonce_tracker_rejects_reuse() {
+        let mut tracker = NonceTracker::new();
+        tracker.reserve(&test_address(), 1, 7).unwrap();
+        assert!(tracker.reserve(&test_address(), 1, 7).is_err());
+
+        // Same address in another spelling is still the same sender.
+        let lower = EthAddress::parse(&TEST_ADDRESS.to_lowercase()).unwrap();
+        assert!(tracker.reserve(&lower, 1, 7).is_err());
+    }
+
+    #[test]
+    fn nonce_tracker_allows_distinct_tuples() {
+        let other = EthAddress::parse("0x000000000000000000000000000000000000bEEF").unwrap();
+        let mut tracker = NonceTracker::new();
+        tracker.reserve(&test_address(), 1, 7).unwrap();
+        tracker.reserve(&test_address(), 1, 8).unwrap();
+        tracker.reserve(&test_address(), 137, 7).unwrap();
+        tracker.reserve(&other, 1, 7).unwrap();
+    }
+
+    // DER and r || s forms of sign_raw_hash([0xAA; 32], TEST_PRIVKEY). r has
+    // its top bit set, so DER pads it with a 0x00 byte.
+    const SIG_DER: &str =
+        "3045022100cf8298b9d991a37626f9816dd75ff8427f5169a0f122a65b8554e9f6504c98\
+                           4f02203490ec2e5a8ddaebd27bbb531fefad6336d2a794dbcf2e0f3cae0e7b34bd33e0";
+    const SIG_RS: &str = "cf8298b9d991a37626f9816dd75ff8427f5169a0f122a65b8554e9f6504c984f\
+                          3490ec2e5a8ddaebd27bbb531fefad6336d2a794dbcf2e0f3cae0e7b34bd33e0";
+
+    #[test]
+    fn der_to_compact_converts_known_signature() {
+        let compact = der_to_compact(&hex::decode(SIG_DER).unwrap()).unwrap();
+        assert_eq!(hex::encode(compact), SIG_RS);
+
+        let sig = sign_raw_hash(&[0xAAu8; 32], &TEST_PRIVKEY).unwrap();
+        assert_eq!(&sig[..64], &compact);
+    }
+
+    #[test]
+    fn der_to_compact_rejects_high_s() {
+        // Same signature with s replaced by n - s.
+        let high_s = hex::decode(
+            "3046022100cf8298b9d991a37626f9816dd75ff8427f5169a0f122a65b8554e9f6504c984f\
+             022100cb6f13d1a57225142d8444ace010529b83dc3551d379722c832450119b790d61",
+        )
+        .unwrap();
+        let err = der_to_compact(&high_s).unwrap_err();
+        assert!(err.to_string().contains("non-canonical"));
+    }
+
+    #[test]
+    fn der_to_compact_rejects_malformed() {
+        let mut der = hex::decode(SIG_DER).unwrap();
+        assert!(der_to_compact(&der[..der.len() - 1]).is_err());
+        der.push(0x00);
+        assert!(der_to_compact(&der).is_err());
+        assert!(der_to_compact(&hex::decode(SIG_RS).unwrap()).is_err());
+    }
+
+    #[test]
+    fn malformed_token_contract_is_not_an_eth_address() {
+        assert!(EthAddress::parse("not-an-address").is_err());
+    }
+
+    #[test]
+    fn short_token_recipient_is_not_an_eth_address() {
+        assert!(EthAddress::parse("bad").is_err());
     }
 
     #[test]
@@ -598,6 +1249,227 @@ mod tests {
         assert_ne!(raw_sig, personal_sig);
     }
 
+    #[test]
+    fn sign_message_v0_differs_from_personal_sign() {
+        let data = b"intended validator payload";
+        let v0_sig = sign_message_v0(&test_address(), data, &TEST_PRIVKEY).unwrap();
+        let personal_sig = sign_message(data, &TEST_PRIVKEY).unwrap();
+
+        assert_eq!(v0_sig.len(), 65);
+        assert_ne!(v0_sig, personal_sig);
+    }
+
+    #[test]
+    fn ecdsa_sign_recoverable_is_deterministic() {
+        let hash = [0x42u8; 32];
+        let first = ecdsa_sign_recoverable(&hash, &TEST_PRIVKEY).unwrap();
+        let second = ecdsa_sign_recoverable(&hash, &TEST_PRIVKEY).unwrap();
+        assert_eq!(first, second);
+
+        // Same RFC 6979 signature as the 65-byte form
+        let (r, s, recovery_id) = first;
+        let sig = sign_raw_hash(&hash, &TEST_PRIVKEY).unwrap();
+        assert_eq!(sig[..32], r);
+        assert_eq!(sig[32..64], s);
+        assert_eq!(sig[64], recovery_id + 27);
+    }
+
+    #[test]
+    fn ecdsa_sign_recoverable_recovers_signer() {
+        use k256::ecdsa::VerifyingKey;
+
+        let hash: [u8; 32] = Keccak256::digest(b"custom protocol payload").into();
+        let (r, s, recovery_id) = ecdsa_sign_recoverable(&hash, &TEST_PRIVKEY).unwrap();
+        assert!(recovery_id <= 1);
+
+        let signature = Signature::from_scalars(r, s).unwrap();
+        let recovery_id = RecoveryId::from_byte(recovery_id).unwrap();
+        let recovered = VerifyingKey::recover_from_prehash(&hash, &signature, recovery_id).unwrap();
+
+        let signer = SigningKey::from_bytes((&TEST_PRIVKEY).into()).unwrap();
+        assert_eq!(&recovered, signer.verifying_key());
+    }
+
+    #[test]
+    fn ecdsa_sign_recoverable_invalid_key() {
+        let result = ecdsa_sign_recoverable(&[1u8; 32], &[0u8; 32]);
+        assert!(matches!(result, Err(EthError::InvalidPrivateKey(_))));
+    }
+
+    #[test]
+    fn sign_message_v0_recovers_signer() {
+        use k256::ecdsa::VerifyingKey;
+
+        let data = b"intended validator payload";
+        let sig = sign_message_v0(&test_address(), data, &TEST_PRIVKEY).unwrap();
+
+        let mut preimage = vec![0x19, 0x00];
+        preimage.extend_from_slice(&hex::decode(&TEST_ADDRESS[2..]).unwrap());
+        preimage.extend_from_slice(data);
+        let hash = Keccak256::digest(&preimage);
+
+        let signature = Signature::from_slice(&sig[..64]).unwrap();
+        let recovery_id = RecoveryId::from_byte(sig[64] - 27).unwrap();
+        let recovered = VerifyingKey::recover_from_prehash(&hash, &signature, recovery_id).unwrap();
+
+        let signer = SigningKey::from_bytes((&TEST_PRIVKEY).into()).unwrap();
+        assert_eq!(&recovered, signer.verifying_key());
+    }
+
+    #[test]
+    fn sign_message_v0_binds_validator() {
+        let data = b"payload";
+        let other = EthAddress::parse("0x000000000000000000000000000000000000bEEF").unwrap();
+        assert_ne!(
+            sign_message_v0(&test_address(), data, &TEST_PRIVKEY).unwrap(),
+            sign_message_v0(&other, data, &TEST_PRIVKEY).unwrap()
+        );
+    }
+
+    #[test]
+    fn short_validator_is_not_an_eth_address() {
+        assert!(EthAddress::parse("0xdead").is_err());
+    }
+
+    #[test]
+    fn recover_with_search_finds_stripped_recovery_id() {
+        let message = b"recover me";
+        let sig = sign_message(message, &TEST_PRIVKEY).unwrap();
+        let prefix = format!("\x19Ethereum Signed Message:\n{}", message.len());
+        let hash = keccak256(&[prefix.as_bytes(), message].concat());
+
+        let r_s: [u8; 64] = sig[..64].try_into().unwrap();
+        let signer = "0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf";
+        assert_eq!(
+            recover_with_search(&r_s, &hash, signer).unwrap(),
+            sig[64] - 27
+        );
+    }
+
+    #[test]
+    fn recover_with_search_rejects_other_signer() {
+        let (r, s, _) = ecdsa_sign_recoverable(&[7u8; 32], &TEST_PRIVKEY).unwrap();
+        let r_s: [u8; 64] = [r, s].concat().try_into().unwrap();
+
+        assert!(recover_with_search(&r_s, &[7u8; 32], TEST_ADDRESS).is_err());
+        assert!(recover_with_search(&r_s, &[8u8; 32], TEST_ADDRESS).is_err());
+    }
+
+    #[test]
+    fn normalize_recovery_id_raw_and_offset() {
+        assert_eq!(normalize_recovery_id(0, None).unwrap(), 0);
+        assert_eq!(normalize_recovery_id(1, None).unwrap(), 1);
+        assert_eq!(normalize_recovery_id(27, None).unwrap(), 0);
+        assert_eq!(normalize_recovery_id(28, Some(1)).unwrap(), 1);
+    }
+
+    #[test]
+    fn normalize_recovery_id_eip155() {
+        // Mainnet: 1 * 2 + 35 = 37 (parity 0) and 38 (parity 1).
+        assert_eq!(normalize_recovery_id(37, Some(1)).unwrap(), 0);
+        assert_eq!(normalize_recovery_id(38, Some(1)).unwrap(), 1);
+        assert_eq!(normalize_recovery_id(38, None).unwrap(), 1);
+        // Polygon (137): 309 / 310.
+        assert_eq!(normalize_recovery_id(310, Some(137)).unwrap(), 1);
+    }
+
+    #[test]
+    fn extract_chain_id_from_eip155_v() {
+        assert_eq!(extract_chain_id_from_v(37), Some(1));
+        assert_eq!(extract_chain_id_from_v(38), Some(1));
+        assert_eq!(extract_chain_id_from_v(310), Some(137));
+        assert_eq!(extract_chain_id_from_v(u64::MAX), Some((u64::MAX - 35) / 2));
+    }
+
+    #[test]
+    fn extract_chain_id_none_for_pre_eip155_v() {
+        assert_eq!(extract_chain_id_from_v(27), None);
+        assert_eq!(extract_chain_id_from_v(28), None);
+        assert_eq!(extract_chain_id_from_v(0), None);
+        assert_eq!(extract_chain_id_from_v(34), None);
+    }
+
+    #[test]
+    fn normalize_recovery_id_rejects_invalid() {
+        assert!(normalize_recovery_id(2, None).is_err());
+        assert!(normalize_recovery_id(29, None).is_err());
+        assert!(normalize_recovery_id(34, None).is_err());
+        // v for chain 1 presented as chain 137.
+        assert!(normalize_recovery_id(37, Some(137)).is_err());
+        assert!(normalize_recovery_id(u64::MAX, Some(u64::MAX)).is_err());
+    }
+
+    #[test]
+    fn compact_signature_roundtrip() {
+        for i in 0u8..8 {
+            let sig: [u8; 65] = sign_message(&[i], &TEST_PRIVKEY)
+                .unwrap()
+                .try_into()
+                .unwrap();
+            let compact = to_compact_signature(&sig).unwrap();
+
+            assert_eq!(&compact[..32], &sig[..32]);
+            assert_eq!(from_compact_signature(&compact), sig);
+        }
+    }
+
+    #[test]
+    fn compact_signature_sets_parity_bit_for_v28() {
+        let (sig, compact) = (0u8..=255)
+            .map(|i| {
+                let sig: [u8; 65] = sign_message(&[i], &TEST_PRIVKEY)
+                    .unwrap()
+                    .try_into()
+                    .unwrap();
+                (sig, to_compact_signature(&sig).unwrap())
+            })
+            .find(|(sig, _)| sig[64] == 28)
+            .unwrap();
+
+        assert_eq!(compact[32] & 0x80, 0x80);
+        assert_eq!(compact[32] & 0x7f, sig[32]);
+        assert_eq!(&compact[33..], &sig[33..64]);
+    }
+
+    #[test]
+    fn compact_signature_accepts_raw_parity() {
+        let mut sig = [0x11u8; 65];
+        sig[64] = 1;
+        assert_eq!(to_compact_signature(&sig).unwrap()[32], 0x91);
+        sig[64] = 0;
+        assert_eq!(to_compact_signature(&sig).unwrap()[32], 0x11);
+        sig[64] = 27;
+        assert_eq!(to_compact_signature(&sig).unwrap()[32], 0x11);
+        sig[64] = 28;
+        assert_eq!(to_compact_signature(&sig).unwrap()[32], 0x91);
+    }
+
+    #[test]
+    fn compact_signature_rejects_invalid_recovery_byte() {
+        let mut sig = [0x11u8; 65];
+        for v in [2, 5, 26, 29, 30, 37, 255] {
+            sig[64] = v;
+            let err = to_compact_signature(&sig).unwrap_err();
+            assert!(err.to_string().contains("recovery byte"), "v = {v}: {err}");
+        }
+    }
+
+    #[test]
+    fn compact_signature_rejects_high_s() {
+        let mut sig: [u8; 65] = sign_message(b"high-s", &TEST_PRIVKEY)
+            .unwrap()
+            .try_into()
+            .unwrap();
+        let low = Signature::from_slice(&sig[..64]).unwrap();
+        // n - s, the malleated twin of a valid signature.
+        let high = Signature::from_scalars(low.r(), -*low.s()).unwrap();
+        sig[..64].copy_from_slice(&high.to_bytes());
+        assert_eq!(sig[32] & 0x80, 0x80);
+
+        let err = to_compact_signature(&sig).unwrap_err();
+        assert!(err.to_string().contains("non-canonical"), "{err}");
+    }
+
     #[test]
     fn sign_raw_hash_invalid_key() {
         let hash = [0xAAu8; 32];
@@ -639,4 +1511,68 @@ mod tests {
         assert_eq!(buf[0], 0x94);
         assert_eq!(&buf[1..], &[0xde; 20]);
     }
+
+    #[test]
+    fn json_roundtrip_signs_identically() {
+        let (mut tx, _) = build_erc20_transfer(
+            137,
+            9,
+            &test_address(),
+            &test_address(),
+            [0x11; 32],
+            1_500_000_000,
+            u128::MAX,
+            65_000,
+        )
+        .unwrap();
+        tx.value = 1_000_000_000_000_000_000;
+        tx.access_list = vec![AccessListEntry {
+            address: TEST_ADDRESS.into(),
+            storage_keys: vec![[0xAB; 32]],
+        }];
+
+        let json = tx.to_json().unwrap();
+        let restored = EthTransaction::from_json(&json).unwrap();
+
+        let original = sign_transaction(&tx, &TEST_PRIVKEY).unwrap();
+        let roundtrip = sign_transaction(&restored, &TEST_PRIVKEY).unwrap();
+        assert_eq!(roundtrip.raw_tx, original.raw_tx);
+        assert_eq!(roundtrip.tx_hash, original.tx_hash);
+    }
+
+    #[test]
+    fn json_uses_strings_for_amounts_and_bytes() {
+        let tx = build_transfer(1, 0, &test_address(), u128::MAX, 100, 200, 21_000, false).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&tx.to_json().unwrap()).unwrap();
+        assert_eq!(value["value"], u128::MAX.to_string());
+        assert_eq!(value["maxPriorityFeePerGas"], "100");
+        assert_eq!(value["data"], "0x");
+        assert_eq!(value["chainId"], 1);
+    }
+
+    #[test]
+    fn from_json_accepts_hex_amounts_and_missing_access_list() {
+        let json = r#"{"chainId":1,"nonce":0,"maxPriorityFeePerGas":"0x64",
+            "maxFeePerGas":"200","gasLimit":21000,
+            "to":"0x000000000000000000000000000000000000dEaD","value":"0","data":"0x"}"#;
+        let tx = EthTransaction::from_json(json).unwrap();
+        assert_eq!(tx.max_priority_fee_per_gas, 100);
+        assert!(tx.access_list.is_empty());
+    }
+
+    #[test]
+    fn from_json_rejects_malformed_fields() {
+        let json = build_transfer(1, 0, &test_address(), 1, 100, 200, 21_000, false)
+            .unwrap()
+            .to_json()
+            .unwrap();
+        let bad_value = json.replace(r#""value":"1""#, r#""value":"1.5""#);
+        let bad_data = json.replace(r#""data":"0x""#, r#""data":"0xzz""#);
+        for bad in [bad_value.as_str(), bad_data.as_str(), "{}", "not json"] {
+            assert!(matches!(
+                EthTransaction::from_json(bad),
+                Err(EthError::EncodingError(_))
+            ));
+        }
+    }
 }
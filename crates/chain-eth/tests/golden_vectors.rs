@@ -0,0 +1,136 @@
+//! Byte-for-byte golden vectors for EIP-1559 signing.
+//!
+//! Each expected value below was produced independently of this crate with
+//! alloy-consensus 1.8 (the successor to ethers-rs): the same fields were put
+//! in a `TxEip1559`, its `signature_hash()` was signed with `TEST_PRIVKEY`
+//! using k256 (RFC 6979, low-s), and the result serialized with
+//! `into_signed(sig).encode_2718()`. Any change to our RLP layout, field order
+//! or signature encoding shows up here as a hex mismatch.
+
+use chain_eth::address::EthAddress;
+use chain_eth::transaction::{
+    build_erc20_transfer, build_transfer, sign_transaction, AccessListEntry,
+};
+
+/// Private key 1 (DO NOT use on mainnet).
+const TEST_PRIVKEY: [u8; 32] = {
+    let mut key = [0u8; 32];
+    key[31] = 1;
+    key
+};
+
+const DEAD: &str = "0x000000000000000000000000000000000000dEaD";
+const USDC: &str = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48";
+
+fn addr(address: &str) -> EthAddress {
+    EthAddress::parse(address).unwrap()
+}
+
+fn amount_u256(value: u64) -> [u8; 32] {
+    let mut amount = [0u8; 32];
+    amount[24..].copy_from_slice(&value.to_be_bytes());
+    amount
+}
+
+#[test]
+fn native_transfer_matches_reference() {
+    let tx = build_transfer(
+        1,
+        7,
+        &addr(DEAD),
+        1_000_000_000_000_000_000,
+        1_500_000_000,
+        30_000_000_000,
+        21_000,
+        false,
+    )
+    .unwrap();
+    let signed = sign_transaction(&tx, &TEST_PRIVKEY).unwrap();
+
+    assert_eq!(
+        hex::encode(&signed.raw_tx),
+        "02f87301078459682f008506fc23ac0082520894000000000000000000000000000000000000dead\
+         880de0b6b3a764000080c080a03f85eba363e168ecaceb74c901e451124182f8d24316a95705a7da\
+         0a80d74c8da00d3e9663b92dcfa178371e3a90545bc395f010075208c9fa9fc6e0cf3588084e"
+    );
+    assert_eq!(
+        signed.tx_hash,
+        "0x3e75cf0a74763ead3f671828edc5908fe4ba0dba4b9960072997ddbaa28d300b"
+    );
+}
+
+#[test]
+fn erc20_transfer_matches_reference() {
+    let (tx, _) = build_erc20_transfer(
+        1,
+        8,
+        &addr(USDC),
+        &addr(DEAD),
+        amount_u256(1_000_000),
+        1_500_000_000,
+        30_000_000_000,
+        65_000,
+    )
+    .unwrap();
+    let signed = sign_transaction(&tx, &TEST_PRIVKEY).unwrap();
+
+    assert_eq!(
+        hex::encode(&signed.raw_tx),
+        "02f8b001088459682f008506fc23ac0082fde894a0b86991c6218b36c1d19d4a2e9eb0ce3606eb48\
+         80b844a9059cbb000000000000000000000000000000000000000000000000000000000000dead00\
+         000000000000000000000000000000000000000000000000000000000f4240c001a0312354936ff0\
+         c652067682a60d536bac3d6199107217fea427685cc450f2b1d0a04243ddb50226056ddab27aa896\
+         734a80c697339f5ce74cc2973c70f53a4c7a86"
+    );
+    assert_eq!(
+        signed.tx_hash,
+        "0x2259fb9ce88b302d6f3af8a4160bf5e76c25e66fb26aac77a2b7abe62cb5e075"
+    );
+}
+
+#[test]
+fn access_list_transfer_matches_reference() {
+    let (mut tx, _) = build_erc20_transfer(
+        137,
+        9,
+        &addr(USDC),
+        &addr(DEAD),
+        amount_u256(1_000_000),
+        30_000_000_000,
+        50_000_000_000,
+        70_000,
+    )
+    .unwrap();
+    let mut slot_3 = [0u8; 32];
+    slot_3[31] = 3;
+    tx.access_list = vec![AccessListEntry {
+        address: USDC.into(),
+        storage_keys: vec![[0u8; 32], slot_3],
+    }];
+    let signed = sign_transaction(&tx, &TEST_PRIVKEY).unwrap();
+
+    assert_eq!(
+        hex::encode(&signed.raw_tx),
+        "02f9010f8189098506fc23ac00850ba43b74008301117094a0b86991c6218b36c1d19d4a2e9eb0ce\
+         3606eb4880b844a9059cbb000000000000000000000000000000000000000000000000000000000000\
+         dead00000000000000000000000000000000000000000000000000000000000f4240f85bf85994a0\
+         b86991c6218b36c1d19d4a2e9eb0ce3606eb48f842a0000000000000000000000000000000000000\
+         0000000000000000000000000000a000000000000000000000000000000000000000000000000000\
+         0000000000000301a09ee5a6a29dd018db24a7f5ead074698fc3d13487a6054ac50cbf552727d4a5\
+         b3a0575898f08f0fec4715af3c5a453aba7a7efed82a78c0f5a8bd1185ea77b77db6"
+    );
+    assert_eq!(
+        signed.tx_hash,
+        "0xe18a2cadc38b7b2acdb38eaf3bfa28a686cbef0efacb07dbbee2b393cd46cd4d"
+    );
+}
+
+#[test]
+fn access_list_with_invalid_address_fails() {
+    let mut tx = build_transfer(1, 0, &addr(DEAD), 1, 1, 1, 21_000, false).unwrap();
+    tx.access_list = vec![AccessListEntry {
+        address: "0x1234".into(),
+        storage_keys: vec![],
+    }];
+    assert!(sign_transaction(&tx, &TEST_PRIVKEY).is_err());
+}
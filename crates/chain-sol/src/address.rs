@@ -4,8 +4,49 @@
 //! There is no hashing step (unlike Bitcoin or Ethereum). The canonical
 //! alphabet is the standard Bitcoin Base58 alphabet used by the `bs58` crate.
 
+use core::fmt;
+
+use zeroize::Zeroize;
+
 use crate::error::SolError;
 
+/// A Solana address that decodes to a 32-byte public key.
+///
+/// Wallet entry points parse into this instead of passing `&str` along, so a
+/// string meant for another chain is rejected up front. The original
+/// spelling is kept for display.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SolAddress {
+    text: String,
+    bytes: [u8; 32],
+}
+
+impl SolAddress {
+    /// Parse a Base58 address; fails unless it decodes to exactly 32 bytes.
+    pub fn parse(address: &str) -> Result<Self, SolError> {
+        Ok(Self {
+            text: address.to_string(),
+            bytes: address_to_bytes(address)?,
+        })
+    }
+
+    /// The address as it was parsed.
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    /// The raw 32-byte public key.
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.bytes
+    }
+}
+
+impl fmt::Display for SolAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
 /// Convert a 32-byte Ed25519 public key to a Solana address string.
 ///
 /// The Solana address is the Base58 encoding of the raw 32-byte public key.
@@ -55,6 +96,62 @@ pub fn bytes_to_address(bytes: &[u8; 32]) -> String {
     bs58::encode(bytes).into_string()
 }
 
+/// Export an Ed25519 key in Solana's 64-byte keypair format:
+/// `secret (32) || public (32)`.
+///
+/// This is the layout of the JSON array in `solana-keygen` keypair files and
+/// what `ed25519_dalek::SigningKey::from_keypair_bytes` accepts.
+///
+/// # Security
+///
+/// The first half is the raw private key; anyone holding the result controls
+/// the account. The caller owns the returned array and should zeroize it as
+/// soon as it has been written out.
+pub fn export_keypair(private_key: &[u8; 32]) -> [u8; 64] {
+    let signing_key = ed25519_dalek::SigningKey::from_bytes(private_key);
+    signing_key.to_keypair_bytes()
+}
+
+/// Import a keypair exported as a Base58 string (Phantom's private key
+/// export), the inverse of [`export_keypair`] plus Base58.
+///
+/// The string must decode to 64 bytes, `secret (32) || public (32)`, and the
+/// public half must be the one derived from the secret, so a truncated or
+/// spliced export is rejected instead of signing for an unexpected account.
+/// Returns `(private_key, public_key)`; the caller should zeroize the private
+/// key when done. Error messages never include the input.
+pub fn import_base58_keypair(s: &str) -> Result<([u8; 32], [u8; 32]), SolError> {
+    let mut bytes = bs58::decode(s.trim())
+        .into_vec()
+        .map_err(|_| SolError::InvalidPrivateKey("keypair is not valid base58".into()))?;
+
+    if bytes.len() != 64 {
+        let len = bytes.len();
+        bytes.zeroize();
+        return Err(SolError::InvalidPrivateKey(format!(
+            "expected a 64-byte keypair, got {len} bytes"
+        )));
+    }
+
+    let mut private_key = [0u8; 32];
+    let mut public_key = [0u8; 32];
+    private_key.copy_from_slice(&bytes[..32]);
+    public_key.copy_from_slice(&bytes[32..]);
+    bytes.zeroize();
+
+    let derived = ed25519_dalek::SigningKey::from_bytes(&private_key)
+        .verifying_key()
+        .to_bytes();
+    if derived != public_key {
+        private_key.zeroize();
+        return Err(SolError::InvalidPrivateKey(
+            "public half does not match the secret key".into(),
+        ));
+    }
+
+    Ok((private_key, public_key))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -137,4 +234,78 @@ mod tests {
         let bytes = address_to_bytes(address).unwrap();
         assert_eq!(bytes.len(), 32);
     }
+
+    #[test]
+    fn export_keypair_layout() {
+        let private_key = [0x11; 32];
+        let keypair = export_keypair(&private_key);
+
+        let public_key = ed25519_dalek::SigningKey::from_bytes(&private_key)
+            .verifying_key()
+            .to_bytes();
+        assert_eq!(&keypair[..32], &private_key);
+        assert_eq!(&keypair[32..], &public_key);
+        assert_eq!(
+            keypair_to_address(&keypair[32..].try_into().unwrap()),
+            keypair_to_address(&public_key)
+        );
+    }
+
+    #[test]
+    fn export_keypair_reimport_signs_identically() {
+        use ed25519_dalek::Signer;
+
+        let private_key = [0x22; 32];
+        let original = ed25519_dalek::SigningKey::from_bytes(&private_key);
+        let reimported =
+            ed25519_dalek::SigningKey::from_keypair_bytes(&export_keypair(&private_key)).unwrap();
+
+        assert_eq!(
+            original.sign(b"anvil").to_bytes(),
+            reimported.sign(b"anvil").to_bytes()
+        );
+    }
+
+    #[test]
+    fn import_base58_keypair_roundtrips_export() {
+        let private_key = [0x33; 32];
+        let exported = bs58::encode(export_keypair(&private_key)).into_string();
+
+        let (secret, public) = import_base58_keypair(&exported).unwrap();
+        assert_eq!(secret, private_key);
+        assert_eq!(&public[..], &export_keypair(&private_key)[32..]);
+    }
+
+    #[test]
+    fn import_base58_keypair_rejects_mismatched_public_half() {
+        let mut keypair = export_keypair(&[0x33; 32]);
+        keypair[32..].copy_from_slice(&export_keypair(&[0x44; 32])[32..]);
+
+        let result = import_base58_keypair(&bs58::encode(keypair).into_string());
+        assert!(matches!(result, Err(SolError::InvalidPrivateKey(_))));
+    }
+
+    #[test]
+    fn import_base58_keypair_rejects_wrong_length_and_garbage() {
+        let secret_only = bs58::encode([0x33; 32]).into_string();
+        assert!(import_base58_keypair(&secret_only).is_err());
+        assert!(import_base58_keypair("0OIl").is_err());
+    }
+
+    #[test]
+    fn sol_address_parses_base58() {
+        let pubkey = [0x42; 32];
+        let text = keypair_to_address(&pubkey);
+        let addr = SolAddress::parse(&text).unwrap();
+
+        assert_eq!(addr.as_str(), text);
+        assert_eq!(addr.to_string(), text);
+        assert_eq!(addr.as_bytes(), &pubkey);
+    }
+
+    #[test]
+    fn sol_address_rejects_other_chains() {
+        assert!(SolAddress::parse("0x000000000000000000000000000000000000dEaD").is_err());
+        assert!(SolAddress::parse("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4").is_err());
+    }
 }
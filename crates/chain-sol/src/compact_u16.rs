@@ -0,0 +1,78 @@
+//! Solana's compact-u16 length prefix ("shortvec").
+//!
+//! Every variable-length array in the wire format is prefixed with this
+//! encoding. It is kept free of `std` so it builds with only `alloc` when the
+//! crate's default `std` feature is disabled.
+
+use alloc::vec::Vec;
+
+use crate::error::SolError;
+
+/// Encode a `u16` value in Solana's compact-u16 format.
+///
+/// - Values 0..0x7f       -> 1 byte
+/// - Values 0x80..0x3fff  -> 2 bytes
+/// - Values 0x4000..      -> 3 bytes (max 0x1_ffff, but u16 caps at 0xffff)
+pub fn encode_compact_u16(value: u16) -> Vec<u8> {
+    let mut val = value as u32;
+    let mut out = Vec::with_capacity(3);
+
+    loop {
+        let mut byte = (val & 0x7f) as u8;
+        val >>= 7;
+        if val > 0 {
+            byte |= 0x80;
+        }
+        out.push(byte);
+        if val == 0 {
+            break;
+        }
+    }
+
+    out
+}
+
+/// Decode a compact-u16 value from a byte slice.
+///
+/// Returns `(value, bytes_consumed)` or an error if the data is truncated.
+pub fn decode_compact_u16(data: &[u8]) -> Result<(u16, usize), SolError> {
+    if data.is_empty() {
+        return Err(SolError::SerializationError(
+            "unexpected end of data while decoding compact-u16".into(),
+        ));
+    }
+
+    let mut value: u32 = 0;
+    let mut shift = 0u32;
+    let mut consumed = 0usize;
+
+    loop {
+        if consumed >= data.len() {
+            return Err(SolError::SerializationError(
+                "unexpected end of data while decoding compact-u16".into(),
+            ));
+        }
+        let byte = data[consumed];
+        consumed += 1;
+
+        value |= ((byte & 0x7f) as u32) << shift;
+        shift += 7;
+
+        if byte & 0x80 == 0 {
+            break;
+        }
+        if consumed >= 3 {
+            return Err(SolError::SerializationError(
+                "compact-u16 continuation bit set on third byte".into(),
+            ));
+        }
+    }
+
+    if value > u16::MAX as u32 {
+        return Err(SolError::SerializationError(
+            "compact-u16 value overflow".into(),
+        ));
+    }
+
+    Ok((value as u16, consumed))
+}
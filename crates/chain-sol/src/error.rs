@@ -1,3 +1,5 @@
+use alloc::string::String;
+
 use thiserror::Error;
 
 /// Solana chain operation errors.
@@ -20,6 +22,9 @@ pub enum SolError {
 
     #[error("serialization error: {0}")]
     SerializationError(String),
+
+    #[error("invalid amount: {0}")]
+    InvalidAmount(String),
 }
 
 #[cfg(test)]
@@ -68,6 +73,12 @@ mod tests {
         );
     }
 
+    #[test]
+    fn display_invalid_amount() {
+        let err = SolError::InvalidAmount("too many decimal places".into());
+        assert_eq!(err.to_string(), "invalid amount: too many decimal places");
+    }
+
     #[test]
     fn error_trait_is_implemented() {
         let err: Box<dyn std::error::Error> =
@@ -1,26 +1,63 @@
 //! Solana chain support for the crypto-wallet.
 //!
 //! This crate handles Solana address derivation, manual transaction wire
-//! format serialization, and SPL token transfers — all without pulling in
+//! format serialization, SPL token transfers and memos — all without pulling in
 //! `solana-sdk` (which drags in tokio and 200+ transitive dependencies).
 //!
 //! Instead we implement Solana's compact binary wire format by hand, using
 //! `ed25519-dalek` for Ed25519 signing and `bs58` for Base58 encoding.
+//!
+//! With the default `std` feature disabled only the compact-u16 codec, the
+//! amount formatting in `units` and the error type are built, using `alloc`
+//! alone.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
 
+#[cfg(feature = "std")]
 pub mod address;
+pub mod compact_u16;
 pub mod error;
+#[cfg(feature = "std")]
+pub mod memo;
+#[cfg(feature = "std")]
+mod serde_helpers;
+#[cfg(feature = "std")]
 pub mod spl_token;
+#[cfg(feature = "std")]
+pub mod system;
+#[cfg(feature = "std")]
 pub mod transaction;
+pub mod units;
 
 // Re-export key public types for ergonomic imports.
-pub use address::{address_to_bytes, bytes_to_address, keypair_to_address, validate_address};
+#[cfg(feature = "std")]
+pub use address::{
+    address_to_bytes, bytes_to_address, import_base58_keypair, keypair_to_address,
+    validate_address, SolAddress,
+};
+pub use compact_u16::{decode_compact_u16, encode_compact_u16};
 pub use error::SolError;
+#[cfg(feature = "std")]
+pub use memo::{build_memo, build_sol_transfer_with_memo, MEMO_PROGRAM_ID};
+#[cfg(feature = "std")]
 pub use spl_token::{
-    build_spl_transfer, derive_associated_token_address, ASSOCIATED_TOKEN_PROGRAM_ID,
-    TOKEN_PROGRAM_ID,
+    build_create_associated_token_account, build_spl_transfer, build_spl_transfer_checked,
+    derive_associated_token_address, parse_transfer, ParsedSplTransfer,
+    ASSOCIATED_TOKEN_PROGRAM_ID, TOKEN_PROGRAM_ID,
+};
+#[cfg(feature = "std")]
+pub use system::{
+    build_initialize_nonce, build_withdraw_nonce, decode_instruction, rent_exempt_minimum,
+    SystemInstruction, SYSVAR_RECENT_BLOCKHASHES_ID, SYSVAR_RENT_ID,
 };
+#[cfg(feature = "std")]
 pub use transaction::{
-    build_sol_transfer, compile_transaction, decode_compact_u16, encode_compact_u16,
-    serialize_message, sign_sol_raw_transaction, sign_transaction, CompiledInstruction,
-    SolAccountMeta, SolInstruction, SolTransaction, SYSTEM_PROGRAM_ID,
+    assemble_signed, assert_recipient, base_fee, build_sol_transfer, build_sol_transfer_with_payer,
+    build_sol_transfer_with_references, compile_transaction, deserialize_transaction,
+    message_to_sign, resolve_v0_accounts, serialize_message, sign_sol_raw_transaction,
+    sign_transaction, total_fee, transaction_id, validate_size, CompiledInstruction,
+    SolAccountMeta, SolInstruction, SolTransaction, LAMPORTS_PER_SIGNATURE, PACKET_DATA_SIZE,
+    SYSTEM_PROGRAM_ID,
 };
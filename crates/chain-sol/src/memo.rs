@@ -0,0 +1,102 @@
+//! SPL Memo program instructions.
+//!
+//! Exchanges and custodians often identify deposits by a memo attached to
+//! the transfer. The Memo program just logs its UTF-8 instruction data; it
+//! has to sit in the same transaction as the transfer it labels.
+
+use crate::error::SolError;
+use crate::transaction::{
+    build_system_transfer_instruction, compile_transaction, SolInstruction, SolTransaction,
+};
+
+/// Memo Program (v2) ID: `MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr`
+pub const MEMO_PROGRAM_ID: [u8; 32] = [
+    0x05, 0x4a, 0x53, 0x5a, 0x99, 0x29, 0x21, 0x06, 0x4d, 0x24, 0xe8, 0x71, 0x60, 0xda, 0x38, 0x7c,
+    0x7c, 0x35, 0xb5, 0xdd, 0xbc, 0x92, 0xbb, 0x81, 0xe4, 0x1f, 0xa8, 0x40, 0x41, 0x05, 0x44, 0x8d,
+];
+
+/// Build a Memo program instruction carrying `memo`.
+///
+/// # Wire format
+///
+/// Data: the raw UTF-8 bytes of `memo`, with no length prefix.
+/// Accounts: none (the memo is not tied to any signer).
+pub fn build_memo(memo: &str) -> SolInstruction {
+    SolInstruction {
+        program_id: MEMO_PROGRAM_ID,
+        accounts: Vec::new(),
+        data: memo.as_bytes().to_vec(),
+    }
+}
+
+/// Build a native SOL transfer followed by a memo, in one transaction.
+///
+/// Instruction 0 is the System `Transfer`, instruction 1 the memo; the sender
+/// pays the fee. Rejects zero lamports and an empty memo.
+pub fn build_sol_transfer_with_memo(
+    from_pubkey: &[u8; 32],
+    to_pubkey: &[u8; 32],
+    lamports: u64,
+    memo: &str,
+    recent_blockhash: &[u8; 32],
+) -> Result<SolTransaction, SolError> {
+    if lamports == 0 {
+        return Err(SolError::TransactionBuildError(
+            "lamports must be > 0".into(),
+        ));
+    }
+    if memo.is_empty() {
+        return Err(SolError::TransactionBuildError(
+            "memo must not be empty".into(),
+        ));
+    }
+
+    let instructions = [
+        build_system_transfer_instruction(from_pubkey, to_pubkey, lamports),
+        build_memo(memo),
+    ];
+    compile_transaction(&instructions, from_pubkey, recent_blockhash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn memo_program_id_matches_base58() {
+        assert_eq!(
+            bs58::encode(MEMO_PROGRAM_ID).into_string(),
+            "MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr"
+        );
+    }
+
+    #[test]
+    fn build_memo_data_is_utf8_bytes() {
+        let ix = build_memo("deposit 42 ✓");
+        assert_eq!(ix.program_id, MEMO_PROGRAM_ID);
+        assert!(ix.accounts.is_empty());
+        assert_eq!(ix.data, "deposit 42 ✓".as_bytes());
+    }
+
+    #[test]
+    fn transfer_with_memo_orders_transfer_then_memo() {
+        let from = [1u8; 32];
+        let to = [2u8; 32];
+        let tx = build_sol_transfer_with_memo(&from, &to, 1_500, "ref-7", &[9u8; 32]).unwrap();
+
+        assert_eq!(tx.compiled_instructions.len(), 2);
+        let program =
+            |i: usize| tx.account_keys[tx.compiled_instructions[i].program_id_index as usize];
+        assert_eq!(program(0), crate::transaction::SYSTEM_PROGRAM_ID);
+        assert_eq!(program(1), MEMO_PROGRAM_ID);
+        assert_eq!(tx.compiled_instructions[1].data, b"ref-7");
+        assert_eq!(tx.account_keys[0], from);
+    }
+
+    #[test]
+    fn transfer_with_memo_rejects_empty_inputs() {
+        let key = [1u8; 32];
+        assert!(build_sol_transfer_with_memo(&key, &key, 0, "x", &key).is_err());
+        assert!(build_sol_transfer_with_memo(&key, &key, 1, "", &key).is_err());
+    }
+}
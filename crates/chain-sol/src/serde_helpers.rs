@@ -0,0 +1,58 @@
+//! `#[serde(with = ...)]` adapters for persisting transactions as JSON.
+//!
+//! Public keys and blockhashes are Base58, as Solana tooling shows them;
+//! instruction data is hex.
+
+/// `[u8; 32]` as a Base58 string.
+pub(crate) mod base58_key {
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    use crate::address::{address_to_bytes, bytes_to_address};
+
+    pub fn serialize<S: Serializer>(key: &[u8; 32], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&bytes_to_address(key))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<[u8; 32], D::Error> {
+        let s = String::deserialize(deserializer)?;
+        address_to_bytes(&s).map_err(D::Error::custom)
+    }
+}
+
+/// `Vec<[u8; 32]>` as a list of Base58 strings.
+pub(crate) mod base58_key_vec {
+    use serde::{de::Error, ser::SerializeSeq, Deserialize, Deserializer, Serializer};
+
+    use crate::address::{address_to_bytes, bytes_to_address};
+
+    pub fn serialize<S: Serializer>(keys: &[[u8; 32]], serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(keys.len()))?;
+        for key in keys {
+            seq.serialize_element(&bytes_to_address(key))?;
+        }
+        seq.end()
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<[u8; 32]>, D::Error> {
+        Vec::<String>::deserialize(deserializer)?
+            .iter()
+            .map(|s| address_to_bytes(s).map_err(D::Error::custom))
+            .collect()
+    }
+}
+
+/// `Vec<u8>` as a hex string (no prefix).
+pub(crate) mod hex_bytes {
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&hex::encode(bytes))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        hex::decode(&s).map_err(|e| D::Error::custom(format!("invalid hex: {e}")))
+    }
+}
@@ -7,8 +7,10 @@
 use sha2::{Digest, Sha256};
 
 use crate::error::SolError;
+use crate::transaction::deserialize_transaction;
 use crate::transaction::SolAccountMeta;
 use crate::transaction::SolInstruction;
+use crate::transaction::SYSTEM_PROGRAM_ID;
 
 // ---------------------------------------------------------------------------
 // Well-known program IDs
@@ -100,6 +102,174 @@ pub fn build_spl_transfer(
     })
 }
 
+/// Build an SPL Token `TransferChecked` instruction.
+///
+/// Like [`build_spl_transfer`], but the token program also checks `mint` and
+/// `decimals` against the source account, so a wrong mint or a mis-scaled
+/// amount fails on-chain instead of moving the wrong quantity.
+///
+/// # Wire format
+///
+/// Instruction index = 12, followed by u64 LE amount and the u8 decimals.
+/// Total data: 10 bytes. Accounts: source, mint, destination, owner.
+pub fn build_spl_transfer_checked(
+    from_token_account: &[u8; 32],
+    mint: &[u8; 32],
+    to_token_account: &[u8; 32],
+    owner: &[u8; 32],
+    amount: u64,
+    decimals: u8,
+) -> Result<SolInstruction, SolError> {
+    if amount == 0 {
+        return Err(SolError::TransactionBuildError(
+            "SPL transfer amount must be > 0".into(),
+        ));
+    }
+
+    // Instruction data: [12] (TransferChecked) + u64 LE amount + u8 decimals.
+    let mut data = Vec::with_capacity(10);
+    data.push(12u8);
+    data.extend_from_slice(&amount.to_le_bytes());
+    data.push(decimals);
+
+    Ok(SolInstruction {
+        program_id: TOKEN_PROGRAM_ID,
+        accounts: vec![
+            SolAccountMeta {
+                pubkey: *from_token_account,
+                is_signer: false,
+                is_writable: true,
+            },
+            SolAccountMeta {
+                pubkey: *mint,
+                is_signer: false,
+                is_writable: false,
+            },
+            SolAccountMeta {
+                pubkey: *to_token_account,
+                is_signer: false,
+                is_writable: true,
+            },
+            SolAccountMeta {
+                pubkey: *owner,
+                is_signer: true,
+                is_writable: false,
+            },
+        ],
+        data,
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Parsing
+// ---------------------------------------------------------------------------
+
+/// An SPL token transfer found in a transaction, for confirmation screens.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedSplTransfer {
+    /// Token account the tokens leave.
+    pub source_ata: [u8; 32],
+    /// Token account the tokens arrive in.
+    pub dest_ata: [u8; 32],
+    /// Wallet authorizing the transfer.
+    pub owner: [u8; 32],
+    /// Token mint; only `TransferChecked` names it.
+    pub mint: Option<[u8; 32]>,
+    /// Amount in the token's base units.
+    pub amount: u64,
+    /// Mint decimals; only `TransferChecked` carries them.
+    pub decimals: Option<u8>,
+}
+
+/// Find the first Token-program `Transfer` or `TransferChecked` in a
+/// wire-format transaction.
+///
+/// Returns `None` if the bytes don't decode (see
+/// [`deserialize_transaction`]) or no such instruction is present.
+pub fn parse_transfer(wire: &[u8]) -> Option<ParsedSplTransfer> {
+    let tx = deserialize_transaction(wire).ok()?;
+    let key = |index: u8| tx.account_keys[usize::from(index)];
+
+    tx.compiled_instructions.iter().find_map(|ix| {
+        if key(ix.program_id_index) != TOKEN_PROGRAM_ID {
+            return None;
+        }
+        let amount = u64::from_le_bytes(ix.data.get(1..9)?.try_into().ok()?);
+        match (
+            ix.data.first()?,
+            ix.data.len(),
+            ix.account_indices.as_slice(),
+        ) {
+            (3, 9, [source, dest, owner, ..]) => Some(ParsedSplTransfer {
+                source_ata: key(*source),
+                dest_ata: key(*dest),
+                owner: key(*owner),
+                mint: None,
+                amount,
+                decimals: None,
+            }),
+            (12, 10, [source, mint, dest, owner, ..]) => Some(ParsedSplTransfer {
+                source_ata: key(*source),
+                dest_ata: key(*dest),
+                owner: key(*owner),
+                mint: Some(key(*mint)),
+                amount,
+                decimals: Some(ix.data[9]),
+            }),
+            _ => None,
+        }
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Associated Token Account creation
+// ---------------------------------------------------------------------------
+
+/// Build an Associated Token Account `CreateIdempotent` instruction.
+///
+/// Creates the ATA of `wallet` for `mint`, funded by `payer`, if it does not
+/// exist yet; if it does, the instruction succeeds without doing anything.
+/// That makes it safe to prepend to a transfer whenever the recipient may
+/// not hold the token yet.
+///
+/// # Wire format
+///
+/// Instruction data is the single byte 1 (`CreateIdempotent`). Accounts:
+/// payer, ATA, wallet, mint, System Program, Token Program.
+pub fn build_create_associated_token_account(
+    payer: &[u8; 32],
+    wallet: &[u8; 32],
+    mint: &[u8; 32],
+) -> Result<SolInstruction, SolError> {
+    let ata = derive_associated_token_address(wallet, mint)?;
+    let readonly = |pubkey: [u8; 32]| SolAccountMeta {
+        pubkey,
+        is_signer: false,
+        is_writable: false,
+    };
+
+    Ok(SolInstruction {
+        program_id: ASSOCIATED_TOKEN_PROGRAM_ID,
+        accounts: vec![
+            SolAccountMeta {
+                pubkey: *payer,
+                is_signer: true,
+                is_writable: true,
+            },
+            SolAccountMeta {
+                pubkey: ata,
+                is_signer: false,
+                is_writable: true,
+            },
+            readonly(*wallet),
+            readonly(*mint),
+            readonly(SYSTEM_PROGRAM_ID),
+            readonly(TOKEN_PROGRAM_ID),
+        ],
+        data: vec![1u8],
+    })
+}
+
 // ---------------------------------------------------------------------------
 // Associated Token Account (PDA) derivation
 // ---------------------------------------------------------------------------
@@ -272,6 +442,161 @@ mod tests {
         assert!(result.is_err());
     }
 
+    // -- SPL TransferChecked instruction ------------------------------------
+
+    fn transfer_checked(amount: u64, decimals: u8) -> Result<SolInstruction, SolError> {
+        build_spl_transfer_checked(
+            &[1u8; 32], &[4u8; 32], &[2u8; 32], &[3u8; 32], amount, decimals,
+        )
+    }
+
+    #[test]
+    fn spl_transfer_checked_data_encoding() {
+        let ix = transfer_checked(500_000, 6).unwrap();
+
+        assert_eq!(ix.program_id, TOKEN_PROGRAM_ID);
+        assert_eq!(ix.data.len(), 10);
+        assert_eq!(ix.data[0], 12);
+        assert_eq!(
+            u64::from_le_bytes(ix.data[1..9].try_into().unwrap()),
+            500_000
+        );
+        assert_eq!(ix.data[9], 6);
+    }
+
+    #[test]
+    fn spl_transfer_checked_account_roles() {
+        let ix = transfer_checked(100, 9).unwrap();
+
+        let roles: Vec<_> = ix
+            .accounts
+            .iter()
+            .map(|a| (a.pubkey[0], a.is_signer, a.is_writable))
+            .collect();
+        assert_eq!(
+            roles,
+            [
+                (1, false, true),
+                (4, false, false),
+                (2, false, true),
+                (3, true, false)
+            ]
+        );
+    }
+
+    #[test]
+    fn spl_transfer_checked_zero_amount_fails() {
+        assert!(transfer_checked(0, 6).is_err());
+    }
+
+    // -- Transfer parsing ---------------------------------------------------
+
+    fn signed_wire(instructions: &[SolInstruction], private_key: &[u8; 32]) -> Vec<u8> {
+        let owner = ed25519_dalek::SigningKey::from_bytes(private_key)
+            .verifying_key()
+            .to_bytes();
+        let tx =
+            crate::transaction::compile_transaction(instructions, &owner, &[0x0B; 32]).unwrap();
+        crate::transaction::sign_transaction(&tx, private_key).unwrap()
+    }
+
+    #[test]
+    fn parse_transfer_recovers_amount_and_accounts() {
+        let private_key = [0x01u8; 32];
+        let owner = ed25519_dalek::SigningKey::from_bytes(&private_key)
+            .verifying_key()
+            .to_bytes();
+        let mint = [0x44u8; 32];
+        let source = derive_associated_token_address(&owner, &mint).unwrap();
+        let dest = derive_associated_token_address(&[0x22; 32], &mint).unwrap();
+        let ix = build_spl_transfer(&source, &dest, &owner, 2_500_000, 6).unwrap();
+
+        let parsed = parse_transfer(&signed_wire(&[ix], &private_key)).unwrap();
+        assert_eq!(
+            parsed,
+            ParsedSplTransfer {
+                source_ata: source,
+                dest_ata: dest,
+                owner,
+                mint: None,
+                amount: 2_500_000,
+                decimals: None,
+            }
+        );
+    }
+
+    #[test]
+    fn parse_transfer_checked_after_create_ata() {
+        let private_key = [0x01u8; 32];
+        let owner = ed25519_dalek::SigningKey::from_bytes(&private_key)
+            .verifying_key()
+            .to_bytes();
+        let mint = [0x44u8; 32];
+        let recipient = [0x22u8; 32];
+        let source = derive_associated_token_address(&owner, &mint).unwrap();
+        let dest = derive_associated_token_address(&recipient, &mint).unwrap();
+        let instructions = [
+            build_create_associated_token_account(&owner, &recipient, &mint).unwrap(),
+            build_spl_transfer_checked(&source, &mint, &dest, &owner, 7, 9).unwrap(),
+        ];
+
+        let parsed = parse_transfer(&signed_wire(&instructions, &private_key)).unwrap();
+        assert_eq!(
+            (parsed.source_ata, parsed.dest_ata, parsed.owner),
+            (source, dest, owner)
+        );
+        assert_eq!(
+            (parsed.mint, parsed.amount, parsed.decimals),
+            (Some(mint), 7, Some(9))
+        );
+    }
+
+    #[test]
+    fn parse_transfer_none_without_token_transfer() {
+        let private_key = [0x01u8; 32];
+        let owner = ed25519_dalek::SigningKey::from_bytes(&private_key)
+            .verifying_key()
+            .to_bytes();
+        let tx =
+            crate::transaction::build_sol_transfer(&owner, &[0x22; 32], 1_000, &[0; 32]).unwrap();
+        let wire = crate::transaction::sign_transaction(&tx, &private_key).unwrap();
+
+        assert_eq!(parse_transfer(&wire), None);
+        assert_eq!(parse_transfer(&[0x01, 0x02]), None);
+    }
+
+    // -- Create ATA instruction ---------------------------------------------
+
+    #[test]
+    fn create_ata_targets_derived_address() {
+        let payer = [0x01u8; 32];
+        let wallet = [0xAAu8; 32];
+        let mint = [0xBBu8; 32];
+
+        let ix = build_create_associated_token_account(&payer, &wallet, &mint).unwrap();
+
+        assert_eq!(ix.program_id, ASSOCIATED_TOKEN_PROGRAM_ID);
+        assert_eq!(ix.data, [1]);
+        let keys: Vec<_> = ix.accounts.iter().map(|a| a.pubkey).collect();
+        assert_eq!(
+            keys,
+            [
+                payer,
+                derive_associated_token_address(&wallet, &mint).unwrap(),
+                wallet,
+                mint,
+                SYSTEM_PROGRAM_ID,
+                TOKEN_PROGRAM_ID,
+            ]
+        );
+        // Only the payer signs; only the payer and the new ATA are writable.
+        assert!(ix.accounts[0].is_signer && ix.accounts[0].is_writable);
+        assert!(!ix.accounts[1].is_signer && ix.accounts[1].is_writable);
+        assert!(ix.accounts[2..]
+            .iter()
+            .all(|a| !a.is_signer && !a.is_writable));
+    }
+
     // -- PDA derivation -----------------------------------------------------
 
     #[test]
@@ -0,0 +1,358 @@
+//! System Program instructions for durable-nonce accounts, and decoding of
+//! System instruction data for confirmation screens.
+//!
+//! A durable nonce lets a transaction use a stored blockhash instead of a
+//! recent one, so it can be signed offline and broadcast later. Reading the
+//! nonce account's stored blockhash is an RPC call and stays in the app; this
+//! module only builds the instructions that manage the account.
+
+use crate::error::SolError;
+use crate::transaction::{
+    SolAccountMeta, SolInstruction, SYSTEM_PROGRAM_ID, SYSTEM_TRANSFER_IX_INDEX,
+};
+
+// ---------------------------------------------------------------------------
+// Sysvars
+// ---------------------------------------------------------------------------
+
+/// RecentBlockhashes sysvar: `SysvarRecentB1ockHashes11111111111111111111`
+pub const SYSVAR_RECENT_BLOCKHASHES_ID: [u8; 32] = [
+    0x06, 0xa7, 0xd5, 0x17, 0x19, 0x2c, 0x56, 0x8e, 0xe0, 0x8a, 0x84, 0x5f, 0x73, 0xd2, 0x97, 0x88,
+    0xcf, 0x03, 0x5c, 0x31, 0x45, 0xb2, 0x1a, 0xb3, 0x44, 0xd8, 0x06, 0x2e, 0xa9, 0x40, 0x00, 0x00,
+];
+
+/// Rent sysvar: `SysvarRent111111111111111111111111111111111`
+pub const SYSVAR_RENT_ID: [u8; 32] = [
+    0x06, 0xa7, 0xd5, 0x17, 0x19, 0x2c, 0x5c, 0x51, 0x21, 0x8c, 0xc9, 0x4c, 0x3d, 0x4a, 0xf1, 0x7f,
+    0x58, 0xda, 0xee, 0x08, 0x9b, 0xa1, 0xfd, 0x44, 0xe3, 0xdb, 0xd9, 0x8a, 0x00, 0x00, 0x00, 0x00,
+];
+
+/// System Program `CreateAccount` instruction index.
+const CREATE_ACCOUNT_IX_INDEX: u32 = 0;
+
+/// System Program `AdvanceNonceAccount` instruction index.
+const ADVANCE_NONCE_IX_INDEX: u32 = 4;
+
+/// System Program `WithdrawNonceAccount` instruction index.
+const WITHDRAW_NONCE_IX_INDEX: u32 = 5;
+
+/// System Program `InitializeNonceAccount` instruction index.
+const INITIALIZE_NONCE_IX_INDEX: u32 = 6;
+
+// ---------------------------------------------------------------------------
+// Rent
+// ---------------------------------------------------------------------------
+
+/// Bytes of account metadata charged for on top of the data.
+const ACCOUNT_STORAGE_OVERHEAD: u64 = 128;
+
+/// Default rent rate (lamports per byte-year).
+const LAMPORTS_PER_BYTE_YEAR: u64 = 3_480;
+
+/// Years of rent an account must hold to be exempt.
+const EXEMPTION_THRESHOLD_YEARS: u64 = 2;
+
+/// Minimum balance for an account with `data_len` bytes of data to be
+/// rent-exempt, at the default rent parameters.
+///
+/// 890,880 lamports for a plain wallet (no data), 1,447,680 for a nonce
+/// account (80 bytes). A new account funded with less is rejected.
+pub fn rent_exempt_minimum(data_len: u64) -> u64 {
+    (ACCOUNT_STORAGE_OVERHEAD + data_len) * LAMPORTS_PER_BYTE_YEAR * EXEMPTION_THRESHOLD_YEARS
+}
+
+// ---------------------------------------------------------------------------
+// Nonce account instructions
+// ---------------------------------------------------------------------------
+
+/// Build a System Program `InitializeNonceAccount` instruction.
+///
+/// The nonce account must already exist, be owned by the System Program and
+/// hold the rent-exempt minimum (usually created by a `CreateAccount` in the
+/// same transaction). `authority` becomes the key allowed to advance or
+/// withdraw from it.
+///
+/// # Wire format
+///
+/// Data: u32 LE index 6 followed by the 32-byte authority (36 bytes).
+/// Accounts: nonce account (writable), RecentBlockhashes sysvar, Rent sysvar.
+pub fn build_initialize_nonce(nonce_account: &[u8; 32], authority: &[u8; 32]) -> SolInstruction {
+    let mut data = Vec::with_capacity(36);
+    data.extend_from_slice(&INITIALIZE_NONCE_IX_INDEX.to_le_bytes());
+    data.extend_from_slice(authority);
+
+    SolInstruction {
+        program_id: SYSTEM_PROGRAM_ID,
+        accounts: vec![
+            SolAccountMeta {
+                pubkey: *nonce_account,
+                is_signer: false,
+                is_writable: true,
+            },
+            sysvar(SYSVAR_RECENT_BLOCKHASHES_ID),
+            sysvar(SYSVAR_RENT_ID),
+        ],
+        data,
+    }
+}
+
+/// Build a System Program `WithdrawNonceAccount` instruction.
+///
+/// Moves `lamports` from the nonce account to `to`, signed by the nonce
+/// `authority`. Withdrawing the whole balance closes the account; a partial
+/// withdrawal must leave it rent-exempt, which the network enforces.
+///
+/// # Wire format
+///
+/// Data: u32 LE index 5 followed by u64 LE lamports (12 bytes).
+/// Accounts: nonce account (writable), `to` (writable), RecentBlockhashes
+/// sysvar, Rent sysvar, authority (signer).
+pub fn build_withdraw_nonce(
+    nonce_account: &[u8; 32],
+    to: &[u8; 32],
+    authority: &[u8; 32],
+    lamports: u64,
+) -> Result<SolInstruction, SolError> {
+    if lamports == 0 {
+        return Err(SolError::TransactionBuildError(
+            "nonce withdrawal must be > 0 lamports".into(),
+        ));
+    }
+
+    let mut data = Vec::with_capacity(12);
+    data.extend_from_slice(&WITHDRAW_NONCE_IX_INDEX.to_le_bytes());
+    data.extend_from_slice(&lamports.to_le_bytes());
+
+    Ok(SolInstruction {
+        program_id: SYSTEM_PROGRAM_ID,
+        accounts: vec![
+            SolAccountMeta {
+                pubkey: *nonce_account,
+                is_signer: false,
+                is_writable: true,
+            },
+            SolAccountMeta {
+                pubkey: *to,
+                is_signer: false,
+                is_writable: true,
+            },
+            sysvar(SYSVAR_RECENT_BLOCKHASHES_ID),
+            sysvar(SYSVAR_RENT_ID),
+            SolAccountMeta {
+                pubkey: *authority,
+                is_signer: true,
+                is_writable: false,
+            },
+        ],
+        data,
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Decoding
+// ---------------------------------------------------------------------------
+
+/// A decoded System Program instruction (the data only; accounts are not
+/// part of it).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SystemInstruction {
+    /// Create an account holding `lamports` with `space` bytes of data,
+    /// owned by the `owner` program.
+    CreateAccount {
+        lamports: u64,
+        space: u64,
+        owner: [u8; 32],
+    },
+    /// Move `lamports` from the first account to the second.
+    Transfer { lamports: u64 },
+    /// Consume the stored durable nonce and store a new one.
+    AdvanceNonceAccount,
+    /// Withdraw `lamports` from a nonce account.
+    WithdrawNonceAccount { lamports: u64 },
+    /// Turn an account into a nonce account controlled by `authority`.
+    InitializeNonceAccount { authority: [u8; 32] },
+}
+
+/// Decode System Program instruction data, e.g. to show a transfer amount
+/// before signing.
+///
+/// Returns `None` for other instructions and for data too short for its
+/// instruction. Bytes after the fields are ignored, as the runtime does.
+pub fn decode_instruction(data: &[u8]) -> Option<SystemInstruction> {
+    let index = u32::from_le_bytes(data.get(..4)?.try_into().ok()?);
+    let args = &data[4..];
+    let u64_at = |offset: usize| -> Option<u64> {
+        Some(u64::from_le_bytes(
+            args.get(offset..offset + 8)?.try_into().ok()?,
+        ))
+    };
+    let key_at =
+        |offset: usize| -> Option<[u8; 32]> { args.get(offset..offset + 32)?.try_into().ok() };
+
+    match index {
+        CREATE_ACCOUNT_IX_INDEX => Some(SystemInstruction::CreateAccount {
+            lamports: u64_at(0)?,
+            space: u64_at(8)?,
+            owner: key_at(16)?,
+        }),
+        SYSTEM_TRANSFER_IX_INDEX => Some(SystemInstruction::Transfer {
+            lamports: u64_at(0)?,
+        }),
+        ADVANCE_NONCE_IX_INDEX => Some(SystemInstruction::AdvanceNonceAccount),
+        WITHDRAW_NONCE_IX_INDEX => Some(SystemInstruction::WithdrawNonceAccount {
+            lamports: u64_at(0)?,
+        }),
+        INITIALIZE_NONCE_IX_INDEX => Some(SystemInstruction::InitializeNonceAccount {
+            authority: key_at(0)?,
+        }),
+        _ => None,
+    }
+}
+
+fn sysvar(pubkey: [u8; 32]) -> SolAccountMeta {
+    SolAccountMeta {
+        pubkey,
+        is_signer: false,
+        is_writable: false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// (pubkey, is_signer, is_writable) for each account of `ix`.
+    fn roles(ix: &SolInstruction) -> Vec<([u8; 32], bool, bool)> {
+        ix.accounts
+            .iter()
+            .map(|m| (m.pubkey, m.is_signer, m.is_writable))
+            .collect()
+    }
+
+    #[test]
+    fn rent_exempt_minimum_matches_network_values() {
+        assert_eq!(rent_exempt_minimum(0), 890_880);
+        assert_eq!(rent_exempt_minimum(80), 1_447_680);
+        // SPL token account (165 bytes).
+        assert_eq!(rent_exempt_minimum(165), 2_039_280);
+    }
+
+    #[test]
+    fn sysvar_ids_match_base58() {
+        assert_eq!(
+            bs58::encode(SYSVAR_RECENT_BLOCKHASHES_ID).into_string(),
+            "SysvarRecentB1ockHashes11111111111111111111"
+        );
+        assert_eq!(
+            bs58::encode(SYSVAR_RENT_ID).into_string(),
+            "SysvarRent111111111111111111111111111111111"
+        );
+    }
+
+    // Expected data and account roles are from solana-program's
+    // `system_instruction` builders for the same inputs.
+
+    #[test]
+    fn initialize_nonce_layout() {
+        let ix = build_initialize_nonce(&[1u8; 32], &[2u8; 32]);
+
+        assert_eq!(ix.program_id, SYSTEM_PROGRAM_ID);
+        assert_eq!(
+            hex::encode(&ix.data),
+            "060000000202020202020202020202020202020202020202020202020202020202020202"
+        );
+        assert_eq!(
+            roles(&ix),
+            vec![
+                ([1u8; 32], false, true),
+                (SYSVAR_RECENT_BLOCKHASHES_ID, false, false),
+                (SYSVAR_RENT_ID, false, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn withdraw_nonce_layout() {
+        let ix = build_withdraw_nonce(&[1u8; 32], &[3u8; 32], &[2u8; 32], 1_000_000).unwrap();
+
+        assert_eq!(ix.program_id, SYSTEM_PROGRAM_ID);
+        assert_eq!(hex::encode(&ix.data), "0500000040420f0000000000");
+        assert_eq!(
+            roles(&ix),
+            vec![
+                ([1u8; 32], false, true),
+                ([3u8; 32], false, true),
+                (SYSVAR_RECENT_BLOCKHASHES_ID, false, false),
+                (SYSVAR_RENT_ID, false, false),
+                ([2u8; 32], true, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn withdraw_nonce_rejects_zero() {
+        assert!(build_withdraw_nonce(&[1u8; 32], &[3u8; 32], &[2u8; 32], 0).is_err());
+    }
+
+    #[test]
+    fn decode_transfer_roundtrip() {
+        let ix = crate::transaction::build_system_transfer_instruction(
+            &[1u8; 32], &[2u8; 32], 1_500_000,
+        );
+        assert_eq!(
+            decode_instruction(&ix.data),
+            Some(SystemInstruction::Transfer {
+                lamports: 1_500_000
+            })
+        );
+    }
+
+    #[test]
+    fn decode_nonce_instructions() {
+        let init = build_initialize_nonce(&[1u8; 32], &[2u8; 32]);
+        assert_eq!(
+            decode_instruction(&init.data),
+            Some(SystemInstruction::InitializeNonceAccount {
+                authority: [2u8; 32]
+            })
+        );
+
+        let withdraw = build_withdraw_nonce(&[1u8; 32], &[3u8; 32], &[2u8; 32], 7).unwrap();
+        assert_eq!(
+            decode_instruction(&withdraw.data),
+            Some(SystemInstruction::WithdrawNonceAccount { lamports: 7 })
+        );
+
+        assert_eq!(
+            decode_instruction(&[4, 0, 0, 0]),
+            Some(SystemInstruction::AdvanceNonceAccount)
+        );
+    }
+
+    #[test]
+    fn decode_create_account() {
+        // solana-program's `create_account(.., lamports = 1_000_000, space = 165, owner)`
+        let mut data = hex::decode("0000000040420f0000000000a500000000000000").unwrap();
+        data.extend_from_slice(&[9u8; 32]);
+        assert_eq!(
+            decode_instruction(&data),
+            Some(SystemInstruction::CreateAccount {
+                lamports: 1_000_000,
+                space: 165,
+                owner: [9u8; 32],
+            })
+        );
+        // Owner cut short.
+        assert_eq!(decode_instruction(&data[..40]), None);
+    }
+
+    #[test]
+    fn decode_unknown_or_short_data_is_none() {
+        // 1 = Assign, not decoded here; 99 is not a System instruction.
+        assert_eq!(decode_instruction(&[1, 0, 0, 0]), None);
+        assert_eq!(decode_instruction(&[99, 0, 0, 0]), None);
+        assert_eq!(decode_instruction(&[2, 0, 0]), None);
+        assert_eq!(decode_instruction(&[2, 0, 0, 0, 1, 2]), None);
+        assert_eq!(decode_instruction(&[]), None);
+    }
+}
@@ -26,9 +26,12 @@
 //! ```
 
 use ed25519_dalek::Signer;
+use serde::{Deserialize, Serialize};
 use zeroize::Zeroize;
 
+use crate::compact_u16::{decode_compact_u16, encode_compact_u16};
 use crate::error::SolError;
+use crate::serde_helpers::{base58_key, base58_key_vec, hex_bytes};
 
 // ---------------------------------------------------------------------------
 // Solana System Program
@@ -39,64 +42,101 @@ use crate::error::SolError;
 pub const SYSTEM_PROGRAM_ID: [u8; 32] = [0u8; 32];
 
 /// System Program `Transfer` instruction index (little-endian u32).
-const SYSTEM_TRANSFER_IX_INDEX: u32 = 2;
+pub(crate) const SYSTEM_TRANSFER_IX_INDEX: u32 = 2;
 
 // ---------------------------------------------------------------------------
-// Compact-u16 encoding
+// Fees
 // ---------------------------------------------------------------------------
 
-/// Encode a `u16` value in Solana's compact-u16 format.
+/// Base fee the network charges per required signature, in lamports.
+pub const LAMPORTS_PER_SIGNATURE: u64 = 5_000;
+
+/// Micro-lamports in one lamport; compute-unit prices are quoted in these.
+const MICRO_LAMPORTS_PER_LAMPORT: u128 = 1_000_000;
+
+/// Base fee for a transaction with `num_signatures` required signatures:
+/// [`LAMPORTS_PER_SIGNATURE`] each.
 ///
-/// - Values 0..0x7f       -> 1 byte
-/// - Values 0x80..0x3fff  -> 2 bytes
-/// - Values 0x4000..      -> 3 bytes (max 0x1_ffff, but u16 caps at 0xffff)
-pub fn encode_compact_u16(value: u16) -> Vec<u8> {
-    let mut val = value as u32;
-    let mut out = Vec::with_capacity(3);
-
-    loop {
-        let mut byte = (val & 0x7f) as u8;
-        val >>= 7;
-        if val > 0 {
-            byte |= 0x80;
-        }
-        out.push(byte);
-        if val == 0 {
-            break;
-        }
-    }
+/// Pass `tx.num_required_signatures` for a built transaction. Prioritization
+/// fees come on top (see [`total_fee`]); this crate does not add
+/// compute-budget instructions, so for its transactions this is the fee.
+pub fn base_fee(num_signatures: u8) -> u64 {
+    LAMPORTS_PER_SIGNATURE * u64::from(num_signatures)
+}
+
+/// Base fee plus the prioritization fee, in lamports.
+///
+/// The prioritization fee is `priority_micro_lamports` (the compute-unit
+/// price) times `compute_units` (the requested limit), converted to lamports
+/// and rounded up as the runtime does. Saturates at `u64::MAX`.
+pub fn total_fee(num_signatures: u8, priority_micro_lamports: u64, compute_units: u32) -> u64 {
+    let micro_lamports = u128::from(priority_micro_lamports) * u128::from(compute_units);
+    let priority_fee = micro_lamports.div_ceil(MICRO_LAMPORTS_PER_LAMPORT);
+    u64::try_from(priority_fee)
+        .unwrap_or(u64::MAX)
+        .saturating_add(base_fee(num_signatures))
+}
+
+// ---------------------------------------------------------------------------
+// Size limit
+// ---------------------------------------------------------------------------
+
+/// Largest serialized transaction the network accepts: the 1280-byte IPv6
+/// minimum MTU minus 48 bytes of IP and UDP headers.
+pub const PACKET_DATA_SIZE: usize = 1232;
 
-    out
+/// Check that a wire-format transaction fits in [`PACKET_DATA_SIZE`].
+///
+/// Oversized transactions are dropped by the network without an error, so
+/// the signing functions refuse to produce them.
+pub fn validate_size(wire: &[u8]) -> Result<(), SolError> {
+    if wire.len() > PACKET_DATA_SIZE {
+        return Err(SolError::TransactionBuildError(format!(
+            "transaction is {} bytes, over the {PACKET_DATA_SIZE}-byte packet limit",
+            wire.len()
+        )));
+    }
+    Ok(())
 }
 
 // ---------------------------------------------------------------------------
 // Data structures
 // ---------------------------------------------------------------------------
 
+// All of these serialize to camelCase JSON with keys in Base58 and data in
+// hex.
+
 /// A single account reference in a Solana instruction.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct SolAccountMeta {
+    #[serde(with = "base58_key")]
     pub pubkey: [u8; 32],
     pub is_signer: bool,
     pub is_writable: bool,
 }
 
 /// A Solana instruction (before it is compiled into a transaction).
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct SolInstruction {
+    #[serde(with = "base58_key")]
     pub program_id: [u8; 32],
     pub accounts: Vec<SolAccountMeta>,
+    #[serde(with = "hex_bytes")]
     pub data: Vec<u8>,
 }
 
 /// A complete Solana transaction (unsigned or partially signed).
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct SolTransaction {
     /// All account keys referenced by this transaction, in canonical order:
     ///   1. writable signers
     ///   2. read-only signers
     ///   3. writable non-signers
     ///   4. read-only non-signers
+    #[serde(with = "base58_key_vec")]
     pub account_keys: Vec<[u8; 32]>,
 
     /// Number of required signatures (first N accounts are signers).
@@ -107,6 +147,7 @@ pub struct SolTransaction {
     pub num_readonly_unsigned: u8,
 
     /// Recent blockhash (32 bytes).
+    #[serde(with = "base58_key")]
     pub recent_blockhash: [u8; 32],
 
     /// Compiled instructions (account references replaced with indices).
@@ -115,16 +156,51 @@ pub struct SolTransaction {
 
 /// A compiled instruction where account references are replaced by u8 indices
 /// into the transaction's `account_keys` array.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct CompiledInstruction {
     /// Index into `account_keys` for the program to invoke.
     pub program_id_index: u8,
     /// Indices into `account_keys` for each account the instruction reads/writes.
     pub account_indices: Vec<u8>,
     /// Opaque instruction data.
+    #[serde(with = "hex_bytes")]
     pub data: Vec<u8>,
 }
 
+impl SolInstruction {
+    /// Serialize the instruction to JSON.
+    pub fn to_json(&self) -> Result<String, SolError> {
+        serde_json::to_string(self).map_err(|e| SolError::SerializationError(e.to_string()))
+    }
+
+    /// Parse an instruction written by [`SolInstruction::to_json`].
+    pub fn from_json(json: &str) -> Result<Self, SolError> {
+        serde_json::from_str(json)
+            .map_err(|e| SolError::SerializationError(format!("invalid instruction JSON: {e}")))
+    }
+}
+
+impl SolTransaction {
+    /// Serialize the transaction to JSON for offline signing.
+    pub fn to_json(&self) -> Result<String, SolError> {
+        serde_json::to_string(self).map_err(|e| SolError::SerializationError(e.to_string()))
+    }
+
+    /// Parse a transaction written by [`SolTransaction::to_json`].
+    ///
+    /// As with [`deserialize_transaction`], every instruction must reference
+    /// accounts that exist in `account_keys`.
+    pub fn from_json(json: &str) -> Result<Self, SolError> {
+        let tx: Self = serde_json::from_str(json)
+            .map_err(|e| SolError::SerializationError(format!("invalid transaction JSON: {e}")))?;
+        for instruction in &tx.compiled_instructions {
+            check_account_indices(instruction, tx.account_keys.len())?;
+        }
+        Ok(tx)
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Transaction building
 // ---------------------------------------------------------------------------
@@ -151,6 +227,73 @@ pub fn build_sol_transfer(
     compile_transaction(&[instruction], from_pubkey, recent_blockhash)
 }
 
+/// Build a native SOL transfer whose network fee is paid by another account.
+///
+/// `from_pubkey` still authorizes the transfer, so the transaction has two
+/// required signatures: the fee payer at index 0 and the sender at index 1.
+/// If both keys are the same this is identical to [`build_sol_transfer`].
+///
+/// Each party signs with [`sign_transaction`] (or [`sign_sol_raw_transaction`]
+/// on the partially signed bytes); the slot of the other signer is left zeroed
+/// until they add their signature.
+pub fn build_sol_transfer_with_payer(
+    from_pubkey: &[u8; 32],
+    to_pubkey: &[u8; 32],
+    lamports: u64,
+    fee_payer: &[u8; 32],
+    recent_blockhash: &[u8; 32],
+) -> Result<SolTransaction, SolError> {
+    if lamports == 0 {
+        return Err(SolError::TransactionBuildError(
+            "lamports must be > 0".into(),
+        ));
+    }
+
+    let instruction = build_system_transfer_instruction(from_pubkey, to_pubkey, lamports);
+    compile_transaction(&[instruction], fee_payer, recent_blockhash)
+}
+
+/// Build a native SOL transfer carrying Solana Pay reference keys.
+///
+/// Each reference is appended to the `Transfer` instruction as a read-only,
+/// non-signer account. The System Program ignores accounts past the first
+/// two, so the transfer is unchanged, but a merchant can find the payment
+/// with `getSignaturesForAddress(reference)`. A reference that is also the
+/// sender or recipient would be merged into that writable account, so it is
+/// rejected.
+pub fn build_sol_transfer_with_references(
+    from_pubkey: &[u8; 32],
+    to_pubkey: &[u8; 32],
+    lamports: u64,
+    references: &[[u8; 32]],
+    recent_blockhash: &[u8; 32],
+) -> Result<SolTransaction, SolError> {
+    if lamports == 0 {
+        return Err(SolError::TransactionBuildError(
+            "lamports must be > 0".into(),
+        ));
+    }
+    if let Some(reference) = references
+        .iter()
+        .find(|r| *r == from_pubkey || *r == to_pubkey)
+    {
+        return Err(SolError::TransactionBuildError(format!(
+            "reference {} is the sender or recipient",
+            bs58::encode(reference).into_string()
+        )));
+    }
+
+    let mut instruction = build_system_transfer_instruction(from_pubkey, to_pubkey, lamports);
+    instruction
+        .accounts
+        .extend(references.iter().map(|reference| SolAccountMeta {
+            pubkey: *reference,
+            is_signer: false,
+            is_writable: false,
+        }));
+    compile_transaction(&[instruction], from_pubkey, recent_blockhash)
+}
+
 /// Build a transaction from a set of instructions with a single fee payer.
 ///
 /// The fee payer is always the first signer and is placed at index 0 in the
@@ -198,33 +341,25 @@ pub fn compile_transaction(
     }
 
     // Sort into canonical order:
-    //   1. writable signers  (fee payer first)
-    //   2. read-only signers
-    //   3. writable non-signers
-    //   4. read-only non-signers
-    entries.sort_by(|a, b| {
-        fn rank(e: &AccountEntry) -> u8 {
-            match (e.is_signer, e.is_writable) {
-                (true, true) => 0,
-                (true, false) => 1,
-                (false, true) => 2,
-                (false, false) => 3,
-            }
+    //   1. fee payer
+    //   2. writable signers
+    //   3. read-only signers
+    //   4. writable non-signers
+    //   5. read-only non-signers
+    // Within a category keys are ordered by their bytes, matching solana-sdk's
+    // `Message::new` so that the same instructions compile to the same bytes.
+    let rank = |e: &AccountEntry| -> u8 {
+        if e.pubkey == *fee_payer {
+            return 0;
         }
-        let ra = rank(a);
-        let rb = rank(b);
-        if ra != rb {
-            return ra.cmp(&rb);
+        match (e.is_signer, e.is_writable) {
+            (true, true) => 1,
+            (true, false) => 2,
+            (false, true) => 3,
+            (false, false) => 4,
         }
-        // Within the same category keep insertion order (fee payer first).
-        std::cmp::Ordering::Equal
-    });
-
-    // Make sure fee payer is at index 0.
-    if entries[0].pubkey != *fee_payer {
-        let pos = entries.iter().position(|e| e.pubkey == *fee_payer).unwrap();
-        entries.swap(0, pos);
-    }
+    };
+    entries.sort_by(|a, b| rank(a).cmp(&rank(b)).then_with(|| a.pubkey.cmp(&b.pubkey)));
 
     let num_signers = entries.iter().filter(|e| e.is_signer).count() as u8;
     let num_readonly_signed = entries
@@ -278,6 +413,14 @@ pub fn compile_transaction(
 
 /// Serialize the transaction message (the bytes that get signed).
 pub fn serialize_message(tx: &SolTransaction) -> Result<Vec<u8>, SolError> {
+    Ok(message_to_sign(tx))
+}
+
+/// The message bytes each required signer signs with Ed25519.
+///
+/// For signing out of process (e.g. on a hardware wallet): hand these bytes
+/// to every signer, then combine the results with [`assemble_signed`].
+pub fn message_to_sign(tx: &SolTransaction) -> Vec<u8> {
     let mut buf = Vec::with_capacity(256);
 
     // Header: 3 bytes.
@@ -308,90 +451,388 @@ pub fn serialize_message(tx: &SolTransaction) -> Result<Vec<u8>, SolError> {
         buf.extend_from_slice(&ix.data);
     }
 
-    Ok(buf)
+    buf
+}
+
+/// Combine externally produced signatures with the message into wire format.
+///
+/// `signatures[i]` goes in the slot of the `i`-th signer (`account_keys[i]`),
+/// so exactly `num_required_signatures` must be given. An all-zero signature
+/// leaves its slot empty for a co-signer; every other signature is checked
+/// against its signer's key, so a signature in the wrong slot is an error.
+pub fn assemble_signed(tx: &SolTransaction, signatures: &[[u8; 64]]) -> Result<Vec<u8>, SolError> {
+    let num_signatures = tx.num_required_signatures as usize;
+    if signatures.len() != num_signatures {
+        return Err(SolError::SigningError(format!(
+            "expected {num_signatures} signatures, got {}",
+            signatures.len()
+        )));
+    }
+
+    let message_bytes = message_to_sign(tx);
+
+    for (i, (sig, signer)) in signatures.iter().zip(&tx.account_keys).enumerate() {
+        if *sig == [0u8; 64] {
+            continue;
+        }
+        let valid = ed25519_dalek::VerifyingKey::from_bytes(signer).is_ok_and(|vk| {
+            vk.verify_strict(&message_bytes, &ed25519_dalek::Signature::from_bytes(sig))
+                .is_ok()
+        });
+        if !valid {
+            return Err(SolError::SigningError(format!(
+                "signature {i} does not verify for signer {}",
+                bs58::encode(signer).into_string()
+            )));
+        }
+    }
+
+    encode_wire(&message_bytes, signatures)
+}
+
+/// Re-validates the transfer recipients of `tx` right before signing.
+///
+/// `SolTransaction` has public fields and round-trips through JSON, so it may
+/// have changed since it was compiled. The header must fit `account_keys`,
+/// every instruction must only reference accounts that exist, and each System
+/// `Transfer` must credit a writable account other than its sender.
+pub fn assert_recipient(tx: &SolTransaction) -> Result<(), SolError> {
+    let num_keys = tx.account_keys.len();
+    let num_signers = tx.num_required_signatures as usize;
+    let readonly_signed = tx.num_readonly_signed as usize;
+    let readonly_unsigned = tx.num_readonly_unsigned as usize;
+    if num_signers > num_keys
+        || readonly_signed > num_signers
+        || readonly_unsigned > num_keys - num_signers
+    {
+        return Err(SolError::TransactionBuildError(
+            "message header does not match the account keys".into(),
+        ));
+    }
+    let is_writable = |i: usize| {
+        if i < num_signers {
+            i < num_signers - readonly_signed
+        } else {
+            i < num_keys - readonly_unsigned
+        }
+    };
+
+    for (n, ix) in tx.compiled_instructions.iter().enumerate() {
+        check_account_indices(ix, num_keys)?;
+
+        let is_transfer = tx.account_keys[ix.program_id_index as usize] == SYSTEM_PROGRAM_ID
+            && ix.data.get(..4) == Some(&SYSTEM_TRANSFER_IX_INDEX.to_le_bytes()[..]);
+        if !is_transfer {
+            continue;
+        }
+        let (Some(&from), Some(&to)) = (ix.account_indices.first(), ix.account_indices.get(1))
+        else {
+            return Err(SolError::TransactionBuildError(format!(
+                "transfer instruction {n} is missing its accounts"
+            )));
+        };
+        let recipient = bs58::encode(tx.account_keys[to as usize]).into_string();
+        if !is_writable(to as usize) {
+            return Err(SolError::InvalidAddress(format!(
+                "transfer recipient {recipient} is not writable"
+            )));
+        }
+        if from == to {
+            return Err(SolError::InvalidAddress(format!(
+                "transfer recipient {recipient} is the sender"
+            )));
+        }
+    }
+
+    Ok(())
 }
 
 /// Sign and serialize a transaction into its wire format.
 ///
 /// The private key is the 32-byte Ed25519 seed. The resulting byte vector
-/// is ready to be submitted via `sendTransaction` RPC.
+/// is ready to be submitted via `sendTransaction` RPC once every required
+/// signature is present.
+///
+/// One signature slot is written per required signer. Only the slot matching
+/// `private_key` is filled; the others are left zeroed for co-signers to fill
+/// with [`sign_sol_raw_transaction`]. Fails if the key is not a signer,
+/// [`assert_recipient`] rejects the transaction, or the result exceeds
+/// [`PACKET_DATA_SIZE`].
 pub fn sign_transaction(
     tx: &SolTransaction,
     private_key: &[u8; 32],
 ) -> Result<Vec<u8>, SolError> {
+    assert_recipient(tx)?;
+
     let message_bytes = serialize_message(tx)?;
 
     // Build the signing key (zeroize-on-drop via ed25519-dalek).
     let mut seed = *private_key;
     let signing_key = ed25519_dalek::SigningKey::from_bytes(&seed);
     seed.zeroize();
+    let our_pubkey = signing_key.verifying_key().to_bytes();
 
-    let signature = signing_key.sign(&message_bytes);
-
-    // Assemble wire format.
-    let mut wire = Vec::with_capacity(1 + 64 + message_bytes.len());
+    let num_signatures = tx.num_required_signatures as usize;
+    let signer_idx = tx
+        .account_keys
+        .iter()
+        .take(num_signatures)
+        .position(|key| *key == our_pubkey)
+        .ok_or_else(|| {
+            SolError::SigningError("wallet pubkey not found in transaction signers".into())
+        })?;
 
-    // Number of signatures (compact-u16, always 1 for single-signer).
-    wire.extend_from_slice(&encode_compact_u16(1));
+    // Signature slots, ours filled in and the rest zeroed.
+    let mut signatures = vec![[0u8; 64]; num_signatures];
+    signatures[signer_idx] = signing_key.sign(&message_bytes).to_bytes();
 
-    // Signature (64 bytes).
-    wire.extend_from_slice(&signature.to_bytes());
+    encode_wire(&message_bytes, &signatures)
+}
 
-    // Message.
-    wire.extend_from_slice(&message_bytes);
+/// Wire format: compact-u16 signature count, the 64-byte signature slots,
+/// then the message. Fails if the result exceeds [`PACKET_DATA_SIZE`].
+fn encode_wire(message_bytes: &[u8], signatures: &[[u8; 64]]) -> Result<Vec<u8>, SolError> {
+    let mut wire = Vec::with_capacity(3 + 64 * signatures.len() + message_bytes.len());
+    wire.extend_from_slice(&encode_compact_u16(signatures.len() as u16));
+    for sig in signatures {
+        wire.extend_from_slice(sig);
+    }
+    wire.extend_from_slice(message_bytes);
 
+    validate_size(&wire)?;
     Ok(wire)
 }
 
 // ---------------------------------------------------------------------------
-// Raw transaction signing (for pre-built transactions from dApps / Jupiter)
+// Decoding
 // ---------------------------------------------------------------------------
 
-/// Decode a compact-u16 value from a byte slice.
+/// Decode a wire-format transaction back into a [`SolTransaction`].
 ///
-/// Returns `(value, bytes_consumed)` or an error if the data is truncated.
-pub fn decode_compact_u16(data: &[u8]) -> Result<(u16, usize), SolError> {
-    if data.is_empty() {
+/// The inverse of [`sign_transaction`]: the signature slots are skipped and
+/// the message is parsed. Only legacy messages are supported; versioned (v0)
+/// messages, whose first byte has the high bit set, are rejected because
+/// their address-table accounts cannot be resolved offline; given the table
+/// contents, [`resolve_v0_accounts`] lists their accounts instead.
+pub fn deserialize_transaction(wire: &[u8]) -> Result<SolTransaction, SolError> {
+    let mut reader = WireReader { data: wire, pos: 0 };
+
+    let num_signatures = reader.compact_u16()? as usize;
+    reader.take(num_signatures * 64)?;
+
+    let num_required_signatures = reader.byte()?;
+    if num_required_signatures & 0x80 != 0 {
         return Err(SolError::SerializationError(
-            "unexpected end of data while decoding compact-u16".into(),
+            "versioned messages are not supported".into(),
         ));
     }
+    let num_readonly_signed = reader.byte()?;
+    let num_readonly_unsigned = reader.byte()?;
+
+    let num_accounts = reader.compact_u16()? as usize;
+    let account_keys = (0..num_accounts)
+        .map(|_| reader.key())
+        .collect::<Result<Vec<_>, _>>()?;
+    let recent_blockhash = reader.key()?;
+
+    let num_instructions = reader.compact_u16()? as usize;
+    let mut compiled_instructions = Vec::with_capacity(num_instructions.min(64));
+    for _ in 0..num_instructions {
+        let program_id_index = reader.byte()?;
+        let num_indices = reader.compact_u16()? as usize;
+        let account_indices = reader.take(num_indices)?.to_vec();
+        let data_len = reader.compact_u16()? as usize;
+        let data = reader.take(data_len)?.to_vec();
+
+        let instruction = CompiledInstruction {
+            program_id_index,
+            account_indices,
+            data,
+        };
+        check_account_indices(&instruction, num_accounts)?;
+        compiled_instructions.push(instruction);
+    }
 
-    let mut value: u32 = 0;
-    let mut shift = 0u32;
-    let mut consumed = 0usize;
+    if reader.pos != wire.len() {
+        return Err(SolError::SerializationError(
+            "trailing bytes after transaction message".into(),
+        ));
+    }
 
-    loop {
-        if consumed >= data.len() {
-            return Err(SolError::SerializationError(
-                "unexpected end of data while decoding compact-u16".into(),
-            ));
-        }
-        let byte = data[consumed];
-        consumed += 1;
+    Ok(SolTransaction {
+        account_keys,
+        num_required_signatures,
+        num_readonly_signed,
+        num_readonly_unsigned,
+        recent_blockhash,
+        compiled_instructions,
+    })
+}
 
-        value |= ((byte & 0x7f) as u32) << shift;
-        shift += 7;
+/// Full, ordered account key list of a v0 `message` (no signatures), given
+/// the contents of the address lookup tables it references.
+///
+/// `lookups` pairs each table's address with its addresses as fetched by the
+/// caller (e.g. via `getAddressLookupTable`); only the tables the message
+/// uses are needed. The result is the static keys, then every writable
+/// lookup address (tables in message order), then every read-only one, which
+/// is the index space the message's instructions use.
+pub fn resolve_v0_accounts(
+    message: &[u8],
+    lookups: &[([u8; 32], Vec<[u8; 32]>)],
+) -> Result<Vec<[u8; 32]>, SolError> {
+    let mut reader = WireReader {
+        data: message,
+        pos: 0,
+    };
 
-        if byte & 0x80 == 0 {
-            break;
-        }
-        if consumed >= 3 {
-            return Err(SolError::SerializationError(
-                "compact-u16 continuation bit set on third byte".into(),
-            ));
-        }
+    if reader.byte()? != 0x80 {
+        return Err(SolError::SerializationError("not a v0 message".into()));
+    }
+    reader.take(3)?; // Header.
+
+    let num_static = reader.compact_u16()? as usize;
+    let mut account_keys = (0..num_static)
+        .map(|_| reader.key())
+        .collect::<Result<Vec<_>, _>>()?;
+    reader.key()?; // Recent blockhash.
+
+    let num_instructions = reader.compact_u16()? as usize;
+    let mut instructions = Vec::with_capacity(num_instructions.min(64));
+    for _ in 0..num_instructions {
+        let program_id_index = reader.byte()?;
+        let num_indices = reader.compact_u16()? as usize;
+        let account_indices = reader.take(num_indices)?.to_vec();
+        let data_len = reader.compact_u16()? as usize;
+        reader.take(data_len)?;
+        instructions.push(CompiledInstruction {
+            program_id_index,
+            account_indices,
+            data: Vec::new(),
+        });
+    }
+
+    let mut writable = Vec::new();
+    let mut readonly = Vec::new();
+    let num_lookups = reader.compact_u16()? as usize;
+    for _ in 0..num_lookups {
+        let table_key = reader.key()?;
+        let (_, table) = lookups
+            .iter()
+            .find(|(key, _)| *key == table_key)
+            .ok_or_else(|| {
+                SolError::SerializationError(format!(
+                    "lookup table {} was not provided",
+                    bs58::encode(table_key).into_string()
+                ))
+            })?;
+        let mut resolve = |out: &mut Vec<[u8; 32]>| -> Result<(), SolError> {
+            let count = reader.compact_u16()? as usize;
+            for &index in reader.take(count)? {
+                let key = table.get(usize::from(index)).ok_or_else(|| {
+                    SolError::SerializationError(format!(
+                        "index {index} is past the end of lookup table {}",
+                        bs58::encode(table_key).into_string()
+                    ))
+                })?;
+                out.push(*key);
+            }
+            Ok(())
+        };
+        resolve(&mut writable)?;
+        resolve(&mut readonly)?;
     }
 
-    if value > u16::MAX as u32 {
+    if reader.pos != message.len() {
+        return Err(SolError::SerializationError(
+            "trailing bytes after v0 message".into(),
+        ));
+    }
+
+    account_keys.extend(writable);
+    account_keys.extend(readonly);
+    for instruction in &instructions {
+        check_account_indices(instruction, account_keys.len())?;
+    }
+    Ok(account_keys)
+}
+
+/// Fail if `instruction` references an account index past `num_accounts`.
+fn check_account_indices(
+    instruction: &CompiledInstruction,
+    num_accounts: usize,
+) -> Result<(), SolError> {
+    let out_of_range = core::iter::once(&instruction.program_id_index)
+        .chain(&instruction.account_indices)
+        .any(|&i| usize::from(i) >= num_accounts);
+    if out_of_range {
+        return Err(SolError::SerializationError(
+            "instruction references a missing account".into(),
+        ));
+    }
+    Ok(())
+}
+
+/// The transaction id of a signed wire-format transaction: its first
+/// signature (the fee payer's), Base58-encoded.
+///
+/// This is what RPCs return from `sendTransaction` and what explorers index.
+/// Fails if the transaction does not decode or is not signed yet.
+pub fn transaction_id(wire: &[u8]) -> Result<String, SolError> {
+    deserialize_transaction(wire)?;
+
+    let mut reader = WireReader { data: wire, pos: 0 };
+    if reader.compact_u16()? == 0 {
+        return Err(SolError::SerializationError(
+            "transaction has no signatures".into(),
+        ));
+    }
+    let signature = reader.take(64)?;
+    if signature.iter().all(|&b| b == 0) {
         return Err(SolError::SerializationError(
-            "compact-u16 value overflow".into(),
+            "transaction is not signed".into(),
         ));
     }
+    Ok(bs58::encode(signature).into_string())
+}
 
-    Ok((value as u16, consumed))
+/// Bounds-checked cursor over wire bytes.
+struct WireReader<'a> {
+    data: &'a [u8],
+    pos: usize,
 }
 
+impl<'a> WireReader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], SolError> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|&end| end <= self.data.len())
+            .ok_or_else(|| SolError::SerializationError("transaction truncated".into()))?;
+        let bytes = &self.data[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn byte(&mut self) -> Result<u8, SolError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn key(&mut self) -> Result<[u8; 32], SolError> {
+        Ok(self.take(32)?.try_into().expect("took 32 bytes"))
+    }
+
+    fn compact_u16(&mut self) -> Result<u16, SolError> {
+        let (value, len) = decode_compact_u16(&self.data[self.pos..])?;
+        self.pos += len;
+        Ok(value)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Raw transaction signing (for pre-built transactions from dApps / Jupiter)
+// ---------------------------------------------------------------------------
+
 /// Sign a pre-built Solana transaction with the given Ed25519 private key.
 ///
 /// The `raw_tx` must be a valid Solana wire-format transaction (as produced by
@@ -404,7 +845,8 @@ pub fn decode_compact_u16(data: &[u8]) -> Result<(u16, usize), SolError> {
 /// 4. Returns the fully-signed transaction bytes.
 ///
 /// This supports both single-signer and multi-signer transactions. If our
-/// pubkey is not in the transaction's signer list, an error is returned.
+/// pubkey is not in the transaction's signer list, or the transaction exceeds
+/// [`PACKET_DATA_SIZE`], an error is returned.
 pub fn sign_sol_raw_transaction(
     private_key: &[u8; 32],
     raw_tx: &[u8],
@@ -486,6 +928,7 @@ pub fn sign_sol_raw_transaction(
     let sig_offset = sigs_start + signer_idx * 64;
     signed_tx[sig_offset..sig_offset + 64].copy_from_slice(&signature.to_bytes());
 
+    validate_size(&signed_tx)?;
     Ok(signed_tx)
 }
 
@@ -494,7 +937,7 @@ pub fn sign_sol_raw_transaction(
 // ---------------------------------------------------------------------------
 
 /// Build a System Program `Transfer` instruction.
-fn build_system_transfer_instruction(
+pub(crate) fn build_system_transfer_instruction(
     from: &[u8; 32],
     to: &[u8; 32],
     lamports: u64,
@@ -747,6 +1190,558 @@ mod tests {
         assert_eq!(wire1, wire2);
     }
 
+    #[test]
+    fn assemble_signed_matches_sign_transaction() {
+        let private_key = [0x42u8; 32];
+        let signing_key = ed25519_dalek::SigningKey::from_bytes(&private_key);
+        let from_pubkey = signing_key.verifying_key().to_bytes();
+        let tx = build_sol_transfer(&from_pubkey, &[0xBBu8; 32], 1_000_000, &[0xCC; 32]).unwrap();
+
+        // Sign "out of process" over the exported message.
+        let signature = signing_key.sign(&message_to_sign(&tx)).to_bytes();
+
+        assert_eq!(
+            assemble_signed(&tx, &[signature]).unwrap(),
+            sign_transaction(&tx, &private_key).unwrap()
+        );
+    }
+
+    #[test]
+    fn assemble_signed_places_signatures_by_signer_order() {
+        let payer = ed25519_dalek::SigningKey::from_bytes(&[0x11u8; 32]);
+        let from = ed25519_dalek::SigningKey::from_bytes(&[0x22u8; 32]);
+        let tx = build_sol_transfer_with_payer(
+            &from.verifying_key().to_bytes(),
+            &[0x33u8; 32],
+            1_000,
+            &payer.verifying_key().to_bytes(),
+            &[0u8; 32],
+        )
+        .unwrap();
+        let message = message_to_sign(&tx);
+        let payer_sig = payer.sign(&message).to_bytes();
+        let from_sig = from.sign(&message).to_bytes();
+
+        // Payer is the first signer; partial signing leaves the other slot zeroed.
+        let partial = assemble_signed(&tx, &[payer_sig, [0u8; 64]]).unwrap();
+        assert_eq!(partial, sign_transaction(&tx, &[0x11u8; 32]).unwrap());
+
+        let full = assemble_signed(&tx, &[payer_sig, from_sig]).unwrap();
+        assert_eq!(
+            full,
+            sign_sol_raw_transaction(&[0x22u8; 32], &partial).unwrap()
+        );
+
+        assert!(assemble_signed(&tx, &[from_sig, payer_sig]).is_err());
+        assert!(assemble_signed(&tx, &[payer_sig]).is_err());
+    }
+
+    #[test]
+    fn sign_transaction_rejects_non_signer_key() {
+        let tx = build_sol_transfer(&[0x11u8; 32], &[0x22u8; 32], 42, &[0x99; 32]).unwrap();
+        let result = sign_transaction(&tx, &[0x55u8; 32]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn sign_transaction_rejects_corrupted_recipient() {
+        let key = [0x11u8; 32];
+        let from = ed25519_dalek::SigningKey::from_bytes(&key)
+            .verifying_key()
+            .to_bytes();
+        let tx = build_sol_transfer(&from, &[0x22u8; 32], 42, &[0x99; 32]).unwrap();
+        assert!(assert_recipient(&tx).is_ok());
+
+        let system_idx = tx
+            .account_keys
+            .iter()
+            .position(|k| *k == SYSTEM_PROGRAM_ID)
+            .unwrap();
+
+        // Recipient pointed at the read-only System program.
+        let mut readonly = tx.clone();
+        readonly.compiled_instructions[0].account_indices[1] = system_idx as u8;
+        match sign_transaction(&readonly, &key) {
+            Err(SolError::InvalidAddress(msg)) => assert!(msg.contains("not writable"), "{msg}"),
+            other => panic!("expected InvalidAddress, got {:?}", other),
+        }
+
+        // Recipient pointed back at the sender.
+        let mut to_self = tx.clone();
+        to_self.compiled_instructions[0].account_indices[1] = 0;
+        assert!(matches!(
+            sign_transaction(&to_self, &key),
+            Err(SolError::InvalidAddress(_))
+        ));
+
+        // Recipient index past the end of account_keys.
+        let mut dangling = tx;
+        dangling.compiled_instructions[0].account_indices[1] = 9;
+        assert!(matches!(
+            sign_transaction(&dangling, &key),
+            Err(SolError::SerializationError(_))
+        ));
+    }
+
+    // -- Solana Pay references --------------------------------------------
+
+    #[test]
+    fn transfer_with_references_appends_readonly_accounts() {
+        let from = [0x11u8; 32];
+        let to = [0x22u8; 32];
+        let references = [[0x33u8; 32], [0x44u8; 32]];
+        let tx =
+            build_sol_transfer_with_references(&from, &to, 1_000, &references, &[0u8; 32]).unwrap();
+
+        let ix = &tx.compiled_instructions[0];
+        assert_eq!(ix.account_indices.len(), 4);
+        let num_keys = tx.account_keys.len();
+        let first_readonly = num_keys - tx.num_readonly_unsigned as usize;
+        for (reference, &idx) in references.iter().zip(&ix.account_indices[2..]) {
+            let idx = idx as usize;
+            assert_eq!(&tx.account_keys[idx], reference);
+            assert!(
+                idx >= tx.num_required_signatures as usize,
+                "reference must not sign"
+            );
+            assert!(idx >= first_readonly, "reference must be read-only");
+        }
+        let plain = build_sol_transfer(&from, &to, 1_000, &[0u8; 32]).unwrap();
+        assert_eq!(ix.data, plain.compiled_instructions[0].data);
+    }
+
+    #[test]
+    fn transfer_with_references_still_verifies() {
+        use ed25519_dalek::{Signature, VerifyingKey};
+
+        let private_key = [0x42u8; 32];
+        let from = ed25519_dalek::SigningKey::from_bytes(&private_key)
+            .verifying_key()
+            .to_bytes();
+        let tx = build_sol_transfer_with_references(
+            &from,
+            &[0x22u8; 32],
+            1_000_000,
+            &[[0x33u8; 32]],
+            &[0xCCu8; 32],
+        )
+        .unwrap();
+        assert!(assert_recipient(&tx).is_ok());
+
+        let wire = sign_transaction(&tx, &private_key).unwrap();
+        let signature = Signature::from_bytes(&wire[1..65].try_into().unwrap());
+        let vk = VerifyingKey::from_bytes(&from).unwrap();
+        assert!(vk.verify_strict(&wire[65..], &signature).is_ok());
+    }
+
+    #[test]
+    fn transfer_with_references_rejects_party_as_reference() {
+        let from = [0x11u8; 32];
+        let to = [0x22u8; 32];
+        for reference in [from, to] {
+            let result =
+                build_sol_transfer_with_references(&from, &to, 1_000, &[reference], &[0u8; 32]);
+            assert!(matches!(result, Err(SolError::TransactionBuildError(_))));
+        }
+        assert!(build_sol_transfer_with_references(&from, &to, 0, &[], &[0u8; 32]).is_err());
+    }
+
+    // -- Separate fee payer ------------------------------------------------
+
+    #[test]
+    fn transfer_with_payer_requires_two_signatures() {
+        let tx = build_sol_transfer_with_payer(
+            &[0x11u8; 32],
+            &[0x22u8; 32],
+            1_000,
+            &[0x33u8; 32],
+            &[0u8; 32],
+        )
+        .unwrap();
+
+        assert_eq!(tx.num_required_signatures, 2);
+        assert_eq!(tx.num_readonly_signed, 0);
+        // payer, sender, recipient, system program
+        assert_eq!(tx.account_keys.len(), 4);
+    }
+
+    #[test]
+    fn transfer_with_payer_puts_fee_payer_first() {
+        let from = [0x11u8; 32];
+        let payer = [0x33u8; 32];
+        let tx =
+            build_sol_transfer_with_payer(&from, &[0x22u8; 32], 1_000, &payer, &[0u8; 32]).unwrap();
+
+        assert_eq!(tx.account_keys[0], payer);
+        assert_eq!(tx.account_keys[1], from);
+    }
+
+    #[test]
+    fn transfer_with_payer_keeps_sender_as_transfer_signer() {
+        let from = [0x11u8; 32];
+        let to = [0x22u8; 32];
+        let tx =
+            build_sol_transfer_with_payer(&from, &to, 1_000, &[0x33u8; 32], &[0u8; 32]).unwrap();
+
+        let ix = &tx.compiled_instructions[0];
+        let sender_idx = ix.account_indices[0] as usize;
+        assert_eq!(tx.account_keys[sender_idx], from);
+        assert!(sender_idx < tx.num_required_signatures as usize);
+        assert_eq!(tx.account_keys[ix.account_indices[1] as usize], to);
+    }
+
+    #[test]
+    fn transfer_with_payer_same_as_sender_matches_plain_transfer() {
+        let from = [0x11u8; 32];
+        let to = [0x22u8; 32];
+        let blockhash = [0x44u8; 32];
+        let with_payer = build_sol_transfer_with_payer(&from, &to, 7, &from, &blockhash).unwrap();
+        let plain = build_sol_transfer(&from, &to, 7, &blockhash).unwrap();
+
+        assert_eq!(
+            serialize_message(&with_payer).unwrap(),
+            serialize_message(&plain).unwrap()
+        );
+    }
+
+    #[test]
+    fn transfer_with_payer_zero_lamports_fails() {
+        let result =
+            build_sol_transfer_with_payer(&[1u8; 32], &[2u8; 32], 0, &[3u8; 32], &[0u8; 32]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn transfer_with_payer_is_co_signed_into_both_slots() {
+        use ed25519_dalek::{Signature, VerifyingKey};
+
+        let sender_key = [0x42u8; 32];
+        let payer_key = [0x43u8; 32];
+        let sender = ed25519_dalek::SigningKey::from_bytes(&sender_key)
+            .verifying_key()
+            .to_bytes();
+        let payer = ed25519_dalek::SigningKey::from_bytes(&payer_key)
+            .verifying_key()
+            .to_bytes();
+
+        let tx = build_sol_transfer_with_payer(&sender, &[0xBBu8; 32], 5_000, &payer, &[0xCC; 32])
+            .unwrap();
+
+        // The user signs first; the payer's slot stays empty.
+        let partial = sign_transaction(&tx, &sender_key).unwrap();
+        assert_eq!(partial[0], 0x02);
+        assert_eq!(&partial[1..65], &[0u8; 64]);
+
+        // The sponsor co-signs the partially signed bytes.
+        let wire = sign_sol_raw_transaction(&payer_key, &partial).unwrap();
+        let message_bytes = &wire[129..];
+        assert_eq!(message_bytes, serialize_message(&tx).unwrap().as_slice());
+
+        for (slot, pubkey) in [(0usize, payer), (1, sender)] {
+            let start = 1 + slot * 64;
+            let sig_bytes: [u8; 64] = wire[start..start + 64].try_into().unwrap();
+            let vk = VerifyingKey::from_bytes(&pubkey).unwrap();
+            assert!(vk
+                .verify_strict(message_bytes, &Signature::from_bytes(&sig_bytes))
+                .is_ok());
+        }
+    }
+
+    // -- Fees --------------------------------------------------------------
+
+    #[test]
+    fn base_fee_counts_required_signatures() {
+        assert_eq!(base_fee(1), 5_000);
+        assert_eq!(base_fee(2), 10_000);
+
+        let single = build_sol_transfer(&[1u8; 32], &[2u8; 32], 1_000, &[0u8; 32]).unwrap();
+        assert_eq!(base_fee(single.num_required_signatures), 5_000);
+
+        let sponsored = build_sol_transfer_with_payer(
+            &[0x11u8; 32],
+            &[0x22u8; 32],
+            1_000,
+            &[0x33u8; 32],
+            &[0u8; 32],
+        )
+        .unwrap();
+        assert_eq!(base_fee(sponsored.num_required_signatures), 10_000);
+    }
+
+    #[test]
+    fn total_fee_adds_rounded_up_priority_fee() {
+        // No compute-unit price: just the base fee
+        assert_eq!(total_fee(1, 0, 200_000), 5_000);
+        // 200k CU at 50_000 micro-lamports/CU = 10_000 lamports
+        assert_eq!(total_fee(1, 50_000, 200_000), 15_000);
+        assert_eq!(total_fee(2, 50_000, 200_000), 20_000);
+        // 1 micro-lamport over a whole lamport rounds up
+        assert_eq!(total_fee(1, 1, 1_000_001), 5_002);
+        assert_eq!(total_fee(1, u64::MAX, u32::MAX), u64::MAX);
+    }
+
+    // -- Size limit ---------------------------------------------------------
+
+    #[test]
+    fn validate_size_accepts_up_to_packet_limit() {
+        assert!(validate_size(&[0u8; PACKET_DATA_SIZE]).is_ok());
+        assert!(validate_size(&[0u8; PACKET_DATA_SIZE + 1]).is_err());
+    }
+
+    #[test]
+    fn plain_transfer_fits_packet() {
+        let private_key = [0x01u8; 32];
+        let from = ed25519_dalek::SigningKey::from_bytes(&private_key)
+            .verifying_key()
+            .to_bytes();
+        let tx = build_sol_transfer(&from, &[0x22; 32], 1_000, &[0u8; 32]).unwrap();
+
+        let wire = sign_transaction(&tx, &private_key).unwrap();
+        assert!(validate_size(&wire).is_ok());
+    }
+
+    #[test]
+    fn oversized_transaction_is_rejected() {
+        let private_key = [0x01u8; 32];
+        let from = ed25519_dalek::SigningKey::from_bytes(&private_key)
+            .verifying_key()
+            .to_bytes();
+        // Each transfer to a new recipient adds a 32-byte key plus 17 bytes of
+        // instruction, so 30 of them overflow the packet.
+        let instructions: Vec<SolInstruction> = (0..30u8)
+            .map(|i| build_system_transfer_instruction(&from, &[i; 32], 1_000))
+            .collect();
+        let tx = compile_transaction(&instructions, &from, &[0u8; 32]).unwrap();
+
+        let err = sign_transaction(&tx, &private_key).unwrap_err();
+        assert!(err.to_string().contains("packet limit"), "{err}");
+    }
+
+    #[test]
+    fn oversized_raw_transaction_is_rejected() {
+        let private_key = [0x01u8; 32];
+        let from = ed25519_dalek::SigningKey::from_bytes(&private_key)
+            .verifying_key()
+            .to_bytes();
+        let tx = build_sol_transfer(&from, &[0x22; 32], 1_000, &[0u8; 32]).unwrap();
+        let mut raw = sign_transaction(&tx, &private_key).unwrap();
+        // Trailing bytes past the message push the wire size over the limit.
+        raw.resize(PACKET_DATA_SIZE + 1, 0);
+
+        assert!(sign_sol_raw_transaction(&private_key, &raw).is_err());
+    }
+
+    // -- JSON persistence ---------------------------------------------------
+
+    #[test]
+    fn instruction_json_roundtrip_compiles_and_signs_identically() {
+        let private_key = [0x42u8; 32];
+        let signing_key = ed25519_dalek::SigningKey::from_bytes(&private_key);
+        let from_pubkey = signing_key.verifying_key().to_bytes();
+        let instruction = SolInstruction {
+            program_id: [0x05; 32],
+            accounts: vec![
+                SolAccountMeta {
+                    pubkey: from_pubkey,
+                    is_signer: true,
+                    is_writable: true,
+                },
+                SolAccountMeta {
+                    pubkey: [0x07; 32],
+                    is_signer: false,
+                    is_writable: false,
+                },
+            ],
+            data: vec![1, 2, 3, 0xff],
+        };
+
+        let restored = SolInstruction::from_json(&instruction.to_json().unwrap()).unwrap();
+        let compile = |ix: &SolInstruction| {
+            let tx =
+                compile_transaction(std::slice::from_ref(ix), &from_pubkey, &[0xCC; 32]).unwrap();
+            sign_transaction(&tx, &private_key).unwrap()
+        };
+        assert_eq!(compile(&restored), compile(&instruction));
+    }
+
+    #[test]
+    fn transaction_json_roundtrip_signs_identically() {
+        let private_key = [0x42u8; 32];
+        let signing_key = ed25519_dalek::SigningKey::from_bytes(&private_key);
+        let from_pubkey = signing_key.verifying_key().to_bytes();
+        let tx = build_sol_transfer(&from_pubkey, &[0xBBu8; 32], 1_000_000, &[0xCC; 32]).unwrap();
+
+        let json = tx.to_json().unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            value["accountKeys"][1],
+            crate::address::bytes_to_address(&[0xBB; 32])
+        );
+        assert_eq!(
+            value["compiledInstructions"][0]["data"],
+            "0200000040420f0000000000"
+        );
+
+        let restored = SolTransaction::from_json(&json).unwrap();
+        assert_eq!(
+            sign_transaction(&restored, &private_key).unwrap(),
+            sign_transaction(&tx, &private_key).unwrap()
+        );
+    }
+
+    #[test]
+    fn from_json_rejects_malformed_fields() {
+        let tx = build_sol_transfer(&[0x11; 32], &[0x22; 32], 1, &[0x33; 32]).unwrap();
+        let json = tx.to_json().unwrap();
+        let blockhash = crate::address::bytes_to_address(&[0x33; 32]);
+        let bad_key = json.replace(&blockhash, "0OIl");
+        let bad_index = json.replace(r#""programIdIndex":2"#, r#""programIdIndex":3"#);
+        assert_ne!(bad_index, json);
+        for bad in [bad_key.as_str(), bad_index.as_str(), "{}", "[]"] {
+            assert!(matches!(
+                SolTransaction::from_json(bad),
+                Err(SolError::SerializationError(_))
+            ));
+        }
+        assert!(SolInstruction::from_json(
+            r#"{"programId":"11111111111111111111111111111111",
+            "accounts":[],"data":"zz"}"#
+        )
+        .is_err());
+    }
+
+    // -- Decoding -----------------------------------------------------------
+
+    #[test]
+    fn deserialize_roundtrips_signed_transfer() {
+        let private_key = [0x01u8; 32];
+        let from = ed25519_dalek::SigningKey::from_bytes(&private_key)
+            .verifying_key()
+            .to_bytes();
+        let tx = build_sol_transfer(&from, &[0x22; 32], 1_000, &[0x0B; 32]).unwrap();
+        let wire = sign_transaction(&tx, &private_key).unwrap();
+
+        let decoded = deserialize_transaction(&wire).unwrap();
+        assert_eq!(decoded.account_keys, tx.account_keys);
+        assert_eq!(decoded.recent_blockhash, tx.recent_blockhash);
+        assert_eq!(
+            serialize_message(&decoded).unwrap(),
+            serialize_message(&tx).unwrap()
+        );
+    }
+
+    #[test]
+    fn deserialize_rejects_truncated_and_trailing_bytes() {
+        let private_key = [0x01u8; 32];
+        let from = ed25519_dalek::SigningKey::from_bytes(&private_key)
+            .verifying_key()
+            .to_bytes();
+        let tx = build_sol_transfer(&from, &[0x22; 32], 1_000, &[0x0B; 32]).unwrap();
+        let wire = sign_transaction(&tx, &private_key).unwrap();
+
+        for len in [0, 1, 65, 100, wire.len() - 1] {
+            assert!(deserialize_transaction(&wire[..len]).is_err(), "len {len}");
+        }
+        let mut padded = wire;
+        padded.push(0);
+        assert!(deserialize_transaction(&padded).is_err());
+    }
+
+    #[test]
+    fn transaction_id_is_first_signature() {
+        let private_key = [0x01u8; 32];
+        let from = ed25519_dalek::SigningKey::from_bytes(&private_key)
+            .verifying_key()
+            .to_bytes();
+        let tx = build_sol_transfer(&from, &[0x22; 32], 1_000, &[0x0B; 32]).unwrap();
+        let wire = sign_transaction(&tx, &private_key).unwrap();
+
+        assert_eq!(
+            transaction_id(&wire).unwrap(),
+            bs58::encode(&wire[1..65]).into_string()
+        );
+
+        let mut unsigned = wire.clone();
+        unsigned[1..65].fill(0);
+        assert!(transaction_id(&unsigned).is_err());
+        assert!(transaction_id(&wire[..wire.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn deserialize_rejects_versioned_message() {
+        let mut wire = vec![0x01];
+        wire.extend_from_slice(&[0u8; 64]);
+        wire.push(0x80); // v0 prefix
+        wire.extend_from_slice(&[0u8; 40]);
+        assert!(deserialize_transaction(&wire).is_err());
+    }
+
+    // -- v0 lookup-table resolution ----------------------------------------
+
+    /// A v0 message with static keys `[payer, program]`, one instruction
+    /// touching accounts 0, 2, 3 and 4, and the given table lookups.
+    fn v0_message(lookups: &[([u8; 32], Vec<u8>, Vec<u8>)]) -> Vec<u8> {
+        let mut msg = vec![0x80, 1, 0, 1];
+        msg.extend_from_slice(&encode_compact_u16(2));
+        msg.extend_from_slice(&[0x01; 32]);
+        msg.extend_from_slice(&[0x02; 32]);
+        msg.extend_from_slice(&[0x99; 32]);
+        msg.extend_from_slice(&encode_compact_u16(1));
+        msg.push(1);
+        msg.extend_from_slice(&encode_compact_u16(4));
+        msg.extend_from_slice(&[0, 2, 3, 4]);
+        msg.extend_from_slice(&encode_compact_u16(1));
+        msg.push(0x07);
+        msg.extend_from_slice(&encode_compact_u16(lookups.len() as u16));
+        for (key, writable, readonly) in lookups {
+            msg.extend_from_slice(key);
+            msg.extend_from_slice(&encode_compact_u16(writable.len() as u16));
+            msg.extend_from_slice(writable);
+            msg.extend_from_slice(&encode_compact_u16(readonly.len() as u16));
+            msg.extend_from_slice(readonly);
+        }
+        msg
+    }
+
+    #[test]
+    fn resolve_v0_accounts_orders_writable_before_readonly() {
+        let table_a = ([0xaa; 32], vec![[0xa0; 32], [0xa1; 32], [0xa2; 32]]);
+        let table_b = ([0xbb; 32], vec![[0xb0; 32], [0xb1; 32]]);
+        let msg = v0_message(&[(table_a.0, vec![2], vec![0]), (table_b.0, vec![1], vec![])]);
+
+        let keys = resolve_v0_accounts(&msg, &[table_b.clone(), table_a.clone()]).unwrap();
+        assert_eq!(
+            keys,
+            [[0x01; 32], [0x02; 32], [0xa2; 32], [0xb1; 32], [0xa0; 32]]
+        );
+    }
+
+    #[test]
+    fn resolve_v0_accounts_rejects_bad_lookups() {
+        let tables = [([0xaa; 32], vec![[0xa0; 32], [0xa1; 32], [0xa2; 32]])];
+        let table_key = tables[0].0;
+
+        // Table not supplied.
+        let msg = v0_message(&[(table_key, vec![0, 1], vec![2])]);
+        assert!(resolve_v0_accounts(&msg, &[]).is_err());
+        assert!(resolve_v0_accounts(&msg, &tables).is_ok());
+
+        // Lookup index past the end of the table.
+        let msg = v0_message(&[(table_key, vec![0, 1], vec![3])]);
+        assert!(resolve_v0_accounts(&msg, &tables).is_err());
+
+        // Too few lookups for the instruction's account index 4.
+        let msg = v0_message(&[(table_key, vec![0], vec![])]);
+        assert!(resolve_v0_accounts(&msg, &tables).is_err());
+
+        // Legacy message and trailing bytes.
+        let tx = build_sol_transfer(&[0x11u8; 32], &[0x22u8; 32], 42, &[0x99; 32]).unwrap();
+        assert!(resolve_v0_accounts(&serialize_message(&tx).unwrap(), &[]).is_err());
+        let mut trailing = v0_message(&[(table_key, vec![0, 1], vec![2])]);
+        trailing.push(0);
+        assert!(resolve_v0_accounts(&trailing, &tables).is_err());
+    }
+
     // -- Self-transfer (from == to) ----------------------------------------
 
     #[test]
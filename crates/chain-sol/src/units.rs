@@ -0,0 +1,148 @@
+//! Decimal formatting and parsing for SOL and SPL token amounts.
+//!
+//! On-chain amounts are integers in base units (lamports, or a token's
+//! smallest unit); users see decimals. Conversions go through strings so no
+//! precision is lost to floating point.
+
+use alloc::format;
+use alloc::string::{String, ToString};
+
+use crate::error::SolError;
+
+/// Decimal places of SOL: 1 SOL = 10^9 lamports.
+pub const SOL_DECIMALS: u8 = 9;
+
+/// Format lamports as a SOL amount, e.g. `1_500_000_000` -> `"1.5"`.
+pub fn format_lamports(lamports: u64) -> String {
+    format_token_amount(lamports, SOL_DECIMALS)
+}
+
+/// Parse a decimal SOL amount (e.g. `"0.25"`) into lamports.
+pub fn parse_sol(decimal: &str) -> Result<u64, SolError> {
+    parse_token_amount(decimal, SOL_DECIMALS)
+}
+
+/// Format a raw token amount with `decimals` decimal places.
+///
+/// Trailing fractional zeros are dropped, and so is the point for whole
+/// amounts: `(1_000_000, 6)` -> `"1"`, `(1_230_000, 6)` -> `"1.23"`.
+pub fn format_token_amount(raw: u64, decimals: u8) -> String {
+    let decimals = usize::from(decimals);
+    let digits = raw.to_string();
+    if decimals == 0 {
+        return digits;
+    }
+
+    // Left-pad so there is at least one integer digit.
+    let padded = format!("{digits:0>width$}", width = decimals + 1);
+    let (int_part, frac_part) = padded.split_at(padded.len() - decimals);
+    let frac_part = frac_part.trim_end_matches('0');
+
+    if frac_part.is_empty() {
+        int_part.to_string()
+    } else {
+        format!("{int_part}.{frac_part}")
+    }
+}
+
+/// Parse a decimal amount into raw token units with `decimals` places.
+///
+/// Accepts plain non-negative decimals like `"12"`, `"0.5"`, `".5"` or `"3."`.
+/// Rejects signs, exponents, separators, more fractional digits than
+/// `decimals` (rather than silently rounding), and values above `u64::MAX`.
+pub fn parse_token_amount(decimal: &str, decimals: u8) -> Result<u64, SolError> {
+    let decimal = decimal.trim();
+    let (int_part, frac_part) = decimal.split_once('.').unwrap_or((decimal, ""));
+
+    let is_digits = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+    if (int_part.is_empty() && frac_part.is_empty())
+        || !is_digits(int_part)
+        || !is_digits(frac_part)
+    {
+        return Err(SolError::InvalidAmount(format!(
+            "not a decimal amount: {decimal:?}"
+        )));
+    }
+    if frac_part.len() > usize::from(decimals) {
+        return Err(SolError::InvalidAmount(format!(
+            "{decimal} has more than {decimals} decimal places"
+        )));
+    }
+
+    let overflow = || SolError::InvalidAmount(format!("{decimal} is too large"));
+    let scale = 10u64
+        .checked_pow(u32::from(decimals))
+        .ok_or_else(overflow)?;
+    let frac_scale = 10u64.pow((usize::from(decimals) - frac_part.len()) as u32);
+
+    let int_value: u64 = if int_part.is_empty() {
+        0
+    } else {
+        int_part.parse().map_err(|_| overflow())?
+    };
+    let frac_value: u64 = if frac_part.is_empty() {
+        0
+    } else {
+        frac_part.parse().map_err(|_| overflow())?
+    };
+
+    int_value
+        .checked_mul(scale)
+        .and_then(|v| v.checked_add(frac_value * frac_scale))
+        .ok_or_else(overflow)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_whole_sol() {
+        assert_eq!(format_lamports(1_000_000_000), "1");
+        assert_eq!(format_lamports(0), "0");
+    }
+
+    #[test]
+    fn format_fractional_sol() {
+        assert_eq!(format_lamports(1_500_000_000), "1.5");
+        assert_eq!(format_lamports(1), "0.000000001");
+        assert_eq!(format_lamports(u64::MAX), "18446744073.709551615");
+    }
+
+    #[test]
+    fn format_token_amount_usdc() {
+        assert_eq!(format_token_amount(1_230_000, 6), "1.23");
+        assert_eq!(format_token_amount(42, 0), "42");
+    }
+
+    #[test]
+    fn parse_fractional_sol() {
+        assert_eq!(parse_sol("1").unwrap(), 1_000_000_000);
+        assert_eq!(parse_sol("0.25").unwrap(), 250_000_000);
+        assert_eq!(parse_sol(".5").unwrap(), 500_000_000);
+        assert_eq!(parse_sol(" 3. ").unwrap(), 3_000_000_000);
+        assert_eq!(parse_sol("0.000000001").unwrap(), 1);
+    }
+
+    #[test]
+    fn parse_rejects_over_precision() {
+        assert!(parse_sol("0.0000000001").is_err());
+        assert!(parse_token_amount("1.2345678", 6).is_err());
+    }
+
+    #[test]
+    fn parse_rejects_malformed_and_overflow() {
+        for bad in ["", ".", "-1", "1e9", "1,5", "1.2.3", "abc"] {
+            assert!(parse_sol(bad).is_err(), "accepted {bad:?}");
+        }
+        assert!(parse_sol("18446744074").is_err());
+        assert!(parse_token_amount("1", 20).is_err());
+    }
+
+    #[test]
+    fn parse_format_roundtrip() {
+        for raw in [0, 1, 999_999_999, 1_000_000_001, u64::MAX] {
+            assert_eq!(parse_sol(&format_lamports(raw)).unwrap(), raw);
+        }
+    }
+}
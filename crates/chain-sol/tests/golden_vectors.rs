@@ -0,0 +1,178 @@
+//! Byte-for-byte golden vectors for the Solana wire format.
+//!
+//! The expected bytes were produced independently with `solana-program` 2.3
+//! (the message types `solana-sdk` re-exports): the same instructions were
+//! compiled with `Message::new_with_blockhash`, serialized with
+//! `Message::serialize`, and each required signer signed the message with
+//! ed25519-dalek using the seeds below. A change to account ordering, header
+//! counts or compact-u16 lengths shows up here as a hex mismatch.
+
+use chain_sol::spl_token::{
+    build_create_associated_token_account, build_spl_transfer, build_spl_transfer_checked,
+    derive_associated_token_address,
+};
+use chain_sol::transaction::{
+    build_sol_transfer, compile_transaction, sign_sol_raw_transaction, sign_transaction,
+    SolAccountMeta, SolInstruction, SYSTEM_PROGRAM_ID,
+};
+
+/// Ed25519 seed of the sending wallet (DO NOT use on mainnet).
+const SENDER_SEED: [u8; 32] = [0x01; 32];
+/// Ed25519 seed of a separate fee payer.
+const PAYER_SEED: [u8; 32] = [0x02; 32];
+const RECIPIENT: [u8; 32] = [0x22; 32];
+const SECOND_RECIPIENT: [u8; 32] = [0x11; 32];
+const BLOCKHASH: [u8; 32] = [0x0B; 32];
+/// USDC mint: EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v
+const USDC_MINT: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+
+fn pubkey(seed: &[u8; 32]) -> [u8; 32] {
+    ed25519_dalek::SigningKey::from_bytes(seed)
+        .verifying_key()
+        .to_bytes()
+}
+
+fn system_transfer(from: &[u8; 32], to: &[u8; 32], lamports: u64) -> SolInstruction {
+    let mut data = 2u32.to_le_bytes().to_vec();
+    data.extend_from_slice(&lamports.to_le_bytes());
+    SolInstruction {
+        program_id: SYSTEM_PROGRAM_ID,
+        accounts: vec![
+            SolAccountMeta {
+                pubkey: *from,
+                is_signer: true,
+                is_writable: true,
+            },
+            SolAccountMeta {
+                pubkey: *to,
+                is_signer: false,
+                is_writable: true,
+            },
+        ],
+        data,
+    }
+}
+
+fn usdc_mint() -> [u8; 32] {
+    bs58::decode(USDC_MINT)
+        .into_vec()
+        .unwrap()
+        .try_into()
+        .unwrap()
+}
+
+fn token_accounts() -> ([u8; 32], [u8; 32]) {
+    let mint = usdc_mint();
+    let sender = pubkey(&SENDER_SEED);
+    (
+        derive_associated_token_address(&sender, &mint).unwrap(),
+        derive_associated_token_address(&RECIPIENT, &mint).unwrap(),
+    )
+}
+
+#[test]
+fn associated_token_addresses_match_reference() {
+    let (source, destination) = token_accounts();
+    assert_eq!(
+        bs58::encode(source).into_string(),
+        "3wvJdyFnGvaMWpbq93NU91SggiVRveULUXL6iX5VZDGP"
+    );
+    assert_eq!(
+        bs58::encode(destination).into_string(),
+        "CzLs8LdpjofzBXcqpQZjkErYQ5XXKhkeUCgTErLmTreN"
+    );
+}
+
+#[test]
+fn sol_transfer_matches_reference() {
+    let tx = build_sol_transfer(&pubkey(&SENDER_SEED), &RECIPIENT, 1_000_000, &BLOCKHASH).unwrap();
+    let wire = sign_transaction(&tx, &SENDER_SEED).unwrap();
+
+    assert_eq!(
+        hex::encode(wire),
+        "016d9e0ac0da462e768e0b59bbcc0f80f18e6b838b19c19e6cc0ca1679ea8aac5555d4fc35c515ab\
+         90f8837cc3ff90aae708ebb55381042251bab44bb3c8ad570c010001038a88e3dd7409f195fd52db\
+         2d3cba5d72ca6709bf1d94121bf3748801b40f6f5c22222222222222222222222222222222222222\
+         22222222222222222222222222000000000000000000000000000000000000000000000000000000\
+         00000000000b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b010202\
+         00010c0200000040420f0000000000"
+    );
+}
+
+#[test]
+fn spl_transfer_matches_reference() {
+    let sender = pubkey(&SENDER_SEED);
+    let (source, destination) = token_accounts();
+    let ix = build_spl_transfer(&source, &destination, &sender, 2_500_000, 6).unwrap();
+    let tx = compile_transaction(&[ix], &sender, &BLOCKHASH).unwrap();
+    let wire = sign_transaction(&tx, &SENDER_SEED).unwrap();
+
+    assert_eq!(
+        hex::encode(wire),
+        "012359c72d9d5a6bd59df1a1f0e8c962628b66661d500b597e5bb7fc6e016f364e9361e1e92c7349\
+         860077e85094c0da8cc87ec84f8e789a1d56560e31cbdeed06010001048a88e3dd7409f195fd52db\
+         2d3cba5d72ca6709bf1d94121bf3748801b40f6f5c2bc90238686902c261f364c614f1ecab4ff3e0\
+         5f581a1c08053fc753d34de76cb2211f75818841b82acd1a967a08f10ea838e253961862e49b0a90\
+         0c333d0ccb06ddf6e1d765a193d9cbe146ceeb79ac1cb485ed5f5b37913a8cf5857eff00a90b0b0b\
+         0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0103030102000903a02526\
+         0000000000"
+    );
+}
+
+#[test]
+fn multi_instruction_with_fee_payer_matches_reference() {
+    let sender = pubkey(&SENDER_SEED);
+    let payer = pubkey(&PAYER_SEED);
+    let (source, destination) = token_accounts();
+    let instructions = [
+        system_transfer(&sender, &RECIPIENT, 1_000),
+        system_transfer(&sender, &SECOND_RECIPIENT, 2_000),
+        build_spl_transfer(&source, &destination, &sender, 3_000, 6).unwrap(),
+    ];
+    let tx = compile_transaction(&instructions, &payer, &BLOCKHASH).unwrap();
+    let partial = sign_transaction(&tx, &SENDER_SEED).unwrap();
+    let wire = sign_sol_raw_transaction(&PAYER_SEED, &partial).unwrap();
+
+    assert_eq!(
+        hex::encode(wire),
+        "02af8e5db6a40046bcd97bb6bcaf321a229773252aba7d50a0e0815fce61a38229e72cc92a030ef0\
+         9d2c73f38f7cd83d1f43e998291164a2403936c6fd31fcfa026b0b789abd2d44d398c8fef7540bdf\
+         02c6866232c02de1681d80ce3507e2450149bc5f96c47411f0ce58c2ea0a891340327be4fce3fe59\
+         769fa58a4ca536d80b020002088139770ea87d175f56a35466c34c7ecccb8d8a91b4ee37a25df60f\
+         5b8fc9b3948a88e3dd7409f195fd52db2d3cba5d72ca6709bf1d94121bf3748801b40f6f5c111111\
+         11111111111111111111111111111111111111111111111111111111112222222222222222222222\
+         2222222222222222222222222222222222222222222bc90238686902c261f364c614f1ecab4ff3e0\
+         5f581a1c08053fc753d34de76cb2211f75818841b82acd1a967a08f10ea838e253961862e49b0a90\
+         0c333d0ccb000000000000000000000000000000000000000000000000000000000000000006ddf6\
+         e1d765a193d9cbe146ceeb79ac1cb485ed5f5b37913a8cf5857eff00a90b0b0b0b0b0b0b0b0b0b0b\
+         0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b03060201030c02000000e80300000000000006\
+         0201020c02000000d00700000000000007030405010903b80b000000000000"
+    );
+}
+
+#[test]
+fn create_ata_then_transfer_checked_matches_reference() {
+    let sender = pubkey(&SENDER_SEED);
+    let (source, destination) = token_accounts();
+    let mint = usdc_mint();
+    let instructions = [
+        build_create_associated_token_account(&sender, &RECIPIENT, &mint).unwrap(),
+        build_spl_transfer_checked(&source, &mint, &destination, &sender, 2_500_000, 6).unwrap(),
+    ];
+    let tx = compile_transaction(&instructions, &sender, &BLOCKHASH).unwrap();
+    let wire = sign_transaction(&tx, &SENDER_SEED).unwrap();
+
+    assert_eq!(
+        hex::encode(wire),
+        "010e54323c098e47826407326a99dc8c5e6cd04fdcd01e85ff4068a18a5175b4239b67026688ae5a\
+         93eacc60e53e50a9752f69ca135af377cfcfc4bdf8af982700010005088a88e3dd7409f195fd52db\
+         2d3cba5d72ca6709bf1d94121bf3748801b40f6f5c2bc90238686902c261f364c614f1ecab4ff3e0\
+         5f581a1c08053fc753d34de76cb2211f75818841b82acd1a967a08f10ea838e253961862e49b0a90\
+         0c333d0ccb000000000000000000000000000000000000000000000000000000000000000006ddf6\
+         e1d765a193d9cbe146ceeb79ac1cb485ed5f5b37913a8cf5857eff00a92222222222222222222222\
+         2222222222222222222222222222222222222222228c97258f4e2489f1bb3d1029148e0d830b5a13\
+         99daff1084048e7bd8dbe9f859c6fa7af3bedbad3a3d65f36aabc97431b1bbe4c2d2f6e0e47ca602\
+         03452f5d610b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b020606\
+         00020507030401010404010702000a0ca02526000000000006"
+    );
+}
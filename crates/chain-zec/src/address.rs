@@ -1,5 +1,4 @@
-use ripemd::Ripemd160;
-use sha2::{Digest, Sha256};
+use crypto_utils::base58check;
 
 use crate::error::ZecError;
 
@@ -40,89 +39,41 @@ pub fn pubkey_to_t_address(
         ));
     }
 
-    // Hash160: RIPEMD-160(SHA-256(pubkey))
-    let sha256_hash = Sha256::digest(pubkey_bytes);
-    let pubkey_hash = Ripemd160::digest(sha256_hash);
+    let pubkey_hash = hash160(pubkey_bytes);
 
-    // Build payload: version (2 bytes) + pubkey_hash (20 bytes)
-    let version = network.t_addr_version();
-    let mut payload = Vec::with_capacity(22);
-    payload.extend_from_slice(&version);
-    payload.extend_from_slice(&pubkey_hash);
-
-    // Base58Check: append 4-byte checksum from double SHA-256
-    let checksum = double_sha256_checksum(&payload);
-    payload.extend_from_slice(&checksum);
-
-    Ok(bs58::encode(&payload).into_string())
+    Ok(base58check::encode(&network.t_addr_version(), &pubkey_hash))
 }
 
 /// Compute Hash160 (RIPEMD-160(SHA-256(data))) — used for P2PKH script creation.
-pub fn hash160(data: &[u8]) -> [u8; 20] {
-    let sha = Sha256::digest(data);
-    let ripemd = Ripemd160::digest(sha);
-    ripemd.into()
-}
+pub use crypto_utils::hashes::hash160;
 
 /// Validate a Zcash transparent address string.
 ///
 /// Checks Base58Check encoding and version prefix for the given network.
 pub fn validate_address(address: &str, network: ZecNetwork) -> Result<bool, ZecError> {
-    let decoded = bs58::decode(address)
-        .into_vec()
-        .map_err(|e| ZecError::InvalidAddress(format!("invalid base58: {e}")))?;
-
-    // Must be exactly 26 bytes: 2 version + 20 hash + 4 checksum
-    if decoded.len() != 26 {
-        return Err(ZecError::InvalidAddress(format!(
-            "expected 26 bytes, got {}",
-            decoded.len()
-        )));
-    }
-
-    // Verify checksum
-    let payload = &decoded[..22];
-    let checksum = &decoded[22..26];
-    let expected = double_sha256_checksum(payload);
-    if checksum != expected {
-        return Err(ZecError::InvalidAddress("invalid checksum".into()));
-    }
-
-    // Check version prefix
-    let expected_version = network.t_addr_version();
-    Ok(decoded[0] == expected_version[0] && decoded[1] == expected_version[1])
+    let (version, _) = decode_t_address(address)?;
+    Ok(version == network.t_addr_version())
 }
 
 /// Extract the 20-byte pubkey hash from a t-address.
 pub fn address_to_pubkey_hash(address: &str) -> Result<[u8; 20], ZecError> {
-    let decoded = bs58::decode(address)
-        .into_vec()
-        .map_err(|e| ZecError::InvalidAddress(format!("invalid base58: {e}")))?;
-
-    if decoded.len() != 26 {
-        return Err(ZecError::InvalidAddress("invalid address length".into()));
-    }
-
-    // Verify checksum
-    let payload = &decoded[..22];
-    let checksum = &decoded[22..26];
-    let expected = double_sha256_checksum(payload);
-    if checksum != expected {
-        return Err(ZecError::InvalidAddress("invalid checksum".into()));
-    }
-
-    let mut hash = [0u8; 20];
-    hash.copy_from_slice(&decoded[2..22]);
+    let (_, hash) = decode_t_address(address)?;
     Ok(hash)
 }
 
-/// Double SHA-256 checksum (first 4 bytes).
-fn double_sha256_checksum(data: &[u8]) -> [u8; 4] {
-    let first = Sha256::digest(data);
-    let second = Sha256::digest(first);
-    let mut checksum = [0u8; 4];
-    checksum.copy_from_slice(&second[..4]);
-    checksum
+/// Decode a t-address into its 2-byte version and 20-byte pubkey hash.
+fn decode_t_address(address: &str) -> Result<([u8; 2], [u8; 20]), ZecError> {
+    let (version, payload) =
+        base58check::decode(address, 2).map_err(|e| ZecError::InvalidAddress(e.to_string()))?;
+
+    // 2 version + 20 hash (the 4-byte checksum is already stripped)
+    let hash = payload.try_into().map_err(|p: Vec<u8>| {
+        ZecError::InvalidAddress(format!("expected 26 bytes, got {}", p.len() + 6))
+    })?;
+    let version = version
+        .try_into()
+        .expect("decode splits off exactly 2 version bytes");
+    Ok((version, hash))
 }
 
 #[cfg(test)]
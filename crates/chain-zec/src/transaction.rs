@@ -3,6 +3,8 @@ use k256::ecdsa::{signature::hazmat::PrehashSigner, Signature, SigningKey};
 use crate::address::{self, ZecNetwork};
 use crate::error::ZecError;
 
+pub use crypto_utils::signer::InputSigner;
+
 /// Zcash v5 transaction constants (NU5 era).
 const TX_VERSION: u32 = 0x80000005; // fOverwintered | v5
 const VERSION_GROUP_ID: u32 = 0x26A7270A;
@@ -13,7 +15,7 @@ const CONSENSUS_BRANCH_ID_TESTNET: u32 = 0xC2D6D0B4; // NU5 (same)
 const SIGHASH_ALL: u8 = 0x01;
 
 /// Dust threshold for Zcash (in zatoshi).
-const DUST_THRESHOLD: u64 = 546;
+pub const DUST_THRESHOLD: u64 = 546;
 
 /// Transaction overhead estimate in bytes.
 const TX_OVERHEAD_BYTES: u64 = 46; // header(4) + vgid(4) + branch(4) + lock(4) + expiry(4) + counts(~6) + sapling(1) + orchard(1) + ~18 margin
@@ -21,6 +23,12 @@ const TX_OVERHEAD_BYTES: u64 = 46; // header(4) + vgid(4) + branch(4) + lock(4)
 const INPUT_BYTES: u64 = 148; // outpoint(36) + scriptSig(~107 for P2PKH) + sequence(4) + overhead
 /// Estimated bytes per transparent output.
 const OUTPUT_BYTES: u64 = 34;
+/// Upper bound on a low-S DER signature plus its sighash type byte.
+const MAX_SIG_WITH_HASHTYPE_BYTES: usize = 72;
+
+/// Blocks of headroom the builder requires before `expiry_height`, matching
+/// zcashd, which refuses transactions that expire within 3 blocks.
+pub const EXPIRING_SOON_THRESHOLD: u32 = 3;
 
 /// A UTXO to spend in a Zcash transaction.
 #[derive(Debug, Clone)]
@@ -30,6 +38,9 @@ pub struct ZecUtxo {
     pub amount_zatoshi: u64,
     /// The scriptPubKey of the UTXO (typically 25 bytes for P2PKH).
     pub script_pubkey: Vec<u8>,
+    /// Multisig redeem script of a P2SH UTXO, used to size its scriptSig in
+    /// the fee estimate. `None` for P2PKH.
+    pub redeem_script: Option<Vec<u8>>,
 }
 
 /// An unsigned Zcash v5 transparent transaction.
@@ -42,6 +53,9 @@ pub struct UnsignedZecTx {
     pub expiry_height: u32,
     pub inputs: Vec<TxInput>,
     pub outputs: Vec<TxOutput>,
+    /// Address the change output pays to, or `None` if the change was below
+    /// the dust threshold and no change output was added.
+    pub change_address: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -49,13 +63,33 @@ pub struct TxInput {
     /// Previous transaction hash (32 bytes, internal byte order).
     pub prev_txid: [u8; 32],
     pub prev_vout: u32,
-    /// Script code for signing (P2PKH scriptPubKey of the UTXO).
+    /// scriptPubKey of the UTXO being spent (P2PKH or P2SH).
     pub script_pubkey: Vec<u8>,
     /// Amount of the UTXO being spent (needed for sighash).
     pub amount: u64,
     pub sequence: u32,
 }
 
+/// How a single transparent input is authorized when signing.
+///
+/// Borrowed so that key material stays wherever the caller keeps (and
+/// zeroizes) it.
+#[derive(Debug, Clone, Copy)]
+pub enum InputAuth<'a> {
+    /// P2PKH: one key, scriptSig `<sig> <pubkey>`.
+    P2pkh { private_key: &'a [u8; 32] },
+    /// P2SH-wrapped `OP_m <pubkeys> OP_n OP_CHECKMULTISIG`, scriptSig
+    /// `OP_0 <sig>... <redeem_script>`.
+    ///
+    /// `private_keys` may be given in any order and must contain at least
+    /// `m` keys from the redeem script; signatures are emitted in the
+    /// script's pubkey order, as `OP_CHECKMULTISIG` requires.
+    P2shMultisig {
+        redeem_script: &'a [u8],
+        private_keys: &'a [[u8; 32]],
+    },
+}
+
 #[derive(Debug, Clone)]
 pub struct TxOutput {
     pub amount: u64,
@@ -70,6 +104,55 @@ pub fn estimate_fee(num_inputs: usize, num_outputs: usize, fee_rate_zat_byte: u6
     size * fee_rate_zat_byte
 }
 
+/// Estimate the fee for a transparent transaction spending `utxos`.
+///
+/// Like [`estimate_fee`], but sizes P2SH multisig inputs by the scriptSig
+/// their redeem script needs instead of as P2PKH.
+pub fn estimate_fee_for_utxos<'a>(
+    utxos: impl IntoIterator<Item = &'a ZecUtxo>,
+    num_outputs: usize,
+    fee_rate_zat_byte: u64,
+) -> Result<u64, ZecError> {
+    let inputs_size = utxos.into_iter().map(input_bytes).sum::<Result<u64, _>>()?;
+    let size = TX_OVERHEAD_BYTES + inputs_size + (num_outputs as u64 * OUTPUT_BYTES);
+    Ok(size * fee_rate_zat_byte)
+}
+
+/// Estimated bytes of an input spending `utxo`.
+///
+/// P2PKH inputs take [`INPUT_BYTES`]. A P2SH multisig input is sized by the
+/// scriptSig `OP_0 <sig>... <redeem_script>` that signing will produce, with
+/// each signature at its maximum length.
+fn input_bytes(utxo: &ZecUtxo) -> Result<u64, ZecError> {
+    let Some(redeem_script) = &utxo.redeem_script else {
+        if is_p2sh_script(&utxo.script_pubkey) {
+            return Err(ZecError::TransactionBuildError(format!(
+                "P2SH UTXO {}:{} needs its redeem script to estimate the fee",
+                utxo.txid, utxo.vout
+            )));
+        }
+        return Ok(INPUT_BYTES);
+    };
+    if utxo.script_pubkey != p2sh_script(&address::hash160(redeem_script)) {
+        return Err(ZecError::TransactionBuildError(format!(
+            "redeem script does not match scriptPubKey of UTXO {}:{}",
+            utxo.txid, utxo.vout
+        )));
+    }
+
+    let (m, _) = parse_multisig_redeem_script(redeem_script)?;
+    let mut script_sig = vec![0x00];
+    for _ in 0..m {
+        push_data(&mut script_sig, &[0; MAX_SIG_WITH_HASHTYPE_BYTES]);
+    }
+    push_data(&mut script_sig, redeem_script);
+
+    let mut script_len = Vec::new();
+    write_compact_size(&mut script_len, script_sig.len() as u64);
+    // outpoint(36) + scriptSig length + scriptSig + sequence(4)
+    Ok((36 + script_len.len() + script_sig.len() + 4) as u64)
+}
+
 /// Build a P2PKH scriptPubKey: OP_DUP OP_HASH160 <20-byte hash> OP_EQUALVERIFY OP_CHECKSIG
 fn p2pkh_script(pubkey_hash: &[u8; 20]) -> Vec<u8> {
     let mut script = Vec::with_capacity(25);
@@ -82,10 +165,129 @@ fn p2pkh_script(pubkey_hash: &[u8; 20]) -> Vec<u8> {
     script
 }
 
+/// Build a P2SH scriptPubKey: OP_HASH160 <20-byte script hash> OP_EQUAL
+fn p2sh_script(script_hash: &[u8; 20]) -> Vec<u8> {
+    let mut script = Vec::with_capacity(23);
+    script.push(0xA9); // OP_HASH160
+    script.push(0x14); // Push 20 bytes
+    script.extend_from_slice(script_hash);
+    script.push(0x87); // OP_EQUAL
+    script
+}
+
+/// Whether `script` has the P2SH scriptPubKey shape of [`p2sh_script`].
+fn is_p2sh_script(script: &[u8]) -> bool {
+    script.len() == 23 && script[..2] == [0xA9, 0x14] && script[22] == 0x87
+}
+
+/// Build an `m`-of-`n` multisig redeem script:
+/// `OP_m <pubkey_1> ... <pubkey_n> OP_n OP_CHECKMULTISIG`.
+///
+/// Public keys are used in the order given; all participants must agree on it
+/// since it determines the P2SH address.
+pub fn multisig_redeem_script(m: u8, pubkeys: &[[u8; 33]]) -> Result<Vec<u8>, ZecError> {
+    let n = pubkeys.len();
+    if m == 0 || n > 16 || usize::from(m) > n {
+        return Err(ZecError::TransactionBuildError(format!(
+            "invalid multisig threshold {m}-of-{n}"
+        )));
+    }
+
+    let mut script = Vec::with_capacity(3 + n * 34);
+    script.push(0x50 + m); // OP_m
+    for pubkey in pubkeys {
+        script.push(33);
+        script.extend_from_slice(pubkey);
+    }
+    script.push(0x50 + n as u8); // OP_n
+    script.push(0xAE); // OP_CHECKMULTISIG
+    Ok(script)
+}
+
+/// Parse a redeem script produced by [`multisig_redeem_script`] into its
+/// threshold and public keys.
+fn parse_multisig_redeem_script(script: &[u8]) -> Result<(usize, Vec<[u8; 33]>), ZecError> {
+    let invalid = || ZecError::SigningError("redeem script is not a standard multisig".into());
+
+    let (&op_m, rest) = script.split_first().ok_or_else(invalid)?;
+    let (&op_checkmultisig, rest) = rest.split_last().ok_or_else(invalid)?;
+    let (&op_n, mut keys) = rest.split_last().ok_or_else(invalid)?;
+    if !(0x51..=0x60).contains(&op_m) || !(0x51..=0x60).contains(&op_n) || op_checkmultisig != 0xAE
+    {
+        return Err(invalid());
+    }
+
+    let mut pubkeys = Vec::new();
+    while let Some((&len, rest)) = keys.split_first() {
+        if len != 33 || rest.len() < 33 {
+            return Err(invalid());
+        }
+        pubkeys.push(rest[..33].try_into().expect("length checked"));
+        keys = &rest[33..];
+    }
+
+    let m = usize::from(op_m - 0x50);
+    if pubkeys.len() != usize::from(op_n - 0x50) || m > pubkeys.len() {
+        return Err(invalid());
+    }
+    Ok((m, pubkeys))
+}
+
+/// Append a minimal data push of `data` to `script`.
+fn push_data(script: &mut Vec<u8>, data: &[u8]) {
+    match data.len() {
+        len @ 0..=0x4B => script.push(len as u8),
+        len @ 0x4C..=0xFF => script.extend_from_slice(&[0x4C, len as u8]), // OP_PUSHDATA1
+        len => {
+            script.push(0x4D); // OP_PUSHDATA2
+            script.extend_from_slice(&(len as u16).to_le_bytes());
+        }
+    }
+    script.extend_from_slice(data);
+}
+
+/// Check that a transaction with `expiry_height` can still be mined.
+///
+/// The next block is `current_height + 1`, and a transaction is only valid in
+/// blocks up to its expiry height. Errors if the expiry height has already
+/// been reached, or if it leaves `margin` blocks or fewer, since the
+/// transaction may then expire before it propagates. An expiry height of 0
+/// disables expiry in Zcash and is always accepted.
+pub fn validate_expiry(
+    expiry_height: u32,
+    current_height: u32,
+    margin: u32,
+) -> Result<(), ZecError> {
+    if expiry_height == 0 {
+        return Ok(());
+    }
+    if expiry_height <= current_height {
+        return Err(ZecError::TransactionBuildError(format!(
+            "expiry height {expiry_height} has passed (current height {current_height})"
+        )));
+    }
+    if expiry_height <= current_height.saturating_add(margin) {
+        return Err(ZecError::TransactionBuildError(format!(
+            "expiry height {expiry_height} is within {margin} blocks of current height \
+             {current_height}"
+        )));
+    }
+    Ok(())
+}
+
 /// Build an unsigned Zcash v5 transparent transaction.
 ///
 /// Uses a simple greedy UTXO selection (largest first). Adds a change output
-/// if change exceeds the dust threshold.
+/// if change exceeds `dust_threshold` (546 zatoshis when `None`). When
+/// `current_height` is given, `expiry_height` is checked with
+/// [`validate_expiry`] using [`EXPIRING_SOON_THRESHOLD`] as the margin.
+///
+/// `lock_time` is the transaction's nLockTime (0 for none): a block height
+/// below 500,000,000, otherwise a Unix timestamp. Inputs use sequence
+/// `0xFFFFFFFE`, so the lock time is enforced, which is what CLTV spends need.
+///
+/// A zero `amount_zat` is rejected unless `allow_zero_amount` is set.
+#[allow(clippy::too_many_arguments)]
 pub fn build_transparent_transaction(
     utxos: &[ZecUtxo],
     recipient: &str,
@@ -94,13 +296,26 @@ pub fn build_transparent_transaction(
     fee_rate_zat_byte: u64,
     network: ZecNetwork,
     expiry_height: u32,
+    dust_threshold: Option<u64>,
+    current_height: Option<u32>,
+    lock_time: u32,
+    allow_zero_amount: bool,
 ) -> Result<UnsignedZecTx, ZecError> {
+    if amount_zat == 0 && !allow_zero_amount {
+        return Err(ZecError::TransactionBuildError(
+            "amount must be greater than zero".into(),
+        ));
+    }
+    if let Some(current_height) = current_height {
+        validate_expiry(expiry_height, current_height, EXPIRING_SOON_THRESHOLD)?;
+    }
+
     let recipient_hash = address::address_to_pubkey_hash(recipient)?;
     let change_hash = address::address_to_pubkey_hash(change_address)?;
 
     // Sort UTXOs by amount (largest first) for greedy selection.
     let mut sorted: Vec<&ZecUtxo> = utxos.iter().collect();
-    sorted.sort_by(|a, b| b.amount_zatoshi.cmp(&a.amount_zatoshi));
+    sorted.sort_by_key(|u| std::cmp::Reverse(u.amount_zatoshi));
 
     // Select UTXOs
     let mut selected = Vec::new();
@@ -110,14 +325,14 @@ pub fn build_transparent_transaction(
         selected.push(*utxo);
         total_in += utxo.amount_zatoshi;
 
-        let fee = estimate_fee(selected.len(), 2, fee_rate_zat_byte);
+        let fee = estimate_fee_for_utxos(selected.iter().copied(), 2, fee_rate_zat_byte)?;
         if total_in >= amount_zat + fee {
             break;
         }
     }
 
-    let fee_2out = estimate_fee(selected.len(), 2, fee_rate_zat_byte);
-    let fee_1out = estimate_fee(selected.len(), 1, fee_rate_zat_byte);
+    let fee_2out = estimate_fee_for_utxos(selected.iter().copied(), 2, fee_rate_zat_byte)?;
+    let fee_1out = estimate_fee_for_utxos(selected.iter().copied(), 1, fee_rate_zat_byte)?;
 
     if total_in < amount_zat + fee_1out {
         return Err(ZecError::InsufficientFunds {
@@ -126,22 +341,12 @@ pub fn build_transparent_transaction(
         });
     }
 
-    // Build inputs
-    let mut inputs = Vec::with_capacity(selected.len());
-    for utxo in &selected {
-        let txid_bytes = parse_txid(&utxo.txid)?;
-        inputs.push(TxInput {
-            prev_txid: txid_bytes,
-            prev_vout: utxo.vout,
-            script_pubkey: utxo.script_pubkey.clone(),
-            amount: utxo.amount_zatoshi,
-            sequence: 0xFFFFFFFE, // Enable nLockTime but no RBF
-        });
-    }
+    let inputs = build_inputs(selected)?;
 
     // Build outputs
     let change_zat = total_in.saturating_sub(amount_zat + fee_2out);
-    let outputs = if change_zat > DUST_THRESHOLD {
+    let has_change = change_zat > dust_threshold.unwrap_or(DUST_THRESHOLD);
+    let outputs = if has_change {
         vec![
             TxOutput {
                 amount: amount_zat,
@@ -168,51 +373,234 @@ pub fn build_transparent_transaction(
         version: TX_VERSION,
         version_group_id: VERSION_GROUP_ID,
         consensus_branch_id: branch_id,
-        lock_time: 0,
+        lock_time,
         expiry_height,
         inputs,
         outputs,
+        change_address: has_change.then(|| change_address.to_string()),
     })
 }
 
-/// Sign an unsigned Zcash v5 transaction with the given private key.
+/// Build an unsigned transparent consolidation spending every UTXO in `utxos`.
 ///
-/// All transparent inputs are assumed to be controlled by the same key.
+/// All inputs go to a single P2PKH output paying `destination` (normally the
+/// wallet's own t-address) the total minus the fee for that shape; there is
+/// no selection and no change. `destination` is reported in
+/// [`UnsignedZecTx::change_address`] since the funds stay in the wallet.
+/// `destination` must be a P2PKH t-address for `network`; P2SH (t3) and
+/// other-network addresses are rejected. Fails if the output would be at or
+/// below [`DUST_THRESHOLD`].
+pub fn build_consolidation(
+    utxos: &[ZecUtxo],
+    destination: &str,
+    fee_rate_zat_byte: u64,
+    network: ZecNetwork,
+    expiry_height: u32,
+) -> Result<UnsignedZecTx, ZecError> {
+    if utxos.is_empty() {
+        return Err(ZecError::TransactionBuildError(
+            "no UTXOs to consolidate".into(),
+        ));
+    }
+    if !address::validate_address(destination, network)? {
+        return Err(ZecError::InvalidAddress(format!(
+            "destination {destination} is not a {network:?} P2PKH t-address"
+        )));
+    }
+    let destination_hash = address::address_to_pubkey_hash(destination)?;
+
+    let overflow = || ZecError::TransactionBuildError("amounts overflow u64".into());
+    let total_in = utxos
+        .iter()
+        .try_fold(0u64, |total, u| total.checked_add(u.amount_zatoshi))
+        .ok_or_else(overflow)?;
+    let fee = estimate_fee_for_utxos(utxos, 1, fee_rate_zat_byte)?;
+    let needed = fee.checked_add(DUST_THRESHOLD + 1).ok_or_else(overflow)?;
+    if total_in < needed {
+        return Err(ZecError::InsufficientFunds {
+            needed,
+            available: total_in,
+        });
+    }
+
+    let branch_id = match network {
+        ZecNetwork::Mainnet => CONSENSUS_BRANCH_ID_MAINNET,
+        ZecNetwork::Testnet => CONSENSUS_BRANCH_ID_TESTNET,
+    };
+
+    Ok(UnsignedZecTx {
+        version: TX_VERSION,
+        version_group_id: VERSION_GROUP_ID,
+        consensus_branch_id: branch_id,
+        lock_time: 0,
+        expiry_height,
+        inputs: build_inputs(utxos)?,
+        outputs: vec![TxOutput {
+            amount: total_in - fee,
+            script_pubkey: p2pkh_script(&destination_hash),
+        }],
+        change_address: Some(destination.to_string()),
+    })
+}
+
+/// Transparent inputs spending `utxos`, in order.
+fn build_inputs<'a>(
+    utxos: impl IntoIterator<Item = &'a ZecUtxo>,
+) -> Result<Vec<TxInput>, ZecError> {
+    utxos
+        .into_iter()
+        .map(|utxo| {
+            Ok(TxInput {
+                prev_txid: parse_txid(&utxo.txid)?,
+                prev_vout: utxo.vout,
+                script_pubkey: utxo.script_pubkey.clone(),
+                amount: utxo.amount_zatoshi,
+                sequence: 0xFFFFFFFE, // Enable nLockTime but no RBF
+            })
+        })
+        .collect()
+}
+
+/// Build an unsigned transparent transaction that returns change to the wallet itself.
+///
+/// Identical to [`build_transparent_transaction`], except that the change
+/// t-address is derived from `change_pubkey` — the compressed public key at the
+/// wallet's internal chain (`m/44'/133'/account'/1/index`) — instead of being
+/// supplied by the caller. The derived address is reported in
+/// [`UnsignedZecTx::change_address`].
+#[allow(clippy::too_many_arguments)]
+pub fn build_transparent_transaction_with_change_key(
+    utxos: &[ZecUtxo],
+    recipient: &str,
+    amount_zat: u64,
+    change_pubkey: &[u8; 33],
+    fee_rate_zat_byte: u64,
+    network: ZecNetwork,
+    expiry_height: u32,
+    current_height: Option<u32>,
+    lock_time: u32,
+) -> Result<UnsignedZecTx, ZecError> {
+    let change_address = address::pubkey_to_t_address(change_pubkey, network)?;
+    build_transparent_transaction(
+        utxos,
+        recipient,
+        amount_zat,
+        &change_address,
+        fee_rate_zat_byte,
+        network,
+        expiry_height,
+        None,
+        current_height,
+        lock_time,
+        false,
+    )
+}
+
+/// Sign an unsigned Zcash v5 transaction whose inputs are all P2PKH.
+///
+/// `signer` gives the key for each input: one key for all of them, or one
+/// per input in input order. Each input must be a P2PKH output paying to its
+/// key; an input whose scriptPubKey does not match is an error. Use
+/// [`sign_transaction_with_auth`] for anything else.
 /// Returns the serialized signed transaction bytes ready for broadcast.
 pub fn sign_transaction(
     unsigned_tx: &UnsignedZecTx,
-    private_key: &[u8; 32],
+    signer: &InputSigner,
 ) -> Result<Vec<u8>, ZecError> {
-    let signing_key = SigningKey::from_bytes(private_key.into())
-        .map_err(|e| ZecError::InvalidPrivateKey(format!("invalid secp256k1 key: {e}")))?;
-    let verifying_key = signing_key.verifying_key();
-    let pubkey_bytes: [u8; 33] = verifying_key
-        .to_sec1_bytes()
-        .as_ref()
-        .try_into()
-        .map_err(|_| ZecError::SigningError("invalid public key".into()))?;
+    let auth: Vec<InputAuth<'_>> = signer
+        .keys(unsigned_tx.inputs.len())
+        .map_err(|e| ZecError::SigningError(e.to_string()))?
+        .into_iter()
+        .map(|private_key| InputAuth::P2pkh { private_key })
+        .collect();
+    sign_transaction_with_auth(unsigned_tx, &auth)
+}
+
+/// Sign an unsigned Zcash v5 transaction, authorizing each input separately.
+///
+/// `auth[i]` describes how to spend `inputs[i]`. For P2SH inputs the redeem
+/// script must hash to the input's scriptPubKey. Unlike legacy and BIP-143
+/// sighashes, ZIP-244 has no script_code: txin_sig_digest (S.2g) commits to
+/// the spent output's scriptPubKey (the P2SH script), not the redeem script.
+pub fn sign_transaction_with_auth(
+    unsigned_tx: &UnsignedZecTx,
+    auth: &[InputAuth<'_>],
+) -> Result<Vec<u8>, ZecError> {
+    if auth.len() != unsigned_tx.inputs.len() {
+        return Err(ZecError::SigningError(format!(
+            "expected authorization for {} inputs, got {}",
+            unsigned_tx.inputs.len(),
+            auth.len()
+        )));
+    }
 
-    // Sign each input
     let mut script_sigs: Vec<Vec<u8>> = Vec::with_capacity(unsigned_tx.inputs.len());
 
-    for input_index in 0..unsigned_tx.inputs.len() {
+    for (input_index, input_auth) in auth.iter().enumerate() {
         let sighash = compute_sighash(unsigned_tx, input_index)?;
 
-        let sig: Signature = signing_key
-            .sign_prehash(&sighash)
-            .map_err(|e| ZecError::SigningError(format!("ECDSA signing failed: {e}")))?;
-
-        // DER-encode the signature + sighash type byte
-        let der_sig = sig.to_der();
-        let mut sig_with_hashtype = der_sig.as_bytes().to_vec();
-        sig_with_hashtype.push(SIGHASH_ALL);
-
-        // P2PKH scriptSig: <sig_len> <sig+hashtype> <pubkey_len> <pubkey>
-        let mut script_sig = Vec::new();
-        script_sig.push(sig_with_hashtype.len() as u8);
-        script_sig.extend_from_slice(&sig_with_hashtype);
-        script_sig.push(33); // compressed pubkey length
-        script_sig.extend_from_slice(&pubkey_bytes);
+        let script_sig = match *input_auth {
+            InputAuth::P2pkh { private_key } => {
+                let (signing_key, pubkey_bytes) = signing_key_and_pubkey(private_key)?;
+                // The sighash commits to this script, so a wrong one would
+                // produce a signature the network rejects.
+                let expected = p2pkh_script(&address::hash160(&pubkey_bytes));
+                if unsigned_tx.inputs[input_index].script_pubkey != expected {
+                    return Err(ZecError::SigningError(format!(
+                        "private key does not match scriptPubKey of input {input_index}"
+                    )));
+                }
+                let sig_with_hashtype = sign_sighash(&signing_key, &sighash)?;
+
+                // P2PKH scriptSig: <sig_len> <sig+hashtype> <pubkey_len> <pubkey>
+                let mut script_sig = Vec::new();
+                script_sig.push(sig_with_hashtype.len() as u8);
+                script_sig.extend_from_slice(&sig_with_hashtype);
+                script_sig.push(33); // compressed pubkey length
+                script_sig.extend_from_slice(&pubkey_bytes);
+                script_sig
+            }
+            InputAuth::P2shMultisig {
+                redeem_script,
+                private_keys,
+            } => {
+                let expected = p2sh_script(&address::hash160(redeem_script));
+                if unsigned_tx.inputs[input_index].script_pubkey != expected {
+                    return Err(ZecError::SigningError(format!(
+                        "redeem script does not match scriptPubKey of input {input_index}"
+                    )));
+                }
+                let (m, pubkeys) = parse_multisig_redeem_script(redeem_script)?;
+
+                // One signature per script position, so duplicate keys don't count twice.
+                let mut sigs: Vec<Option<Vec<u8>>> = vec![None; pubkeys.len()];
+                for private_key in private_keys {
+                    let (signing_key, pubkey_bytes) = signing_key_and_pubkey(private_key)?;
+                    let position = pubkeys
+                        .iter()
+                        .position(|pk| *pk == pubkey_bytes)
+                        .ok_or_else(|| {
+                            ZecError::SigningError("key is not part of the redeem script".into())
+                        })?;
+                    sigs[position] = Some(sign_sighash(&signing_key, &sighash)?);
+                }
+
+                let available = sigs.iter().flatten().count();
+                if available < m {
+                    return Err(ZecError::SigningError(format!(
+                        "multisig needs {m} signatures, got {available}"
+                    )));
+                }
+
+                // OP_0 works around the extra stack pop in OP_CHECKMULTISIG.
+                let mut script_sig = vec![0x00];
+                for sig in sigs.iter().flatten().take(m) {
+                    push_data(&mut script_sig, sig);
+                }
+                push_data(&mut script_sig, redeem_script);
+                script_sig
+            }
+        };
 
         script_sigs.push(script_sig);
     }
@@ -221,6 +609,29 @@ pub fn sign_transaction(
     serialize_v5_tx(unsigned_tx, &script_sigs)
 }
 
+fn signing_key_and_pubkey(private_key: &[u8; 32]) -> Result<(SigningKey, [u8; 33]), ZecError> {
+    let signing_key = SigningKey::from_bytes(private_key.into())
+        .map_err(|e| ZecError::InvalidPrivateKey(format!("invalid secp256k1 key: {e}")))?;
+    let pubkey_bytes: [u8; 33] = signing_key
+        .verifying_key()
+        .to_sec1_bytes()
+        .as_ref()
+        .try_into()
+        .map_err(|_| ZecError::SigningError("invalid public key".into()))?;
+    Ok((signing_key, pubkey_bytes))
+}
+
+/// ECDSA-sign a sighash and return the DER signature followed by the sighash type byte.
+fn sign_sighash(signing_key: &SigningKey, sighash: &[u8; 32]) -> Result<Vec<u8>, ZecError> {
+    let sig: Signature = signing_key
+        .sign_prehash(sighash)
+        .map_err(|e| ZecError::SigningError(format!("ECDSA signing failed: {e}")))?;
+
+    let mut sig_with_hashtype = sig.to_der().as_bytes().to_vec();
+    sig_with_hashtype.push(SIGHASH_ALL);
+    Ok(sig_with_hashtype)
+}
+
 /// Compute the ZIP-244 signature digest for a specific transparent input.
 fn compute_sighash(tx: &UnsignedZecTx, input_index: usize) -> Result<[u8; 32], ZecError> {
     let header_digest = compute_header_digest(tx);
@@ -276,17 +687,10 @@ fn compute_transparent_sig_digest(
         for inp in &tx.inputs {
             data.extend_from_slice(&(inp.amount as i64).to_le_bytes());
         }
-        blake2b_256(b"ZTxIdAmountsHash", &data)
+        blake2b_256(b"ZTxTrAmountsHash", &data)
     };
 
-    let scriptpubkeys_digest = {
-        let mut data = Vec::new();
-        for inp in &tx.inputs {
-            write_compact_size(&mut data, inp.script_pubkey.len() as u64);
-            data.extend_from_slice(&inp.script_pubkey);
-        }
-        blake2b_256(b"ZTxIdScriptsHash", &data)
-    };
+    let scriptpubkeys_digest = compute_scriptpubkeys_digest(tx);
 
     let sequence_digest = {
         let mut data = Vec::new();
@@ -332,11 +736,27 @@ fn compute_transparent_sig_digest(
     Ok(blake2b_256(b"ZTxIdTranspaHash", &combined))
 }
 
+/// ZIP-244 digest of the scriptPubKeys of every spent output (S.2c).
+fn compute_scriptpubkeys_digest(tx: &UnsignedZecTx) -> [u8; 32] {
+    let mut data = Vec::new();
+    for inp in &tx.inputs {
+        write_compact_size(&mut data, inp.script_pubkey.len() as u64);
+        data.extend_from_slice(&inp.script_pubkey);
+    }
+    blake2b_256(b"ZTxTrScriptsHash", &data)
+}
+
 /// Serialize a signed Zcash v5 transaction (transparent only).
 fn serialize_v5_tx(
     tx: &UnsignedZecTx,
     script_sigs: &[Vec<u8>],
 ) -> Result<Vec<u8>, ZecError> {
+    if script_sigs.len() != tx.inputs.len() {
+        return Err(ZecError::SigningError(
+            "script_sigs/inputs length mismatch".into(),
+        ));
+    }
+
     let mut buf = Vec::with_capacity(512);
 
     // Header fields
@@ -430,14 +850,14 @@ mod tests {
     use super::*;
 
     fn make_test_utxo(txid: &str, vout: u32, amount: u64) -> ZecUtxo {
-        // P2PKH scriptPubKey for a known pubkey hash
-        let pubkey_hash = [0xAB; 20];
-        let script = p2pkh_script(&pubkey_hash);
+        // P2PKH scriptPubKey of private key 1, which the signing tests use
+        let script = p2pkh_script(&address::hash160(&pubkey_of(&privkey(1))));
         ZecUtxo {
             txid: txid.to_string(),
             vout,
             amount_zatoshi: amount,
             script_pubkey: script,
+            redeem_script: None,
         }
     }
 
@@ -494,6 +914,10 @@ mod tests {
             1,
             ZecNetwork::Mainnet,
             1_000_000,
+            None,
+            None,
+            0,
+            false,
         );
 
         assert!(result.is_ok());
@@ -505,6 +929,29 @@ mod tests {
         assert_eq!(tx.consensus_branch_id, CONSENSUS_BRANCH_ID_MAINNET);
     }
 
+    #[test]
+    fn build_transaction_rejects_zero_amount() {
+        let utxos = vec![make_test_utxo(&"a".repeat(64), 0, 10_000_000)];
+        let pubkey_hex = "0279BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798";
+        let pubkey: [u8; 33] = hex::decode(pubkey_hex).unwrap().try_into().unwrap();
+        let addr = address::pubkey_to_t_address(&pubkey, ZecNetwork::Mainnet).unwrap();
+
+        let result = build_transparent_transaction(
+            &utxos,
+            &addr,
+            0,
+            &addr,
+            1,
+            ZecNetwork::Mainnet,
+            1_000_000,
+            None,
+            None,
+            0,
+            false,
+        );
+        assert!(matches!(result, Err(ZecError::TransactionBuildError(_))));
+    }
+
     #[test]
     fn build_transaction_dust_change_omitted() {
         let txid = "b".repeat(64);
@@ -523,6 +970,10 @@ mod tests {
             1,
             ZecNetwork::Mainnet,
             1_000_000,
+            None,
+            None,
+            0,
+            false,
         );
 
         assert!(result.is_ok());
@@ -530,6 +981,52 @@ mod tests {
         assert_eq!(tx.outputs.len(), 1); // no change output
     }
 
+    fn build_with_dust_threshold(amount_zat: u64, dust_threshold: Option<u64>) -> UnsignedZecTx {
+        let utxos = vec![make_test_utxo(&"b".repeat(64), 0, 1_000_000)];
+        let pubkey_hex = "0279BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798";
+        let pubkey: [u8; 33] = hex::decode(pubkey_hex).unwrap().try_into().unwrap();
+        let addr = address::pubkey_to_t_address(&pubkey, ZecNetwork::Mainnet).unwrap();
+
+        build_transparent_transaction(
+            &utxos,
+            &addr,
+            amount_zat,
+            &addr,
+            1,
+            ZecNetwork::Mainnet,
+            1_000_000,
+            dust_threshold,
+            None,
+            0,
+            false,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn build_transaction_lower_dust_threshold_keeps_change() {
+        // 1_000_000 in, 262 zat fee for two outputs: 200 zat of change.
+        let amount = 1_000_000 - estimate_fee(1, 2, 1) - 200;
+
+        assert_eq!(build_with_dust_threshold(amount, None).outputs.len(), 1);
+
+        let tx = build_with_dust_threshold(amount, Some(100));
+        assert_eq!(tx.outputs.len(), 2);
+        assert_eq!(tx.outputs[1].amount, 200);
+        assert!(tx.change_address.is_some());
+    }
+
+    #[test]
+    fn build_transaction_higher_dust_threshold_drops_change() {
+        let amount = 1_000_000 - estimate_fee(1, 2, 1) - 600;
+
+        assert_eq!(build_with_dust_threshold(amount, None).outputs.len(), 2);
+
+        let tx = build_with_dust_threshold(amount, Some(1_000));
+        assert_eq!(tx.outputs.len(), 1);
+        assert!(tx.change_address.is_none());
+    }
+
     #[test]
     fn build_transaction_insufficient_funds() {
         let txid = "c".repeat(64);
@@ -547,6 +1044,10 @@ mod tests {
             1,
             ZecNetwork::Mainnet,
             1_000_000,
+            None,
+            None,
+            0,
+            false,
         );
 
         assert!(result.is_err());
@@ -556,6 +1057,223 @@ mod tests {
         }
     }
 
+    #[test]
+    fn build_transaction_reports_explicit_change_address() {
+        let txid = "a".repeat(64);
+        let utxos = vec![make_test_utxo(&txid, 0, 10_000_000)];
+
+        let pubkey_hex = "0279BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798";
+        let pubkey: [u8; 33] = hex::decode(pubkey_hex).unwrap().try_into().unwrap();
+        let addr = address::pubkey_to_t_address(&pubkey, ZecNetwork::Mainnet).unwrap();
+
+        let tx = build_transparent_transaction(
+            &utxos,
+            &addr,
+            5_000_000,
+            &addr,
+            1,
+            ZecNetwork::Mainnet,
+            1_000_000,
+            None,
+            None,
+            0,
+            false,
+        )
+        .unwrap();
+
+        assert_eq!(tx.change_address.as_deref(), Some(addr.as_str()));
+    }
+
+    #[test]
+    fn build_with_change_key_pays_change_to_derived_script() {
+        let txid = "a".repeat(64);
+        let utxos = vec![make_test_utxo(&txid, 0, 10_000_000)];
+
+        let recipient_hex = "0279BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798";
+        let recipient_pk: [u8; 33] = hex::decode(recipient_hex).unwrap().try_into().unwrap();
+        let recipient = address::pubkey_to_t_address(&recipient_pk, ZecNetwork::Mainnet).unwrap();
+
+        // Compressed pubkey for private key = 2.
+        let change_hex = "02C6047F9441ED7D6D3045406E95C07CD85C778E4B8CEF3CA7ABAC09B95C709EE5";
+        let change_pk: [u8; 33] = hex::decode(change_hex).unwrap().try_into().unwrap();
+
+        let tx = build_transparent_transaction_with_change_key(
+            &utxos,
+            &recipient,
+            5_000_000,
+            &change_pk,
+            1,
+            ZecNetwork::Mainnet,
+            1_000_000,
+            None,
+            0,
+        )
+        .unwrap();
+
+        assert_eq!(tx.outputs.len(), 2);
+        assert_eq!(
+            tx.outputs[1].script_pubkey,
+            p2pkh_script(&address::hash160(&change_pk))
+        );
+        let expected = address::pubkey_to_t_address(&change_pk, ZecNetwork::Mainnet).unwrap();
+        assert_eq!(tx.change_address, Some(expected));
+    }
+
+    #[test]
+    fn build_with_change_key_invalid_pubkey_fails() {
+        let txid = "a".repeat(64);
+        let utxos = vec![make_test_utxo(&txid, 0, 10_000_000)];
+
+        let pubkey_hex = "0279BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798";
+        let pubkey: [u8; 33] = hex::decode(pubkey_hex).unwrap().try_into().unwrap();
+        let addr = address::pubkey_to_t_address(&pubkey, ZecNetwork::Mainnet).unwrap();
+
+        let mut bad = [0u8; 33];
+        bad[0] = 0x04;
+        let result = build_transparent_transaction_with_change_key(
+            &utxos,
+            &addr,
+            5_000_000,
+            &bad,
+            1,
+            ZecNetwork::Mainnet,
+            1_000_000,
+            None,
+            0,
+        );
+        assert!(result.is_err());
+    }
+
+    // ─── build_consolidation ────────────────────────────────────────
+
+    #[test]
+    fn build_consolidation_spends_everything_into_one_output() {
+        let utxos = vec![
+            make_test_utxo(&"a".repeat(64), 0, 1_000_000),
+            make_test_utxo(&"b".repeat(64), 3, 250_000),
+            make_test_utxo(&"c".repeat(64), 1, 40_000),
+        ];
+        let destination =
+            address::pubkey_to_t_address(&pubkey_of(&privkey(1)), ZecNetwork::Mainnet).unwrap();
+
+        let tx =
+            build_consolidation(&utxos, &destination, 2, ZecNetwork::Mainnet, 1_000_000).unwrap();
+
+        let outpoints: Vec<([u8; 32], u32)> = tx
+            .inputs
+            .iter()
+            .map(|i| (i.prev_txid, i.prev_vout))
+            .collect();
+        let expected: Vec<([u8; 32], u32)> = utxos
+            .iter()
+            .map(|u| (parse_txid(&u.txid).unwrap(), u.vout))
+            .collect();
+        assert_eq!(outpoints, expected);
+
+        assert_eq!(tx.outputs.len(), 1);
+        assert_eq!(tx.outputs[0].amount, 1_290_000 - estimate_fee(3, 1, 2));
+        assert_eq!(tx.outputs[0].script_pubkey, utxos[0].script_pubkey);
+        assert_eq!(tx.change_address, Some(destination));
+        assert!(sign_transaction(&tx, &InputSigner::SingleKey(privkey(1))).is_ok());
+    }
+
+    #[test]
+    fn build_consolidation_rejects_empty_and_dust_results() {
+        let destination =
+            address::pubkey_to_t_address(&pubkey_of(&privkey(1)), ZecNetwork::Mainnet).unwrap();
+        assert!(build_consolidation(&[], &destination, 1, ZecNetwork::Mainnet, 1_000_000).is_err());
+
+        let tiny = [make_test_utxo(&"a".repeat(64), 0, 600)];
+        let err = build_consolidation(&tiny, &destination, 1, ZecNetwork::Mainnet, 1_000_000)
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            ZecError::InsufficientFunds { available: 600, .. }
+        ));
+    }
+
+    #[test]
+    fn build_consolidation_rejects_overflowing_amounts() {
+        let destination =
+            address::pubkey_to_t_address(&pubkey_of(&privkey(1)), ZecNetwork::Mainnet).unwrap();
+        let huge = [
+            make_test_utxo(&"a".repeat(64), 0, u64::MAX),
+            make_test_utxo(&"b".repeat(64), 0, 1),
+        ];
+        let err = build_consolidation(&huge, &destination, 1, ZecNetwork::Mainnet, 1_000_000)
+            .unwrap_err();
+        assert!(err.to_string().contains("overflow"), "{err}");
+    }
+
+    #[test]
+    fn build_consolidation_rejects_wrong_network_destination() {
+        let destination =
+            address::pubkey_to_t_address(&pubkey_of(&privkey(1)), ZecNetwork::Testnet).unwrap();
+        let utxos = [make_test_utxo(&"a".repeat(64), 0, 1_000_000)];
+        let err = build_consolidation(&utxos, &destination, 1, ZecNetwork::Mainnet, 1_000_000)
+            .unwrap_err();
+        assert!(matches!(err, ZecError::InvalidAddress(_)));
+    }
+
+    #[test]
+    fn build_consolidation_rejects_t3_destination() {
+        // Mainnet P2SH version prefix.
+        let destination = crypto_utils::base58check::encode(&[0x1C, 0xBD], &[0x42; 20]);
+        assert!(destination.starts_with("t3"));
+        let utxos = [make_test_utxo(&"a".repeat(64), 0, 1_000_000)];
+        let err = build_consolidation(&utxos, &destination, 1, ZecNetwork::Mainnet, 1_000_000)
+            .unwrap_err();
+        assert!(matches!(err, ZecError::InvalidAddress(_)));
+    }
+
+    #[test]
+    fn validate_expiry_rejects_expired_height() {
+        assert!(validate_expiry(1_000_000, 1_000_000, 3).is_err());
+        assert!(validate_expiry(999_999, 1_000_000, 0).is_err());
+    }
+
+    #[test]
+    fn validate_expiry_rejects_height_within_margin() {
+        let err = validate_expiry(1_000_003, 1_000_000, 3).unwrap_err();
+        assert!(err.to_string().contains("within 3 blocks"), "{err}");
+    }
+
+    #[test]
+    fn validate_expiry_accepts_comfortable_height() {
+        assert!(validate_expiry(1_000_040, 1_000_000, 3).is_ok());
+        assert!(validate_expiry(1_000_004, 1_000_000, 3).is_ok());
+        // 0 means the transaction never expires.
+        assert!(validate_expiry(0, 1_000_000, 3).is_ok());
+    }
+
+    #[test]
+    fn build_checks_expiry_only_when_height_given() {
+        let utxos = vec![make_test_utxo(&"a".repeat(64), 0, 10_000_000)];
+        let pubkey_hex = "0279BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798";
+        let pubkey: [u8; 33] = hex::decode(pubkey_hex).unwrap().try_into().unwrap();
+        let addr = address::pubkey_to_t_address(&pubkey, ZecNetwork::Mainnet).unwrap();
+        let build = |current_height| {
+            build_transparent_transaction(
+                &utxos,
+                &addr,
+                5_000_000,
+                &addr,
+                1,
+                ZecNetwork::Mainnet,
+                1_000_000,
+                None,
+                current_height,
+                0,
+                false,
+            )
+        };
+
+        assert!(build(None).is_ok());
+        assert!(build(Some(999_960)).is_ok());
+        assert!(build(Some(999_998)).is_err());
+        assert!(build(Some(1_000_000)).is_err());
+    }
+
     #[test]
     fn sign_transaction_produces_valid_bytes() {
         let txid = "a".repeat(64);
@@ -573,6 +1291,10 @@ mod tests {
             1,
             ZecNetwork::Mainnet,
             1_000_000,
+            None,
+            None,
+            0,
+            false,
         )
         .unwrap();
 
@@ -580,7 +1302,7 @@ mod tests {
         let mut privkey = [0u8; 32];
         privkey[31] = 1;
 
-        let signed = sign_transaction(&unsigned, &privkey).unwrap();
+        let signed = sign_transaction(&unsigned, &InputSigner::SingleKey(privkey)).unwrap();
         assert!(!signed.is_empty());
         assert!(signed.len() > 100);
 
@@ -599,15 +1321,25 @@ mod tests {
         let addr = address::pubkey_to_t_address(&pubkey, ZecNetwork::Mainnet).unwrap();
 
         let unsigned = build_transparent_transaction(
-            &utxos, &addr, 2_000_000, &addr, 1, ZecNetwork::Mainnet, 1_000_000,
+            &utxos,
+            &addr,
+            2_000_000,
+            &addr,
+            1,
+            ZecNetwork::Mainnet,
+            1_000_000,
+            None,
+            None,
+            0,
+            false,
         )
         .unwrap();
 
         let mut privkey = [0u8; 32];
         privkey[31] = 1;
 
-        let signed1 = sign_transaction(&unsigned, &privkey).unwrap();
-        let signed2 = sign_transaction(&unsigned, &privkey).unwrap();
+        let signed1 = sign_transaction(&unsigned, &InputSigner::SingleKey(privkey)).unwrap();
+        let signed2 = sign_transaction(&unsigned, &InputSigner::SingleKey(privkey)).unwrap();
         assert_eq!(signed1, signed2);
     }
 
@@ -621,12 +1353,174 @@ mod tests {
         let addr = address::pubkey_to_t_address(&pubkey, ZecNetwork::Mainnet).unwrap();
 
         let unsigned = build_transparent_transaction(
-            &utxos, &addr, 2_000_000, &addr, 1, ZecNetwork::Mainnet, 1_000_000,
+            &utxos,
+            &addr,
+            2_000_000,
+            &addr,
+            1,
+            ZecNetwork::Mainnet,
+            1_000_000,
+            None,
+            None,
+            0,
+            false,
         )
         .unwrap();
 
         let bad_key = [0u8; 32]; // zero is not a valid secp256k1 key
-        assert!(sign_transaction(&unsigned, &bad_key).is_err());
+        assert!(sign_transaction(&unsigned, &InputSigner::SingleKey(bad_key)).is_err());
+    }
+
+    #[test]
+    fn sign_transaction_rejects_foreign_script_pubkey() {
+        let mut utxos = vec![make_test_utxo(&"f".repeat(64), 0, 5_000_000)];
+        // Someone else's P2PKH output
+        utxos[0].script_pubkey = p2pkh_script(&[0xAB; 20]);
+        let addr =
+            address::pubkey_to_t_address(&pubkey_of(&privkey(1)), ZecNetwork::Mainnet).unwrap();
+        let unsigned = build_transparent_transaction(
+            &utxos,
+            &addr,
+            2_000_000,
+            &addr,
+            1,
+            ZecNetwork::Mainnet,
+            1_000_000,
+            None,
+            None,
+            0,
+            false,
+        )
+        .unwrap();
+
+        let err = sign_transaction(&unsigned, &InputSigner::SingleKey(privkey(1))).unwrap_err();
+        assert!(err.to_string().contains("scriptPubKey of input 0"), "{err}");
+    }
+
+    #[test]
+    fn sign_transaction_rejects_key_not_owning_input() {
+        let utxos = vec![make_test_utxo(&"f".repeat(64), 0, 5_000_000)];
+        let addr =
+            address::pubkey_to_t_address(&pubkey_of(&privkey(1)), ZecNetwork::Mainnet).unwrap();
+        let unsigned = build_transparent_transaction(
+            &utxos,
+            &addr,
+            2_000_000,
+            &addr,
+            1,
+            ZecNetwork::Mainnet,
+            1_000_000,
+            None,
+            None,
+            0,
+            false,
+        )
+        .unwrap();
+
+        assert!(sign_transaction(&unsigned, &InputSigner::SingleKey(privkey(1))).is_ok());
+        assert!(sign_transaction(&unsigned, &InputSigner::SingleKey(privkey(2))).is_err());
+    }
+
+    #[test]
+    fn sign_transaction_key_per_input() {
+        let owned_by = |txid: &str, amount, key: u8| ZecUtxo {
+            script_pubkey: p2pkh_script(&address::hash160(&pubkey_of(&privkey(key)))),
+            ..make_test_utxo(txid, 0, amount)
+        };
+        // Largest first, so inputs are [key 1's UTXO, key 2's UTXO].
+        let utxos = vec![
+            owned_by(&"a".repeat(64), 3_000_000, 1),
+            owned_by(&"b".repeat(64), 2_000_000, 2),
+        ];
+        let addr =
+            address::pubkey_to_t_address(&pubkey_of(&privkey(3)), ZecNetwork::Mainnet).unwrap();
+        let unsigned = build_transparent_transaction(
+            &utxos,
+            &addr,
+            4_000_000,
+            &addr,
+            1,
+            ZecNetwork::Mainnet,
+            1_000_000,
+            None,
+            None,
+            0,
+            false,
+        )
+        .unwrap();
+        assert_eq!(unsigned.inputs.len(), 2);
+
+        let signer = InputSigner::KeyPerInput(vec![privkey(1), privkey(2)]);
+        let signed = sign_transaction(&unsigned, &signer).unwrap();
+        let auth = [
+            InputAuth::P2pkh {
+                private_key: &privkey(1),
+            },
+            InputAuth::P2pkh {
+                private_key: &privkey(2),
+            },
+        ];
+        assert_eq!(
+            signed,
+            sign_transaction_with_auth(&unsigned, &auth).unwrap()
+        );
+
+        // Keys in the wrong order don't match the inputs' scripts.
+        let swapped = InputSigner::KeyPerInput(vec![privkey(2), privkey(1)]);
+        assert!(sign_transaction(&unsigned, &swapped).is_err());
+        // Neither key alone owns both inputs.
+        assert!(sign_transaction(&unsigned, &InputSigner::SingleKey(privkey(1))).is_err());
+    }
+
+    #[test]
+    fn sign_transaction_key_per_input_length_mismatch() {
+        let utxos = vec![make_test_utxo(&"f".repeat(64), 0, 5_000_000)];
+        let addr =
+            address::pubkey_to_t_address(&pubkey_of(&privkey(1)), ZecNetwork::Mainnet).unwrap();
+        let unsigned = build_transparent_transaction(
+            &utxos,
+            &addr,
+            2_000_000,
+            &addr,
+            1,
+            ZecNetwork::Mainnet,
+            1_000_000,
+            None,
+            None,
+            0,
+            false,
+        )
+        .unwrap();
+
+        let signer = InputSigner::KeyPerInput(vec![privkey(1), privkey(1)]);
+        let err = sign_transaction(&unsigned, &signer).unwrap_err();
+        assert!(err.to_string().contains("one key per input"), "{err}");
+    }
+
+    #[test]
+    fn serialize_script_sigs_mismatch_is_an_error() {
+        let utxos = vec![make_test_utxo(&"e".repeat(64), 0, 5_000_000)];
+        let pubkey_hex = "0279BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798";
+        let pubkey: [u8; 33] = hex::decode(pubkey_hex).unwrap().try_into().unwrap();
+        let addr = address::pubkey_to_t_address(&pubkey, ZecNetwork::Mainnet).unwrap();
+        let unsigned = build_transparent_transaction(
+            &utxos,
+            &addr,
+            2_000_000,
+            &addr,
+            1,
+            ZecNetwork::Mainnet,
+            1_000_000,
+            None,
+            None,
+            0,
+            false,
+        )
+        .unwrap();
+
+        let err = serialize_v5_tx(&unsigned, &[]).unwrap_err();
+        assert!(err.to_string().contains("length mismatch"));
+        assert!(serialize_v5_tx(&unsigned, &[vec![], vec![]]).is_err());
     }
 
     #[test]
@@ -687,9 +1581,319 @@ mod tests {
             expiry_height: 1_000_000,
             inputs: vec![],
             outputs: vec![],
+            change_address: None,
         };
         let d1 = compute_header_digest(&tx);
         let d2 = compute_header_digest(&tx);
         assert_eq!(d1, d2);
     }
+
+    fn build_with_lock_time(lock_time: u32) -> UnsignedZecTx {
+        let utxos = vec![make_test_utxo(&"a".repeat(64), 0, 10_000_000)];
+        let pubkey_hex = "0279BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798";
+        let pubkey: [u8; 33] = hex::decode(pubkey_hex).unwrap().try_into().unwrap();
+        let addr = address::pubkey_to_t_address(&pubkey, ZecNetwork::Mainnet).unwrap();
+        build_transparent_transaction(
+            &utxos,
+            &addr,
+            5_000_000,
+            &addr,
+            1,
+            ZecNetwork::Mainnet,
+            1_000_000,
+            None,
+            None,
+            lock_time,
+            false,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn serialized_tx_carries_lock_time() {
+        let unsigned = build_with_lock_time(2_500_000);
+        assert_eq!(unsigned.lock_time, 2_500_000);
+        // A final sequence would disable the lock time.
+        assert!(unsigned.inputs.iter().all(|inp| inp.sequence < 0xFFFFFFFF));
+
+        let signed = sign_transaction(&unsigned, &InputSigner::SingleKey(privkey(1))).unwrap();
+        // version(4) + version_group_id(4) + branch_id(4), then nLockTime
+        assert_eq!(signed[12..16], 2_500_000u32.to_le_bytes());
+    }
+
+    #[test]
+    fn header_digest_reflects_lock_time() {
+        let locked = build_with_lock_time(2_500_000);
+        let unlocked = build_with_lock_time(0);
+        assert_ne!(
+            compute_header_digest(&locked),
+            compute_header_digest(&unlocked)
+        );
+
+        let mut header = Vec::new();
+        header.extend_from_slice(&TX_VERSION.to_le_bytes());
+        header.extend_from_slice(&VERSION_GROUP_ID.to_le_bytes());
+        header.extend_from_slice(&CONSENSUS_BRANCH_ID_MAINNET.to_le_bytes());
+        header.extend_from_slice(&2_500_000u32.to_le_bytes());
+        header.extend_from_slice(&1_000_000u32.to_le_bytes());
+        assert_eq!(
+            compute_header_digest(&locked),
+            blake2b_256(b"ZTxIdHeadersHash", &header)
+        );
+
+        // The header digest is committed to by every input's sighash.
+        assert_ne!(
+            compute_sighash(&locked, 0).unwrap(),
+            compute_sighash(&unlocked, 0).unwrap()
+        );
+    }
+
+    // ─── P2SH multisig ──────────────────────────────────────────────
+
+    fn privkey(n: u8) -> [u8; 32] {
+        let mut key = [0u8; 32];
+        key[31] = n;
+        key
+    }
+
+    fn pubkey_of(private_key: &[u8; 32]) -> [u8; 33] {
+        signing_key_and_pubkey(private_key).unwrap().1
+    }
+
+    /// A 2-of-2 redeem script over keys 1 and 2, and an unsigned tx spending
+    /// one P2SH output locked to it.
+    fn make_2of2_tx() -> (Vec<u8>, UnsignedZecTx) {
+        let redeem =
+            multisig_redeem_script(2, &[pubkey_of(&privkey(1)), pubkey_of(&privkey(2))]).unwrap();
+        let utxo = ZecUtxo {
+            txid: "f".repeat(64),
+            vout: 0,
+            amount_zatoshi: 10_000_000,
+            script_pubkey: p2sh_script(&address::hash160(&redeem)),
+            redeem_script: Some(redeem.clone()),
+        };
+        let addr =
+            address::pubkey_to_t_address(&pubkey_of(&privkey(3)), ZecNetwork::Mainnet).unwrap();
+        let unsigned = build_transparent_transaction(
+            &[utxo],
+            &addr,
+            5_000_000,
+            &addr,
+            1,
+            ZecNetwork::Mainnet,
+            1_000_000,
+            None,
+            None,
+            0,
+            false,
+        )
+        .unwrap();
+        (redeem, unsigned)
+    }
+
+    #[test]
+    fn multisig_redeem_script_roundtrip() {
+        let pubkeys = [
+            pubkey_of(&privkey(1)),
+            pubkey_of(&privkey(2)),
+            pubkey_of(&privkey(3)),
+        ];
+        let script = multisig_redeem_script(2, &pubkeys).unwrap();
+
+        assert_eq!(script.len(), 3 + 3 * 34);
+        assert_eq!(script[0], 0x52); // OP_2
+        assert_eq!(script[script.len() - 2], 0x53); // OP_3
+        assert_eq!(
+            parse_multisig_redeem_script(&script).unwrap(),
+            (2, pubkeys.to_vec())
+        );
+    }
+
+    #[test]
+    fn multisig_redeem_script_rejects_bad_threshold() {
+        let pubkeys = [pubkey_of(&privkey(1))];
+        assert!(multisig_redeem_script(0, &pubkeys).is_err());
+        assert!(multisig_redeem_script(2, &pubkeys).is_err());
+    }
+
+    #[test]
+    fn sign_p2sh_2of2_script_sig_structure() {
+        use k256::ecdsa::{signature::hazmat::PrehashVerifier, VerifyingKey};
+
+        let (redeem, unsigned) = make_2of2_tx();
+        // Keys deliberately out of script order.
+        let keys = [privkey(2), privkey(1)];
+        let auth = [InputAuth::P2shMultisig {
+            redeem_script: &redeem,
+            private_keys: &keys,
+        }];
+        let signed = sign_transaction_with_auth(&unsigned, &auth).unwrap();
+
+        // header(20) + input count(1) + outpoint(36), then the scriptSig length.
+        let script_len = signed[57] as usize;
+        let script_sig = &signed[58..58 + script_len];
+        assert_eq!(script_sig[0], 0x00); // OP_0
+
+        let sighash = compute_sighash(&unsigned, 0).unwrap();
+        let mut pos = 1;
+        for signer in [privkey(1), privkey(2)] {
+            let len = script_sig[pos] as usize;
+            let sig = &script_sig[pos + 1..pos + 1 + len];
+            assert_eq!(sig[len - 1], SIGHASH_ALL);
+
+            let verifying_key = VerifyingKey::from_sec1_bytes(&pubkey_of(&signer)).unwrap();
+            let signature = Signature::from_der(&sig[..len - 1]).unwrap();
+            verifying_key.verify_prehash(&sighash, &signature).unwrap();
+            pos += 1 + len;
+        }
+
+        assert_eq!(script_sig[pos] as usize, redeem.len());
+        assert_eq!(&script_sig[pos + 1..], redeem.as_slice());
+    }
+
+    #[test]
+    fn p2sh_multisig_input_sized_by_its_script_sig() {
+        let (redeem, unsigned) = make_2of2_tx();
+        let keys = [privkey(1), privkey(2)];
+        let auth = [InputAuth::P2shMultisig {
+            redeem_script: &redeem,
+            private_keys: &keys,
+        }];
+        let signed = sign_transaction_with_auth(&unsigned, &auth).unwrap();
+        let script_len = signed[57] as u64;
+
+        let utxo = ZecUtxo {
+            txid: "f".repeat(64),
+            vout: 0,
+            amount_zatoshi: 10_000_000,
+            script_pubkey: p2sh_script(&address::hash160(&redeem)),
+            redeem_script: Some(redeem),
+        };
+        // outpoint(36) + length byte + scriptSig + sequence(4); each of the two
+        // signatures may come out a byte shorter than the maximum.
+        let estimated = input_bytes(&utxo).unwrap();
+        assert!(estimated > INPUT_BYTES);
+        assert!((36 + 1 + script_len + 4..=36 + 1 + script_len + 4 + 2).contains(&estimated));
+
+        // The builder charges for the larger input.
+        let fee = |utxo: &ZecUtxo| estimate_fee_for_utxos([utxo], 2, 1).unwrap();
+        let p2pkh = make_test_utxo(&"f".repeat(64), 0, 1);
+        assert_eq!(fee(&utxo) - fee(&p2pkh), estimated - INPUT_BYTES);
+    }
+
+    #[test]
+    fn p2sh_utxo_without_redeem_script_fails_fee_estimate() {
+        let (redeem, _) = make_2of2_tx();
+        let mut utxo = make_test_utxo(&"f".repeat(64), 0, 10_000_000);
+        utxo.script_pubkey = p2sh_script(&address::hash160(&redeem));
+        assert!(estimate_fee_for_utxos([&utxo], 1, 1).is_err());
+
+        // A redeem script for a different P2SH address is rejected too.
+        let other = multisig_redeem_script(1, &[pubkey_of(&privkey(4))]).unwrap();
+        utxo.redeem_script = Some(other);
+        assert!(estimate_fee_for_utxos([&utxo], 1, 1).is_err());
+    }
+
+    #[test]
+    fn estimate_fee_for_utxos_matches_estimate_fee_for_p2pkh() {
+        let utxos = [
+            make_test_utxo(&"a".repeat(64), 0, 1),
+            make_test_utxo(&"b".repeat(64), 0, 1),
+        ];
+        assert_eq!(
+            estimate_fee_for_utxos(&utxos, 2, 3).unwrap(),
+            estimate_fee(2, 2, 3)
+        );
+    }
+
+    /// Sighash for `make_2of2_tx` input 0, computed with librustzcash
+    /// (zcash_primitives 0.30 `signature_hash`, SIGHASH_ALL). Its result is
+    /// the same whether the redeem script or the P2SH script is passed as
+    /// script_code, since ZIP-244 only hashes the scriptPubKey.
+    #[test]
+    fn p2sh_sighash_matches_librustzcash() {
+        let (redeem, unsigned) = make_2of2_tx();
+        assert_eq!(
+            hex::encode(&redeem),
+            "52210279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798\
+             2102c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee552ae"
+        );
+        assert_eq!(
+            hex::encode(serialize_v5_tx(&unsigned, &[vec![]]).unwrap()),
+            "050000800a27a726b4d0d6c20000000040420f0001ffffffffffffffffffffffffffffffff\
+             ffffffffffffffffffffffffffffffff0000000000feffffff02404b4c00000000001976a9147d\
+             d65592d0ab2fe0d0257d571abf032cd9db93dc88acca494c00000000001976a9147dd65592d0ab\
+             2fe0d0257d571abf032cd9db93dc88ac000000"
+        );
+        assert_eq!(
+            hex::encode(compute_sighash(&unsigned, 0).unwrap()),
+            "dbc6e104fb00d610bffd1289038ff57d833cbd4282ea00bcfdd5f3e29a4dbf24"
+        );
+    }
+
+    #[test]
+    fn p2sh_scriptpubkeys_digest_commits_to_p2sh_script() {
+        let (redeem, unsigned) = make_2of2_tx();
+        let p2sh = p2sh_script(&address::hash160(&redeem));
+
+        let mut expected = vec![p2sh.len() as u8];
+        expected.extend_from_slice(&p2sh);
+        assert_eq!(
+            compute_scriptpubkeys_digest(&unsigned),
+            blake2b_256(b"ZTxTrScriptsHash", &expected)
+        );
+    }
+
+    #[test]
+    fn sign_p2sh_rejects_missing_signature() {
+        let (redeem, unsigned) = make_2of2_tx();
+        let keys = [privkey(1)];
+        let auth = [InputAuth::P2shMultisig {
+            redeem_script: &redeem,
+            private_keys: &keys,
+        }];
+        assert!(sign_transaction_with_auth(&unsigned, &auth).is_err());
+
+        // A repeated key is still only one signature.
+        let keys = [privkey(1), privkey(1)];
+        let auth = [InputAuth::P2shMultisig {
+            redeem_script: &redeem,
+            private_keys: &keys,
+        }];
+        assert!(sign_transaction_with_auth(&unsigned, &auth).is_err());
+    }
+
+    #[test]
+    fn sign_p2sh_rejects_foreign_key_and_wrong_script() {
+        let (redeem, unsigned) = make_2of2_tx();
+
+        let keys = [privkey(1), privkey(3)];
+        let auth = [InputAuth::P2shMultisig {
+            redeem_script: &redeem,
+            private_keys: &keys,
+        }];
+        assert!(sign_transaction_with_auth(&unsigned, &auth).is_err());
+
+        let other = multisig_redeem_script(1, &[pubkey_of(&privkey(1))]).unwrap();
+        let keys = [privkey(1)];
+        let auth = [InputAuth::P2shMultisig {
+            redeem_script: &other,
+            private_keys: &keys,
+        }];
+        assert!(sign_transaction_with_auth(&unsigned, &auth).is_err());
+    }
+
+    #[test]
+    fn sign_with_auth_requires_one_entry_per_input() {
+        let (_, unsigned) = make_2of2_tx();
+        assert!(sign_transaction_with_auth(&unsigned, &[]).is_err());
+    }
+
+    #[test]
+    fn push_data_uses_pushdata1_above_75_bytes() {
+        let mut script = Vec::new();
+        push_data(&mut script, &[0xAA; 105]);
+        assert_eq!(&script[..2], &[0x4C, 105]);
+        assert_eq!(script.len(), 107);
+    }
 }
@@ -0,0 +1,97 @@
+//! Base58Check encoding as used by Bitcoin and Zcash transparent addresses
+//! and WIF keys: `base58(version || payload || checksum)`, where the checksum
+//! is the first 4 bytes of SHA-256d over `version || payload`.
+
+use crate::error::CryptoError;
+use crate::hashes::sha256d;
+
+/// Length of the trailing SHA-256d checksum.
+const CHECKSUM_LEN: usize = 4;
+
+/// Double SHA-256 checksum (first 4 bytes).
+pub fn double_sha256_checksum(data: &[u8]) -> [u8; CHECKSUM_LEN] {
+    let mut checksum = [0u8; CHECKSUM_LEN];
+    checksum.copy_from_slice(&sha256d(data)[..CHECKSUM_LEN]);
+    checksum
+}
+
+/// Base58Check-encode `payload` behind a `version` prefix.
+pub fn encode(version: &[u8], payload: &[u8]) -> String {
+    let mut data = Vec::with_capacity(version.len() + payload.len() + CHECKSUM_LEN);
+    data.extend_from_slice(version);
+    data.extend_from_slice(payload);
+    let checksum = double_sha256_checksum(&data);
+    data.extend_from_slice(&checksum);
+    bs58::encode(data).into_string()
+}
+
+/// Decode a Base58Check string and verify its checksum.
+///
+/// Returns `(version, payload)`, splitting off the first
+/// `expected_version_len` bytes as the version. The caller checks that the
+/// version and payload length are the ones it expects.
+pub fn decode(s: &str, expected_version_len: usize) -> Result<(Vec<u8>, Vec<u8>), CryptoError> {
+    let mut data = bs58::decode(s)
+        .into_vec()
+        .map_err(|e| CryptoError::InvalidInput(format!("invalid base58: {e}")))?;
+
+    if data.len() < expected_version_len + CHECKSUM_LEN {
+        return Err(CryptoError::InvalidInput(format!(
+            "base58check data too short: {} bytes",
+            data.len()
+        )));
+    }
+
+    let checksum = data.split_off(data.len() - CHECKSUM_LEN);
+    if checksum != double_sha256_checksum(&data) {
+        return Err(CryptoError::InvalidInput("invalid checksum".into()));
+    }
+
+    let payload = data.split_off(expected_version_len);
+    Ok((data, payload))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Genesis block coinbase P2PKH address and its hash160.
+    const GENESIS_ADDRESS: &str = "1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa";
+    const GENESIS_HASH160: &str = "62e907b15cbf27d5425399ebf6f0fb50ebb88f18";
+
+    #[test]
+    fn encode_bitcoin_p2pkh() {
+        let hash = hex::decode(GENESIS_HASH160).unwrap();
+        assert_eq!(encode(&[0x00], &hash), GENESIS_ADDRESS);
+    }
+
+    #[test]
+    fn decode_bitcoin_p2pkh() {
+        let (version, payload) = decode(GENESIS_ADDRESS, 1).unwrap();
+        assert_eq!(version, vec![0x00]);
+        assert_eq!(hex::encode(payload), GENESIS_HASH160);
+    }
+
+    #[test]
+    fn decode_rejects_bad_checksum() {
+        // Last character changed, which corrupts the checksum.
+        let result = decode("1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNb", 1);
+        assert!(matches!(result, Err(CryptoError::InvalidInput(m)) if m == "invalid checksum"));
+    }
+
+    #[test]
+    fn decode_rejects_invalid_base58_and_short_input() {
+        assert!(decode("0OIl", 1).is_err());
+        assert!(decode(&bs58::encode([0u8; 3]).into_string(), 1).is_err());
+    }
+
+    #[test]
+    fn roundtrip_two_byte_version() {
+        let encoded = encode(&[0x1C, 0xB8], &[0xAB; 20]);
+        assert!(encoded.starts_with("t1"));
+        assert_eq!(
+            decode(&encoded, 2).unwrap(),
+            (vec![0x1C, 0xB8], vec![0xAB; 20])
+        );
+    }
+}
@@ -1,52 +1,173 @@
-use aes_gcm::aead::{Aead, OsRng};
-use aes_gcm::{AeadCore, Aes256Gcm, Key, KeyInit, Nonce};
+use aes_gcm::aead::{Aead, Nonce, OsRng};
+use aes_gcm::{AeadCore, Aes256Gcm, KeyInit};
+use chacha20poly1305::XChaCha20Poly1305;
 
 use crate::error::CryptoError;
 
 /// AES-256-GCM nonce size in bytes.
 const NONCE_SIZE: usize = 12;
 
-/// Encrypts `plaintext` using AES-256-GCM with the given 32-byte `key`.
+/// AEAD ciphers supported by the tagged format ([`encrypt_tagged`]).
 ///
-/// A random 12-byte nonce is generated and prepended to the ciphertext. The
-/// returned vector has the layout: `[nonce (12 bytes) | ciphertext + tag]`.
-pub fn encrypt(plaintext: &[u8], key: &[u8; 32]) -> Result<Vec<u8>, CryptoError> {
-    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key));
-    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+/// Each variant has a stable one-byte discriminator that is written in front
+/// of the nonce, so blobs from different ciphers can sit side by side and be
+/// opened by the same [`decrypt_tagged`] call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AeadAlgorithm {
+    /// AES-256-GCM, 12-byte random nonce.
+    Aes256Gcm,
+    /// XChaCha20-Poly1305, 24-byte random nonce.
+    XChaCha20Poly1305,
+}
+
+impl AeadAlgorithm {
+    /// Discriminator byte stored at the start of a tagged blob.
+    pub fn id(self) -> u8 {
+        match self {
+            Self::Aes256Gcm => 0x01,
+            Self::XChaCha20Poly1305 => 0x02,
+        }
+    }
+
+    /// Looks up the algorithm for a stored discriminator byte.
+    pub fn from_id(id: u8) -> Result<Self, CryptoError> {
+        match id {
+            0x01 => Ok(Self::Aes256Gcm),
+            0x02 => Ok(Self::XChaCha20Poly1305),
+            other => Err(CryptoError::InvalidInput(format!(
+                "unknown AEAD algorithm id: 0x{other:02x}"
+            ))),
+        }
+    }
+
+    /// Nonce length in bytes.
+    pub fn nonce_size(self) -> usize {
+        match self {
+            Self::Aes256Gcm => NONCE_SIZE,
+            Self::XChaCha20Poly1305 => 24,
+        }
+    }
+
+    /// Authentication tag length in bytes (16 for both ciphers).
+    pub fn tag_size(self) -> usize {
+        16
+    }
+}
+
+/// Encrypts with cipher `C`, returning `[nonce | ciphertext + tag]`.
+fn seal<C: Aead + AeadCore + KeyInit>(
+    plaintext: &[u8],
+    key: &[u8; 32],
+) -> Result<Vec<u8>, CryptoError> {
+    let cipher = C::new_from_slice(key).map_err(|_| CryptoError::InvalidKeyLength)?;
+    let nonce = C::generate_nonce(&mut OsRng);
 
     let ciphertext = cipher
         .encrypt(&nonce, plaintext)
         .map_err(|e| CryptoError::EncryptionFailed(e.to_string()))?;
 
-    let mut output = Vec::with_capacity(NONCE_SIZE + ciphertext.len());
+    let mut output = Vec::with_capacity(nonce.len() + ciphertext.len());
     output.extend_from_slice(&nonce);
     output.extend_from_slice(&ciphertext);
 
     Ok(output)
 }
 
-/// Decrypts data previously encrypted with [`encrypt`].
-///
-/// Expects `ciphertext_with_nonce` to begin with the 12-byte nonce followed by
-/// the ciphertext and authentication tag.
-pub fn decrypt(ciphertext_with_nonce: &[u8], key: &[u8; 32]) -> Result<Vec<u8>, CryptoError> {
-    if ciphertext_with_nonce.len() < NONCE_SIZE {
+/// Splits `nonce_size` leading bytes off `data` and decrypts the rest with `C`.
+fn open<C: Aead + AeadCore + KeyInit>(
+    data: &[u8],
+    nonce_size: usize,
+    key: &[u8; 32],
+) -> Result<Vec<u8>, CryptoError> {
+    if data.len() < nonce_size {
         return Err(CryptoError::InvalidInput(format!(
             "ciphertext too short: expected at least {} bytes, got {}",
-            NONCE_SIZE,
-            ciphertext_with_nonce.len()
+            nonce_size,
+            data.len()
         )));
     }
 
-    let (nonce_bytes, ciphertext) = ciphertext_with_nonce.split_at(NONCE_SIZE);
-    let nonce = Nonce::from_slice(nonce_bytes);
-    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key));
+    let (nonce_bytes, ciphertext) = data.split_at(nonce_size);
+    let nonce = Nonce::<C>::from_slice(nonce_bytes);
+    let cipher = C::new_from_slice(key).map_err(|_| CryptoError::InvalidKeyLength)?;
 
     cipher
         .decrypt(nonce, ciphertext)
         .map_err(|e| CryptoError::DecryptionFailed(e.to_string()))
 }
 
+/// Encrypts `plaintext` using AES-256-GCM with the given 32-byte `key`.
+///
+/// A random 12-byte nonce is generated and prepended to the ciphertext. The
+/// returned vector has the layout: `[nonce (12 bytes) | ciphertext + tag]`.
+///
+/// This is the untagged legacy layout used for stored seeds; new formats that
+/// may change cipher should use [`encrypt_tagged`].
+pub fn encrypt(plaintext: &[u8], key: &[u8; 32]) -> Result<Vec<u8>, CryptoError> {
+    seal::<Aes256Gcm>(plaintext, key)
+}
+
+/// Decrypts data previously encrypted with [`encrypt`].
+///
+/// Expects `ciphertext_with_nonce` to begin with the 12-byte nonce followed by
+/// the ciphertext and authentication tag.
+pub fn decrypt(ciphertext_with_nonce: &[u8], key: &[u8; 32]) -> Result<Vec<u8>, CryptoError> {
+    open::<Aes256Gcm>(ciphertext_with_nonce, NONCE_SIZE, key)
+}
+
+/// Encrypts each of `items` under the same `key`, each with its own random
+/// nonce, in the [`encrypt`] layout.
+///
+/// For storing several secrets behind one password: derive the key once and
+/// pass it here rather than running the KDF per item.
+pub fn encrypt_many(items: &[&[u8]], key: &[u8; 32]) -> Result<Vec<Vec<u8>>, CryptoError> {
+    items.iter().map(|item| encrypt(item, key)).collect()
+}
+
+/// Decrypts each blob produced by [`encrypt_many`] (or [`encrypt`]) with
+/// `key`. Fails on the first blob that does not decrypt.
+pub fn decrypt_many(blobs: &[Vec<u8>], key: &[u8; 32]) -> Result<Vec<Vec<u8>>, CryptoError> {
+    blobs.iter().map(|blob| decrypt(blob, key)).collect()
+}
+
+/// Encrypts `plaintext` with `algorithm`, recording which cipher was used.
+///
+/// Layout: `[algorithm id (1 byte) | nonce | ciphertext + tag]`, where the
+/// nonce length is [`AeadAlgorithm::nonce_size`].
+pub fn encrypt_tagged(
+    algorithm: AeadAlgorithm,
+    plaintext: &[u8],
+    key: &[u8; 32],
+) -> Result<Vec<u8>, CryptoError> {
+    let sealed = match algorithm {
+        AeadAlgorithm::Aes256Gcm => seal::<Aes256Gcm>(plaintext, key)?,
+        AeadAlgorithm::XChaCha20Poly1305 => seal::<XChaCha20Poly1305>(plaintext, key)?,
+    };
+
+    let mut output = Vec::with_capacity(1 + sealed.len());
+    output.push(algorithm.id());
+    output.extend_from_slice(&sealed);
+    Ok(output)
+}
+
+/// Decrypts a blob produced by [`encrypt_tagged`], whatever cipher it used.
+///
+/// The leading discriminator selects the algorithm, which in turn decides how
+/// many nonce bytes to split off.
+pub fn decrypt_tagged(data: &[u8], key: &[u8; 32]) -> Result<Vec<u8>, CryptoError> {
+    let (&id, rest) = data
+        .split_first()
+        .ok_or_else(|| CryptoError::InvalidInput("ciphertext is empty".into()))?;
+    let algorithm = AeadAlgorithm::from_id(id)?;
+
+    match algorithm {
+        AeadAlgorithm::Aes256Gcm => open::<Aes256Gcm>(rest, algorithm.nonce_size(), key),
+        AeadAlgorithm::XChaCha20Poly1305 => {
+            open::<XChaCha20Poly1305>(rest, algorithm.nonce_size(), key)
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -164,4 +285,115 @@ mod tests {
 
         assert_eq!(decrypted, plaintext);
     }
+
+    #[test]
+    fn encrypt_many_items_decrypt_independently() {
+        let key = test_key();
+        let items: [&[u8]; 3] = [b"seed bytes", b"", b"wallet notes"];
+
+        let blobs = encrypt_many(&items, &key).unwrap();
+        assert_eq!(blobs.len(), 3);
+        for (blob, item) in blobs.iter().zip(items) {
+            assert_eq!(decrypt(blob, &key).unwrap(), item);
+        }
+        assert_eq!(
+            decrypt_many(&blobs, &key).unwrap(),
+            items.map(<[u8]>::to_vec)
+        );
+
+        // Same key, but every item gets its own nonce.
+        let again = encrypt_many(&[b"same", b"same"], &key).unwrap();
+        assert_ne!(again[0][..NONCE_SIZE], again[1][..NONCE_SIZE]);
+    }
+
+    #[test]
+    fn decrypt_many_fails_on_any_bad_blob() {
+        let key = test_key();
+        let mut blobs = encrypt_many(&[b"a", b"b"], &key).unwrap();
+        let last = blobs[1].len() - 1;
+        blobs[1][last] ^= 0x01;
+        assert!(decrypt_many(&blobs, &key).is_err());
+    }
+
+    #[test]
+    fn nonce_sizes_match_cipher_types() {
+        use aes_gcm::aead::generic_array::typenum::Unsigned;
+
+        assert_eq!(
+            AeadAlgorithm::Aes256Gcm.nonce_size(),
+            <Aes256Gcm as AeadCore>::NonceSize::USIZE
+        );
+        assert_eq!(
+            AeadAlgorithm::XChaCha20Poly1305.nonce_size(),
+            <XChaCha20Poly1305 as AeadCore>::NonceSize::USIZE
+        );
+    }
+
+    #[test]
+    fn algorithm_id_roundtrip() {
+        for alg in [AeadAlgorithm::Aes256Gcm, AeadAlgorithm::XChaCha20Poly1305] {
+            assert_eq!(AeadAlgorithm::from_id(alg.id()).unwrap(), alg);
+        }
+        assert!(AeadAlgorithm::from_id(0x00).is_err());
+    }
+
+    #[test]
+    fn tagged_blobs_have_algorithm_specific_layout() {
+        let key = test_key();
+        let plaintext = b"layout";
+
+        for alg in [AeadAlgorithm::Aes256Gcm, AeadAlgorithm::XChaCha20Poly1305] {
+            let blob = encrypt_tagged(alg, plaintext, &key).unwrap();
+            assert_eq!(blob[0], alg.id());
+            assert_eq!(
+                blob.len(),
+                1 + alg.nonce_size() + plaintext.len() + alg.tag_size()
+            );
+        }
+    }
+
+    #[test]
+    fn dispatcher_decrypts_mixed_gcm_and_xchacha_blobs() {
+        let key = test_key();
+        let blobs = [
+            encrypt_tagged(AeadAlgorithm::Aes256Gcm, b"from gcm", &key).unwrap(),
+            encrypt_tagged(AeadAlgorithm::XChaCha20Poly1305, b"from xchacha", &key).unwrap(),
+        ];
+
+        let opened: Vec<Vec<u8>> = blobs
+            .iter()
+            .map(|blob| decrypt_tagged(blob, &key).unwrap())
+            .collect();
+        assert_eq!(opened, [b"from gcm".to_vec(), b"from xchacha".to_vec()]);
+    }
+
+    #[test]
+    fn dispatcher_rejects_swapped_discriminator() {
+        let key = test_key();
+        let mut blob = encrypt_tagged(AeadAlgorithm::XChaCha20Poly1305, b"x", &key).unwrap();
+        blob[0] = AeadAlgorithm::Aes256Gcm.id();
+
+        assert!(matches!(
+            decrypt_tagged(&blob, &key),
+            Err(CryptoError::DecryptionFailed(_))
+        ));
+    }
+
+    #[test]
+    fn dispatcher_rejects_unknown_or_empty_input() {
+        let key = test_key();
+        assert!(matches!(
+            decrypt_tagged(&[], &key),
+            Err(CryptoError::InvalidInput(_))
+        ));
+        assert!(matches!(
+            decrypt_tagged(&[0x7f; 40], &key),
+            Err(CryptoError::InvalidInput(_))
+        ));
+        // XChaCha id with fewer than 24 nonce bytes.
+        assert!(matches!(
+            decrypt_tagged(&[0x02; 20], &key),
+            Err(CryptoError::InvalidInput(_))
+        ));
+    }
 }
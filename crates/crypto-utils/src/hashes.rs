@@ -0,0 +1,63 @@
+//! Hash primitives shared by the chain crates: Bitcoin-style HASH160 and
+//! SHA-256d, and Ethereum's Keccak-256.
+
+use ripemd::Ripemd160;
+use sha2::{Digest, Sha256};
+use sha3::Keccak256;
+
+/// HASH160: `RIPEMD-160(SHA-256(data))`, the P2PKH / P2SH pubkey-hash.
+pub fn hash160(data: &[u8]) -> [u8; 20] {
+    Ripemd160::digest(Sha256::digest(data)).into()
+}
+
+/// SHA-256d: `SHA-256(SHA-256(data))`, used for txids and Base58Check.
+pub fn sha256d(data: &[u8]) -> [u8; 32] {
+    Sha256::digest(Sha256::digest(data)).into()
+}
+
+/// Keccak-256 (the pre-standard SHA-3 padding Ethereum uses).
+pub fn keccak256(data: &[u8]) -> [u8; 32] {
+    Keccak256::digest(data).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hash160_of_generator_pubkey() {
+        // Compressed secp256k1 G; its hash160 is the witness program of
+        // bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4 (BIP-173).
+        let g = hex::decode("0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798")
+            .unwrap();
+        assert_eq!(
+            hex::encode(hash160(&g)),
+            "751e76e8199196d454941c45d1b3a323f1433bd6"
+        );
+    }
+
+    #[test]
+    fn sha256d_known_vectors() {
+        assert_eq!(
+            hex::encode(sha256d(b"")),
+            "5df6e0e2761359d30a8275058e299fcc0381534545f55cf43e41983f5d4c9456"
+        );
+        assert_eq!(
+            hex::encode(sha256d(b"hello")),
+            "9595c9df90075148eb06860365df33584b75bff782a510c6cd4883a419833d50"
+        );
+    }
+
+    #[test]
+    fn keccak256_known_vectors() {
+        assert_eq!(
+            hex::encode(keccak256(b"")),
+            "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+        );
+        // ERC-20 `transfer(address,uint256)` selector.
+        assert_eq!(
+            hex::encode(&keccak256(b"transfer(address,uint256)")[..4]),
+            "a9059cbb"
+        );
+    }
+}
@@ -1,12 +1,16 @@
 //! # crypto-utils
 //!
-//! Encryption, key derivation, memory safety, and secure random generation
+//! Encryption, key derivation, memory safety, secure random generation,
+//! Base58Check encoding, shared hash primitives and input-signer selection
 //! utilities for the crypto wallet.
 
+pub mod base58check;
 pub mod encryption;
 pub mod error;
+pub mod hashes;
 pub mod kdf;
 pub mod random;
+pub mod signer;
 pub mod zeroizing;
 
 pub use error::CryptoError;
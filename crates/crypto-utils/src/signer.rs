@@ -0,0 +1,58 @@
+//! Private-key selection for signing multi-input transactions.
+
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::error::CryptoError;
+
+/// Which private key signs each input of a UTXO transaction.
+///
+/// Keys are 32-byte secp256k1 scalars and are zeroized on drop.
+#[derive(Clone, Zeroize, ZeroizeOnDrop)]
+pub enum InputSigner {
+    /// One key signs every input.
+    SingleKey([u8; 32]),
+    /// `keys[i]` signs input `i`; there must be exactly one key per input.
+    KeyPerInput(Vec<[u8; 32]>),
+}
+
+impl InputSigner {
+    /// The key for each of `input_count` inputs, in input order.
+    ///
+    /// Fails if a `KeyPerInput` signer holds a different number of keys.
+    pub fn keys(&self, input_count: usize) -> Result<Vec<&[u8; 32]>, CryptoError> {
+        match self {
+            InputSigner::SingleKey(key) => Ok(vec![key; input_count]),
+            InputSigner::KeyPerInput(keys) if keys.len() == input_count => {
+                Ok(keys.iter().collect())
+            }
+            InputSigner::KeyPerInput(keys) => Err(CryptoError::InvalidInput(format!(
+                "expected one key per input ({input_count} inputs), got {} keys",
+                keys.len()
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn single_key_signs_every_input() {
+        let signer = InputSigner::SingleKey([7; 32]);
+        assert_eq!(signer.keys(3).unwrap(), vec![&[7; 32]; 3]);
+    }
+
+    #[test]
+    fn key_per_input_keeps_order() {
+        let signer = InputSigner::KeyPerInput(vec![[1; 32], [2; 32]]);
+        assert_eq!(signer.keys(2).unwrap(), vec![&[1; 32], &[2; 32]]);
+    }
+
+    #[test]
+    fn key_per_input_length_mismatch_is_an_error() {
+        let signer = InputSigner::KeyPerInput(vec![[1; 32]]);
+        assert!(matches!(signer.keys(2), Err(CryptoError::InvalidInput(_))));
+        assert!(signer.keys(0).is_err());
+    }
+}
@@ -93,6 +93,61 @@ impl From<&str> for ZeroizingString {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::sync::atomic::{AtomicU8, AtomicUsize, Ordering};
+    use std::sync::Mutex;
+
+    /// The system allocator, except that it inspects the buffer at `WATCHED`
+    /// just before freeing it and records in `FREED` whether it was all zeros.
+    struct WipeCheckingAlloc;
+
+    static WATCH_LOCK: Mutex<()> = Mutex::new(());
+    static WATCHED: AtomicUsize = AtomicUsize::new(0);
+    static FREED: AtomicU8 = AtomicU8::new(NOT_FREED);
+    const NOT_FREED: u8 = 0;
+    const FREED_INTACT: u8 = 1;
+    const FREED_ZEROED: u8 = 2;
+
+    unsafe impl GlobalAlloc for WipeCheckingAlloc {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            System.alloc(layout)
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            let watched = ptr as usize;
+            if WATCHED
+                .compare_exchange(watched, 0, Ordering::SeqCst, Ordering::SeqCst)
+                .is_ok()
+            {
+                // Still allocated here, so reading it is sound.
+                let buf = std::slice::from_raw_parts(ptr, layout.size());
+                let state = if buf.iter().all(|&b| b == 0) {
+                    FREED_ZEROED
+                } else {
+                    FREED_INTACT
+                };
+                FREED.store(state, Ordering::SeqCst);
+            }
+            System.dealloc(ptr, layout)
+        }
+    }
+
+    #[global_allocator]
+    static ALLOCATOR: WipeCheckingAlloc = WipeCheckingAlloc;
+
+    /// Run `drop_it` and report whether the heap buffer at `ptr` was zeroed
+    /// before it was freed.
+    fn freed_zeroed(ptr: *const u8, drop_it: impl FnOnce()) -> bool {
+        let _one_watch_at_a_time = WATCH_LOCK.lock().unwrap();
+        FREED.store(NOT_FREED, Ordering::SeqCst);
+        WATCHED.store(ptr as usize, Ordering::SeqCst);
+        drop_it();
+        match FREED.load(Ordering::SeqCst) {
+            FREED_ZEROED => true,
+            FREED_INTACT => false,
+            _ => panic!("the watched buffer was not freed"),
+        }
+    }
 
     #[test]
     fn zeroizing_bytes_deref() {
@@ -169,14 +224,30 @@ mod tests {
 
     #[test]
     fn zeroizing_bytes_drop_zeroes_memory() {
-        // We cannot directly observe zeroing after drop, but we can verify
-        // that manual zeroize works, which ZeroizeOnDrop calls automatically.
+        // Manual zeroize, which ZeroizeOnDrop calls automatically; the drop
+        // itself is checked by zeroizing_bytes_drop_wipes_buffer_before_freeing.
         let mut zb = ZeroizingBytes::new(vec![0xAA; 32]);
         zb.zeroize();
         // After zeroize, the inner Vec is cleared.
         assert!(zb.is_empty());
     }
 
+    #[test]
+    fn zeroizing_bytes_drop_wipes_buffer_before_freeing() {
+        // A plain Vec frees its bytes intact, so the check can tell the two apart.
+        let plain = vec![0xAAu8; 64];
+        assert!(!freed_zeroed(plain.as_ptr(), || drop(plain)));
+
+        let zb = ZeroizingBytes::new(vec![0xAA; 64]);
+        assert!(freed_zeroed(zb.as_ptr(), || drop(zb)));
+    }
+
+    #[test]
+    fn zeroizing_string_drop_wipes_buffer_before_freeing() {
+        let zs = ZeroizingString::new("abandon abandon abandon".into());
+        assert!(freed_zeroed(zs.as_ptr(), || drop(zs)));
+    }
+
     #[test]
     fn zeroizing_string_manual_zeroize() {
         let mut zs = ZeroizingString::new("sensitive".into());
@@ -1,6 +1,10 @@
+use std::collections::BTreeSet;
+
+use sha2::{Digest, Sha256};
+
 use crate::error::WalletError;
-use crate::hd_derivation;
-use crate::types::{Chain, DerivedAddress};
+use crate::hd_derivation::{self, DerivedKey};
+use crate::types::{Chain, DerivedAddress, WalletSummary};
 
 /// Derive an address for a given chain from seed bytes
 pub fn derive_address(
@@ -47,6 +51,246 @@ pub fn derive_all_addresses(
     Ok(addresses)
 }
 
+/// Derive the EVM address once and tag it with every supported EVM chain
+///
+/// All EVM chains share coin type 60, so the address is identical; the
+/// per-chain entries let the UI show "this address on Ethereum, Polygon, ...".
+pub fn derive_evm_addresses(
+    seed: &[u8],
+    account: u32,
+    index: u32,
+) -> Result<Vec<DerivedAddress>, WalletError> {
+    let derived = derive_eth_address(seed, Chain::Ethereum, account, index)?;
+    Ok(Chain::EVM_CHAINS
+        .into_iter()
+        .map(|chain| DerivedAddress {
+            chain,
+            ..derived.clone()
+        })
+        .collect())
+}
+
+/// Derive the default addresses and master fingerprint for one account
+pub fn derive_wallet_summary(seed: &[u8], account: u32) -> Result<WalletSummary, WalletError> {
+    Ok(WalletSummary {
+        account,
+        master_fingerprint: hex::encode(hd_derivation::master_fingerprint(seed)?),
+        addresses: derive_all_addresses(seed, account)?,
+    })
+}
+
+/// Upper bound on `count` for [`derive_address_range`]; a gap-limit scan
+/// (BIP-44 suggests a gap of 20) never needs anywhere near this many.
+pub const MAX_ADDRESS_RANGE: u32 = 1_000;
+
+/// Derive receive addresses for indices `start..start + count` in one pass
+///
+/// Used by wallet restore to scan for used addresses under the BIP-44 gap
+/// limit. Solana addresses have no index level (one address per account), so
+/// Solana is rejected; scan accounts with [`derive_address`] instead.
+pub fn derive_address_range(
+    seed: &[u8],
+    chain: Chain,
+    account: u32,
+    start: u32,
+    count: u32,
+) -> Result<Vec<DerivedAddress>, WalletError> {
+    if count > MAX_ADDRESS_RANGE {
+        return Err(WalletError::DerivationFailed {
+            message: format!("cannot derive more than {MAX_ADDRESS_RANGE} addresses at once"),
+        });
+    }
+    if matches!(chain, Chain::Solana | Chain::SolanaDevnet) {
+        return Err(WalletError::UnsupportedChain {
+            message: "Solana has no address index; scan accounts instead".into(),
+        });
+    }
+
+    hd_derivation::derive_secp256k1_keys(seed, chain, account, start, count)?
+        .iter()
+        .map(|key| secp256k1_address(chain, key))
+        .collect()
+}
+
+/// Upper bound on `count` for [`derive_account_range`]
+pub const MAX_ACCOUNT_RANGE: u32 = 100;
+
+/// Derive the index-0 receive address of accounts `start..start + count`
+///
+/// Works for every chain, Solana included (its one address per account is
+/// exactly what this returns). The caller derives the seed once for the batch.
+pub fn derive_account_range(
+    seed: &[u8],
+    chain: Chain,
+    start_account: u32,
+    count: u32,
+) -> Result<Vec<DerivedAddress>, WalletError> {
+    if count > MAX_ACCOUNT_RANGE {
+        return Err(WalletError::DerivationFailed {
+            message: format!("cannot derive more than {MAX_ACCOUNT_RANGE} accounts at once"),
+        });
+    }
+
+    (0..count)
+        .map(|offset| {
+            let account =
+                start_account
+                    .checked_add(offset)
+                    .ok_or_else(|| WalletError::DerivationFailed {
+                        message: "account index overflows u32".into(),
+                    })?;
+            derive_address(seed, chain, account, 0)
+        })
+        .collect()
+}
+
+/// Upper bound on `max_index` for [`find_vanity_index`]
+pub const MAX_VANITY_INDEX: u32 = 100_000;
+
+/// First index in `0..=max_index` whose receive address starts with `prefix`
+///
+/// EVM matching ignores case and an optional `0x` on either side; Bitcoin
+/// (bech32) also ignores case; Solana and Zcash are case-sensitive. Solana has
+/// one address per account, so there the scan runs over accounts and
+/// `account` is ignored. Returns `None` if nothing matches within the bound.
+pub fn find_vanity_index(
+    seed: &[u8],
+    chain: Chain,
+    account: u32,
+    prefix: &str,
+    max_index: u32,
+) -> Result<Option<u32>, WalletError> {
+    if max_index > MAX_VANITY_INDEX {
+        return Err(WalletError::DerivationFailed {
+            message: format!("cannot scan more than {MAX_VANITY_INDEX} indices"),
+        });
+    }
+
+    if matches!(chain, Chain::Solana | Chain::SolanaDevnet) {
+        for candidate in 0..=max_index {
+            let derived = derive_address(seed, chain, candidate, 0)?;
+            if matches_vanity_prefix(chain, &derived.address, prefix) {
+                return Ok(Some(candidate));
+            }
+        }
+        return Ok(None);
+    }
+
+    // Batches of MAX_ADDRESS_RANGE share one hardened parent derivation.
+    let mut start = 0u32;
+    loop {
+        let count = (max_index - start).min(MAX_ADDRESS_RANGE - 1) + 1;
+        let batch = derive_address_range(seed, chain, account, start, count)?;
+        if let Some(offset) = batch
+            .iter()
+            .position(|derived| matches_vanity_prefix(chain, &derived.address, prefix))
+        {
+            return Ok(Some(start + offset as u32));
+        }
+        if start + count > max_index {
+            return Ok(None);
+        }
+        start += count;
+    }
+}
+
+fn matches_vanity_prefix(chain: Chain, address: &str, prefix: &str) -> bool {
+    match chain {
+        Chain::Bitcoin | Chain::BitcoinTestnet => {
+            address.len() >= prefix.len() && address[..prefix.len()].eq_ignore_ascii_case(prefix)
+        }
+
+        Chain::Ethereum
+        | Chain::Polygon
+        | Chain::Arbitrum
+        | Chain::Base
+        | Chain::Optimism
+        | Chain::Bsc
+        | Chain::Avalanche
+        | Chain::Sepolia
+        | Chain::PolygonAmoy => {
+            let hex = address.trim_start_matches("0x");
+            let prefix = prefix.strip_prefix("0x").unwrap_or(prefix);
+            hex.len() >= prefix.len() && hex[..prefix.len()].eq_ignore_ascii_case(prefix)
+        }
+
+        Chain::Solana | Chain::SolanaDevnet | Chain::Zcash | Chain::ZcashTestnet => {
+            address.starts_with(prefix)
+        }
+    }
+}
+
+/// Picks fresh change indices from the set the app has already used
+///
+/// The crate keeps no wallet state, so the app supplies the used change
+/// indices (from its own records or a chain scan) and gets back the lowest
+/// unused one. Filling gaps first keeps change inside the restore gap limit.
+#[derive(Debug, Clone, Default)]
+pub struct ChangeIndexTracker {
+    used: BTreeSet<u32>,
+}
+
+impl ChangeIndexTracker {
+    /// Start from the change indices already used on-chain
+    pub fn new(used: impl IntoIterator<Item = u32>) -> Self {
+        Self {
+            used: used.into_iter().collect(),
+        }
+    }
+
+    /// Lowest change index not yet used
+    pub fn next_index(&self) -> Result<u32, WalletError> {
+        // `used` is sorted, so the first index that differs from its
+        // position is the first gap.
+        let next = self
+            .used
+            .iter()
+            .zip(0u32..)
+            .find(|(used, expected)| *used != expected)
+            .map_or(self.used.len() as u32, |(_, expected)| expected);
+
+        if next >= HARDENED_OFFSET {
+            return Err(WalletError::DerivationFailed {
+                message: "no unused change index left".into(),
+            });
+        }
+        Ok(next)
+    }
+
+    /// Record `index` as used, e.g. once a transaction paying change to it is signed
+    pub fn mark_used(&mut self, index: u32) {
+        self.used.insert(index);
+    }
+
+    /// The change address at [`Self::next_index`] (`m/.../account'/1/index`)
+    ///
+    /// Only UTXO chains (BTC, ZEC) have change addresses.
+    pub fn next_change_address(
+        &self,
+        seed: &[u8],
+        chain: Chain,
+        account: u32,
+    ) -> Result<DerivedAddress, WalletError> {
+        let index = self.next_index()?;
+        let key = hd_derivation::derive_secp256k1_change_key(seed, chain, account, index)?;
+        secp256k1_address(chain, &key)
+    }
+}
+
+/// Non-hardened BIP-32 indices stop below 2^31
+const HARDENED_OFFSET: u32 = 0x8000_0000;
+
+/// Short, non-sensitive checksum of an address for display
+///
+/// The first two bytes of SHA-256(address) as four uppercase hex characters.
+/// Lets the UI show "this passphrase gives wallet 3F9A" so a user can notice a
+/// mistyped BIP-39 passphrase, which otherwise silently opens a different,
+/// empty wallet. Not unique: it is a confirmation aid, not an identifier.
+pub fn address_fingerprint(address: &str) -> String {
+    let digest = Sha256::digest(address.as_bytes());
+    hex::encode_upper(&digest[..2])
+}
+
 fn derive_btc_address(
     seed: &[u8],
     chain: Chain,
@@ -54,20 +298,7 @@ fn derive_btc_address(
     index: u32,
 ) -> Result<DerivedAddress, WalletError> {
     let key = hd_derivation::derive_secp256k1_key(seed, chain, account, index)?;
-
-    let network = match chain {
-        Chain::BitcoinTestnet => chain_btc::network::BtcNetwork::Testnet,
-        _ => chain_btc::network::BtcNetwork::Mainnet,
-    };
-
-    let address =
-        chain_btc::address::pubkey_to_p2wpkh_address(&key.public_key_compressed, network)?;
-
-    Ok(DerivedAddress {
-        chain,
-        address,
-        derivation_path: key.derivation_path.clone(),
-    })
+    secp256k1_address(chain, &key)
 }
 
 fn derive_eth_address(
@@ -77,15 +308,7 @@ fn derive_eth_address(
     index: u32,
 ) -> Result<DerivedAddress, WalletError> {
     let key = hd_derivation::derive_secp256k1_key(seed, chain, account, index)?;
-
-    let address =
-        chain_eth::address::pubkey_bytes_to_eth_address(&key.public_key_compressed)?;
-
-    Ok(DerivedAddress {
-        chain,
-        address,
-        derivation_path: key.derivation_path.clone(),
-    })
+    secp256k1_address(chain, &key)
 }
 
 fn derive_sol_address(
@@ -111,14 +334,46 @@ fn derive_zec_address(
     index: u32,
 ) -> Result<DerivedAddress, WalletError> {
     let key = hd_derivation::derive_secp256k1_key(seed, chain, account, index)?;
+    secp256k1_address(chain, &key)
+}
 
-    let network = match chain {
-        Chain::ZcashTestnet => chain_zec::address::ZecNetwork::Testnet,
-        _ => chain_zec::address::ZecNetwork::Mainnet,
-    };
+/// Encode a derived secp256k1 key as the chain's receive address
+fn secp256k1_address(chain: Chain, key: &DerivedKey) -> Result<DerivedAddress, WalletError> {
+    let address = match chain {
+        Chain::Bitcoin | Chain::BitcoinTestnet => {
+            let network = match chain {
+                Chain::BitcoinTestnet => chain_btc::network::BtcNetwork::Testnet,
+                _ => chain_btc::network::BtcNetwork::Mainnet,
+            };
+            chain_btc::address::pubkey_to_p2wpkh_address(&key.public_key_compressed, network)?
+        }
 
-    let address =
-        chain_zec::address::pubkey_to_t_address(&key.public_key_compressed, network)?;
+        Chain::Zcash | Chain::ZcashTestnet => {
+            let network = match chain {
+                Chain::ZcashTestnet => chain_zec::address::ZecNetwork::Testnet,
+                _ => chain_zec::address::ZecNetwork::Mainnet,
+            };
+            chain_zec::address::pubkey_to_t_address(&key.public_key_compressed, network)?
+        }
+
+        Chain::Solana | Chain::SolanaDevnet => {
+            return Err(WalletError::UnsupportedChain {
+                message: "Solana keys are not secp256k1".into(),
+            })
+        }
+
+        Chain::Ethereum
+        | Chain::Polygon
+        | Chain::Arbitrum
+        | Chain::Base
+        | Chain::Optimism
+        | Chain::Bsc
+        | Chain::Avalanche
+        | Chain::Sepolia
+        | Chain::PolygonAmoy => {
+            chain_eth::address::pubkey_bytes_to_eth_address(&key.public_key_compressed)?
+        }
+    };
 
     Ok(DerivedAddress {
         chain,
@@ -128,15 +383,24 @@ fn derive_zec_address(
 }
 
 /// Validate an address for a given chain
+///
+/// The chain fixes the network: `Ok(false)` means a well-formed address for
+/// another network (a `t1...` Zcash address under `ZcashTestnet`, say).
+/// `BitcoinTestnet` also accepts signet addresses, which share the testnet
+/// encoding, and Solana addresses are the same on every cluster.
 pub fn validate_address(address: &str, chain: Chain) -> Result<bool, WalletError> {
     match chain {
         Chain::Bitcoin => {
             chain_btc::address::validate_address(address, chain_btc::network::BtcNetwork::Mainnet)
-                .map_err(|e| WalletError::InvalidAddress(e.to_string()))
+                .map_err(|e| WalletError::InvalidAddress {
+                    message: e.to_string(),
+                })
         }
         Chain::BitcoinTestnet => {
             chain_btc::address::validate_address(address, chain_btc::network::BtcNetwork::Testnet)
-                .map_err(|e| WalletError::InvalidAddress(e.to_string()))
+                .map_err(|e| WalletError::InvalidAddress {
+                    message: e.to_string(),
+                })
         }
         Chain::Ethereum
         | Chain::Polygon
@@ -146,20 +410,27 @@ pub fn validate_address(address: &str, chain: Chain) -> Result<bool, WalletError
         | Chain::Bsc
         | Chain::Avalanche
         | Chain::Sepolia
-        | Chain::PolygonAmoy => chain_eth::address::validate_address(address)
-            .map_err(|e| WalletError::InvalidAddress(e.to_string())),
+        | Chain::PolygonAmoy => {
+            chain_eth::address::validate_address(address).map_err(|e| WalletError::InvalidAddress {
+                message: e.to_string(),
+            })
+        }
         Chain::Solana | Chain::SolanaDevnet => chain_sol::address::validate_address(address)
-            .map_err(|e| WalletError::InvalidAddress(e.to_string())),
-        Chain::Zcash => chain_zec::address::validate_address(
-            address,
-            chain_zec::address::ZecNetwork::Mainnet,
-        )
-        .map_err(|e| WalletError::InvalidAddress(e.to_string())),
-        Chain::ZcashTestnet => chain_zec::address::validate_address(
-            address,
-            chain_zec::address::ZecNetwork::Testnet,
-        )
-        .map_err(|e| WalletError::InvalidAddress(e.to_string())),
+            .map_err(|e| WalletError::InvalidAddress {
+                message: e.to_string(),
+            }),
+        Chain::Zcash => {
+            chain_zec::address::validate_address(address, chain_zec::address::ZecNetwork::Mainnet)
+                .map_err(|e| WalletError::InvalidAddress {
+                    message: e.to_string(),
+                })
+        }
+        Chain::ZcashTestnet => {
+            chain_zec::address::validate_address(address, chain_zec::address::ZecNetwork::Testnet)
+                .map_err(|e| WalletError::InvalidAddress {
+                    message: e.to_string(),
+                })
+        }
     }
 }
 
@@ -174,6 +445,172 @@ mod tests {
         mnemonic_to_seed(TEST_MNEMONIC, "").unwrap()
     }
 
+    #[test]
+    fn test_derive_address_range() {
+        let seed = test_seed();
+        let range = derive_address_range(&seed, Chain::Bitcoin, 0, 0, 20).unwrap();
+
+        assert_eq!(range.len(), 20);
+        let unique: std::collections::HashSet<_> = range.iter().map(|a| &a.address).collect();
+        assert_eq!(unique.len(), 20);
+        assert_eq!(
+            range[0].address,
+            derive_address(&seed, Chain::Bitcoin, 0, 0).unwrap().address
+        );
+        assert_eq!(range[19].derivation_path, "m/84'/0'/0'/0/19");
+    }
+
+    #[test]
+    fn test_derive_evm_addresses() {
+        let seed = test_seed();
+        let addresses = derive_evm_addresses(&seed, 0, 3).unwrap();
+        let expected = derive_address(&seed, Chain::Ethereum, 0, 3).unwrap();
+
+        assert!(addresses.iter().all(|a| a.address == expected.address));
+        assert!(addresses
+            .iter()
+            .all(|a| a.derivation_path == expected.derivation_path));
+
+        let ids: Vec<u64> = addresses
+            .iter()
+            .map(|a| a.chain.evm_chain_id().unwrap())
+            .collect();
+        let supported: Vec<u64> = chain_eth::chains::supported_chains()
+            .iter()
+            .map(|c| c.chain_id)
+            .collect();
+        assert_eq!(ids, supported);
+    }
+
+    #[test]
+    fn test_derive_account_range() {
+        let seed = test_seed();
+        let accounts = derive_account_range(&seed, Chain::Ethereum, 0, 3).unwrap();
+
+        assert_eq!(accounts.len(), 3);
+        let unique: std::collections::HashSet<_> = accounts.iter().map(|a| &a.address).collect();
+        assert_eq!(unique.len(), 3);
+        for (account, derived) in accounts.iter().enumerate() {
+            assert_eq!(derived.derivation_path, format!("m/44'/60'/{account}'/0/0"));
+        }
+
+        let sol = derive_account_range(&seed, Chain::Solana, 5, 2).unwrap();
+        assert_eq!(sol[1].derivation_path, "m/44'/501'/6'/0'");
+        assert_eq!(
+            sol[1].address,
+            derive_address(&seed, Chain::Solana, 6, 0).unwrap().address
+        );
+    }
+
+    #[test]
+    fn test_derive_account_range_rejects_oversized_and_overflow() {
+        let seed = test_seed();
+        assert!(derive_account_range(&seed, Chain::Bitcoin, 0, MAX_ACCOUNT_RANGE + 1).is_err());
+        assert!(derive_account_range(&seed, Chain::Bitcoin, u32::MAX, 2).is_err());
+    }
+
+    #[test]
+    fn test_find_vanity_index_single_char() {
+        let seed = test_seed();
+        let found = find_vanity_index(&seed, Chain::Ethereum, 0, "0xA", 200)
+            .unwrap()
+            .unwrap();
+
+        let range = derive_address_range(&seed, Chain::Ethereum, 0, 0, found + 1).unwrap();
+        assert!(range[found as usize].address[2..]
+            .to_lowercase()
+            .starts_with('a'));
+        assert!(range[..found as usize]
+            .iter()
+            .all(|derived| !derived.address[2..].to_lowercase().starts_with('a')));
+    }
+
+    #[test]
+    fn test_find_vanity_index_solana_scans_accounts() {
+        let seed = test_seed();
+        let target = derive_address(&seed, Chain::Solana, 4, 0).unwrap().address;
+
+        let found = find_vanity_index(&seed, Chain::Solana, 0, &target[..3], 10)
+            .unwrap()
+            .unwrap();
+        assert!(found <= 4);
+        let derived = derive_address(&seed, Chain::Solana, found, 0).unwrap();
+        assert!(derived.address.starts_with(&target[..3]));
+    }
+
+    #[test]
+    fn test_find_vanity_index_gives_up_at_bound() {
+        let seed = test_seed();
+        assert_eq!(
+            find_vanity_index(&seed, Chain::Ethereum, 0, "ffffffffffff", 20).unwrap(),
+            None
+        );
+        assert!(find_vanity_index(&seed, Chain::Ethereum, 0, "a", MAX_VANITY_INDEX + 1).is_err());
+    }
+
+    #[test]
+    fn test_change_index_tracker_fills_gaps() {
+        let mut tracker = ChangeIndexTracker::new([0, 1, 3]);
+        assert_eq!(tracker.next_index().unwrap(), 2);
+
+        tracker.mark_used(2);
+        assert_eq!(tracker.next_index().unwrap(), 4);
+
+        assert_eq!(ChangeIndexTracker::default().next_index().unwrap(), 0);
+    }
+
+    #[test]
+    fn test_change_index_tracker_derives_change_address() {
+        let seed = test_seed();
+        let tracker = ChangeIndexTracker::new([0, 1, 3]);
+
+        let change = tracker
+            .next_change_address(&seed, Chain::Bitcoin, 0)
+            .unwrap();
+        let key = hd_derivation::derive_secp256k1_change_key(&seed, Chain::Bitcoin, 0, 2).unwrap();
+        assert_eq!(change.derivation_path, "m/84'/0'/0'/1/2");
+        assert_eq!(
+            change.address,
+            secp256k1_address(Chain::Bitcoin, &key).unwrap().address
+        );
+
+        assert!(tracker
+            .next_change_address(&seed, Chain::Ethereum, 0)
+            .is_err());
+    }
+
+    #[test]
+    fn test_derive_address_range_rejects_solana_and_oversized() {
+        let seed = test_seed();
+        assert!(derive_address_range(&seed, Chain::Solana, 0, 0, 5).is_err());
+        assert!(derive_address_range(&seed, Chain::Ethereum, 0, 0, MAX_ADDRESS_RANGE + 1).is_err());
+    }
+
+    #[test]
+    fn test_address_fingerprint_format() {
+        let fp = address_fingerprint("bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu");
+        assert_eq!(fp.len(), 4);
+        assert!(fp
+            .chars()
+            .all(|c| c.is_ascii_digit() || c.is_ascii_uppercase()));
+        assert_eq!(
+            fp,
+            address_fingerprint("bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu")
+        );
+    }
+
+    #[test]
+    fn test_address_fingerprint_differs_per_passphrase() {
+        let plain = derive_address(&test_seed(), Chain::Ethereum, 0, 0).unwrap();
+        let with_pass = mnemonic_to_seed(TEST_MNEMONIC, "TREZOR").unwrap();
+        let hidden = derive_address(&with_pass, Chain::Ethereum, 0, 0).unwrap();
+
+        assert_ne!(
+            address_fingerprint(&plain.address),
+            address_fingerprint(&hidden.address)
+        );
+    }
+
     #[test]
     fn test_derive_btc_address() {
         let seed = test_seed();
@@ -207,6 +644,39 @@ mod tests {
         assert_eq!(addresses.len(), 4); // BTC, ETH, SOL, ZEC
     }
 
+    #[test]
+    fn test_derive_zcash_addresses() {
+        let seed = test_seed();
+        let cases = [
+            (Chain::Zcash, "t1", "m/44'/133'/0'/0/0"),
+            (Chain::ZcashTestnet, "tm", "m/44'/1'/0'/0/0"),
+        ];
+
+        for (chain, prefix, path) in cases {
+            let addr = derive_address(&seed, chain, 0, 0).unwrap();
+            assert!(
+                addr.address.starts_with(prefix),
+                "{chain:?}: {}",
+                addr.address
+            );
+            assert_eq!(addr.derivation_path, path);
+
+            // The t-address commits to the key derived at that path.
+            let key = hd_derivation::derive_secp256k1_key(&seed, chain, 0, 0).unwrap();
+            assert_eq!(
+                chain_zec::address::address_to_pubkey_hash(&addr.address).unwrap(),
+                chain_zec::address::hash160(&key.public_key_compressed)
+            );
+        }
+    }
+
+    #[test]
+    fn test_derive_all_addresses_includes_zcash() {
+        let addresses = derive_all_addresses(&test_seed(), 0).unwrap();
+        let zec = addresses.iter().find(|a| a.chain == Chain::Zcash).unwrap();
+        assert!(zec.address.starts_with("t1"));
+    }
+
     #[test]
     fn test_evm_chains_same_address() {
         let seed = test_seed();
@@ -218,6 +688,42 @@ mod tests {
         assert_eq!(eth_addr.address, arb_addr.address);
     }
 
+    /// P2PKH t-addresses of private key 1 (pubkey hash 751e76e8...3bd6).
+    const ZEC_MAINNET_ADDR: &str = "t1UYsZVJkLPeMjxEtACvSxfWuNmddpWfxzs";
+    const ZEC_TESTNET_ADDR: &str = "tmLPctKo9j49rtCSKpwEBpLBeykiTGomGQs";
+
+    #[test]
+    fn test_validate_zcash_address_per_network() {
+        assert!(validate_address(ZEC_MAINNET_ADDR, Chain::Zcash).unwrap());
+        assert!(!validate_address(ZEC_MAINNET_ADDR, Chain::ZcashTestnet).unwrap());
+        assert!(validate_address(ZEC_TESTNET_ADDR, Chain::ZcashTestnet).unwrap());
+        assert!(!validate_address(ZEC_TESTNET_ADDR, Chain::Zcash).unwrap());
+        assert!(validate_address("t1notbase58check", Chain::Zcash).is_err());
+    }
+
+    #[test]
+    fn test_validate_derived_zcash_addresses() {
+        let seed = test_seed();
+        for chain in [Chain::Zcash, Chain::ZcashTestnet] {
+            let derived = derive_address(&seed, chain, 0, 0).unwrap();
+            assert!(validate_address(&derived.address, chain).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_validate_signet_and_devnet_addresses() {
+        use chain_btc::network::BtcNetwork;
+
+        // Signet shares testnet's tb1 encoding, so BitcoinTestnet covers it.
+        let tb1 = "tb1qw508d6qejxtdg4y5r3zarvary0c5xw7kxpjzsx";
+        assert!(chain_btc::address::validate_address(tb1, BtcNetwork::Signet).unwrap());
+        assert!(validate_address(tb1, Chain::BitcoinTestnet).unwrap());
+        assert!(!validate_address(tb1, Chain::Bitcoin).unwrap());
+
+        let sol = derive_address(&test_seed(), Chain::SolanaDevnet, 0, 0).unwrap();
+        assert!(validate_address(&sol.address, Chain::SolanaDevnet).unwrap());
+    }
+
     #[test]
     fn test_addresses_deterministic() {
         let seed = test_seed();
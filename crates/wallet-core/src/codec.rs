@@ -0,0 +1,162 @@
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+
+use crate::error::WalletError;
+
+/// Decode a hex string into bytes.
+///
+/// A leading `0x`/`0X` (as used for Ethereum data) is accepted, and both
+/// upper- and lowercase digits are allowed.
+pub fn hex_to_bytes(hex: &str) -> Result<Vec<u8>, WalletError> {
+    let digits = hex
+        .strip_prefix("0x")
+        .or_else(|| hex.strip_prefix("0X"))
+        .unwrap_or(hex);
+    hex::decode(digits).map_err(|e| WalletError::InvalidEncoding {
+        message: format!("hex: {e}"),
+    })
+}
+
+/// Encode bytes as lowercase hex without a `0x` prefix
+pub fn bytes_to_hex(data: &[u8]) -> String {
+    hex::encode(data)
+}
+
+/// Decode a Base58 string (Bitcoin alphabet, as used by Solana) into bytes.
+///
+/// No checksum is expected; use the chain address validators for Base58Check.
+pub fn base58_to_bytes(base58: &str) -> Result<Vec<u8>, WalletError> {
+    bs58::decode(base58)
+        .into_vec()
+        .map_err(|e| WalletError::InvalidEncoding {
+            message: format!("base58: {e}"),
+        })
+}
+
+/// Encode bytes as Base58 (Bitcoin alphabet, no checksum)
+pub fn bytes_to_base58(data: &[u8]) -> String {
+    bs58::encode(data).into_string()
+}
+
+/// Decode a standard (RFC 4648, padded) Base64 string into bytes
+pub fn base64_to_bytes(base64: &str) -> Result<Vec<u8>, WalletError> {
+    BASE64
+        .decode(base64)
+        .map_err(|e| WalletError::InvalidEncoding {
+            message: format!("base64: {e}"),
+        })
+}
+
+/// Encode bytes as standard (RFC 4648, padded) Base64
+pub fn bytes_to_base64(data: &[u8]) -> String {
+    BASE64.encode(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &[u8] = &[0x00, 0x00, 0x01, 0x7f, 0x80, 0xde, 0xad, 0xbe, 0xef, 0xff];
+
+    // ─── Hex ────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_hex_roundtrip() {
+        let encoded = bytes_to_hex(SAMPLE);
+        assert_eq!(encoded, "0000017f80deadbeefff");
+        assert_eq!(hex_to_bytes(&encoded).unwrap(), SAMPLE);
+    }
+
+    #[test]
+    fn test_hex_accepts_prefix_and_uppercase() {
+        assert_eq!(
+            hex_to_bytes("0xDEADbeef").unwrap(),
+            vec![0xde, 0xad, 0xbe, 0xef]
+        );
+        assert_eq!(
+            hex_to_bytes("0XdeadBEEF").unwrap(),
+            vec![0xde, 0xad, 0xbe, 0xef]
+        );
+        assert!(hex_to_bytes("0x").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_hex_rejects_malformed() {
+        assert!(matches!(
+            hex_to_bytes("abc"),
+            Err(WalletError::InvalidEncoding { .. })
+        ));
+        assert!(matches!(
+            hex_to_bytes("zz"),
+            Err(WalletError::InvalidEncoding { .. })
+        ));
+        assert!(matches!(
+            hex_to_bytes("0x0x00"),
+            Err(WalletError::InvalidEncoding { .. })
+        ));
+    }
+
+    // ─── Base58 ─────────────────────────────────────────────────────
+
+    #[test]
+    fn test_base58_roundtrip() {
+        let encoded = bytes_to_base58(SAMPLE);
+        // Leading zero bytes become leading '1's.
+        assert!(encoded.starts_with("11"));
+        assert_eq!(base58_to_bytes(&encoded).unwrap(), SAMPLE);
+    }
+
+    #[test]
+    fn test_base58_system_program_id() {
+        assert_eq!(
+            base58_to_bytes("11111111111111111111111111111111").unwrap(),
+            vec![0u8; 32]
+        );
+        assert_eq!(
+            bytes_to_base58(&[0u8; 32]),
+            "11111111111111111111111111111111"
+        );
+    }
+
+    #[test]
+    fn test_base58_rejects_malformed() {
+        // '0', 'O', 'I' and 'l' are not in the Base58 alphabet.
+        for bad in ["0abc", "abcO", "Iabc", "abcl", "ab c"] {
+            assert!(
+                matches!(
+                    base58_to_bytes(bad),
+                    Err(WalletError::InvalidEncoding { .. })
+                ),
+                "{bad} should be rejected"
+            );
+        }
+    }
+
+    // ─── Base64 ─────────────────────────────────────────────────────
+
+    #[test]
+    fn test_base64_roundtrip() {
+        let encoded = bytes_to_base64(SAMPLE);
+        assert_eq!(encoded, "AAABf4Derb7v/w==");
+        assert_eq!(base64_to_bytes(&encoded).unwrap(), SAMPLE);
+    }
+
+    #[test]
+    fn test_base64_empty() {
+        assert_eq!(bytes_to_base64(&[]), "");
+        assert!(base64_to_bytes("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_base64_rejects_malformed() {
+        for bad in ["AAABf4Derb7v/w", "AA=A", "AA*A", "-_-_"] {
+            assert!(
+                matches!(
+                    base64_to_bytes(bad),
+                    Err(WalletError::InvalidEncoding { .. })
+                ),
+                "{bad} should be rejected"
+            );
+        }
+    }
+}
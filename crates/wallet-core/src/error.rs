@@ -1,67 +1,201 @@
 use thiserror::Error;
 
+/// Errors returned across the FFI boundary.
+///
+/// Every variant is exported to Swift with its fields, so callers can branch
+/// on the kind of failure and read structured data such as the shortfall in
+/// [`WalletError::InsufficientFunds`].
 #[derive(Debug, Error)]
 pub enum WalletError {
-    #[error("Invalid mnemonic: {0}")]
-    InvalidMnemonic(String),
+    #[error("Invalid mnemonic: {message}")]
+    InvalidMnemonic { message: String },
 
-    #[error("Key derivation failed: {0}")]
-    DerivationFailed(String),
+    #[error("Key derivation failed: {message}")]
+    DerivationFailed { message: String },
 
-    #[error("Encryption failed: {0}")]
-    EncryptionFailed(String),
+    #[error("Encryption failed: {message}")]
+    EncryptionFailed { message: String },
 
-    #[error("Decryption failed: {0}")]
-    DecryptionFailed(String),
+    #[error("Decryption failed: {message}")]
+    DecryptionFailed { message: String },
 
-    #[error("Invalid seed: {0}")]
-    InvalidSeed(String),
+    #[error("Invalid seed: {message}")]
+    InvalidSeed { message: String },
 
-    #[error("Invalid private key: {0}")]
-    InvalidPrivateKey(String),
+    #[error("Invalid private key: {message}")]
+    InvalidPrivateKey { message: String },
 
-    #[error("Invalid address: {0}")]
-    InvalidAddress(String),
+    #[error("Invalid address: {message}")]
+    InvalidAddress { message: String },
 
-    #[error("Unsupported chain: {0}")]
-    UnsupportedChain(String),
+    #[error("Invalid encoding: {message}")]
+    InvalidEncoding { message: String },
 
-    #[error("Signing failed: {0}")]
-    SigningFailed(String),
+    #[error("Unsupported chain: {message}")]
+    UnsupportedChain { message: String },
 
-    #[error("Transaction build failed: {0}")]
-    TransactionFailed(String),
+    #[error("Signing failed: {message}")]
+    SigningFailed { message: String },
 
-    #[error("Internal error: {0}")]
-    Internal(String),
+    #[error("Transaction build failed: {message}")]
+    TransactionFailed { message: String },
+
+    /// Selected inputs don't cover amount + fee. Amounts are in the chain's
+    /// base unit (satoshi / zatoshi).
+    #[error("Insufficient funds: need {needed}, have {available}")]
+    InsufficientFunds { needed: u64, available: u64 },
+
+    #[error("Internal error: {message}")]
+    Internal { message: String },
 }
 
 impl From<crypto_utils::error::CryptoError> for WalletError {
     fn from(e: crypto_utils::error::CryptoError) -> Self {
-        WalletError::EncryptionFailed(e.to_string())
+        WalletError::EncryptionFailed {
+            message: e.to_string(),
+        }
     }
 }
 
 impl From<chain_btc::error::BtcError> for WalletError {
     fn from(e: chain_btc::error::BtcError) -> Self {
-        WalletError::TransactionFailed(format!("BTC: {e}"))
+        use chain_btc::error::BtcError;
+        let message = format!("BTC: {e}");
+        match e {
+            BtcError::InsufficientFunds { needed, available } => {
+                WalletError::InsufficientFunds { needed, available }
+            }
+            BtcError::InvalidAddress(_) => WalletError::InvalidAddress { message },
+            BtcError::InvalidPrivateKey(_) => WalletError::InvalidPrivateKey { message },
+            BtcError::SigningError(_) => WalletError::SigningFailed { message },
+            BtcError::InvalidNetwork(_) => WalletError::UnsupportedChain { message },
+            BtcError::InvalidPublicKey(_) | BtcError::TransactionBuildError(_) => {
+                WalletError::TransactionFailed { message }
+            }
+        }
     }
 }
 
 impl From<chain_eth::error::EthError> for WalletError {
     fn from(e: chain_eth::error::EthError) -> Self {
-        WalletError::TransactionFailed(format!("ETH: {e}"))
+        use chain_eth::error::EthError;
+        let message = format!("ETH: {e}");
+        match e {
+            EthError::InvalidAddress(_) => WalletError::InvalidAddress { message },
+            EthError::InvalidPrivateKey(_) => WalletError::InvalidPrivateKey { message },
+            EthError::SigningError(_) => WalletError::SigningFailed { message },
+            EthError::UnsupportedChain(_) => WalletError::UnsupportedChain { message },
+            EthError::InvalidPublicKey(_)
+            | EthError::TransactionBuildError(_)
+            | EthError::EncodingError(_) => WalletError::TransactionFailed { message },
+        }
     }
 }
 
 impl From<chain_sol::error::SolError> for WalletError {
     fn from(e: chain_sol::error::SolError) -> Self {
-        WalletError::TransactionFailed(format!("SOL: {e}"))
+        use chain_sol::error::SolError;
+        let message = format!("SOL: {e}");
+        match e {
+            SolError::InvalidAddress(_) => WalletError::InvalidAddress { message },
+            SolError::InvalidPrivateKey(_) => WalletError::InvalidPrivateKey { message },
+            SolError::SigningError(_) => WalletError::SigningFailed { message },
+            SolError::InvalidAmount(_) => WalletError::InvalidEncoding { message },
+            SolError::InvalidPublicKey(_)
+            | SolError::TransactionBuildError(_)
+            | SolError::SerializationError(_) => WalletError::TransactionFailed { message },
+        }
     }
 }
 
 impl From<chain_zec::error::ZecError> for WalletError {
     fn from(e: chain_zec::error::ZecError) -> Self {
-        WalletError::TransactionFailed(format!("ZEC: {e}"))
+        use chain_zec::error::ZecError;
+        let message = format!("ZEC: {e}");
+        match e {
+            ZecError::InsufficientFunds { needed, available } => {
+                WalletError::InsufficientFunds { needed, available }
+            }
+            ZecError::InvalidAddress(_) => WalletError::InvalidAddress { message },
+            ZecError::InvalidPrivateKey(_) => WalletError::InvalidPrivateKey { message },
+            ZecError::SigningError(_) => WalletError::SigningFailed { message },
+            ZecError::InvalidPublicKey(_) | ZecError::TransactionBuildError(_) => {
+                WalletError::TransactionFailed { message }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_btc_shortfall_keeps_amounts() {
+        let err: WalletError = chain_btc::error::BtcError::InsufficientFunds {
+            needed: 50_000,
+            available: 1_000,
+        }
+        .into();
+        assert!(matches!(
+            err,
+            WalletError::InsufficientFunds {
+                needed: 50_000,
+                available: 1_000
+            }
+        ));
+    }
+
+    #[test]
+    fn test_zec_shortfall_keeps_amounts() {
+        let err: WalletError = chain_zec::error::ZecError::InsufficientFunds {
+            needed: 7,
+            available: 3,
+        }
+        .into();
+        assert!(matches!(
+            err,
+            WalletError::InsufficientFunds {
+                needed: 7,
+                available: 3
+            }
+        ));
+    }
+
+    #[test]
+    fn test_chain_errors_keep_their_kind() {
+        let err: WalletError = chain_eth::error::EthError::InvalidAddress("bad".into()).into();
+        match err {
+            WalletError::InvalidAddress { message } => {
+                assert_eq!(message, "ETH: invalid address: bad")
+            }
+            other => panic!("expected InvalidAddress, got: {other}"),
+        }
+
+        let err: WalletError = chain_sol::error::SolError::SigningError("fail".into()).into();
+        assert!(matches!(err, WalletError::SigningFailed { .. }));
+
+        let err: WalletError =
+            chain_btc::error::BtcError::TransactionBuildError("no UTXOs".into()).into();
+        assert!(matches!(err, WalletError::TransactionFailed { .. }));
+    }
+
+    #[test]
+    fn test_insufficient_funds_lowers_with_amounts() {
+        use uniffi::LowerError;
+
+        let err = WalletError::InsufficientFunds {
+            needed: 0x0102_0304_0506_0708,
+            available: 42,
+        };
+        let buf =
+            <WalletError as LowerError<crate::UniFfiTag>>::lower_error(err).destroy_into_vec();
+
+        // Variant index (1-based, declaration order) followed by the fields as
+        // big-endian u64s.
+        assert_eq!(&buf[..4], &12i32.to_be_bytes());
+        assert_eq!(&buf[4..12], &0x0102_0304_0506_0708u64.to_be_bytes());
+        assert_eq!(&buf[12..20], &42u64.to_be_bytes());
+        assert_eq!(buf.len(), 20);
     }
 }
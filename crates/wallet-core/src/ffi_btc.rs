@@ -1,7 +1,10 @@
 use crate::error::WalletError;
+use crate::ffi_common::{validate_send_amount, with_zeroized_seed};
 use crate::hd_derivation;
-use crate::types::Chain;
-use zeroize::Zeroize;
+use crate::types::{Chain, FeeQuote, SignedUtxoTransaction, TxBreakdown};
+use chain_btc::address::BtcAddress;
+use std::ops::Deref;
+use zeroize::{Zeroize, Zeroizing};
 
 /// UTXO data passed from Swift for Bitcoin transaction signing
 pub struct UtxoData {
@@ -11,64 +14,706 @@ pub struct UtxoData {
     pub script_pubkey: Vec<u8>,
 }
 
-/// Execute a closure with the seed, guaranteeing zeroization on both success and error paths.
-fn with_zeroized_seed<F, T>(mut seed: Vec<u8>, f: F) -> Result<T, WalletError>
-where
-    F: FnOnce(&[u8]) -> Result<T, WalletError>,
-{
-    let result = f(&seed);
-    seed.zeroize();
-    result
+/// A UTXO together with the receive index (`m/84'/coin'/account'/0/index`)
+/// of the address that owns it
+pub struct IndexedUtxoData {
+    pub utxo: UtxoData,
+    pub index: u32,
+}
+
+/// Fee rates above this (sat/vB) are almost always a unit mix-up (e.g. sat/kvB)
+const HIGH_FEE_RATE_SAT_VBYTE: u64 = 500;
+
+fn chain_and_network(is_testnet: bool) -> (Chain, chain_btc::network::BtcNetwork) {
+    if is_testnet {
+        (
+            Chain::BitcoinTestnet,
+            chain_btc::network::BtcNetwork::Testnet,
+        )
+    } else {
+        (Chain::Bitcoin, chain_btc::network::BtcNetwork::Mainnet)
+    }
+}
+
+fn to_btc_utxos(utxos: Vec<UtxoData>) -> Vec<chain_btc::utxo::Utxo> {
+    utxos
+        .into_iter()
+        .map(|u| chain_btc::utxo::Utxo {
+            txid: u.txid,
+            vout: u.vout,
+            amount_sat: u.amount_sat,
+            script_pubkey: u.script_pubkey,
+        })
+        .collect()
+}
+
+/// Build the unsigned transaction exactly as `sign_btc_transaction` would
+#[allow(clippy::too_many_arguments)]
+fn build_unsigned(
+    seed: &[u8],
+    is_testnet: bool,
+    account: u32,
+    change_index: u32,
+    utxos: &[chain_btc::utxo::Utxo],
+    recipient_address: &str,
+    amount_sat: u64,
+    change_address: Option<&str>,
+    fee_rate_sat_vbyte: u64,
+) -> Result<chain_btc::transaction::UnsignedBtcTx, WalletError> {
+    let (chain, network) = chain_and_network(is_testnet);
+    let recipient_address = BtcAddress::parse(recipient_address, network)?;
+
+    let unsigned_tx = match change_address {
+        Some(change_address) => chain_btc::transaction::build_p2wpkh_transaction(
+            utxos,
+            &recipient_address,
+            amount_sat,
+            &BtcAddress::parse(change_address, network)?,
+            fee_rate_sat_vbyte,
+            network,
+            None,
+            None,
+            false,
+        )?,
+        None => {
+            let change_key =
+                hd_derivation::derive_secp256k1_change_key(seed, chain, account, change_index)?;
+            chain_btc::transaction::build_p2wpkh_transaction_with_change_key(
+                utxos,
+                &recipient_address,
+                amount_sat,
+                &change_key.public_key_compressed,
+                fee_rate_sat_vbyte,
+                network,
+            )?
+        }
+    };
+    Ok(unsigned_tx)
 }
 
 /// Sign a Bitcoin P2WPKH transaction
+///
+/// If `change_address` is `None`, change is sent to the wallet's own change
+/// address at `m/84'/coin'/account'/1/change_index`. The result reports the
+/// address the change went to.
+#[allow(clippy::too_many_arguments)]
 pub fn sign_btc_transaction(
     seed: Vec<u8>,
     account: u32,
     index: u32,
+    change_index: u32,
     utxos: Vec<UtxoData>,
     recipient_address: String,
     amount_sat: u64,
-    change_address: String,
+    change_address: Option<String>,
     fee_rate_sat_vbyte: u64,
     is_testnet: bool,
-) -> Result<Vec<u8>, WalletError> {
-    let chain = if is_testnet { Chain::BitcoinTestnet } else { Chain::Bitcoin };
-    let network = if is_testnet {
-        chain_btc::network::BtcNetwork::Testnet
-    } else {
-        chain_btc::network::BtcNetwork::Mainnet
-    };
+) -> Result<SignedUtxoTransaction, WalletError> {
+    sign_btc_transaction_with_seed(
+        seed,
+        account,
+        index,
+        change_index,
+        utxos,
+        recipient_address,
+        amount_sat,
+        change_address,
+        fee_rate_sat_vbyte,
+        is_testnet,
+    )
+}
 
-    // Convert FFI UtxoData to chain_btc Utxo before entering closure
-    let btc_utxos: Vec<chain_btc::utxo::Utxo> = utxos
-        .into_iter()
-        .map(|u| chain_btc::utxo::Utxo {
-            txid: u.txid,
-            vout: u.vout,
-            amount_sat: u.amount_sat,
-            script_pubkey: u.script_pubkey,
-        })
-        .collect();
+/// [`sign_btc_transaction`] over any zeroizable seed container, e.g. a
+/// `ZeroizingBytes`. The amount is checked inside the zeroizing closure, so
+/// the seed is wiped even when it is rejected.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn sign_btc_transaction_with_seed<S>(
+    seed: S,
+    account: u32,
+    index: u32,
+    change_index: u32,
+    utxos: Vec<UtxoData>,
+    recipient_address: String,
+    amount_sat: u64,
+    change_address: Option<String>,
+    fee_rate_sat_vbyte: u64,
+    is_testnet: bool,
+) -> Result<SignedUtxoTransaction, WalletError>
+where
+    S: Deref<Target = [u8]> + Zeroize,
+{
+    let (chain, network) = chain_and_network(is_testnet);
+    let btc_utxos = to_btc_utxos(utxos);
 
     with_zeroized_seed(seed, |s| {
+        validate_send_amount(chain, amount_sat)?;
         let key = hd_derivation::derive_secp256k1_key(s, chain, account, index)?;
 
-        let unsigned_tx = chain_btc::transaction::build_p2wpkh_transaction(
+        let unsigned_tx = build_unsigned(
+            s,
+            is_testnet,
+            account,
+            change_index,
             &btc_utxos,
             &recipient_address,
             amount_sat,
-            &change_address,
+            change_address.as_deref(),
             fee_rate_sat_vbyte,
-            network,
         )?;
 
-        let signed_bytes = chain_btc::transaction::sign_transaction(
-            &unsigned_tx,
-            &key.private_key,
-            network,
+        let signer = chain_btc::transaction::InputSigner::SingleKey(key.private_key);
+        let raw_tx = chain_btc::transaction::sign_transaction(&unsigned_tx, &signer, network)?;
+
+        Ok(SignedUtxoTransaction {
+            raw_tx,
+            change_address: unsigned_tx.change_address,
+        })
+    })
+}
+
+/// Sign a Bitcoin P2WPKH transaction spending UTXOs from several receive
+/// addresses of one account
+///
+/// Each input is signed with the key at its UTXO's own `index`. If
+/// `change_address` is `None`, change goes to `m/84'/coin'/account'/1/change_index`.
+#[allow(clippy::too_many_arguments)]
+pub fn sign_btc_transaction_multi_index(
+    seed: Vec<u8>,
+    account: u32,
+    utxos: Vec<IndexedUtxoData>,
+    change_index: u32,
+    recipient_address: String,
+    amount_sat: u64,
+    change_address: Option<String>,
+    fee_rate_sat_vbyte: u64,
+    is_testnet: bool,
+) -> Result<SignedUtxoTransaction, WalletError> {
+    let (chain, network) = chain_and_network(is_testnet);
+    let (utxos, indices): (Vec<_>, Vec<_>) = utxos.into_iter().map(|u| (u.utxo, u.index)).unzip();
+    let btc_utxos = to_btc_utxos(utxos);
+
+    with_zeroized_seed(seed, |s| {
+        validate_send_amount(chain, amount_sat)?;
+        let unsigned_tx = build_unsigned(
+            s,
+            is_testnet,
+            account,
+            change_index,
+            &btc_utxos,
+            &recipient_address,
+            amount_sat,
+            change_address.as_deref(),
+            fee_rate_sat_vbyte,
         )?;
 
-        Ok(signed_bytes)
+        // Coin selection reorders the UTXOs, so match inputs back by outpoint.
+        // The keys are wiped on drop if a later input fails to match.
+        let mut keys = Zeroizing::new(Vec::with_capacity(unsigned_tx.tx.input.len()));
+        for input in &unsigned_tx.tx.input {
+            let outpoint = input.previous_output;
+            let position = btc_utxos
+                .iter()
+                .position(|u| {
+                    u.vout == outpoint.vout
+                        && u.txid.eq_ignore_ascii_case(&outpoint.txid.to_string())
+                })
+                .ok_or_else(|| WalletError::TransactionFailed {
+                    message: format!("no UTXO for input {outpoint}"),
+                })?;
+            let key = hd_derivation::derive_secp256k1_key(s, chain, account, indices[position])?;
+            keys.push(key.private_key);
+        }
+
+        // The signer zeroizes the keys when dropped.
+        let signer = chain_btc::transaction::InputSigner::KeyPerInput(std::mem::take(&mut *keys));
+        let raw_tx = chain_btc::transaction::sign_transaction(&unsigned_tx, &signer, network)?;
+        Ok(SignedUtxoTransaction {
+            raw_tx,
+            change_address: unsigned_tx.change_address,
+        })
     })
 }
+
+/// Dry-run a Bitcoin transaction: same parameters as `sign_btc_transaction`
+/// minus the signing index, but returns what the transaction would do instead
+/// of signature bytes
+///
+/// Fails with the same error signing would (bad address, `InsufficientFunds`,
+/// ...) plus hard sanity failures (zero fee rate, dust amount). Suspicious but
+/// valid values are reported in `TxBreakdown::warnings`.
+#[allow(clippy::too_many_arguments)]
+pub fn validate_btc_transaction(
+    seed: Vec<u8>,
+    account: u32,
+    change_index: u32,
+    utxos: Vec<UtxoData>,
+    recipient_address: String,
+    amount_sat: u64,
+    change_address: Option<String>,
+    fee_rate_sat_vbyte: u64,
+    is_testnet: bool,
+) -> Result<TxBreakdown, WalletError> {
+    let btc_utxos = to_btc_utxos(utxos);
+    let unsigned_tx = with_zeroized_seed(seed, |s| {
+        if fee_rate_sat_vbyte == 0 {
+            return Err(WalletError::TransactionFailed {
+                message: "fee rate must be at least 1 sat/vB".into(),
+            });
+        }
+        validate_send_amount(chain_and_network(is_testnet).0, amount_sat)?;
+        build_unsigned(
+            s,
+            is_testnet,
+            account,
+            change_index,
+            &btc_utxos,
+            &recipient_address,
+            amount_sat,
+            change_address.as_deref(),
+            fee_rate_sat_vbyte,
+        )
+    })?;
+
+    let total_in: u64 = unsigned_tx.prevouts.iter().map(|o| o.value.to_sat()).sum();
+    let total_out: u64 = unsigned_tx.tx.output.iter().map(|o| o.value.to_sat()).sum();
+    let fee = total_in - total_out;
+    let change = total_out - amount_sat;
+
+    let mut warnings = Vec::new();
+    if fee_rate_sat_vbyte > HIGH_FEE_RATE_SAT_VBYTE {
+        warnings.push(format!(
+            "fee rate {fee_rate_sat_vbyte} sat/vB is unusually high; check the unit"
+        ));
+    }
+    warnings.extend(TxBreakdown::high_fee_warning(fee.into(), amount_sat.into()));
+
+    Ok(TxBreakdown {
+        amount: amount_sat.to_string(),
+        fee: fee.to_string(),
+        change: change.to_string(),
+        change_address: unsigned_tx.change_address,
+        total: (amount_sat + fee).to_string(),
+        input_count: unsigned_tx.tx.input.len() as u32,
+        warnings,
+    })
+}
+
+/// Quote the fee, total and change of a P2WPKH send without addresses or a seed
+///
+/// Runs the same coin selection and dust handling as `sign_btc_transaction`,
+/// so the quote matches the transaction that would be built.
+pub fn estimate_btc_send_cost(
+    utxos: Vec<UtxoData>,
+    amount_sat: u64,
+    fee_rate_sat_vbyte: u64,
+) -> Result<FeeQuote, WalletError> {
+    let plan = chain_btc::transaction::plan_p2wpkh_spend(
+        &to_btc_utxos(utxos),
+        amount_sat,
+        fee_rate_sat_vbyte,
+        None,
+    )?;
+
+    Ok(FeeQuote {
+        fee: plan.fee_sat.to_string(),
+        total_spent: (amount_sat + plan.fee_sat).to_string(),
+        change: plan.change_sat.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mnemonic;
+
+    const TEST_MNEMONIC: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+    const RECIPIENT: &str = "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4";
+
+    fn test_seed() -> Vec<u8> {
+        mnemonic::mnemonic_to_seed(TEST_MNEMONIC, "").unwrap()
+    }
+
+    fn test_utxos() -> Vec<UtxoData> {
+        vec![UtxoData {
+            txid: "a".repeat(64),
+            vout: 0,
+            amount_sat: 100_000,
+            script_pubkey: hex::decode(format!("0014{}", "ab".repeat(20))).unwrap(),
+        }]
+    }
+
+    // ─── sign_btc_transaction ───────────────────────────────────────
+
+    fn change_address(change_index: u32) -> String {
+        let change_key = hd_derivation::derive_secp256k1_change_key(
+            &test_seed(),
+            Chain::Bitcoin,
+            0,
+            change_index,
+        )
+        .unwrap();
+        chain_btc::address::pubkey_to_p2wpkh_address(
+            &change_key.public_key_compressed,
+            chain_btc::network::BtcNetwork::Mainnet,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn sign_btc_transaction_auto_change_pays_derived_change_address() {
+        let auto = sign_btc_transaction(
+            test_seed(),
+            0,
+            0,
+            0,
+            test_utxos(),
+            RECIPIENT.into(),
+            50_000,
+            None,
+            1,
+            false,
+        )
+        .unwrap();
+        let explicit = sign_btc_transaction(
+            test_seed(),
+            0,
+            0,
+            0,
+            test_utxos(),
+            RECIPIENT.into(),
+            50_000,
+            Some(change_address(0)),
+            1,
+            false,
+        )
+        .unwrap();
+
+        assert_eq!(auto.raw_tx, explicit.raw_tx);
+        assert_eq!(auto.change_address, Some(change_address(0)));
+    }
+
+    #[test]
+    fn sign_btc_transaction_auto_change_uses_change_index() {
+        let signed = sign_btc_transaction(
+            test_seed(),
+            0,
+            0,
+            7,
+            test_utxos(),
+            RECIPIENT.into(),
+            50_000,
+            None,
+            1,
+            false,
+        )
+        .unwrap();
+        assert_eq!(signed.change_address, Some(change_address(7)));
+        assert_ne!(signed.change_address, Some(change_address(0)));
+    }
+
+    #[test]
+    fn sign_btc_transaction_explicit_change_still_works() {
+        let auto = sign_btc_transaction(
+            test_seed(),
+            0,
+            0,
+            0,
+            test_utxos(),
+            RECIPIENT.into(),
+            50_000,
+            None,
+            1,
+            false,
+        )
+        .unwrap();
+        let explicit = sign_btc_transaction(
+            test_seed(),
+            0,
+            0,
+            0,
+            test_utxos(),
+            RECIPIENT.into(),
+            50_000,
+            Some(RECIPIENT.into()),
+            1,
+            false,
+        )
+        .unwrap();
+
+        assert!(!explicit.raw_tx.is_empty());
+        assert_ne!(auto.raw_tx, explicit.raw_tx);
+        assert_eq!(explicit.change_address, Some(RECIPIENT.into()));
+    }
+
+    #[test]
+    fn sign_btc_transaction_invalid_explicit_change_fails() {
+        let result = sign_btc_transaction(
+            test_seed(),
+            0,
+            0,
+            0,
+            test_utxos(),
+            RECIPIENT.into(),
+            50_000,
+            Some("not-an-address".into()),
+            1,
+            false,
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn sign_btc_transaction_wipes_seed_on_dust_amount() {
+        let (probe, wiped) = crate::ffi_common::SeedProbe::new(test_seed());
+        let result = sign_btc_transaction_with_seed(
+            probe,
+            0,
+            0,
+            0,
+            test_utxos(),
+            RECIPIENT.into(),
+            546,
+            None,
+            1,
+            false,
+        );
+
+        assert!(matches!(
+            result,
+            Err(WalletError::TransactionFailed { message }) if message.contains("dust")
+        ));
+        assert_eq!(wiped.get(), Some(true));
+    }
+
+    // ─── sign_btc_transaction_multi_index ───────────────────────────
+
+    fn owned_utxo(txid: char, amount_sat: u64, index: u32) -> IndexedUtxoData {
+        let key = derive_key(index);
+        let network = chain_btc::network::BtcNetwork::Mainnet;
+        let address =
+            chain_btc::address::pubkey_to_p2wpkh_address(&key.public_key_compressed, network)
+                .unwrap();
+        IndexedUtxoData {
+            utxo: UtxoData {
+                txid: txid.to_string().repeat(64),
+                vout: 0,
+                amount_sat,
+                script_pubkey: BtcAddress::parse(&address, network)
+                    .unwrap()
+                    .script_pubkey()
+                    .to_bytes(),
+            },
+            index,
+        }
+    }
+
+    fn derive_key(index: u32) -> hd_derivation::DerivedKey {
+        hd_derivation::derive_secp256k1_key(&test_seed(), Chain::Bitcoin, 0, index).unwrap()
+    }
+
+    fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+        haystack.windows(needle.len()).any(|w| w == needle)
+    }
+
+    #[test]
+    fn sign_btc_transaction_multi_index_signs_each_input_with_its_key() {
+        let utxos = vec![owned_utxo('a', 60_000, 0), owned_utxo('b', 50_000, 1)];
+        let raw = sign_btc_transaction_multi_index(
+            test_seed(),
+            0,
+            utxos,
+            0,
+            RECIPIENT.into(),
+            90_000,
+            None,
+            1,
+            false,
+        )
+        .unwrap()
+        .raw_tx;
+
+        // Each witness carries the public key of the index that owns its UTXO.
+        assert!(contains(&raw, &derive_key(0).public_key_compressed));
+        assert!(contains(&raw, &derive_key(1).public_key_compressed));
+    }
+
+    #[test]
+    fn sign_btc_transaction_multi_index_rejects_out_of_range_index() {
+        let mut utxo = owned_utxo('a', 100_000, 0);
+        utxo.index = 0x8000_0000;
+        let result = sign_btc_transaction_multi_index(
+            test_seed(),
+            0,
+            vec![utxo],
+            0,
+            RECIPIENT.into(),
+            50_000,
+            None,
+            1,
+            false,
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn sign_btc_transaction_multi_index_matches_single_index_signing() {
+        let multi = sign_btc_transaction_multi_index(
+            test_seed(),
+            0,
+            vec![owned_utxo('a', 100_000, 0)],
+            0,
+            RECIPIENT.into(),
+            50_000,
+            None,
+            1,
+            false,
+        )
+        .unwrap();
+        let single = sign_btc_transaction(
+            test_seed(),
+            0,
+            0,
+            0,
+            vec![owned_utxo('a', 100_000, 0).utxo],
+            RECIPIENT.into(),
+            50_000,
+            None,
+            1,
+            false,
+        )
+        .unwrap();
+        assert_eq!(multi.raw_tx, single.raw_tx);
+        assert_eq!(multi.change_address, single.change_address);
+    }
+
+    // ─── validate_btc_transaction ───────────────────────────────────
+
+    #[test]
+    fn validate_btc_transaction_reports_breakdown() {
+        let breakdown = validate_btc_transaction(
+            test_seed(),
+            0,
+            0,
+            test_utxos(),
+            RECIPIENT.into(),
+            50_000,
+            None,
+            1,
+            false,
+        )
+        .unwrap();
+
+        let fee: u64 = breakdown.fee.parse().unwrap();
+        let change: u64 = breakdown.change.parse().unwrap();
+        assert_eq!(breakdown.amount, "50000");
+        assert_eq!(50_000 + fee + change, 100_000);
+        assert_eq!(breakdown.total, (50_000 + fee).to_string());
+        assert_eq!(breakdown.input_count, 1);
+        assert_eq!(breakdown.change_address, Some(change_address(0)));
+        assert!(breakdown.warnings.is_empty());
+    }
+
+    #[test]
+    fn validate_btc_transaction_underfunded_is_insufficient_funds() {
+        let result = validate_btc_transaction(
+            test_seed(),
+            0,
+            0,
+            test_utxos(),
+            RECIPIENT.into(),
+            200_000,
+            None,
+            1,
+            false,
+        );
+        assert!(matches!(
+            result,
+            Err(WalletError::InsufficientFunds {
+                available: 100_000,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn validate_btc_transaction_rejects_zero_rate_and_dust() {
+        let zero_rate = validate_btc_transaction(
+            test_seed(),
+            0,
+            0,
+            test_utxos(),
+            RECIPIENT.into(),
+            50_000,
+            None,
+            0,
+            false,
+        );
+        assert!(zero_rate.is_err());
+
+        let dust = validate_btc_transaction(
+            test_seed(),
+            0,
+            0,
+            test_utxos(),
+            RECIPIENT.into(),
+            500,
+            None,
+            1,
+            false,
+        );
+        assert!(dust.is_err());
+    }
+
+    #[test]
+    fn validate_btc_transaction_warns_on_high_fee() {
+        let breakdown = validate_btc_transaction(
+            test_seed(),
+            0,
+            0,
+            test_utxos(),
+            RECIPIENT.into(),
+            10_000,
+            None,
+            600,
+            false,
+        )
+        .unwrap();
+        assert_eq!(breakdown.warnings.len(), 2);
+    }
+
+    // ─── estimate_btc_send_cost ─────────────────────────────────────
+
+    #[test]
+    fn btc_quote_matches_built_transaction() {
+        for amount in [50_000, 99_500] {
+            let quote = estimate_btc_send_cost(test_utxos(), amount, 2).unwrap();
+            let unsigned = build_unsigned(
+                &test_seed(),
+                false,
+                0,
+                0,
+                &to_btc_utxos(test_utxos()),
+                RECIPIENT,
+                amount,
+                None,
+                2,
+            )
+            .unwrap();
+
+            let total_in: u64 = unsigned.prevouts.iter().map(|o| o.value.to_sat()).sum();
+            let total_out: u64 = unsigned.tx.output.iter().map(|o| o.value.to_sat()).sum();
+            assert_eq!(quote.fee, (total_in - total_out).to_string());
+            assert_eq!(quote.change, (total_out - amount).to_string());
+            assert_eq!(
+                quote.total_spent,
+                (total_in - (total_out - amount)).to_string()
+            );
+        }
+    }
+
+    #[test]
+    fn btc_quote_insufficient_funds() {
+        let result = estimate_btc_send_cost(test_utxos(), 200_000, 1);
+        assert!(matches!(result, Err(WalletError::InsufficientFunds { .. })));
+    }
+}
@@ -0,0 +1,385 @@
+use std::ops::Deref;
+
+use serde::Serialize;
+use zeroize::Zeroize;
+
+use crate::address;
+use crate::codec;
+use crate::error::WalletError;
+use crate::types::Chain;
+
+/// Run `f` on a seed, then zeroize the seed on both the success and error paths.
+///
+/// Every seed-taking signer goes through this. It is generic over the container
+/// so the same path serves the `Vec<u8>` handed over by UniFFI, a
+/// `ZeroizingBytes`, and the drop-observing probe the tests use to prove the
+/// wipe happens.
+pub(crate) fn with_zeroized_seed<S, F, T>(mut seed: S, f: F) -> Result<T, WalletError>
+where
+    S: Deref<Target = [u8]> + Zeroize,
+    F: FnOnce(&[u8]) -> Result<T, WalletError>,
+{
+    let result = f(&seed);
+    seed.zeroize();
+    result
+}
+
+/// Encrypted seed data returned to Swift (ciphertext + Argon2id salt)
+pub struct EncryptedSeedData {
+    pub ciphertext: Vec<u8>,
+    pub salt: Vec<u8>,
+}
+
+/// An address book entry for [`validate_addresses`]
+pub struct AddressEntry {
+    pub address: String,
+    pub chain: Chain,
+}
+
+/// Validate an address for a given chain
+pub fn validate_address(address: String, chain: Chain) -> Result<bool, WalletError> {
+    address::validate_address(&address, chain)
+}
+
+/// Validate a list of saved addresses in one call
+///
+/// Returns one flag per entry, in order. An entry that fails to validate is
+/// `false` rather than an error, so one bad entry doesn't hide the rest.
+pub fn validate_addresses(entries: Vec<AddressEntry>) -> Vec<bool> {
+    entries
+        .iter()
+        .map(|e| address::validate_address(&e.address, e.chain).unwrap_or(false))
+        .collect()
+}
+
+/// Reject a send amount below the chain's minimum
+///
+/// Bitcoin and Zcash outputs at or below the 546-unit dust threshold are
+/// non-standard and won't relay. Solana transfers below the rent-exempt
+/// minimum of a plain account (890,880 lamports) fail when they would create
+/// the recipient account, so they are refused up front. EVM chains have no
+/// minimum. `amount` is in the chain's base unit.
+pub fn validate_send_amount(chain: Chain, amount: u64) -> Result<(), WalletError> {
+    match chain {
+        Chain::Bitcoin | Chain::BitcoinTestnet => {
+            check_dust(amount, chain_btc::transaction::DUST_THRESHOLD_SAT, "sat")
+        }
+        Chain::Zcash | Chain::ZcashTestnet => {
+            check_dust(amount, chain_zec::transaction::DUST_THRESHOLD, "zat")
+        }
+        Chain::Solana | Chain::SolanaDevnet => {
+            let minimum = chain_sol::system::rent_exempt_minimum(0);
+            if amount < minimum {
+                return Err(WalletError::TransactionFailed {
+                    message: format!(
+                        "amount {amount} lamports is below the rent-exempt minimum of \
+                         {minimum} lamports"
+                    ),
+                });
+            }
+            Ok(())
+        }
+        _ => Ok(()),
+    }
+}
+
+fn check_dust(amount: u64, dust: u64, unit: &str) -> Result<(), WalletError> {
+    if amount <= dust {
+        return Err(WalletError::TransactionFailed {
+            message: format!(
+                "amount {amount} {unit} is at or below the dust threshold of {dust} {unit}"
+            ),
+        });
+    }
+    Ok(())
+}
+
+/// Compute Keccak-256 hash of arbitrary data
+pub fn keccak256(data: Vec<u8>) -> Vec<u8> {
+    use sha3::{Digest, Keccak256};
+    Keccak256::digest(&data).to_vec()
+}
+
+/// Decode a hex string (optional 0x prefix)
+pub fn hex_to_bytes(hex: String) -> Result<Vec<u8>, WalletError> {
+    codec::hex_to_bytes(&hex)
+}
+
+/// Encode bytes as lowercase hex without a 0x prefix
+pub fn bytes_to_hex(data: Vec<u8>) -> String {
+    codec::bytes_to_hex(&data)
+}
+
+/// Decode a Base58 string (Bitcoin alphabet, no checksum)
+pub fn base58_to_bytes(base58: String) -> Result<Vec<u8>, WalletError> {
+    codec::base58_to_bytes(&base58)
+}
+
+/// Encode bytes as Base58 (Bitcoin alphabet, no checksum)
+pub fn bytes_to_base58(data: Vec<u8>) -> String {
+    codec::bytes_to_base58(&data)
+}
+
+/// Decode a standard padded Base64 string
+pub fn base64_to_bytes(base64: String) -> Result<Vec<u8>, WalletError> {
+    codec::base64_to_bytes(&base64)
+}
+
+/// Encode bytes as standard padded Base64
+pub fn bytes_to_base64(data: Vec<u8>) -> String {
+    codec::bytes_to_base64(&data)
+}
+
+/// What the app sends to, or logs for, a network: `{"raw", "chain", "hash"}`
+#[derive(Serialize)]
+struct SignedTxJson {
+    raw: String,
+    chain: Chain,
+    hash: String,
+}
+
+/// Describe a signed transaction as `{"raw": "0x..", "chain": "..", "hash": ".."}`
+///
+/// `hash` is the id the chain's explorers use: Keccak-256 as 0x-hex for EVM
+/// chains, the txid (double SHA-256, display order) for Bitcoin, and the
+/// Base58 first signature for Solana. Zcash v5 txids (ZIP-244) are not
+/// computed, so Zcash is rejected.
+pub fn signed_tx_to_json(chain: Chain, raw_tx: Vec<u8>) -> Result<String, WalletError> {
+    let hash = match chain {
+        Chain::Bitcoin | Chain::BitcoinTestnet => chain_btc::transaction::txid(&raw_tx)?,
+
+        Chain::Solana | Chain::SolanaDevnet => chain_sol::transaction::transaction_id(&raw_tx)?,
+
+        Chain::Zcash | Chain::ZcashTestnet => {
+            return Err(WalletError::UnsupportedChain {
+                message: "Zcash transaction ids are not supported".into(),
+            })
+        }
+
+        Chain::Ethereum
+        | Chain::Polygon
+        | Chain::Arbitrum
+        | Chain::Base
+        | Chain::Optimism
+        | Chain::Bsc
+        | Chain::Avalanche
+        | Chain::Sepolia
+        | Chain::PolygonAmoy => {
+            use sha3::{Digest, Keccak256};
+            format!("0x{}", hex::encode(Keccak256::digest(&raw_tx)))
+        }
+    };
+
+    let json = SignedTxJson {
+        raw: format!("0x{}", hex::encode(&raw_tx)),
+        chain,
+        hash,
+    };
+    serde_json::to_string(&json).map_err(|e| WalletError::Internal {
+        message: e.to_string(),
+    })
+}
+
+/// Test-only seed container that records, when dropped, whether its bytes had
+/// been zeroized first.
+#[cfg(test)]
+pub(crate) struct SeedProbe {
+    bytes: Box<[u8]>,
+    wiped: std::rc::Rc<std::cell::Cell<Option<bool>>>,
+}
+
+#[cfg(test)]
+impl SeedProbe {
+    /// Wrap `seed`; the returned cell is `Some(true)` once the probe is dropped
+    /// with every byte zeroed, `Some(false)` if dropped with the seed intact.
+    pub(crate) fn new(seed: Vec<u8>) -> (Self, std::rc::Rc<std::cell::Cell<Option<bool>>>) {
+        let wiped = std::rc::Rc::new(std::cell::Cell::new(None));
+        (
+            Self {
+                bytes: seed.into_boxed_slice(),
+                wiped: wiped.clone(),
+            },
+            wiped,
+        )
+    }
+}
+
+#[cfg(test)]
+impl Deref for SeedProbe {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+#[cfg(test)]
+impl Zeroize for SeedProbe {
+    fn zeroize(&mut self) {
+        // Zeroes in place without truncating, so Drop can still inspect it.
+        self.bytes.zeroize();
+    }
+}
+
+#[cfg(test)]
+impl Drop for SeedProbe {
+    fn drop(&mut self) {
+        self.wiped.set(Some(self.bytes.iter().all(|&b| b == 0)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seed_probe_reports_unwiped_drop() {
+        let (probe, wiped) = SeedProbe::new(vec![0xAA; 64]);
+        assert_eq!(wiped.get(), None);
+        drop(probe);
+        assert_eq!(wiped.get(), Some(false));
+    }
+
+    #[test]
+    fn with_zeroized_seed_wipes_on_success_and_error() {
+        let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon \
+                      abandon abandon about";
+        let seed = crate::mnemonic::mnemonic_to_seed_zeroizing(phrase, "TREZOR").unwrap();
+        let (probe, wiped) = SeedProbe::new(seed.to_vec());
+        let seen = with_zeroized_seed(probe, |s| Ok(s.to_vec())).unwrap();
+        assert_eq!(seen, &seed[..], "the closure sees the seed before the wipe");
+        assert_eq!(wiped.get(), Some(true));
+
+        let (probe, wiped) = SeedProbe::new(vec![0xAA; 64]);
+        let result: Result<(), _> = with_zeroized_seed(probe, |_| {
+            Err(WalletError::Internal {
+                message: "fail".into(),
+            })
+        });
+        assert!(result.is_err());
+        assert_eq!(wiped.get(), Some(true));
+    }
+
+    fn parse(json: &str) -> serde_json::Value {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn validate_addresses_flags_each_entry() {
+        let entry = |address: &str, chain| AddressEntry {
+            address: address.into(),
+            chain,
+        };
+        let entries = vec![
+            entry("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4", Chain::Bitcoin),
+            entry(
+                "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4",
+                Chain::BitcoinTestnet,
+            ),
+            entry(
+                "0x000000000000000000000000000000000000dEaD",
+                Chain::Ethereum,
+            ),
+            entry("0x000000000000000000000000000000000000dEaD", Chain::Solana),
+            entry(
+                "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA",
+                Chain::SolanaDevnet,
+            ),
+            entry("t1UYsZVJkLPeMjxEtACvSxfWuNmddpWfxzs", Chain::Zcash),
+            entry("", Chain::Polygon),
+        ];
+
+        assert_eq!(
+            validate_addresses(entries),
+            vec![true, false, true, false, true, true, false]
+        );
+        assert!(validate_addresses(vec![]).is_empty());
+    }
+
+    #[test]
+    fn validate_send_amount_rejects_btc_dust() {
+        let err = validate_send_amount(Chain::Bitcoin, 546).unwrap_err();
+        assert!(matches!(
+            &err,
+            WalletError::TransactionFailed { message } if message.contains("dust")
+        ));
+        assert!(validate_send_amount(Chain::Bitcoin, 547).is_ok());
+        assert!(validate_send_amount(Chain::ZcashTestnet, 500).is_err());
+    }
+
+    #[test]
+    fn validate_send_amount_rejects_sol_below_rent_exemption() {
+        let err = validate_send_amount(Chain::Solana, 890_879).unwrap_err();
+        assert!(matches!(
+            &err,
+            WalletError::TransactionFailed { message } if message.contains("rent-exempt")
+        ));
+        assert!(validate_send_amount(Chain::SolanaDevnet, 890_880).is_ok());
+
+        // Distinct from the dust error, so the app can explain which rule applies.
+        let dust = validate_send_amount(Chain::Bitcoin, 1)
+            .unwrap_err()
+            .to_string();
+        assert_ne!(err.to_string(), dust);
+    }
+
+    #[test]
+    fn validate_send_amount_has_no_evm_floor() {
+        assert!(validate_send_amount(Chain::Ethereum, 1).is_ok());
+    }
+
+    #[test]
+    fn signed_tx_to_json_eth_hash_is_keccak() {
+        let to =
+            chain_eth::address::EthAddress::parse("0x000000000000000000000000000000000000dEaD")
+                .unwrap();
+        let tx = chain_eth::transaction::build_transfer(1, 0, &to, 1, 1, 2, 21_000, false).unwrap();
+        let signed = chain_eth::transaction::sign_transaction(&tx, &[0x11; 32]).unwrap();
+
+        let json = parse(&signed_tx_to_json(Chain::Polygon, signed.raw_tx.clone()).unwrap());
+        assert_eq!(json["hash"], signed.tx_hash);
+        assert_eq!(json["chain"], "Polygon");
+        assert_eq!(json["raw"], format!("0x{}", hex::encode(&signed.raw_tx)));
+    }
+
+    #[test]
+    fn signed_tx_to_json_btc_hash_is_txid() {
+        // Bitcoin genesis coinbase transaction.
+        let raw = hex::decode(
+            "01000000010000000000000000000000000000000000000000000000000000000000000000ffffffff\
+             4d04ffff001d0104455468652054696d65732030332f4a616e2f32303039204368616e63656c6c6f72\
+             206f6e206272696e6b206f66207365636f6e64206261696c6f757420666f722062616e6b73ffffffff\
+             0100f2052a01000000434104678afdb0fe5548271967f1a67130b7105cd6a828e03909a67962e0ea1f\
+             61deb649f6bc3f4cef38c4f35504e51ec112de5c384df7ba0b8d578a4c702b6bf11d5fac00000000",
+        )
+        .unwrap();
+
+        let json = parse(&signed_tx_to_json(Chain::Bitcoin, raw).unwrap());
+        assert_eq!(
+            json["hash"],
+            "4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b"
+        );
+        assert_eq!(json["chain"], "Bitcoin");
+    }
+
+    #[test]
+    fn signed_tx_to_json_sol_hash_is_first_signature() {
+        let private_key = [0x01u8; 32];
+        let from = ed25519_dalek::SigningKey::from_bytes(&private_key)
+            .verifying_key()
+            .to_bytes();
+        let tx = chain_sol::transaction::build_sol_transfer(&from, &[0x22; 32], 1_000, &[0x0B; 32])
+            .unwrap();
+        let wire = chain_sol::transaction::sign_transaction(&tx, &private_key).unwrap();
+
+        let json = parse(&signed_tx_to_json(Chain::Solana, wire.clone()).unwrap());
+        assert_eq!(json["hash"], bs58::encode(&wire[1..65]).into_string());
+    }
+
+    #[test]
+    fn signed_tx_to_json_rejects_zcash_and_garbage() {
+        assert!(signed_tx_to_json(Chain::Zcash, vec![0x05]).is_err());
+        assert!(signed_tx_to_json(Chain::Bitcoin, vec![0x01, 0x02]).is_err());
+        assert!(signed_tx_to_json(Chain::Solana, vec![0x00]).is_err());
+    }
+}
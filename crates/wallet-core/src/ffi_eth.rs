@@ -1,18 +1,11 @@
 use crate::error::WalletError;
+use crate::ffi_common::with_zeroized_seed;
 use crate::hd_derivation;
-use crate::types::Chain;
+use crate::types::{Chain, FeeQuote, SignedErc20Transfer, TxBreakdown};
+use chain_eth::address::EthAddress;
+use std::ops::Deref;
 use zeroize::Zeroize;
 
-/// Execute a closure with the seed, guaranteeing zeroization on both success and error paths.
-fn with_zeroized_seed<F, T>(mut seed: Vec<u8>, f: F) -> Result<T, WalletError>
-where
-    F: FnOnce(&[u8]) -> Result<T, WalletError>,
-{
-    let result = f(&seed);
-    seed.zeroize();
-    result
-}
-
 /// Sign an arbitrary message with EIP-191 personal_sign.
 /// Returns 65-byte signature (r + s + v).
 pub fn sign_eth_message(
@@ -23,12 +16,68 @@ pub fn sign_eth_message(
 ) -> Result<Vec<u8>, WalletError> {
     with_zeroized_seed(seed, |s| {
         let key = hd_derivation::derive_secp256k1_key(s, Chain::Ethereum, account, index)?;
-        chain_eth::transaction::sign_message(&message, &key.private_key)
-            .map_err(|e| WalletError::TransactionFailed(e.to_string()))
+        chain_eth::transaction::sign_message(&message, &key.private_key).map_err(|e| {
+            WalletError::TransactionFailed {
+                message: e.to_string(),
+            }
+        })
     })
 }
 
+/// Max fee per gas above this (in wei) is almost always a unit mix-up
+const HIGH_MAX_FEE_WEI: u128 = 1_000_000_000_000; // 1000 gwei
+
+/// Per-transaction gas cap (EIP-7825); a higher limit can never be included
+const MAX_TX_GAS_LIMIT: u64 = 1 << 24;
+
+/// Parse a hex wei amount (value, fee cap, balance) that must fit in u128
+fn parse_wei_hex(value_hex: &str, what: &str) -> Result<u128, WalletError> {
+    chain_eth::amount::parse_u256_hex(value_hex)
+        .and_then(|value| chain_eth::amount::u256_to_u128(&value))
+        .map_err(|e| WalletError::TransactionFailed {
+            message: format!("Invalid {what}: {e}"),
+        })
+}
+
+/// Build the unsigned transaction exactly as `sign_eth_transaction` would
+#[allow(clippy::too_many_arguments)]
+fn build_unsigned(
+    chain_id: u64,
+    nonce: u64,
+    to_address: &str,
+    value_wei_hex: &str,
+    data: Vec<u8>,
+    max_priority_fee_hex: &str,
+    max_fee_hex: &str,
+    gas_limit: u64,
+) -> Result<chain_eth::transaction::EthTransaction, WalletError> {
+    let value_wei = parse_wei_hex(value_wei_hex, "value")?;
+    let max_priority_fee = parse_wei_hex(max_priority_fee_hex, "priority fee")?;
+    let max_fee = parse_wei_hex(max_fee_hex, "max fee")?;
+
+    let to_address = EthAddress::parse(to_address)?;
+
+    // Calldata makes it a contract call, where zero value is normal
+    let mut tx = chain_eth::transaction::build_transfer(
+        chain_id,
+        nonce,
+        &to_address,
+        value_wei,
+        max_priority_fee,
+        max_fee,
+        gas_limit,
+        !data.is_empty(),
+    )?;
+    tx.data = data;
+    Ok(tx)
+}
+
 /// Sign an Ethereum EIP-1559 transaction
+///
+/// With `strict_chain` set, a `chain_id` that is not one of the supported EVM
+/// chains is rejected, so a mistyped id can't produce a signature for the
+/// wrong network. Leave it unset for custom chains.
+#[allow(clippy::too_many_arguments)]
 pub fn sign_eth_transaction(
     seed: Vec<u8>,
     account: u32,
@@ -41,69 +90,266 @@ pub fn sign_eth_transaction(
     max_priority_fee_hex: String,
     max_fee_hex: String,
     gas_limit: u64,
+    strict_chain: bool,
 ) -> Result<Vec<u8>, WalletError> {
+    sign_eth_transaction_with_seed(
+        seed,
+        account,
+        index,
+        chain_id,
+        nonce,
+        to_address,
+        value_wei_hex,
+        data,
+        max_priority_fee_hex,
+        max_fee_hex,
+        gas_limit,
+        strict_chain,
+    )
+}
+
+/// [`sign_eth_transaction`] over any zeroizable seed container, e.g. a
+/// `ZeroizingBytes`; the seed is wiped when signing finishes or fails.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn sign_eth_transaction_with_seed<S>(
+    seed: S,
+    account: u32,
+    index: u32,
+    chain_id: u64,
+    nonce: u64,
+    to_address: String,
+    value_wei_hex: String,
+    data: Vec<u8>,
+    max_priority_fee_hex: String,
+    max_fee_hex: String,
+    gas_limit: u64,
+    strict_chain: bool,
+) -> Result<Vec<u8>, WalletError>
+where
+    S: Deref<Target = [u8]> + Zeroize,
+{
     with_zeroized_seed(seed, |s| {
+        if strict_chain && chain_eth::chains::get_chain(chain_id).is_none() {
+            return Err(WalletError::UnsupportedChain {
+                message: format!("chain id {chain_id} is not a supported EVM chain"),
+            });
+        }
         let key = hd_derivation::derive_secp256k1_key(s, Chain::Ethereum, account, index)?;
 
-        let value_wei = u128::from_str_radix(value_wei_hex.trim_start_matches("0x"), 16)
-            .map_err(|e| WalletError::TransactionFailed(format!("Invalid value: {e}")))?;
-        let max_priority_fee = u128::from_str_radix(max_priority_fee_hex.trim_start_matches("0x"), 16)
-            .map_err(|e| WalletError::TransactionFailed(format!("Invalid priority fee: {e}")))?;
-        let max_fee = u128::from_str_radix(max_fee_hex.trim_start_matches("0x"), 16)
-            .map_err(|e| WalletError::TransactionFailed(format!("Invalid max fee: {e}")))?;
-
-        let tx = if data.is_empty() {
-            chain_eth::transaction::build_transfer(
-                chain_id,
-                nonce,
-                &to_address,
-                value_wei,
-                max_priority_fee,
-                max_fee,
-                gas_limit,
-            )?
-        } else {
-            let mut tx = chain_eth::transaction::build_transfer(
-                chain_id,
-                nonce,
-                &to_address,
-                value_wei,
-                max_priority_fee,
-                max_fee,
-                gas_limit,
-            )?;
-            tx.data = data;
-            tx
-        };
+        let tx = build_unsigned(
+            chain_id,
+            nonce,
+            &to_address,
+            &value_wei_hex,
+            data,
+            &max_priority_fee_hex,
+            &max_fee_hex,
+            gas_limit,
+        )?;
 
         let signed = chain_eth::transaction::sign_transaction(&tx, &key.private_key)?;
         Ok(signed.raw_tx)
     })
 }
 
+/// Sign an EIP-1559 transaction with a raw private key instead of a seed
+///
+/// For imported accounts that only have a key. The key must be 32 bytes and
+/// is zeroized after use.
+#[allow(clippy::too_many_arguments)]
+pub fn sign_eth_transaction_with_key(
+    private_key: Vec<u8>,
+    chain_id: u64,
+    nonce: u64,
+    to_address: String,
+    value_wei_hex: String,
+    data: Vec<u8>,
+    max_priority_fee_hex: String,
+    max_fee_hex: String,
+    gas_limit: u64,
+) -> Result<Vec<u8>, WalletError> {
+    with_zeroized_seed(private_key, |k| {
+        let key: &[u8; 32] = k.try_into().map_err(|_| WalletError::InvalidPrivateKey {
+            message: format!("expected a 32-byte private key, got {} bytes", k.len()),
+        })?;
+
+        let tx = build_unsigned(
+            chain_id,
+            nonce,
+            &to_address,
+            &value_wei_hex,
+            data,
+            &max_priority_fee_hex,
+            &max_fee_hex,
+            gas_limit,
+        )?;
+
+        let signed = chain_eth::transaction::sign_transaction(&tx, key)?;
+        Ok(signed.raw_tx)
+    })
+}
+
+/// Dry-run an Ethereum transaction: same parameters as `sign_eth_transaction`
+/// (minus the key) plus the sender's balance, returning what the transaction
+/// would cost instead of signature bytes
+///
+/// The fee is the worst case, `gas_limit * max_fee_per_gas`, which is what
+/// the node checks against the balance. Fails on bad parameters, a gas limit
+/// below the 21000 intrinsic cost or above the 2^24 per-transaction cap, a
+/// zero max fee, a priority fee above the max fee, or `value + fee > balance`
+/// (`InsufficientFunds` when the amounts fit in u64).
+#[allow(clippy::too_many_arguments)]
+pub fn validate_eth_transaction(
+    chain_id: u64,
+    nonce: u64,
+    to_address: String,
+    value_wei_hex: String,
+    data: Vec<u8>,
+    max_priority_fee_hex: String,
+    max_fee_hex: String,
+    gas_limit: u64,
+    balance_wei_hex: String,
+) -> Result<TxBreakdown, WalletError> {
+    let tx = build_unsigned(
+        chain_id,
+        nonce,
+        &to_address,
+        &value_wei_hex,
+        data,
+        &max_priority_fee_hex,
+        &max_fee_hex,
+        gas_limit,
+    )?;
+    let balance = parse_wei_hex(&balance_wei_hex, "balance")?;
+
+    let intrinsic_gas = chain_eth::gas::estimate_gas_limit(chain_eth::gas::TxKind::NativeTransfer);
+    if tx.gas_limit < intrinsic_gas {
+        return Err(WalletError::TransactionFailed {
+            message: format!(
+                "gas limit {} is below the intrinsic {intrinsic_gas}",
+                tx.gas_limit
+            ),
+        });
+    }
+    if tx.gas_limit > MAX_TX_GAS_LIMIT {
+        return Err(WalletError::TransactionFailed {
+            message: format!(
+                "gas limit {} exceeds the cap {MAX_TX_GAS_LIMIT}",
+                tx.gas_limit
+            ),
+        });
+    }
+    if tx.max_fee_per_gas == 0 {
+        return Err(WalletError::TransactionFailed {
+            message: "max fee per gas is zero".into(),
+        });
+    }
+    if tx.max_priority_fee_per_gas > tx.max_fee_per_gas {
+        return Err(WalletError::TransactionFailed {
+            message: "max priority fee exceeds max fee per gas".into(),
+        });
+    }
+
+    let overflow = || WalletError::TransactionFailed {
+        message: "fee overflows u128".into(),
+    };
+    let fee = tx
+        .max_fee_per_gas
+        .checked_mul(u128::from(tx.gas_limit))
+        .ok_or_else(overflow)?;
+    let total = tx.value.checked_add(fee).ok_or_else(overflow)?;
+
+    if total > balance {
+        return Err(match (u64::try_from(total), u64::try_from(balance)) {
+            (Ok(needed), Ok(available)) => WalletError::InsufficientFunds { needed, available },
+            _ => WalletError::TransactionFailed {
+                message: format!("insufficient funds: need {total} wei, have {balance}"),
+            },
+        });
+    }
+
+    let mut warnings = Vec::new();
+    if tx.max_fee_per_gas > HIGH_MAX_FEE_WEI {
+        warnings.push("max fee per gas is above 1000 gwei; check the unit".into());
+    }
+    warnings.extend(TxBreakdown::high_fee_warning(fee, tx.value));
+
+    Ok(TxBreakdown {
+        amount: tx.value.to_string(),
+        fee: fee.to_string(),
+        change: "0".into(),
+        change_address: None,
+        total: total.to_string(),
+        input_count: 0,
+        warnings,
+    })
+}
+
+/// Quote the worst-case cost of an EIP-1559 send: `gas_limit * max_fee` plus value
+pub fn estimate_eth_send_cost(
+    value_wei_hex: String,
+    max_fee_hex: String,
+    gas_limit: u64,
+) -> Result<FeeQuote, WalletError> {
+    let value = parse_wei_hex(&value_wei_hex, "value")?;
+    let max_fee = parse_wei_hex(&max_fee_hex, "max fee")?;
+
+    let overflow = || WalletError::TransactionFailed {
+        message: "cost overflows u128".into(),
+    };
+    let fee = u128::from(gas_limit)
+        .checked_mul(max_fee)
+        .ok_or_else(overflow)?;
+    let total = value.checked_add(fee).ok_or_else(overflow)?;
+
+    Ok(FeeQuote {
+        fee: fee.to_string(),
+        total_spent: total.to_string(),
+        change: "0".into(),
+    })
+}
+
+/// Validate an Ethereum address and return its EIP-55 checksummed form.
+/// Used to normalize pasted lowercase addresses before display.
+pub fn eth_checksum_address(address: String) -> Result<String, WalletError> {
+    Ok(chain_eth::address::to_checksummed(address.trim())?)
+}
+
 /// Recover uncompressed secp256k1 public key from a 65-byte signature + 32-byte message hash.
 /// Returns 65-byte uncompressed public key (0x04 || x || y).
+/// `v` may be a raw recovery id (0/1), legacy 27/28 or an EIP-155 value.
 pub fn recover_eth_pubkey(signature: Vec<u8>, message_hash: Vec<u8>) -> Result<Vec<u8>, WalletError> {
     use k256::ecdsa::{RecoveryId, Signature, VerifyingKey};
 
     if signature.len() != 65 {
-        return Err(WalletError::SigningFailed("Signature must be 65 bytes".into()));
+        return Err(WalletError::SigningFailed {
+            message: "Signature must be 65 bytes".into(),
+        });
     }
     if message_hash.len() != 32 {
-        return Err(WalletError::SigningFailed("Message hash must be 32 bytes".into()));
+        return Err(WalletError::SigningFailed {
+            message: "Message hash must be 32 bytes".into(),
+        });
     }
 
     let r_s = &signature[..64];
-    let v = signature[64];
-    let recovery_id = if v >= 27 { v - 27 } else { v };
+    // Accept v as 0/1, 27/28 or EIP-155 (chain_id * 2 + 35 + parity).
+    let recovery_id =
+        chain_eth::transaction::normalize_recovery_id(u64::from(signature[64]), None)?;
 
-    let sig = Signature::from_slice(r_s)
-        .map_err(|e| WalletError::SigningFailed(format!("Invalid signature: {e}")))?;
-    let recid = RecoveryId::from_byte(recovery_id)
-        .ok_or_else(|| WalletError::SigningFailed("Invalid recovery ID".into()))?;
+    let sig = Signature::from_slice(r_s).map_err(|e| WalletError::SigningFailed {
+        message: format!("Invalid signature: {e}"),
+    })?;
+    let recid = RecoveryId::from_byte(recovery_id).ok_or_else(|| WalletError::SigningFailed {
+        message: "Invalid recovery ID".into(),
+    })?;
 
-    let recovered_key = VerifyingKey::recover_from_prehash(&message_hash, &sig, recid)
-        .map_err(|e| WalletError::SigningFailed(format!("Recovery failed: {e}")))?;
+    let recovered_key =
+        VerifyingKey::recover_from_prehash(&message_hash, &sig, recid).map_err(|e| {
+            WalletError::SigningFailed {
+                message: format!("Recovery failed: {e}"),
+            }
+        })?;
 
     Ok(recovered_key.to_encoded_point(false).as_bytes().to_vec())
 }
@@ -116,20 +362,70 @@ pub fn sign_eth_raw_hash(
     index: u32,
     hash: Vec<u8>,
 ) -> Result<Vec<u8>, WalletError> {
-    if hash.len() != 32 {
-        return Err(WalletError::SigningFailed(
-            "Hash must be exactly 32 bytes".into(),
-        ));
-    }
     with_zeroized_seed(seed, |s| {
+        let hash_arr: [u8; 32] =
+            hash.as_slice()
+                .try_into()
+                .map_err(|_| WalletError::SigningFailed {
+                    message: "Hash must be exactly 32 bytes".into(),
+                })?;
         let key = hd_derivation::derive_secp256k1_key(s, Chain::Ethereum, account, index)?;
-        let hash_arr: [u8; 32] = hash.as_slice().try_into().unwrap();
-        chain_eth::transaction::sign_raw_hash(&hash_arr, &key.private_key)
-            .map_err(|e| WalletError::SigningFailed(e.to_string()))
+        chain_eth::transaction::sign_raw_hash(&hash_arr, &key.private_key).map_err(|e| {
+            WalletError::SigningFailed {
+                message: e.to_string(),
+            }
+        })
+    })
+}
+
+/// Sign an `eth_signTypedData_v4` request (EIP-712), e.g. from WalletConnect.
+/// Takes the full typed-data JSON, computes its digest and returns the 65-byte
+/// signature (r || s || v, v = 27/28).
+pub fn sign_typed_data_v4(
+    seed: Vec<u8>,
+    account: u32,
+    index: u32,
+    typed_data_json: String,
+) -> Result<Vec<u8>, WalletError> {
+    sign_typed_data_v4_with_seed(seed, account, index, &typed_data_json)
+}
+
+/// [`sign_typed_data_v4`] over any zeroizable seed container
+///
+/// The payload is hashed inside the closure, so the seed is wiped even when
+/// it is malformed.
+pub(crate) fn sign_typed_data_v4_with_seed<S>(
+    seed: S,
+    account: u32,
+    index: u32,
+    typed_data_json: &str,
+) -> Result<Vec<u8>, WalletError>
+where
+    S: Deref<Target = [u8]> + Zeroize,
+{
+    with_zeroized_seed(seed, |s| {
+        let digest = chain_eth::eip712::hash_typed_data_v4(typed_data_json)?;
+        let key = hd_derivation::derive_secp256k1_key(s, Chain::Ethereum, account, index)?;
+        chain_eth::transaction::sign_raw_hash(&digest, &key.private_key).map_err(|e| {
+            WalletError::SigningFailed {
+                message: e.to_string(),
+            }
+        })
+    })
+}
+
+/// Parse a hex amount as a big-endian [u8; 32] uint256
+fn parse_uint256_hex(amount_hex: &str) -> Result<[u8; 32], WalletError> {
+    chain_eth::amount::parse_u256_hex(amount_hex).map_err(|e| WalletError::TransactionFailed {
+        message: format!("Invalid amount: {e}"),
     })
 }
 
 /// Sign an ERC-20 token transfer on any EVM chain
+///
+/// A gas limit below what token transfers usually need is still signed, but
+/// reported in [`SignedErc20Transfer::warnings`].
+#[allow(clippy::too_many_arguments)]
 pub fn sign_erc20_transfer(
     seed: Vec<u8>,
     account: u32,
@@ -142,35 +438,104 @@ pub fn sign_erc20_transfer(
     max_priority_fee_hex: String,
     max_fee_hex: String,
     gas_limit: u64,
-) -> Result<Vec<u8>, WalletError> {
-    // Parse amount as big-endian [u8; 32] uint256 (before entering closure to avoid seed leak on parse error)
-    let amount_str = amount_hex.trim_start_matches("0x");
-    // Left-pad odd-length hex to even length (e.g. "f4240" -> "0f4240")
-    let padded = if amount_str.len() % 2 != 0 {
-        format!("0{amount_str}")
-    } else {
-        amount_str.to_string()
-    };
-    let amount_bytes = hex::decode(&padded)
-        .map_err(|e| WalletError::TransactionFailed(format!("Invalid amount hex: {e}")))?;
-    if amount_bytes.len() > 32 {
-        return Err(WalletError::TransactionFailed("Amount exceeds uint256".into()));
-    }
-    let mut amount = [0u8; 32];
-    amount[32 - amount_bytes.len()..].copy_from_slice(&amount_bytes);
+) -> Result<SignedErc20Transfer, WalletError> {
+    sign_erc20_transfer_with_seed(
+        seed,
+        account,
+        index,
+        chain_id,
+        nonce,
+        token_contract,
+        to_address,
+        amount_hex,
+        max_priority_fee_hex,
+        max_fee_hex,
+        gas_limit,
+    )
+}
 
+/// [`sign_erc20_transfer`] over any zeroizable seed container
+///
+/// Inputs are parsed inside the closure, so the seed is wiped even when one
+/// of them is malformed.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn sign_erc20_transfer_with_seed<S>(
+    seed: S,
+    account: u32,
+    index: u32,
+    chain_id: u64,
+    nonce: u64,
+    token_contract: String,
+    to_address: String,
+    amount_hex: String,
+    max_priority_fee_hex: String,
+    max_fee_hex: String,
+    gas_limit: u64,
+) -> Result<SignedErc20Transfer, WalletError>
+where
+    S: Deref<Target = [u8]> + Zeroize,
+{
     with_zeroized_seed(seed, |s| {
+        let amount = parse_uint256_hex(&amount_hex)?;
+        let max_priority_fee = parse_wei_hex(&max_priority_fee_hex, "priority fee")?;
+        let max_fee = parse_wei_hex(&max_fee_hex, "max fee")?;
+        let token_contract = EthAddress::parse(&token_contract)?;
+        let to_address = EthAddress::parse(&to_address)?;
         let key = hd_derivation::derive_secp256k1_key(s, Chain::Ethereum, account, index)?;
 
-        let max_priority_fee = u128::from_str_radix(max_priority_fee_hex.trim_start_matches("0x"), 16)
-            .map_err(|e| WalletError::TransactionFailed(format!("Invalid priority fee: {e}")))?;
-        let max_fee = u128::from_str_radix(max_fee_hex.trim_start_matches("0x"), 16)
-            .map_err(|e| WalletError::TransactionFailed(format!("Invalid max fee: {e}")))?;
+        // A low-gas warning is advisory; signing honours the caller's limit
+        // and hands the warnings back for the app to show.
+        let (tx, warnings) = chain_eth::transaction::build_erc20_transfer(
+            chain_id,
+            nonce,
+            &token_contract,
+            &to_address,
+            amount,
+            max_priority_fee,
+            max_fee,
+            gas_limit,
+        )?;
+
+        let signed = chain_eth::transaction::sign_transaction(&tx, &key.private_key)?;
+        Ok(SignedErc20Transfer {
+            raw_tx: signed.raw_tx,
+            warnings: warnings.iter().map(ToString::to_string).collect(),
+        })
+    })
+}
 
-        let tx = chain_eth::transaction::build_erc20_transfer(
+/// Sign an ERC-20 `transferFrom` (delegated spend of `from_address`'s tokens)
+///
+/// The signing account must hold an allowance from `from_address` on the token.
+#[allow(clippy::too_many_arguments)]
+pub fn sign_erc20_transfer_from(
+    seed: Vec<u8>,
+    account: u32,
+    index: u32,
+    chain_id: u64,
+    nonce: u64,
+    token_contract: String,
+    from_address: String,
+    to_address: String,
+    amount_hex: String,
+    max_priority_fee_hex: String,
+    max_fee_hex: String,
+    gas_limit: u64,
+) -> Result<Vec<u8>, WalletError> {
+    with_zeroized_seed(seed, |s| {
+        let amount = parse_uint256_hex(&amount_hex)?;
+        let max_priority_fee = parse_wei_hex(&max_priority_fee_hex, "priority fee")?;
+        let max_fee = parse_wei_hex(&max_fee_hex, "max fee")?;
+        let token_contract = EthAddress::parse(&token_contract)?;
+        let from_address = EthAddress::parse(&from_address)?;
+        let to_address = EthAddress::parse(&to_address)?;
+        let key = hd_derivation::derive_secp256k1_key(s, Chain::Ethereum, account, index)?;
+
+        let tx = chain_eth::transaction::build_erc20_transfer_from(
             chain_id,
             nonce,
             &token_contract,
+            &from_address,
             &to_address,
             amount,
             max_priority_fee,
@@ -183,6 +548,95 @@ pub fn sign_erc20_transfer(
     })
 }
 
+/// Sign an unlimited ERC-20 approval, `approve(spender, 2^256 - 1)`
+///
+/// The spender can then move all of this token from the account until the
+/// approval is revoked; apps should make the user confirm that explicitly.
+#[allow(clippy::too_many_arguments)]
+pub fn sign_erc20_approve_max(
+    seed: Vec<u8>,
+    account: u32,
+    index: u32,
+    chain_id: u64,
+    nonce: u64,
+    token_contract: String,
+    spender: String,
+    max_priority_fee_hex: String,
+    max_fee_hex: String,
+    gas_limit: u64,
+) -> Result<Vec<u8>, WalletError> {
+    with_zeroized_seed(seed, |s| {
+        let calldata = chain_eth::erc20::encode_approve_max(&spender)?;
+        let tx = build_unsigned(
+            chain_id,
+            nonce,
+            &token_contract,
+            "0x0",
+            calldata,
+            &max_priority_fee_hex,
+            &max_fee_hex,
+            gas_limit,
+        )?;
+        let key = hd_derivation::derive_secp256k1_key(s, Chain::Ethereum, account, index)?;
+        let signed = chain_eth::transaction::sign_transaction(&tx, &key.private_key)?;
+        Ok(signed.raw_tx)
+    })
+}
+
+/// Sign an ERC-20 allowance change as `approve(spender, 0)` then
+/// `approve(spender, amount)`, for tokens that reject non-zero to non-zero
+/// changes. Returns both raw transactions, using `nonce` and `nonce + 1`;
+/// broadcast them in order.
+#[allow(clippy::too_many_arguments)]
+pub fn sign_erc20_safe_approve(
+    seed: Vec<u8>,
+    account: u32,
+    index: u32,
+    chain_id: u64,
+    nonce: u64,
+    token_contract: String,
+    spender: String,
+    amount_hex: String,
+    max_priority_fee_hex: String,
+    max_fee_hex: String,
+    gas_limit: u64,
+) -> Result<Vec<Vec<u8>>, WalletError> {
+    with_zeroized_seed(seed, |s| {
+        let amount = parse_uint256_hex(&amount_hex)?;
+        let calldata = chain_eth::erc20::encode_safe_approve(&spender, amount)?;
+        let second_nonce = nonce
+            .checked_add(1)
+            .ok_or_else(|| WalletError::TransactionFailed {
+                message: "Nonce overflow".into(),
+            })?;
+
+        let txs = [nonce, second_nonce]
+            .into_iter()
+            .zip(calldata)
+            .map(|(nonce, data)| {
+                build_unsigned(
+                    chain_id,
+                    nonce,
+                    &token_contract,
+                    "0x0",
+                    data,
+                    &max_priority_fee_hex,
+                    &max_fee_hex,
+                    gas_limit,
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let key = hd_derivation::derive_secp256k1_key(s, Chain::Ethereum, account, index)?;
+        txs.iter()
+            .map(|tx| {
+                let signed = chain_eth::transaction::sign_transaction(tx, &key.private_key)?;
+                Ok(signed.raw_tx)
+            })
+            .collect()
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -194,6 +648,340 @@ mod tests {
         mnemonic::mnemonic_to_seed(TEST_MNEMONIC, "").unwrap()
     }
 
+    // ─── seed zeroization ───────────────────────────────────────────
+
+    #[test]
+    fn eth_signing_wipes_seed() {
+        let (probe, wiped) = crate::ffi_common::SeedProbe::new(test_seed());
+        let signed = sign_eth_transaction_with_seed(
+            probe,
+            0,
+            0,
+            1,
+            0,
+            TO.into(),
+            "0x1".into(),
+            vec![],
+            "0x1".into(),
+            "0x2".into(),
+            21_000,
+            false,
+        )
+        .unwrap();
+
+        assert_eq!(wiped.get(), Some(true));
+        let expected = sign_eth_transaction(
+            test_seed(),
+            0,
+            0,
+            1,
+            0,
+            TO.into(),
+            "0x1".into(),
+            vec![],
+            "0x1".into(),
+            "0x2".into(),
+            21_000,
+            false,
+        )
+        .unwrap();
+        assert_eq!(signed, expected);
+    }
+
+    #[test]
+    fn eth_signing_wipes_seed_on_error() {
+        let (probe, wiped) = crate::ffi_common::SeedProbe::new(test_seed());
+        let result = sign_eth_transaction_with_seed(
+            probe,
+            0,
+            0,
+            999_999,
+            0,
+            TO.into(),
+            "0x1".into(),
+            vec![],
+            "0x1".into(),
+            "0x2".into(),
+            21_000,
+            true,
+        );
+
+        assert!(result.is_err());
+        assert_eq!(wiped.get(), Some(true));
+    }
+
+    // ─── sign_eth_transaction strict_chain ──────────────────────────
+
+    #[test]
+    fn strict_chain_rejects_unknown_chain_id() {
+        let sign = |chain_id, strict| {
+            sign_eth_transaction(
+                test_seed(),
+                0,
+                0,
+                chain_id,
+                0,
+                TO.into(),
+                "0x1".into(),
+                vec![],
+                "0x1".into(),
+                "0x2".into(),
+                21_000,
+                strict,
+            )
+        };
+
+        assert!(matches!(
+            sign(999_999, true),
+            Err(WalletError::UnsupportedChain { .. })
+        ));
+        assert!(sign(999_999, false).is_ok());
+        assert_eq!(sign(137, true).unwrap(), sign(137, false).unwrap());
+    }
+
+    // ─── sign_eth_transaction_with_key ──────────────────────────────
+
+    #[test]
+    fn sign_with_key_matches_seed_signing() {
+        let key = hd_derivation::derive_secp256k1_key(&test_seed(), Chain::Ethereum, 0, 3).unwrap();
+
+        let via_seed = sign_eth_transaction(
+            test_seed(),
+            0,
+            3,
+            1,
+            7,
+            TO.into(),
+            "0x2386f26fc10000".into(),
+            vec![],
+            "0x3b9aca00".into(),
+            "0x6fc23ac00".into(),
+            21_000,
+            false,
+        )
+        .unwrap();
+        let via_key = sign_eth_transaction_with_key(
+            key.private_key.to_vec(),
+            1,
+            7,
+            TO.into(),
+            "0x2386f26fc10000".into(),
+            vec![],
+            "0x3b9aca00".into(),
+            "0x6fc23ac00".into(),
+            21_000,
+        )
+        .unwrap();
+
+        assert_eq!(via_key, via_seed);
+    }
+
+    #[test]
+    fn sign_with_key_rejects_wrong_length() {
+        let result = sign_eth_transaction_with_key(
+            vec![0x11; 31],
+            1,
+            0,
+            TO.into(),
+            "0x0".into(),
+            vec![],
+            "0x1".into(),
+            "0x2".into(),
+            21_000,
+        );
+        assert!(matches!(result, Err(WalletError::InvalidPrivateKey { .. })));
+    }
+
+    #[test]
+    fn zero_value_allowed_only_with_calldata() {
+        let plain = sign_eth_transaction_with_key(
+            vec![0x11; 32],
+            1,
+            0,
+            TO.into(),
+            "0x0".into(),
+            vec![],
+            "0x1".into(),
+            "0x2".into(),
+            21_000,
+        );
+        assert!(plain.is_err());
+
+        let call = sign_eth_transaction_with_key(
+            vec![0x11; 32],
+            1,
+            0,
+            TO.into(),
+            "0x0".into(),
+            vec![0xa9, 0x05, 0x9c, 0xbb],
+            "0x1".into(),
+            "0x2".into(),
+            60_000,
+        );
+        assert!(call.is_ok());
+    }
+
+    // ─── estimate_eth_send_cost ─────────────────────────────────────
+
+    #[test]
+    fn eth_quote_matches_built_transaction() {
+        let quote =
+            estimate_eth_send_cost("0xde0b6b3a7640000".into(), "0x6fc23ac00".into(), 21_000)
+                .unwrap();
+        let tx = build_unsigned(
+            1,
+            0,
+            TO,
+            "0xde0b6b3a7640000",
+            vec![],
+            "0x77359400",
+            "0x6fc23ac00",
+            21_000,
+        )
+        .unwrap();
+
+        let fee = u128::from(tx.gas_limit) * tx.max_fee_per_gas;
+        assert_eq!(quote.fee, fee.to_string());
+        assert_eq!(quote.total_spent, (tx.value + fee).to_string());
+        assert_eq!(quote.change, "0");
+    }
+
+    #[test]
+    fn eth_quote_rejects_bad_hex() {
+        assert!(estimate_eth_send_cost("0xzz".into(), "0x1".into(), 21_000).is_err());
+    }
+
+    // ─── recover_eth_pubkey ─────────────────────────────────────────
+
+    #[test]
+    fn recover_eth_pubkey_accepts_any_v_encoding() {
+        let hash = [0x5Au8; 32];
+        let sig = sign_eth_raw_hash(test_seed(), 0, 0, hash.to_vec()).unwrap();
+        let expected = recover_eth_pubkey(sig.clone(), hash.to_vec()).unwrap();
+
+        let parity = sig[64] - 27;
+        for v in [parity, 27 + parity, 37 + parity] {
+            let mut alt = sig.clone();
+            alt[64] = v;
+            assert_eq!(
+                recover_eth_pubkey(alt, hash.to_vec()).unwrap(),
+                expected,
+                "v = {v}"
+            );
+        }
+
+        let mut bad = sig;
+        bad[64] = 30;
+        assert!(recover_eth_pubkey(bad, hash.to_vec()).is_err());
+    }
+
+    // ─── validate_eth_transaction ───────────────────────────────────
+
+    const TO: &str = "0x000000000000000000000000000000000000dEaD";
+
+    /// 1 ETH transfer at 30 gwei max fee against `balance_hex`
+    fn validate_transfer(balance_hex: &str) -> Result<TxBreakdown, WalletError> {
+        validate_eth_transaction(
+            1,
+            0,
+            TO.into(),
+            "0xde0b6b3a7640000".into(),
+            vec![],
+            "0x77359400".into(),
+            "0x6fc23ac00".into(),
+            21_000,
+            balance_hex.into(),
+        )
+    }
+
+    #[test]
+    fn validate_eth_transaction_reports_worst_case_fee() {
+        let breakdown = validate_transfer("0x1bc16d674ec80000").unwrap(); // 2 ETH
+
+        assert_eq!(breakdown.amount, "1000000000000000000");
+        assert_eq!(breakdown.fee, (21_000u128 * 30_000_000_000).to_string());
+        assert_eq!(
+            breakdown.total,
+            (1_000_000_000_000_000_000u128 + 630_000_000_000_000).to_string()
+        );
+        assert_eq!(breakdown.input_count, 0);
+        assert!(breakdown.warnings.is_empty());
+    }
+
+    #[test]
+    fn validate_eth_transaction_insufficient_funds() {
+        let result = validate_transfer("0xde0b6b3a7640000"); // exactly 1 ETH, nothing for gas
+        assert!(matches!(
+            result,
+            Err(WalletError::InsufficientFunds {
+                available: 1_000_000_000_000_000_000,
+                ..
+            })
+        ));
+    }
+
+    /// Error message for a 1 wei transfer with the given fee parameters
+    fn fee_params_error(priority_hex: &str, max_fee_hex: &str, gas_limit: u64) -> String {
+        validate_eth_transaction(
+            1,
+            0,
+            TO.into(),
+            "0x1".into(),
+            vec![],
+            priority_hex.into(),
+            max_fee_hex.into(),
+            gas_limit,
+            "0xffffffffffff".into(),
+        )
+        .unwrap_err()
+        .to_string()
+    }
+
+    #[test]
+    fn validate_eth_transaction_rejects_bad_fee_params() {
+        assert!(fee_params_error("0x1", "0x2", 20_000).contains("below the intrinsic"));
+        assert!(fee_params_error("0x3", "0x2", 21_000).contains("priority fee exceeds"));
+        assert!(fee_params_error("0x0", "0x0", 21_000).contains("max fee per gas is zero"));
+        assert!(fee_params_error("0x1", "0x2", MAX_TX_GAS_LIMIT + 1).contains("exceeds the cap"));
+    }
+
+    #[test]
+    fn validate_eth_transaction_accepts_gas_limit_at_cap() {
+        let breakdown = validate_eth_transaction(
+            1,
+            0,
+            TO.into(),
+            "0x1".into(),
+            vec![],
+            "0x1".into(),
+            "0x2".into(),
+            MAX_TX_GAS_LIMIT,
+            "0xffffffffffff".into(),
+        )
+        .unwrap();
+        assert_eq!(
+            breakdown.fee,
+            (2 * u128::from(MAX_TX_GAS_LIMIT)).to_string()
+        );
+    }
+
+    // ─── eth_checksum_address ───────────────────────────────────────
+
+    #[test]
+    fn eth_checksum_address_normalizes_pasted_input() {
+        let result = eth_checksum_address(" 0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed\n".into());
+        assert_eq!(
+            result.unwrap(),
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"
+        );
+    }
+
+    #[test]
+    fn eth_checksum_address_invalid() {
+        let result = eth_checksum_address("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD".into());
+        assert!(matches!(result, Err(WalletError::InvalidAddress { .. })));
+    }
+
     // ─── sign_eth_raw_hash ───────────────────────────────────────────
 
     #[test]
@@ -230,8 +1018,155 @@ mod tests {
         assert_ne!(raw_sig, personal_sig);
     }
 
+    // ─── sign_typed_data_v4 ─────────────────────────────────────────
+
+    /// A Uniswap Permit2 `PermitSingle` approving the Universal Router to
+    /// spend USDC, as dapps send it over WalletConnect (numbers as strings).
+    const PERMIT2_SINGLE: &str = r#"{
+        "types": {
+            "EIP712Domain": [
+                {"name": "name", "type": "string"},
+                {"name": "chainId", "type": "uint256"},
+                {"name": "verifyingContract", "type": "address"}
+            ],
+            "PermitSingle": [
+                {"name": "details", "type": "PermitDetails"},
+                {"name": "spender", "type": "address"},
+                {"name": "sigDeadline", "type": "uint256"}
+            ],
+            "PermitDetails": [
+                {"name": "token", "type": "address"},
+                {"name": "amount", "type": "uint160"},
+                {"name": "expiration", "type": "uint48"},
+                {"name": "nonce", "type": "uint48"}
+            ]
+        },
+        "domain": {
+            "name": "Permit2",
+            "chainId": "1",
+            "verifyingContract": "0x000000000022d473030f116ddee9f6b43ac78ba3"
+        },
+        "primaryType": "PermitSingle",
+        "message": {
+            "details": {
+                "token": "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48",
+                "amount": "1461501637330902918203684832716283019655932542975",
+                "expiration": "1767225600",
+                "nonce": "0"
+            },
+            "spender": "0x3fc91a3afd70395cd496c647d5a6cc9d4b2b7fad",
+            "sigDeadline": "1764635400"
+        }
+    }"#;
+
+    /// An EIP-2612 USDC permit for the Aave V3 pool, as used by supply-with-permit.
+    const AAVE_USDC_PERMIT: &str = r#"{
+        "types": {
+            "EIP712Domain": [
+                {"name": "name", "type": "string"},
+                {"name": "version", "type": "string"},
+                {"name": "chainId", "type": "uint256"},
+                {"name": "verifyingContract", "type": "address"}
+            ],
+            "Permit": [
+                {"name": "owner", "type": "address"},
+                {"name": "spender", "type": "address"},
+                {"name": "value", "type": "uint256"},
+                {"name": "nonce", "type": "uint256"},
+                {"name": "deadline", "type": "uint256"}
+            ]
+        },
+        "primaryType": "Permit",
+        "domain": {
+            "name": "USD Coin",
+            "version": "2",
+            "chainId": 1,
+            "verifyingContract": "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48"
+        },
+        "message": {
+            "owner": "0x9858EfFD232B4033E47d90003D41EC34EcaEda94",
+            "spender": "0x87870Bca3F3fD6335C3F4ce8392D69350B4fA4E2",
+            "value": "250000000",
+            "nonce": 0,
+            "deadline": "0x6954e380"
+        }
+    }"#;
+
+    #[test]
+    fn sign_typed_data_v4_permit_digests_match_reference() {
+        // Reference digests computed with alloy-dyn-abi.
+        let cases = [
+            (
+                PERMIT2_SINGLE,
+                "95478c1485d1c7c784b8bca2aaea4be9efa787def7f1ad1c643345ec7fba0304",
+            ),
+            (
+                AAVE_USDC_PERMIT,
+                "305333244828f3d9cf9e5ade4cd46750b2427a04fccbe360b6c20febf376039c",
+            ),
+        ];
+
+        for (payload, digest) in cases {
+            let digest = hex::decode(digest).unwrap();
+            let sig = sign_typed_data_v4(test_seed(), 0, 0, payload.into()).unwrap();
+            assert_eq!(sig.len(), 65);
+            assert_eq!(sig, sign_eth_raw_hash(test_seed(), 0, 0, digest).unwrap());
+        }
+    }
+
+    #[test]
+    fn sign_typed_data_v4_recovers_to_signer() {
+        let digest = chain_eth::eip712::hash_typed_data_v4(AAVE_USDC_PERMIT).unwrap();
+        let sig = sign_typed_data_v4(test_seed(), 0, 0, AAVE_USDC_PERMIT.into()).unwrap();
+
+        let pubkey = recover_eth_pubkey(sig, digest.to_vec()).unwrap();
+        let signer =
+            chain_eth::address::pubkey_to_eth_address(&pubkey.try_into().unwrap()).unwrap();
+        // The permit's owner is this wallet's first address.
+        assert_eq!(signer, "0x9858EfFD232B4033E47d90003D41EC34EcaEda94");
+    }
+
+    #[test]
+    fn sign_typed_data_v4_wipes_seed_on_malformed_payload() {
+        let (probe, wiped) = crate::ffi_common::SeedProbe::new(test_seed());
+        let truncated = &PERMIT2_SINGLE[..PERMIT2_SINGLE.len() / 2];
+
+        assert!(sign_typed_data_v4_with_seed(probe, 0, 0, truncated).is_err());
+        assert_eq!(wiped.get(), Some(true));
+    }
+
+    #[test]
+    fn sign_typed_data_v4_rejects_malformed_payload() {
+        let truncated = &PERMIT2_SINGLE[..PERMIT2_SINGLE.len() / 2];
+        assert!(sign_typed_data_v4(test_seed(), 0, 0, truncated.into()).is_err());
+
+        let bad_amount = PERMIT2_SINGLE.replace("\"0\"", "\"-1\"");
+        assert!(sign_typed_data_v4(test_seed(), 0, 0, bad_amount).is_err());
+    }
+
     // ─── sign_erc20_transfer ────────────────────────────────────────
 
+    #[test]
+    fn sign_erc20_transfer_wipes_seed_on_parse_error() {
+        let (probe, wiped) = crate::ffi_common::SeedProbe::new(test_seed());
+        let result = sign_erc20_transfer_with_seed(
+            probe,
+            0,
+            0,
+            1,
+            0,
+            "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48".into(),
+            TO.into(),
+            "0x64".into(),
+            "not-hex".into(),
+            "0xba43b7400".into(),
+            65_000,
+        );
+
+        assert!(result.is_err());
+        assert_eq!(wiped.get(), Some(true));
+    }
+
     #[test]
     fn sign_erc20_transfer_produces_valid_tx() {
         let seed = test_seed();
@@ -243,41 +1178,91 @@ mod tests {
             0,
             "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48".into(), // USDC
             "0x000000000000000000000000000000000000dEaD".into(),
-            "0x64".into(), // 100
-            "0x3b9aca00".into(), // 1 gwei
+            "0x64".into(),        // 100
+            "0x3b9aca00".into(),  // 1 gwei
             "0xba43b7400".into(), // 50 gwei
             65_000,
         );
         assert!(result.is_ok());
-        let tx_bytes = result.unwrap();
-        assert_eq!(tx_bytes[0], 0x02); // EIP-1559 type byte
-        assert!(tx_bytes.len() > 10);
+        let signed = result.unwrap();
+        assert_eq!(signed.raw_tx[0], 0x02); // EIP-1559 type byte
+        assert!(signed.raw_tx.len() > 10);
+        assert!(signed.warnings.is_empty());
+    }
+
+    #[test]
+    fn sign_erc20_transfer_returns_low_gas_warning() {
+        let signed = sign_erc20_transfer(
+            test_seed(),
+            0,
+            0,
+            1,
+            0,
+            "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48".into(),
+            TO.into(),
+            "0x64".into(),
+            "0x3b9aca00".into(),
+            "0xba43b7400".into(),
+            21_000,
+        )
+        .unwrap();
+
+        assert_eq!(signed.raw_tx[0], 0x02);
+        assert_eq!(signed.warnings.len(), 1);
+        assert!(
+            signed.warnings[0].contains("21000"),
+            "{}",
+            signed.warnings[0]
+        );
     }
 
     #[test]
     fn sign_erc20_transfer_deterministic() {
         let result1 = sign_erc20_transfer(
-            test_seed(), 0, 0, 1, 0,
+            test_seed(),
+            0,
+            0,
+            1,
+            0,
             "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48".into(),
             "0x000000000000000000000000000000000000dEaD".into(),
-            "0x64".into(), "0x3b9aca00".into(), "0xba43b7400".into(), 65_000,
-        ).unwrap();
+            "0x64".into(),
+            "0x3b9aca00".into(),
+            "0xba43b7400".into(),
+            65_000,
+        )
+        .unwrap();
         let result2 = sign_erc20_transfer(
-            test_seed(), 0, 0, 1, 0,
+            test_seed(),
+            0,
+            0,
+            1,
+            0,
             "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48".into(),
             "0x000000000000000000000000000000000000dEaD".into(),
-            "0x64".into(), "0x3b9aca00".into(), "0xba43b7400".into(), 65_000,
-        ).unwrap();
-        assert_eq!(result1, result2);
+            "0x64".into(),
+            "0x3b9aca00".into(),
+            "0xba43b7400".into(),
+            65_000,
+        )
+        .unwrap();
+        assert_eq!(result1.raw_tx, result2.raw_tx);
     }
 
     #[test]
     fn sign_erc20_transfer_invalid_contract() {
         let result = sign_erc20_transfer(
-            test_seed(), 0, 0, 1, 0,
+            test_seed(),
+            0,
+            0,
+            1,
+            0,
             "not-an-address".into(),
             "0x000000000000000000000000000000000000dEaD".into(),
-            "0x64".into(), "0x0".into(), "0x0".into(), 65_000,
+            "0x64".into(),
+            "0x0".into(),
+            "0x0".into(),
+            65_000,
         );
         assert!(result.is_err());
     }
@@ -285,10 +1270,17 @@ mod tests {
     #[test]
     fn sign_erc20_transfer_invalid_recipient() {
         let result = sign_erc20_transfer(
-            test_seed(), 0, 0, 1, 0,
+            test_seed(),
+            0,
+            0,
+            1,
+            0,
             "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48".into(),
             "bad-address".into(),
-            "0x64".into(), "0x0".into(), "0x0".into(), 65_000,
+            "0x64".into(),
+            "0x0".into(),
+            "0x0".into(),
+            65_000,
         );
         assert!(result.is_err());
     }
@@ -296,10 +1288,17 @@ mod tests {
     #[test]
     fn sign_erc20_transfer_invalid_amount_hex() {
         let result = sign_erc20_transfer(
-            test_seed(), 0, 0, 1, 0,
+            test_seed(),
+            0,
+            0,
+            1,
+            0,
             "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48".into(),
             "0x000000000000000000000000000000000000dEaD".into(),
-            "not-hex".into(), "0x0".into(), "0x0".into(), 65_000,
+            "not-hex".into(),
+            "0x0".into(),
+            "0x0".into(),
+            65_000,
         );
         assert!(result.is_err());
     }
@@ -307,17 +1306,205 @@ mod tests {
     #[test]
     fn sign_erc20_transfer_different_chains_differ() {
         let result1 = sign_erc20_transfer(
-            test_seed(), 0, 0, 1, 0, // Ethereum
+            test_seed(),
+            0,
+            0,
+            1,
+            0, // Ethereum
             "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48".into(),
             "0x000000000000000000000000000000000000dEaD".into(),
-            "0x64".into(), "0x0".into(), "0x0".into(), 65_000,
-        ).unwrap();
+            "0x64".into(),
+            "0x0".into(),
+            "0x0".into(),
+            65_000,
+        )
+        .unwrap();
         let result2 = sign_erc20_transfer(
-            test_seed(), 0, 0, 137, 0, // Polygon
+            test_seed(),
+            0,
+            0,
+            137,
+            0, // Polygon
             "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48".into(),
             "0x000000000000000000000000000000000000dEaD".into(),
-            "0x64".into(), "0x0".into(), "0x0".into(), 65_000,
-        ).unwrap();
-        assert_ne!(result1, result2);
+            "0x64".into(),
+            "0x0".into(),
+            "0x0".into(),
+            65_000,
+        )
+        .unwrap();
+        assert_ne!(result1.raw_tx, result2.raw_tx);
+    }
+
+    // ─── sign_erc20_transfer_from ───────────────────────────────────
+
+    #[test]
+    fn sign_erc20_transfer_from_carries_calldata() {
+        let owner = "0x1111111111111111111111111111111111111111";
+        let signed = sign_erc20_transfer_from(
+            test_seed(),
+            0,
+            0,
+            1,
+            0,
+            "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48".into(),
+            owner.into(),
+            TO.into(),
+            "0x64".into(),
+            "0x3b9aca00".into(),
+            "0xba43b7400".into(),
+            80_000,
+        )
+        .unwrap();
+        assert_eq!(signed[0], 0x02);
+
+        let mut amount = [0u8; 32];
+        amount[31] = 0x64;
+        let calldata = chain_eth::erc20::encode_transfer_from(
+            &EthAddress::parse(owner).unwrap(),
+            &EthAddress::parse(TO).unwrap(),
+            amount,
+        );
+        assert!(signed.windows(calldata.len()).any(|w| w == calldata));
+    }
+
+    #[test]
+    fn sign_erc20_transfer_from_invalid_owner() {
+        let result = sign_erc20_transfer_from(
+            test_seed(),
+            0,
+            0,
+            1,
+            0,
+            "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48".into(),
+            "bad-owner".into(),
+            TO.into(),
+            "0x64".into(),
+            "0x0".into(),
+            "0x0".into(),
+            80_000,
+        );
+        assert!(result.is_err());
+    }
+
+    // ─── sign_erc20_approve_max ─────────────────────────────────────
+
+    #[test]
+    fn sign_erc20_approve_max_carries_max_amount() {
+        let token = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48";
+        let signed = sign_erc20_approve_max(
+            test_seed(),
+            0,
+            0,
+            1,
+            3,
+            token.into(),
+            TO.into(),
+            "0x3b9aca00".into(),
+            "0xba43b7400".into(),
+            60_000,
+        )
+        .unwrap();
+
+        let calldata = chain_eth::erc20::encode_approve_max(TO).unwrap();
+        let expected = sign_eth_transaction(
+            test_seed(),
+            0,
+            0,
+            1,
+            3,
+            token.into(),
+            "0x0".into(),
+            calldata,
+            "0x3b9aca00".into(),
+            "0xba43b7400".into(),
+            60_000,
+            false,
+        )
+        .unwrap();
+        assert_eq!(signed, expected);
+    }
+
+    #[test]
+    fn sign_erc20_approve_max_invalid_spender() {
+        let result = sign_erc20_approve_max(
+            test_seed(),
+            0,
+            0,
+            1,
+            0,
+            TO.into(),
+            "bad-spender".into(),
+            "0x0".into(),
+            "0x0".into(),
+            60_000,
+        );
+        assert!(result.is_err());
+    }
+
+    // ─── sign_erc20_safe_approve ────────────────────────────────────
+
+    const USDT: &str = "0xdAC17F958D2ee523a2206206994597C13D831ec7";
+
+    #[test]
+    fn sign_erc20_safe_approve_resets_then_approves() {
+        let signed = sign_erc20_safe_approve(
+            test_seed(),
+            0,
+            0,
+            1,
+            7,
+            USDT.into(),
+            TO.into(),
+            "0x64".into(),
+            "0x3b9aca00".into(),
+            "0xba43b7400".into(),
+            60_000,
+        )
+        .unwrap();
+        assert_eq!(signed.len(), 2);
+
+        let mut amount = [0u8; 32];
+        amount[31] = 0x64;
+        let [reset, approve] = chain_eth::erc20::encode_safe_approve(TO, amount).unwrap();
+
+        // Signing is deterministic, so each must equal a plain contract call
+        // carrying that calldata at the expected nonce.
+        for (raw, nonce, data) in [(&signed[0], 7, reset), (&signed[1], 8, approve)] {
+            let expected = sign_eth_transaction(
+                test_seed(),
+                0,
+                0,
+                1,
+                nonce,
+                USDT.into(),
+                "0x0".into(),
+                data,
+                "0x3b9aca00".into(),
+                "0xba43b7400".into(),
+                60_000,
+                false,
+            )
+            .unwrap();
+            assert_eq!(raw, &expected);
+        }
+    }
+
+    #[test]
+    fn sign_erc20_safe_approve_rejects_nonce_overflow() {
+        let result = sign_erc20_safe_approve(
+            test_seed(),
+            0,
+            0,
+            1,
+            u64::MAX,
+            USDT.into(),
+            TO.into(),
+            "0x64".into(),
+            "0x0".into(),
+            "0x0".into(),
+            60_000,
+        );
+        assert!(matches!(result, Err(WalletError::TransactionFailed { .. })));
     }
 }
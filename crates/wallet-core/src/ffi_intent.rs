@@ -0,0 +1,285 @@
+use crate::error::WalletError;
+use crate::ffi_btc::{self, UtxoData};
+use crate::ffi_common::with_zeroized_seed;
+use crate::ffi_eth;
+use crate::ffi_sol;
+use crate::ffi_zec::{self, ZecUtxoData};
+use crate::types::Chain;
+use crypto_utils::zeroizing::ZeroizingBytes;
+use std::ops::Deref;
+use zeroize::Zeroize;
+
+/// Chain-specific fee and replay parameters for a [`TransferIntent`]
+///
+/// The variant must match the intent's chain family.
+pub enum FeeHint {
+    /// Any EVM chain: EIP-1559 fees and the account nonce
+    Evm {
+        nonce: u64,
+        max_priority_fee_hex: String,
+        max_fee_hex: String,
+        gas_limit: u64,
+    },
+    /// Solana: the blockhash the transaction is valid against
+    Solana { recent_blockhash: Vec<u8> },
+    /// Bitcoin: the UTXOs to choose from, the fee rate and the change index
+    Bitcoin {
+        utxos: Vec<UtxoData>,
+        fee_rate_sat_vbyte: u64,
+        change_index: u32,
+    },
+    /// Zcash: the UTXOs to choose from, the fee rate, the expiry height and the
+    /// change index
+    Zcash {
+        utxos: Vec<ZecUtxoData>,
+        fee_rate_zat_byte: u64,
+        expiry_height: u32,
+        change_index: u32,
+    },
+}
+
+/// A native-coin transfer, described the same way on every chain
+///
+/// `amount` is a decimal string in the chain's base unit (wei, lamports,
+/// satoshi, zatoshi). Solana derives keys per account only, so `from_index`
+/// is ignored there. UTXO chains send change to the wallet's own change
+/// address at the fee hint's `change_index`.
+pub struct TransferIntent {
+    pub chain: Chain,
+    pub from_account: u32,
+    pub from_index: u32,
+    pub to: String,
+    pub amount: String,
+    pub fee_hint: FeeHint,
+}
+
+/// Sign a [`TransferIntent`] with the chain's dedicated signer
+///
+/// Returns the same bytes as calling that signer directly.
+pub fn sign_intent(seed: Vec<u8>, intent: TransferIntent) -> Result<Vec<u8>, WalletError> {
+    sign_intent_with_seed(seed, intent)
+}
+
+/// [`sign_intent`] over any zeroizable seed container. The amount is parsed
+/// and the fee hint matched inside the zeroizing closure, so the seed is wiped
+/// even when the intent is rejected.
+pub(crate) fn sign_intent_with_seed<S>(
+    seed: S,
+    intent: TransferIntent,
+) -> Result<Vec<u8>, WalletError>
+where
+    S: Deref<Target = [u8]> + Zeroize,
+{
+    let TransferIntent {
+        chain,
+        from_account,
+        from_index,
+        to,
+        amount,
+        fee_hint,
+    } = intent;
+
+    with_zeroized_seed(seed, |s| match (chain, fee_hint) {
+        (
+            chain,
+            FeeHint::Evm {
+                nonce,
+                max_priority_fee_hex,
+                max_fee_hex,
+                gas_limit,
+            },
+        ) if chain.evm_chain_id().is_some() => {
+            let chain_id = chain.evm_chain_id().unwrap_or_default();
+            let value_wei = chain_eth::amount::parse_u256(&amount)?;
+            ffi_eth::sign_eth_transaction_with_seed(
+                ZeroizingBytes::from(s),
+                from_account,
+                from_index,
+                chain_id,
+                nonce,
+                to,
+                format!("0x{}", hex::encode(value_wei)),
+                Vec::new(),
+                max_priority_fee_hex,
+                max_fee_hex,
+                gas_limit,
+                true,
+            )
+        }
+        (Chain::Solana | Chain::SolanaDevnet, FeeHint::Solana { recent_blockhash }) => {
+            ffi_sol::sign_sol_transfer_with_seed(
+                ZeroizingBytes::from(s),
+                from_account,
+                to,
+                parse_amount(&amount)?,
+                recent_blockhash,
+            )
+        }
+        (
+            chain @ (Chain::Bitcoin | Chain::BitcoinTestnet),
+            FeeHint::Bitcoin {
+                utxos,
+                fee_rate_sat_vbyte,
+                change_index,
+            },
+        ) => ffi_btc::sign_btc_transaction_with_seed(
+            ZeroizingBytes::from(s),
+            from_account,
+            from_index,
+            change_index,
+            utxos,
+            to,
+            parse_amount(&amount)?,
+            None,
+            fee_rate_sat_vbyte,
+            chain == Chain::BitcoinTestnet,
+        )
+        .map(|signed| signed.raw_tx),
+        (
+            chain @ (Chain::Zcash | Chain::ZcashTestnet),
+            FeeHint::Zcash {
+                utxos,
+                fee_rate_zat_byte,
+                expiry_height,
+                change_index,
+            },
+        ) => ffi_zec::sign_zec_transaction_with_seed(
+            ZeroizingBytes::from(s),
+            from_account,
+            from_index,
+            change_index,
+            utxos,
+            to,
+            parse_amount(&amount)?,
+            None,
+            fee_rate_zat_byte,
+            expiry_height,
+            chain == Chain::ZcashTestnet,
+        )
+        .map(|signed| signed.raw_tx),
+        (chain, _) => Err(WalletError::TransactionFailed {
+            message: format!("fee hint does not match chain {chain:?}"),
+        }),
+    })
+}
+
+/// Parse a decimal base-unit amount
+fn parse_amount(amount: &str) -> Result<u64, WalletError> {
+    amount.parse().map_err(|e| WalletError::TransactionFailed {
+        message: format!("Invalid amount {amount:?}: {e}"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ffi_common::SeedProbe;
+    use crate::mnemonic;
+
+    const TEST_MNEMONIC: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+    const ETH_TO: &str = "0x000000000000000000000000000000000000dEaD";
+    const SOL_TO: &str = "11111111111111111111111111111112";
+
+    fn test_seed() -> Vec<u8> {
+        mnemonic::mnemonic_to_seed(TEST_MNEMONIC, "").unwrap()
+    }
+
+    fn evm_hint() -> FeeHint {
+        FeeHint::Evm {
+            nonce: 4,
+            max_priority_fee_hex: "0x3b9aca00".into(),
+            max_fee_hex: "0x6fc23ac00".into(),
+            gas_limit: 21_000,
+        }
+    }
+
+    fn intent(chain: Chain, to: &str, amount: &str, fee_hint: FeeHint) -> TransferIntent {
+        TransferIntent {
+            chain,
+            from_account: 0,
+            from_index: 1,
+            to: to.into(),
+            amount: amount.into(),
+            fee_hint,
+        }
+    }
+
+    #[test]
+    fn eth_intent_matches_dedicated_signer() {
+        let eth_intent = intent(Chain::Polygon, ETH_TO, "10000000000000000", evm_hint());
+        let signed = sign_intent(test_seed(), eth_intent).unwrap();
+        let expected = ffi_eth::sign_eth_transaction(
+            test_seed(),
+            0,
+            1,
+            137,
+            4,
+            ETH_TO.into(),
+            "0x2386f26fc10000".into(),
+            vec![],
+            "0x3b9aca00".into(),
+            "0x6fc23ac00".into(),
+            21_000,
+            false,
+        )
+        .unwrap();
+        assert_eq!(signed, expected);
+    }
+
+    #[test]
+    fn sol_intent_matches_dedicated_signer() {
+        let hint = FeeHint::Solana {
+            recent_blockhash: vec![7; 32],
+        };
+        let signed =
+            sign_intent(test_seed(), intent(Chain::Solana, SOL_TO, "1500000", hint)).unwrap();
+        let expected =
+            ffi_sol::sign_sol_transfer(test_seed(), 0, SOL_TO.into(), 1_500_000, vec![7; 32])
+                .unwrap();
+        assert_eq!(signed, expected);
+    }
+
+    #[test]
+    fn mismatched_fee_hint_is_an_error() {
+        let result = sign_intent(test_seed(), intent(Chain::Solana, SOL_TO, "1", evm_hint()));
+        assert!(matches!(result, Err(WalletError::TransactionFailed { .. })));
+    }
+
+    #[test]
+    fn mismatched_fee_hint_wipes_seed() {
+        let (probe, wiped) = SeedProbe::new(test_seed());
+        let result = sign_intent_with_seed(probe, intent(Chain::Solana, SOL_TO, "1", evm_hint()));
+        assert!(matches!(result, Err(WalletError::TransactionFailed { .. })));
+        assert_eq!(wiped.get(), Some(true));
+    }
+
+    #[test]
+    fn invalid_amount_wipes_seed() {
+        let hint = FeeHint::Solana {
+            recent_blockhash: vec![7; 32],
+        };
+        let (probe, wiped) = SeedProbe::new(test_seed());
+        let result = sign_intent_with_seed(probe, intent(Chain::Solana, SOL_TO, "1.5", hint));
+        assert!(matches!(result, Err(WalletError::TransactionFailed { .. })));
+        assert_eq!(wiped.get(), Some(true));
+    }
+
+    #[test]
+    fn invalid_amount_is_an_error() {
+        let result = sign_intent(
+            test_seed(),
+            intent(Chain::Ethereum, ETH_TO, "1.5", evm_hint()),
+        );
+        assert!(matches!(result, Err(WalletError::TransactionFailed { .. })));
+
+        // 10^78 does not fit in 256 bits.
+        let too_big = format!("1{}", "0".repeat(78));
+        let eth_intent = intent(Chain::Ethereum, ETH_TO, &too_big, evm_hint());
+        match sign_intent(test_seed(), eth_intent) {
+            Err(WalletError::TransactionFailed { message }) => {
+                assert!(message.contains("256 bits"))
+            }
+            other => panic!("expected TransactionFailed, got {other:?}"),
+        }
+    }
+}
@@ -1,47 +1,211 @@
 use crate::error::WalletError;
+use crate::ffi_common::{validate_send_amount, with_zeroized_seed};
 use crate::hd_derivation;
-use crate::types::Chain;
+use crate::mnemonic;
+use crate::types::{Chain, FeeQuote};
+use chain_sol::address::SolAddress;
+use std::ops::Deref;
 use zeroize::Zeroize;
 
-/// Execute a closure with the seed, guaranteeing zeroization on both success and error paths.
-fn with_zeroized_seed<F, T>(mut seed: Vec<u8>, f: F) -> Result<T, WalletError>
+/// Sign a Solana SOL transfer
+pub fn sign_sol_transfer(
+    seed: Vec<u8>,
+    account: u32,
+    to_address: String,
+    lamports: u64,
+    recent_blockhash: Vec<u8>,
+) -> Result<Vec<u8>, WalletError> {
+    sign_sol_transfer_with_seed(seed, account, to_address, lamports, recent_blockhash)
+}
+
+/// [`sign_sol_transfer`] over any zeroizable seed container, e.g. a
+/// `ZeroizingBytes`. Inputs are validated inside the zeroizing closure, so the
+/// seed is wiped even when they are rejected.
+pub(crate) fn sign_sol_transfer_with_seed<S>(
+    seed: S,
+    account: u32,
+    to_address: String,
+    lamports: u64,
+    recent_blockhash: Vec<u8>,
+) -> Result<Vec<u8>, WalletError>
 where
-    F: FnOnce(&[u8]) -> Result<T, WalletError>,
+    S: Deref<Target = [u8]> + Zeroize,
 {
-    let result = f(&seed);
-    seed.zeroize();
+    with_zeroized_seed(seed, |s| {
+        validate_send_amount(Chain::Solana, lamports)?;
+        let to_address = SolAddress::parse(&to_address)?;
+        let blockhash: [u8; 32] =
+            recent_blockhash
+                .as_slice()
+                .try_into()
+                .map_err(|_| WalletError::TransactionFailed {
+                    message: "Invalid blockhash length".into(),
+                })?;
+
+        let key = hd_derivation::derive_ed25519_key(s, Chain::Solana, account)?;
+
+        let tx = chain_sol::transaction::build_sol_transfer(
+            &key.public_key,
+            to_address.as_bytes(),
+            lamports,
+            &blockhash,
+        )?;
+
+        Ok(chain_sol::transaction::sign_transaction(&tx, &key.private_key)?)
+    })
+}
+
+/// Sign a Solana SOL transfer with an imported Base58 keypair (Phantom export)
+///
+/// For accounts imported without a seed. The keypair must decode to 64 bytes
+/// whose public half matches the secret; the sender is that public key. The
+/// decoded secret and the input string are zeroized after use.
+pub fn sign_sol_transfer_with_imported_key(
+    mut keypair_base58: String,
+    to_address: String,
+    lamports: u64,
+    recent_blockhash: Vec<u8>,
+) -> Result<Vec<u8>, WalletError> {
+    let imported = chain_sol::address::import_base58_keypair(&keypair_base58);
+    keypair_base58.zeroize();
+    let (mut private_key, public_key) = imported?;
+
+    let result =
+        (|| {
+            validate_send_amount(Chain::Solana, lamports)?;
+            let to_address = SolAddress::parse(&to_address)?;
+            let blockhash: [u8; 32] = recent_blockhash.as_slice().try_into().map_err(|_| {
+                WalletError::TransactionFailed {
+                    message: "Invalid blockhash length".into(),
+                }
+            })?;
+
+            let tx = chain_sol::transaction::build_sol_transfer(
+                &public_key,
+                to_address.as_bytes(),
+                lamports,
+                &blockhash,
+            )?;
+
+            Ok(chain_sol::transaction::sign_transaction(&tx, &private_key)?)
+        })();
+    private_key.zeroize();
     result
 }
 
-/// Sign a Solana SOL transfer
-pub fn sign_sol_transfer(
+/// Sign a Solana SOL transfer with a memo (e.g. an exchange deposit reference)
+///
+/// The Memo program instruction follows the transfer in the same transaction,
+/// so the memo cannot be separated from the payment it labels.
+pub fn sign_sol_transfer_memo(
+    seed: Vec<u8>,
+    account: u32,
+    to_address: String,
+    lamports: u64,
+    memo: String,
+    recent_blockhash: Vec<u8>,
+) -> Result<Vec<u8>, WalletError> {
+    with_zeroized_seed(seed, |s| {
+        validate_send_amount(Chain::Solana, lamports)?;
+        let to_address = SolAddress::parse(&to_address)?;
+        let blockhash: [u8; 32] =
+            recent_blockhash
+                .as_slice()
+                .try_into()
+                .map_err(|_| WalletError::TransactionFailed {
+                    message: "Invalid blockhash length".into(),
+                })?;
+
+        let key = hd_derivation::derive_ed25519_key(s, Chain::Solana, account)?;
+
+        let tx = chain_sol::memo::build_sol_transfer_with_memo(
+            &key.public_key,
+            to_address.as_bytes(),
+            lamports,
+            &memo,
+            &blockhash,
+        )?;
+
+        Ok(chain_sol::transaction::sign_transaction(
+            &tx,
+            &key.private_key,
+        )?)
+    })
+}
+
+/// Sign a Solana Pay SOL transfer carrying reference keys
+///
+/// `references` are Base58 public keys from the payment request; each is added
+/// to the transfer as a read-only account so the merchant can find the payment
+/// on-chain.
+pub fn sign_sol_transfer_with_references(
     seed: Vec<u8>,
     account: u32,
     to_address: String,
     lamports: u64,
+    references: Vec<String>,
     recent_blockhash: Vec<u8>,
 ) -> Result<Vec<u8>, WalletError> {
-    let to_bytes = chain_sol::address::address_to_bytes(&to_address)?;
-    let blockhash: [u8; 32] = recent_blockhash
-        .as_slice()
-        .try_into()
-        .map_err(|_| WalletError::TransactionFailed("Invalid blockhash length".into()))?;
+    sign_sol_transfer_with_references_with_seed(
+        seed,
+        account,
+        to_address,
+        lamports,
+        references,
+        recent_blockhash,
+    )
+}
 
+/// [`sign_sol_transfer_with_references`] over any zeroizable seed container.
+/// Inputs are validated inside the zeroizing closure, so the seed is wiped
+/// even when they are rejected.
+pub(crate) fn sign_sol_transfer_with_references_with_seed<S>(
+    seed: S,
+    account: u32,
+    to_address: String,
+    lamports: u64,
+    references: Vec<String>,
+    recent_blockhash: Vec<u8>,
+) -> Result<Vec<u8>, WalletError>
+where
+    S: Deref<Target = [u8]> + Zeroize,
+{
     with_zeroized_seed(seed, |s| {
+        validate_send_amount(Chain::Solana, lamports)?;
+        let to_address = SolAddress::parse(&to_address)?;
+        let references = references
+            .iter()
+            .map(|r| SolAddress::parse(r).map(|a| *a.as_bytes()))
+            .collect::<Result<Vec<_>, _>>()?;
+        let blockhash: [u8; 32] =
+            recent_blockhash
+                .as_slice()
+                .try_into()
+                .map_err(|_| WalletError::TransactionFailed {
+                    message: "Invalid blockhash length".into(),
+                })?;
+
         let key = hd_derivation::derive_ed25519_key(s, Chain::Solana, account)?;
 
-        let tx = chain_sol::transaction::build_sol_transfer(
+        let tx = chain_sol::transaction::build_sol_transfer_with_references(
             &key.public_key,
-            &to_bytes,
+            to_address.as_bytes(),
             lamports,
+            &references,
             &blockhash,
         )?;
 
-        Ok(chain_sol::transaction::sign_transaction(&tx, &key.private_key)?)
+        Ok(chain_sol::transaction::sign_transaction(
+            &tx,
+            &key.private_key,
+        )?)
     })
 }
 
 /// Sign an SPL token transfer on Solana
+///
+/// Plain `Transfer` between the derived ATAs; fails on-chain if the recipient
+/// has no token account yet. Prefer [`sign_spl_transfer_full`].
 pub fn sign_spl_transfer(
     seed: Vec<u8>,
     account: u32,
@@ -51,38 +215,95 @@ pub fn sign_spl_transfer(
     decimals: u8,
     recent_blockhash: Vec<u8>,
 ) -> Result<Vec<u8>, WalletError> {
-    let to_bytes = chain_sol::address::address_to_bytes(&to_address)?;
-    let mint_bytes = chain_sol::address::address_to_bytes(&mint_address)?;
-    let blockhash: [u8; 32] = recent_blockhash
-        .as_slice()
-        .try_into()
-        .map_err(|_| WalletError::TransactionFailed("Invalid blockhash length".into()))?;
+    sign_spl_transfer_full(
+        seed,
+        account,
+        to_address,
+        mint_address,
+        amount,
+        decimals,
+        recent_blockhash,
+        false,
+        false,
+    )
+}
+
+/// Sign an SPL token transfer on Solana -- the recommended SPL entry point
+///
+/// Derives the sender's and recipient's associated token accounts from the
+/// wallet addresses. With `create_recipient_ata` an idempotent create-ATA
+/// instruction (paid by the sender) goes first, so sending to a wallet that
+/// never held the token works. With `use_transfer_checked` the transfer is a
+/// `TransferChecked`, which makes the token program verify mint and decimals.
+#[allow(clippy::too_many_arguments)]
+pub fn sign_spl_transfer_full(
+    seed: Vec<u8>,
+    account: u32,
+    recipient_wallet: String,
+    mint_address: String,
+    amount: u64,
+    decimals: u8,
+    recent_blockhash: Vec<u8>,
+    create_recipient_ata: bool,
+    use_transfer_checked: bool,
+) -> Result<Vec<u8>, WalletError> {
+    sign_spl_transfer_full_with_seed(
+        seed,
+        account,
+        recipient_wallet,
+        mint_address,
+        amount,
+        decimals,
+        recent_blockhash,
+        create_recipient_ata,
+        use_transfer_checked,
+    )
+}
 
+/// [`sign_spl_transfer_full`] over any zeroizable seed container. Inputs are
+/// validated inside the zeroizing closure, so the seed is wiped even when
+/// they are rejected.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn sign_spl_transfer_full_with_seed<S>(
+    seed: S,
+    account: u32,
+    recipient_wallet: String,
+    mint_address: String,
+    amount: u64,
+    decimals: u8,
+    recent_blockhash: Vec<u8>,
+    create_recipient_ata: bool,
+    use_transfer_checked: bool,
+) -> Result<Vec<u8>, WalletError>
+where
+    S: Deref<Target = [u8]> + Zeroize,
+{
     with_zeroized_seed(seed, |s| {
-        let key = hd_derivation::derive_ed25519_key(s, Chain::Solana, account)?;
+        let recipient_wallet = SolAddress::parse(&recipient_wallet)?;
+        let mint_address = SolAddress::parse(&mint_address)?;
+        let blockhash: [u8; 32] =
+            recent_blockhash
+                .as_slice()
+                .try_into()
+                .map_err(|_| WalletError::TransactionFailed {
+                    message: "Invalid blockhash length".into(),
+                })?;
 
-        // Derive ATAs for sender and recipient
-        let sender_ata = chain_sol::spl_token::derive_associated_token_address(
-            &key.public_key,
-            &mint_bytes,
-        )?;
-        let recipient_ata = chain_sol::spl_token::derive_associated_token_address(
-            &to_bytes,
-            &mint_bytes,
-        )?;
+        let key = hd_derivation::derive_ed25519_key(s, Chain::Solana, account)?;
 
-        // Build SPL transfer instruction
-        let spl_ix = chain_sol::spl_token::build_spl_transfer(
-            &sender_ata,
-            &recipient_ata,
+        let instructions = spl_transfer_instructions(
             &key.public_key,
+            &recipient_wallet,
+            &mint_address,
             amount,
             decimals,
+            create_recipient_ata,
+            use_transfer_checked,
         )?;
 
         // Compile into a transaction with the sender as fee payer
         let tx = chain_sol::transaction::compile_transaction(
-            &[spl_ix],
+            &instructions,
             &key.public_key,
             &blockhash,
         )?;
@@ -91,6 +312,49 @@ pub fn sign_spl_transfer(
     })
 }
 
+/// Instructions for an SPL transfer from `sender`'s ATA to `recipient`'s:
+/// the optional create-ATA instruction first, then the transfer.
+fn spl_transfer_instructions(
+    sender: &[u8; 32],
+    recipient: &SolAddress,
+    mint: &SolAddress,
+    amount: u64,
+    decimals: u8,
+    create_recipient_ata: bool,
+    use_transfer_checked: bool,
+) -> Result<Vec<chain_sol::transaction::SolInstruction>, WalletError> {
+    use chain_sol::spl_token;
+
+    // Derive ATAs for sender and recipient
+    let sender_ata = spl_token::derive_associated_token_address(sender, mint.as_bytes())?;
+    let recipient_ata =
+        spl_token::derive_associated_token_address(recipient.as_bytes(), mint.as_bytes())?;
+
+    let mut instructions = Vec::with_capacity(2);
+    if create_recipient_ata {
+        instructions.push(spl_token::build_create_associated_token_account(
+            sender,
+            recipient.as_bytes(),
+            mint.as_bytes(),
+        )?);
+    }
+
+    instructions.push(if use_transfer_checked {
+        spl_token::build_spl_transfer_checked(
+            &sender_ata,
+            mint.as_bytes(),
+            &recipient_ata,
+            sender,
+            amount,
+            decimals,
+        )?
+    } else {
+        spl_token::build_spl_transfer(&sender_ata, &recipient_ata, sender, amount, decimals)?
+    });
+
+    Ok(instructions)
+}
+
 /// Sign an arbitrary message with the Solana Ed25519 key.
 /// Used by WalletConnect `solana_signMessage` -- signs raw bytes, returns 64-byte Ed25519 signature.
 pub fn sign_sol_message(
@@ -126,17 +390,86 @@ pub fn sign_sol_raw_transaction(
     })
 }
 
+/// Export the Solana keypair for `account` as a `solana-keygen` JSON byte array
+///
+/// Returns `[secret(32) || public(32)]` as a string like `[12,34,...]` for
+/// import into the Solana CLI or another wallet.
+///
+/// The result IS the private key. The app must only show it behind an
+/// explicit export confirmation and must not log, cache, or send it anywhere.
+/// Strings returned across the FFI boundary cannot be zeroized from Rust.
+pub fn export_sol_keypair(
+    mnemonic_phrase: String,
+    passphrase: String,
+    account: u32,
+) -> Result<String, WalletError> {
+    let seed = mnemonic::mnemonic_to_seed_zeroizing(&mnemonic_phrase, &passphrase)?;
+    let key = hd_derivation::derive_ed25519_key(&seed, Chain::Solana, account)?;
+
+    let mut keypair = chain_sol::address::export_keypair(&key.private_key);
+    let json = serde_json::to_string(&keypair[..]);
+    keypair.zeroize();
+
+    json.map_err(|e| WalletError::Internal {
+        message: e.to_string(),
+    })
+}
+
+/// Format lamports as a decimal SOL string ("1.5")
+pub fn format_lamports(lamports: u64) -> String {
+    chain_sol::units::format_lamports(lamports)
+}
+
+/// Parse a decimal SOL string into lamports; rejects more than 9 decimals
+pub fn parse_sol(decimal: String) -> Result<u64, WalletError> {
+    Ok(chain_sol::units::parse_sol(&decimal)?)
+}
+
+/// Format a raw SPL token amount with the mint's decimals
+pub fn format_spl_token_amount(raw: u64, decimals: u8) -> String {
+    chain_sol::units::format_token_amount(raw, decimals)
+}
+
+/// Parse a decimal SPL token amount into raw units for the mint's decimals
+pub fn parse_spl_token_amount(decimal: String, decimals: u8) -> Result<u64, WalletError> {
+    Ok(chain_sol::units::parse_token_amount(&decimal, decimals)?)
+}
+
+/// Quote the cost of a Solana send: the base fee of 5000 lamports per signature
+///
+/// A plain transfer has one signature; a sponsored one (separate fee payer) two.
+pub fn estimate_sol_send_cost(lamports: u64, num_signatures: u32) -> Result<FeeQuote, WalletError> {
+    if num_signatures == 0 {
+        return Err(WalletError::TransactionFailed {
+            message: "a transaction needs at least one signature".into(),
+        });
+    }
+
+    let fee = chain_sol::transaction::LAMPORTS_PER_SIGNATURE * u64::from(num_signatures);
+    let total = lamports
+        .checked_add(fee)
+        .ok_or_else(|| WalletError::TransactionFailed {
+            message: "cost overflows u64".into(),
+        })?;
+
+    Ok(FeeQuote {
+        fee: fee.to_string(),
+        total_spent: total.to_string(),
+        change: "0".into(),
+    })
+}
+
 /// Derive the associated token account address for a wallet + mint pair
 pub fn derive_sol_token_address(
     wallet_address: String,
     mint_address: String,
 ) -> Result<String, WalletError> {
-    let wallet_bytes = chain_sol::address::address_to_bytes(&wallet_address)?;
-    let mint_bytes = chain_sol::address::address_to_bytes(&mint_address)?;
+    let wallet_address = SolAddress::parse(&wallet_address)?;
+    let mint_address = SolAddress::parse(&mint_address)?;
 
     let ata = chain_sol::spl_token::derive_associated_token_address(
-        &wallet_bytes,
-        &mint_bytes,
+        wallet_address.as_bytes(),
+        mint_address.as_bytes(),
     )?;
 
     Ok(chain_sol::address::bytes_to_address(&ata))
@@ -153,6 +486,178 @@ mod tests {
         mnemonic::mnemonic_to_seed(TEST_MNEMONIC, "").unwrap()
     }
 
+    // ─── seed zeroization ───────────────────────────────────────────
+
+    #[test]
+    fn sol_signing_wipes_seed() {
+        let to = chain_sol::address::keypair_to_address(&[0x42; 32]);
+        let (probe, wiped) = crate::ffi_common::SeedProbe::new(test_seed());
+        let signed =
+            sign_sol_transfer_with_seed(probe, 0, to.clone(), 1_000_000, vec![7; 32]).unwrap();
+
+        assert_eq!(wiped.get(), Some(true));
+        assert_eq!(
+            signed,
+            sign_sol_transfer(test_seed(), 0, to, 1_000_000, vec![7; 32]).unwrap()
+        );
+    }
+
+    #[test]
+    fn sol_signing_wipes_seed_when_inputs_are_rejected() {
+        let to = chain_sol::address::keypair_to_address(&[0x42; 32]);
+        let (probe, wiped) = crate::ffi_common::SeedProbe::new(test_seed());
+        let result = sign_sol_transfer_with_seed(probe, 0, to, 1_000_000, vec![7; 31]);
+
+        assert!(matches!(result, Err(WalletError::TransactionFailed { .. })));
+        assert_eq!(wiped.get(), Some(true));
+    }
+
+    // ─── sign_sol_transfer_with_imported_key ────────────────────────
+
+    #[test]
+    fn imported_key_signs_like_seed_account() {
+        let key = hd_derivation::derive_ed25519_key(&test_seed(), Chain::Solana, 0).unwrap();
+        let exported = bs58::encode(chain_sol::address::export_keypair(&key.private_key));
+        let to = chain_sol::address::keypair_to_address(&[0x42; 32]);
+
+        let lamports = 1_000_000;
+        let via_seed =
+            sign_sol_transfer(test_seed(), 0, to.clone(), lamports, vec![7; 32]).unwrap();
+        let via_import =
+            sign_sol_transfer_with_imported_key(exported.into_string(), to, lamports, vec![7; 32])
+                .unwrap();
+        assert_eq!(via_import, via_seed);
+    }
+
+    #[test]
+    fn imported_key_rejects_mismatched_keypair() {
+        let mut keypair = chain_sol::address::export_keypair(&[0x11; 32]);
+        keypair[32..].copy_from_slice(&[0x22; 32]);
+        let to = chain_sol::address::keypair_to_address(&[0x42; 32]);
+
+        let result = sign_sol_transfer_with_imported_key(
+            bs58::encode(keypair).into_string(),
+            to,
+            5_000,
+            vec![7; 32],
+        );
+        assert!(matches!(result, Err(WalletError::InvalidPrivateKey { .. })));
+    }
+
+    // ─── sign_sol_transfer_with_references ──────────────────────────
+
+    #[test]
+    fn references_are_carried_in_signed_transfer() {
+        let reference = chain_sol::address::keypair_to_address(&[0x33; 32]);
+        let to = chain_sol::address::keypair_to_address(&[0x42; 32]);
+
+        let wire = sign_sol_transfer_with_references(
+            test_seed(),
+            0,
+            to,
+            1_000_000,
+            vec![reference],
+            vec![7; 32],
+        )
+        .unwrap();
+        let tx = chain_sol::transaction::deserialize_transaction(&wire).unwrap();
+        assert!(tx.account_keys.contains(&[0x33; 32]));
+        assert_eq!(tx.compiled_instructions[0].account_indices.len(), 3);
+    }
+
+    #[test]
+    fn references_must_be_valid_addresses() {
+        let to = chain_sol::address::keypair_to_address(&[0x42; 32]);
+        let (probe, wiped) = crate::ffi_common::SeedProbe::new(test_seed());
+        let result = sign_sol_transfer_with_references_with_seed(
+            probe,
+            0,
+            to,
+            1_000_000,
+            vec!["not-base58!".into()],
+            vec![7; 32],
+        );
+        assert!(matches!(result, Err(WalletError::InvalidAddress { .. })));
+        assert_eq!(wiped.get(), Some(true));
+    }
+
+    // ─── export_sol_keypair ─────────────────────────────────────────
+
+    #[test]
+    fn export_sol_keypair_matches_derived_address() {
+        let json = export_sol_keypair(TEST_MNEMONIC.into(), String::new(), 0).unwrap();
+        let bytes: Vec<u8> = serde_json::from_str(&json).unwrap();
+        assert_eq!(bytes.len(), 64);
+
+        let key = hd_derivation::derive_ed25519_key(&test_seed(), Chain::Solana, 0).unwrap();
+        let public_half: [u8; 32] = bytes[32..].try_into().unwrap();
+        assert_eq!(
+            chain_sol::address::keypair_to_address(&public_half),
+            chain_sol::address::keypair_to_address(&key.public_key)
+        );
+    }
+
+    #[test]
+    fn export_sol_keypair_reimport_signs_identically() {
+        use ed25519_dalek::Signer;
+
+        let json = export_sol_keypair(TEST_MNEMONIC.into(), String::new(), 0).unwrap();
+        let bytes: Vec<u8> = serde_json::from_str(&json).unwrap();
+        let reimported =
+            ed25519_dalek::SigningKey::from_keypair_bytes(&bytes.try_into().unwrap()).unwrap();
+
+        let message = b"anvil export".to_vec();
+        assert_eq!(
+            reimported.sign(&message).to_bytes().to_vec(),
+            sign_sol_message(test_seed(), 0, message).unwrap()
+        );
+    }
+
+    #[test]
+    fn export_sol_keypair_invalid_mnemonic() {
+        assert!(export_sol_keypair("not a mnemonic".into(), String::new(), 0).is_err());
+    }
+
+    // ─── amount formatting ──────────────────────────────────────────
+
+    #[test]
+    fn sol_amount_ffi_roundtrip() {
+        assert_eq!(format_lamports(1_000_000_000), "1");
+        assert_eq!(parse_sol("1.5".into()).unwrap(), 1_500_000_000);
+        assert_eq!(format_spl_token_amount(2_500_000, 6), "2.5");
+        assert_eq!(parse_spl_token_amount("2.5".into(), 6).unwrap(), 2_500_000);
+    }
+
+    #[test]
+    fn parse_sol_over_precision_is_invalid_encoding() {
+        let result = parse_sol("0.0000000001".into());
+        assert!(matches!(result, Err(WalletError::InvalidEncoding { .. })));
+    }
+
+    // ─── estimate_sol_send_cost ─────────────────────────────────────
+
+    #[test]
+    fn sol_quote_matches_built_transaction() {
+        let tx = chain_sol::transaction::build_sol_transfer(
+            &[0x11; 32],
+            &[0x22; 32],
+            1_000_000,
+            &[0u8; 32],
+        )
+        .unwrap();
+        let quote = estimate_sol_send_cost(1_000_000, 1).unwrap();
+
+        let base_fee = chain_sol::transaction::base_fee(tx.num_required_signatures);
+        assert_eq!(quote.fee, base_fee.to_string());
+        assert_eq!(quote.total_spent, "1005000");
+        assert_eq!(quote.change, "0");
+    }
+
+    #[test]
+    fn sol_quote_rejects_zero_signatures() {
+        assert!(estimate_sol_send_cost(1_000, 0).is_err());
+    }
+
     // ─── sign_spl_transfer ──────────────────────────────────────────
 
     #[test]
@@ -247,6 +752,164 @@ mod tests {
         assert!(result.is_err());
     }
 
+    // ─── sign_sol_transfer_memo ─────────────────────────────────────
+
+    #[test]
+    fn sign_sol_transfer_memo_carries_transfer_and_memo() {
+        let signed = sign_sol_transfer_memo(
+            test_seed(),
+            0,
+            RECIPIENT.into(),
+            2_500_000,
+            "exchange-tag 90210".into(),
+            vec![0xAA; 32],
+        )
+        .unwrap();
+        let tx = chain_sol::deserialize_transaction(&signed).unwrap();
+
+        assert_eq!(tx.compiled_instructions.len(), 2);
+        let transfer = &tx.compiled_instructions[0];
+        assert_eq!(
+            chain_sol::decode_instruction(&transfer.data),
+            Some(chain_sol::SystemInstruction::Transfer {
+                lamports: 2_500_000
+            })
+        );
+        let memo = &tx.compiled_instructions[1];
+        assert_eq!(
+            tx.account_keys[memo.program_id_index as usize],
+            chain_sol::MEMO_PROGRAM_ID
+        );
+        assert_eq!(memo.data, b"exchange-tag 90210");
+    }
+
+    #[test]
+    fn sign_sol_transfer_memo_rejects_empty_memo() {
+        let result = sign_sol_transfer_memo(
+            test_seed(),
+            0,
+            RECIPIENT.into(),
+            1_000_000,
+            String::new(),
+            vec![0xAA; 32],
+        );
+        assert!(result.is_err());
+    }
+
+    // ─── sign_spl_transfer_full ─────────────────────────────────────
+
+    const RECIPIENT: &str = "11111111111111111111111111111112";
+    const USDC_MINT: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+
+    fn full_instructions(create: bool, checked: bool) -> Vec<chain_sol::SolInstruction> {
+        let key = hd_derivation::derive_ed25519_key(&test_seed(), Chain::Solana, 0).unwrap();
+        spl_transfer_instructions(
+            &key.public_key,
+            &SolAddress::parse(RECIPIENT).unwrap(),
+            &SolAddress::parse(USDC_MINT).unwrap(),
+            1_000_000,
+            6,
+            create,
+            checked,
+        )
+        .unwrap()
+    }
+
+    fn sign_full(create: bool, checked: bool) -> Vec<u8> {
+        sign_spl_transfer_full(
+            test_seed(),
+            0,
+            RECIPIENT.into(),
+            USDC_MINT.into(),
+            1_000_000,
+            6,
+            vec![0xAA; 32],
+            create,
+            checked,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn spl_full_flag_combinations_select_instructions() {
+        let token = chain_sol::TOKEN_PROGRAM_ID;
+        let ata = chain_sol::ASSOCIATED_TOKEN_PROGRAM_ID;
+        let cases = [
+            (false, false, vec![(token, 3u8)]),
+            (false, true, vec![(token, 12)]),
+            (true, false, vec![(ata, 1), (token, 3)]),
+            (true, true, vec![(ata, 1), (token, 12)]),
+        ];
+
+        for (create, checked, expected) in cases {
+            let shape: Vec<_> = full_instructions(create, checked)
+                .iter()
+                .map(|ix| (ix.program_id, ix.data[0]))
+                .collect();
+            assert_eq!(shape, expected, "create={create} checked={checked}");
+        }
+    }
+
+    #[test]
+    fn spl_full_creates_recipient_ata_before_transfer() {
+        let instructions = full_instructions(true, true);
+        let recipient_ata = chain_sol::derive_associated_token_address(
+            SolAddress::parse(RECIPIENT).unwrap().as_bytes(),
+            SolAddress::parse(USDC_MINT).unwrap().as_bytes(),
+        )
+        .unwrap();
+
+        // The create instruction makes the very account the transfer credits.
+        assert_eq!(instructions[0].accounts[1].pubkey, recipient_ata);
+        assert_eq!(instructions[1].accounts[2].pubkey, recipient_ata);
+    }
+
+    #[test]
+    fn spl_full_signs_every_flag_combination() {
+        let all: Vec<_> = [(false, false), (false, true), (true, false), (true, true)]
+            .into_iter()
+            .map(|(create, checked)| sign_full(create, checked))
+            .collect();
+
+        for (i, tx) in all.iter().enumerate() {
+            assert_eq!(tx[0], 0x01, "one signature, sender pays");
+            assert!(all[i + 1..].iter().all(|other| other != tx));
+        }
+    }
+
+    #[test]
+    fn spl_full_without_flags_matches_sign_spl_transfer() {
+        let plain = sign_spl_transfer(
+            test_seed(),
+            0,
+            RECIPIENT.into(),
+            USDC_MINT.into(),
+            1_000_000,
+            6,
+            vec![0xAA; 32],
+        )
+        .unwrap();
+        assert_eq!(sign_full(false, false), plain);
+    }
+
+    #[test]
+    fn spl_full_wipes_seed_on_invalid_mint() {
+        let (probe, wiped) = crate::ffi_common::SeedProbe::new(test_seed());
+        let result = sign_spl_transfer_full_with_seed(
+            probe,
+            0,
+            RECIPIENT.into(),
+            "not-a-mint".into(),
+            1_000_000,
+            6,
+            vec![0xAA; 32],
+            true,
+            true,
+        );
+        assert!(matches!(result, Err(WalletError::InvalidAddress { .. })));
+        assert_eq!(wiped.get(), Some(true));
+    }
+
     // ─── derive_sol_token_address ───────────────────────────────────
 
     #[test]
@@ -0,0 +1,255 @@
+use crypto_utils::zeroizing::ZeroizingBytes;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::error::WalletError;
+use crate::ffi_common::EncryptedSeedData;
+use crate::types::{Chain, DerivedAddress, EncryptedSeed};
+use crate::{address, ffi_eth, mnemonic, seed_encryption};
+
+/// A wallet handle that signs without the app ever passing the seed around
+///
+/// Holds only the encrypted seed and the password. Every method decrypts the
+/// seed, uses it and zeroizes it before returning; the plaintext seed is never
+/// stored in the object or handed back across the FFI. The password (and the
+/// ciphertext) are zeroized when the object is dropped.
+#[derive(Zeroize, ZeroizeOnDrop)]
+pub struct Wallet {
+    ciphertext: Vec<u8>,
+    salt: Vec<u8>,
+    password: ZeroizingBytes,
+}
+
+impl Wallet {
+    /// Open a wallet from an encrypted seed (as stored in the Keychain)
+    ///
+    /// Decrypts once up front so a wrong password fails here rather than on
+    /// the first signature.
+    pub fn new(ciphertext: Vec<u8>, salt: Vec<u8>, password: String) -> Result<Self, WalletError> {
+        let wallet = Self {
+            ciphertext,
+            salt,
+            password: ZeroizingBytes::new(password.into_bytes()),
+        };
+        wallet.decrypt_seed()?.zeroize();
+        Ok(wallet)
+    }
+
+    /// Create a wallet from a raw seed, encrypting it under `password`
+    pub fn from_seed(seed: Vec<u8>, password: String) -> Result<Self, WalletError> {
+        Self::encrypting(&ZeroizingBytes::new(seed), password)
+    }
+
+    /// Create a wallet from a mnemonic + BIP-39 passphrase, encrypting the
+    /// resulting seed under `password`
+    pub fn from_mnemonic(
+        mnemonic_phrase: String,
+        passphrase: String,
+        password: String,
+    ) -> Result<Self, WalletError> {
+        let seed = mnemonic::mnemonic_to_seed_zeroizing(&mnemonic_phrase, &passphrase)?;
+        Self::encrypting(&seed, password)
+    }
+
+    fn encrypting(seed: &[u8], password: String) -> Result<Self, WalletError> {
+        let encrypted = seed_encryption::encrypt_seed(seed, password.as_bytes())?;
+        Ok(Self {
+            ciphertext: encrypted.ciphertext,
+            salt: encrypted.salt,
+            password: ZeroizingBytes::new(password.into_bytes()),
+        })
+    }
+
+    /// The encrypted seed, for the app to persist
+    pub fn encrypted_seed(&self) -> EncryptedSeedData {
+        EncryptedSeedData {
+            ciphertext: self.ciphertext.clone(),
+            salt: self.salt.clone(),
+        }
+    }
+
+    /// Derive an address for a specific chain
+    pub fn derive_address(
+        &self,
+        chain: Chain,
+        account: u32,
+        index: u32,
+    ) -> Result<DerivedAddress, WalletError> {
+        let seed = ZeroizingBytes::new(self.decrypt_seed()?);
+        address::derive_address(&seed, chain, account, index)
+    }
+
+    /// Sign an arbitrary message with EIP-191 personal_sign
+    pub fn sign_eth_message(
+        &self,
+        account: u32,
+        index: u32,
+        message: Vec<u8>,
+    ) -> Result<Vec<u8>, WalletError> {
+        ffi_eth::sign_eth_message(self.decrypt_seed()?, account, index, message)
+    }
+
+    /// Sign an Ethereum EIP-1559 transaction
+    #[allow(clippy::too_many_arguments)]
+    pub fn sign_eth_transaction(
+        &self,
+        account: u32,
+        index: u32,
+        chain_id: u64,
+        nonce: u64,
+        to_address: String,
+        value_wei_hex: String,
+        data: Vec<u8>,
+        max_priority_fee_hex: String,
+        max_fee_hex: String,
+        gas_limit: u64,
+        strict_chain: bool,
+    ) -> Result<Vec<u8>, WalletError> {
+        ffi_eth::sign_eth_transaction_with_seed(
+            ZeroizingBytes::new(self.decrypt_seed()?),
+            account,
+            index,
+            chain_id,
+            nonce,
+            to_address,
+            value_wei_hex,
+            data,
+            max_priority_fee_hex,
+            max_fee_hex,
+            gas_limit,
+            strict_chain,
+        )
+    }
+
+    /// Decrypt the seed; the callee owns it and must zeroize it.
+    fn decrypt_seed(&self) -> Result<Vec<u8>, WalletError> {
+        let encrypted = EncryptedSeed {
+            ciphertext: self.ciphertext.clone(),
+            salt: self.salt.clone(),
+            se_ciphertext: None,
+        };
+        seed_encryption::decrypt_seed(&encrypted, &self.password)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_MNEMONIC: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+    const PASSWORD: &str = "correct horse battery staple";
+    const TO: &str = "0x000000000000000000000000000000000000dEaD";
+
+    fn test_seed() -> Vec<u8> {
+        mnemonic::mnemonic_to_seed(TEST_MNEMONIC, "").unwrap()
+    }
+
+    fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+        haystack.windows(needle.len()).any(|w| w == needle)
+    }
+
+    #[test]
+    fn methods_match_stateless_functions() {
+        let wallet =
+            Wallet::from_mnemonic(TEST_MNEMONIC.into(), String::new(), PASSWORD.into()).unwrap();
+
+        assert_eq!(
+            wallet
+                .derive_address(Chain::Ethereum, 0, 0)
+                .unwrap()
+                .address,
+            address::derive_address(&test_seed(), Chain::Ethereum, 0, 0)
+                .unwrap()
+                .address
+        );
+
+        assert_eq!(
+            wallet
+                .sign_eth_transaction(
+                    0,
+                    0,
+                    1,
+                    0,
+                    TO.into(),
+                    "0x1".into(),
+                    vec![],
+                    "0x1".into(),
+                    "0x2".into(),
+                    21_000,
+                    false,
+                )
+                .unwrap(),
+            ffi_eth::sign_eth_transaction(
+                test_seed(),
+                0,
+                0,
+                1,
+                0,
+                TO.into(),
+                "0x1".into(),
+                vec![],
+                "0x1".into(),
+                "0x2".into(),
+                21_000,
+                false,
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn reopened_wallet_signs_identically() {
+        let wallet = Wallet::from_seed(test_seed(), PASSWORD.into()).unwrap();
+        let stored = wallet.encrypted_seed();
+        let reopened = Wallet::new(stored.ciphertext, stored.salt, PASSWORD.into()).unwrap();
+
+        let message = b"anvil".to_vec();
+        assert_eq!(
+            reopened.sign_eth_message(0, 0, message.clone()).unwrap(),
+            ffi_eth::sign_eth_message(test_seed(), 0, 0, message).unwrap()
+        );
+    }
+
+    #[test]
+    fn wrong_password_fails_to_open() {
+        let stored = Wallet::from_seed(test_seed(), PASSWORD.into())
+            .unwrap()
+            .encrypted_seed();
+        let result = Wallet::new(stored.ciphertext, stored.salt, "wrong".into());
+        assert!(matches!(result, Err(WalletError::DecryptionFailed { .. })));
+    }
+
+    #[test]
+    fn seed_is_not_held_in_plaintext() {
+        let seed = test_seed();
+        let wallet = Wallet::from_seed(seed.clone(), PASSWORD.into()).unwrap();
+        wallet.derive_address(Chain::Bitcoin, 0, 0).unwrap();
+
+        let stored = wallet.encrypted_seed();
+        let blob = EncryptedSeed {
+            ciphertext: stored.ciphertext.clone(),
+            salt: stored.salt.clone(),
+            se_ciphertext: None,
+        };
+        // The persisted blob does hold the seed, just not in the clear.
+        assert_eq!(
+            seed_encryption::decrypt_seed(&blob, PASSWORD.as_bytes()).unwrap(),
+            seed
+        );
+
+        // No 8-byte run of the seed may appear in the stored bytes...
+        for chunk in seed.windows(8) {
+            for field in [
+                &stored.ciphertext[..],
+                &stored.salt[..],
+                &wallet.password[..],
+            ] {
+                assert!(!contains(field, chunk));
+            }
+        }
+        // ...nor in the serialized form the app writes to the Keychain.
+        let json = seed_encryption::serialize_encrypted_seed(&blob).unwrap();
+        let seed_json = serde_json::to_string(&seed[..8]).unwrap();
+        assert!(!json.contains(seed_json.trim_start_matches('[').trim_end_matches(']')));
+        assert!(!json.contains(&hex::encode(&seed[..8])));
+    }
+}
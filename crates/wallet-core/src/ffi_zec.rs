@@ -1,6 +1,8 @@
 use crate::error::WalletError;
+use crate::ffi_common::{validate_send_amount, with_zeroized_seed};
 use crate::hd_derivation;
-use crate::types::Chain;
+use crate::types::{Chain, SignedUtxoTransaction};
+use std::ops::Deref;
 use zeroize::Zeroize;
 
 /// Zcash UTXO data passed from Swift for transaction signing
@@ -11,29 +13,60 @@ pub struct ZecUtxoData {
     pub script_pubkey: Vec<u8>,
 }
 
-/// Execute a closure with the seed, guaranteeing zeroization on both success and error paths.
-fn with_zeroized_seed<F, T>(mut seed: Vec<u8>, f: F) -> Result<T, WalletError>
-where
-    F: FnOnce(&[u8]) -> Result<T, WalletError>,
-{
-    let result = f(&seed);
-    seed.zeroize();
-    result
-}
-
 /// Sign a Zcash transparent P2PKH transaction (v5 format with ZIP-244 sighash)
+///
+/// If `change_address` is `None`, change is sent to the wallet's own change
+/// address at `m/44'/133'/account'/1/change_index`. The result reports the
+/// address the change went to.
+#[allow(clippy::too_many_arguments)]
 pub fn sign_zec_transaction(
     seed: Vec<u8>,
     account: u32,
     index: u32,
+    change_index: u32,
     utxos: Vec<ZecUtxoData>,
     recipient_address: String,
     amount_zatoshi: u64,
-    change_address: String,
+    change_address: Option<String>,
     fee_rate_zat_byte: u64,
     expiry_height: u32,
     is_testnet: bool,
-) -> Result<Vec<u8>, WalletError> {
+) -> Result<SignedUtxoTransaction, WalletError> {
+    sign_zec_transaction_with_seed(
+        seed,
+        account,
+        index,
+        change_index,
+        utxos,
+        recipient_address,
+        amount_zatoshi,
+        change_address,
+        fee_rate_zat_byte,
+        expiry_height,
+        is_testnet,
+    )
+}
+
+/// [`sign_zec_transaction`] over any zeroizable seed container, e.g. a
+/// `ZeroizingBytes`. The amount is checked inside the zeroizing closure, so
+/// the seed is wiped even when it is rejected.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn sign_zec_transaction_with_seed<S>(
+    seed: S,
+    account: u32,
+    index: u32,
+    change_index: u32,
+    utxos: Vec<ZecUtxoData>,
+    recipient_address: String,
+    amount_zatoshi: u64,
+    change_address: Option<String>,
+    fee_rate_zat_byte: u64,
+    expiry_height: u32,
+    is_testnet: bool,
+) -> Result<SignedUtxoTransaction, WalletError>
+where
+    S: Deref<Target = [u8]> + Zeroize,
+{
     let chain = if is_testnet { Chain::ZcashTestnet } else { Chain::Zcash };
     let network = if is_testnet {
         chain_zec::address::ZecNetwork::Testnet
@@ -49,27 +82,210 @@ pub fn sign_zec_transaction(
             vout: u.vout,
             amount_zatoshi: u.amount_zatoshi,
             script_pubkey: u.script_pubkey,
+            redeem_script: None,
         })
         .collect();
 
     with_zeroized_seed(seed, |s| {
+        validate_send_amount(chain, amount_zatoshi)?;
         let key = hd_derivation::derive_secp256k1_key(s, chain, account, index)?;
 
-        let unsigned_tx = chain_zec::transaction::build_transparent_transaction(
-            &zec_utxos,
-            &recipient_address,
-            amount_zatoshi,
-            &change_address,
-            fee_rate_zat_byte,
-            network,
-            expiry_height,
-        )?;
-
-        let signed_bytes = chain_zec::transaction::sign_transaction(
-            &unsigned_tx,
-            &key.private_key,
-        )?;
-
-        Ok(signed_bytes)
+        let unsigned_tx = match &change_address {
+            Some(change_address) => chain_zec::transaction::build_transparent_transaction(
+                &zec_utxos,
+                &recipient_address,
+                amount_zatoshi,
+                change_address,
+                fee_rate_zat_byte,
+                network,
+                expiry_height,
+                None,
+                None,
+                0,
+                false,
+            )?,
+            None => {
+                let change_key =
+                    hd_derivation::derive_secp256k1_change_key(s, chain, account, change_index)?;
+                chain_zec::transaction::build_transparent_transaction_with_change_key(
+                    &zec_utxos,
+                    &recipient_address,
+                    amount_zatoshi,
+                    &change_key.public_key_compressed,
+                    fee_rate_zat_byte,
+                    network,
+                    expiry_height,
+                    None,
+                    0,
+                )?
+            }
+        };
+
+        let signer = chain_zec::transaction::InputSigner::SingleKey(key.private_key);
+        let raw_tx = chain_zec::transaction::sign_transaction(&unsigned_tx, &signer)?;
+
+        Ok(SignedUtxoTransaction {
+            raw_tx,
+            change_address: unsigned_tx.change_address,
+        })
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mnemonic;
+
+    const TEST_MNEMONIC: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    fn test_seed() -> Vec<u8> {
+        mnemonic::mnemonic_to_seed(TEST_MNEMONIC, "").unwrap()
+    }
+
+    fn receive_address() -> String {
+        crate::address::derive_address(&test_seed(), Chain::Zcash, 0, 0)
+            .unwrap()
+            .address
+    }
+
+    fn test_utxos() -> Vec<ZecUtxoData> {
+        let hash = chain_zec::address::address_to_pubkey_hash(&receive_address()).unwrap();
+        let mut script = vec![0x76, 0xA9, 0x14];
+        script.extend_from_slice(&hash);
+        script.extend_from_slice(&[0x88, 0xAC]);
+        vec![ZecUtxoData {
+            txid: "a".repeat(64),
+            vout: 0,
+            amount_zatoshi: 10_000_000,
+            script_pubkey: script,
+        }]
+    }
+
+    // ─── sign_zec_transaction ───────────────────────────────────────
+
+    fn change_address(change_index: u32) -> String {
+        let change_key =
+            hd_derivation::derive_secp256k1_change_key(&test_seed(), Chain::Zcash, 0, change_index)
+                .unwrap();
+        chain_zec::address::pubkey_to_t_address(
+            &change_key.public_key_compressed,
+            chain_zec::address::ZecNetwork::Mainnet,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn sign_zec_transaction_auto_change_pays_derived_change_address() {
+        let auto = sign_zec_transaction(
+            test_seed(),
+            0,
+            0,
+            0,
+            test_utxos(),
+            receive_address(),
+            5_000_000,
+            None,
+            1,
+            1_000_000,
+            false,
+        )
+        .unwrap();
+        let explicit = sign_zec_transaction(
+            test_seed(),
+            0,
+            0,
+            0,
+            test_utxos(),
+            receive_address(),
+            5_000_000,
+            Some(change_address(0)),
+            1,
+            1_000_000,
+            false,
+        )
+        .unwrap();
+
+        assert_eq!(auto.raw_tx, explicit.raw_tx);
+        assert_eq!(auto.change_address, Some(change_address(0)));
+    }
+
+    #[test]
+    fn sign_zec_transaction_auto_change_uses_change_index() {
+        let signed = sign_zec_transaction(
+            test_seed(),
+            0,
+            0,
+            7,
+            test_utxos(),
+            receive_address(),
+            5_000_000,
+            None,
+            1,
+            1_000_000,
+            false,
+        )
+        .unwrap();
+        assert_eq!(signed.change_address, Some(change_address(7)));
+        assert_ne!(signed.change_address, Some(change_address(0)));
+    }
+
+    #[test]
+    fn sign_zec_transaction_explicit_change_still_works() {
+        let auto = sign_zec_transaction(
+            test_seed(),
+            0,
+            0,
+            0,
+            test_utxos(),
+            receive_address(),
+            5_000_000,
+            None,
+            1,
+            1_000_000,
+            false,
+        )
+        .unwrap();
+        let explicit = sign_zec_transaction(
+            test_seed(),
+            0,
+            0,
+            0,
+            test_utxos(),
+            receive_address(),
+            5_000_000,
+            Some(receive_address()),
+            1,
+            1_000_000,
+            false,
+        )
+        .unwrap();
+
+        assert!(!explicit.raw_tx.is_empty());
+        assert_ne!(auto.raw_tx, explicit.raw_tx);
+        assert_eq!(explicit.change_address, Some(receive_address()));
+    }
+
+    #[test]
+    fn sign_zec_transaction_wipes_seed_on_dust_amount() {
+        let (probe, wiped) = crate::ffi_common::SeedProbe::new(test_seed());
+        let result = sign_zec_transaction_with_seed(
+            probe,
+            0,
+            0,
+            0,
+            test_utxos(),
+            receive_address(),
+            546,
+            None,
+            1,
+            1_000_000,
+            false,
+        );
+
+        assert!(matches!(
+            result,
+            Err(WalletError::TransactionFailed { message }) if message.contains("dust")
+        ));
+        assert_eq!(wiped.get(), Some(true));
+    }
+}
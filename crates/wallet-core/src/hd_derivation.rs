@@ -1,20 +1,37 @@
-use bip32::{DerivationPath, XPrv};
+use bip32::{ChildNumber, DerivationPath, XPrv};
 use k256::ecdsa::SigningKey;
 use zeroize::Zeroize;
 
 use crate::error::WalletError;
 use crate::types::Chain;
 
+/// BIP-44 `change` level for receiving (external) addresses
+const EXTERNAL_CHAIN: u32 = 0;
+
+/// BIP-44 `change` level for change (internal) addresses
+const INTERNAL_CHAIN: u32 = 1;
+
 /// BIP-44 derivation path: m/purpose'/coin_type'/account'/change/address_index
 ///
 /// - BTC:  m/84'/0'/0'/0/0  (BIP-84 for native SegWit P2WPKH)
 /// - ETH:  m/44'/60'/0'/0/0 (BIP-44 standard)
 /// - SOL:  m/44'/501'/0'/0' (Solana uses hardened at all levels)
 fn derivation_path_for_chain(chain: Chain, account: u32, index: u32) -> Result<String, WalletError> {
+    derivation_path_for_branch(chain, account, EXTERNAL_CHAIN, index)
+}
+
+/// Same as `derivation_path_for_chain`, but with an explicit `change` level
+/// (0 = receive, 1 = change). Solana has no change level and ignores it.
+fn derivation_path_for_branch(
+    chain: Chain,
+    account: u32,
+    change: u32,
+    index: u32,
+) -> Result<String, WalletError> {
     match chain {
         // BIP-84 for native SegWit
-        Chain::Bitcoin => Ok(format!("m/84'/0'/{}'/0/{}", account, index)),
-        Chain::BitcoinTestnet => Ok(format!("m/84'/1'/{}'/0/{}", account, index)),
+        Chain::Bitcoin => Ok(format!("m/84'/0'/{}'/{}/{}", account, change, index)),
+        Chain::BitcoinTestnet => Ok(format!("m/84'/1'/{}'/{}/{}", account, change, index)),
 
         // BIP-44 for all EVM chains (same derivation, different chain_id at TX level)
         Chain::Ethereum
@@ -25,14 +42,14 @@ fn derivation_path_for_chain(chain: Chain, account: u32, index: u32) -> Result<S
         | Chain::Bsc
         | Chain::Avalanche
         | Chain::Sepolia
-        | Chain::PolygonAmoy => Ok(format!("m/44'/60'/{}'/0/{}", account, index)),
+        | Chain::PolygonAmoy => Ok(format!("m/44'/60'/{}'/{}/{}", account, change, index)),
 
         // Solana: all hardened
         Chain::Solana | Chain::SolanaDevnet => Ok(format!("m/44'/501'/{}'/0'", account)),
 
         // Zcash: BIP-44 coin type 133
-        Chain::Zcash => Ok(format!("m/44'/133'/{}'/0/{}", account, index)),
-        Chain::ZcashTestnet => Ok(format!("m/44'/1'/{}'/0/{}", account, index)),
+        Chain::Zcash => Ok(format!("m/44'/133'/{}'/{}/{}", account, change, index)),
+        Chain::ZcashTestnet => Ok(format!("m/44'/1'/{}'/{}/{}", account, change, index)),
     }
 }
 
@@ -44,29 +61,141 @@ pub fn derive_secp256k1_key(
     index: u32,
 ) -> Result<DerivedKey, WalletError> {
     let path_str = derivation_path_for_chain(chain, account, index)?;
+    derive_secp256k1_key_at_path(seed, path_str)
+}
+
+/// Derive the secp256k1 key for a change address (`m/.../account'/1/index`).
+///
+/// Only UTXO chains (BTC, ZEC) have change addresses; other chains are rejected.
+pub fn derive_secp256k1_change_key(
+    seed: &[u8],
+    chain: Chain,
+    account: u32,
+    index: u32,
+) -> Result<DerivedKey, WalletError> {
+    match chain {
+        Chain::Bitcoin | Chain::BitcoinTestnet | Chain::Zcash | Chain::ZcashTestnet => {}
+        _ => {
+            return Err(WalletError::UnsupportedChain {
+                message: format!("{} has no change addresses", chain.display_name()),
+            })
+        }
+    }
+    let path_str = derivation_path_for_branch(chain, account, INTERNAL_CHAIN, index)?;
+    derive_secp256k1_key_at_path(seed, path_str)
+}
+
+/// Derive `count` consecutive receive keys starting at `start`
+/// (`m/.../account'/0/start` .. `start + count - 1`).
+///
+/// The hardened prefix up to the `change` level is derived once and each
+/// address key is a single non-hardened step from it, which is what makes a
+/// gap-limit scan over many indices cheap.
+pub fn derive_secp256k1_keys(
+    seed: &[u8],
+    chain: Chain,
+    account: u32,
+    start: u32,
+    count: u32,
+) -> Result<Vec<DerivedKey>, WalletError> {
+    if matches!(chain, Chain::Solana | Chain::SolanaDevnet) {
+        return Err(WalletError::UnsupportedChain {
+            message: format!("{} is not a secp256k1 chain", chain.display_name()),
+        });
+    }
+
+    // Every secp256k1 path ends in `/change/index`; drop the index to get the parent.
+    let first_path = derivation_path_for_branch(chain, account, EXTERNAL_CHAIN, start)?;
+    let (parent_path, _) =
+        first_path
+            .rsplit_once('/')
+            .ok_or_else(|| WalletError::DerivationFailed {
+                message: format!("malformed path {first_path}"),
+            })?;
+    let parent = xprv_at_path(seed, parent_path)?;
+
+    let mut keys = Vec::with_capacity(count as usize);
+    for offset in 0..count {
+        let index = start
+            .checked_add(offset)
+            .ok_or_else(|| WalletError::DerivationFailed {
+                message: "address index overflows u32".into(),
+            })?;
+        let child_number =
+            ChildNumber::new(index, false).map_err(|e| WalletError::DerivationFailed {
+                message: e.to_string(),
+            })?;
+        let child =
+            parent
+                .derive_child(child_number)
+                .map_err(|e| WalletError::DerivationFailed {
+                    message: e.to_string(),
+                })?;
+        keys.push(derived_key_from_xprv(
+            &child,
+            format!("{parent_path}/{index}"),
+        )?);
+    }
+    Ok(keys)
+}
 
-    let path: DerivationPath = path_str
-        .parse()
-        .map_err(|e: bip32::Error| WalletError::DerivationFailed(e.to_string()))?;
+/// BIP-32 master key fingerprint: the first 4 bytes of HASH160 of the master
+/// public key, as shown in descriptors and PSBTs (`[73c5da0a/84'/0'/0']`).
+pub fn master_fingerprint(seed: &[u8]) -> Result<[u8; 4], WalletError> {
+    let master = XPrv::new(seed).map_err(|e| WalletError::DerivationFailed {
+        message: e.to_string(),
+    })?;
+    Ok(master.public_key().fingerprint())
+}
 
-    let xprv = XPrv::derive_from_path(seed, &path)
-        .map_err(|e| WalletError::DerivationFailed(e.to_string()))?;
+fn xprv_at_path(seed: &[u8], path_str: &str) -> Result<XPrv, WalletError> {
+    let path: DerivationPath =
+        path_str
+            .parse()
+            .map_err(|e: bip32::Error| WalletError::DerivationFailed {
+                message: e.to_string(),
+            })?;
 
-    let private_key_bytes: [u8; 32] = xprv.to_bytes().into();
-    let signing_key = SigningKey::from_bytes(&private_key_bytes.into())
-        .map_err(|e| WalletError::DerivationFailed(e.to_string()))?;
+    XPrv::derive_from_path(seed, &path).map_err(|e| WalletError::DerivationFailed {
+        message: e.to_string(),
+    })
+}
 
+fn derive_secp256k1_key_at_path(seed: &[u8], path_str: String) -> Result<DerivedKey, WalletError> {
+    let xprv = xprv_at_path(seed, &path_str)?;
+    derived_key_from_xprv(&xprv, path_str)
+}
+
+fn derived_key_from_xprv(xprv: &XPrv, path_str: String) -> Result<DerivedKey, WalletError> {
+    let private_key_bytes: [u8; 32] = xprv.to_bytes();
+    let signing_key = SigningKey::from_bytes(&private_key_bytes.into()).map_err(|e| {
+        WalletError::DerivationFailed {
+            message: e.to_string(),
+        }
+    })?;
+    derived_key_from_signing_key(&signing_key, private_key_bytes, path_str)
+}
+
+fn derived_key_from_signing_key(
+    signing_key: &SigningKey,
+    private_key_bytes: [u8; 32],
+    path_str: String,
+) -> Result<DerivedKey, WalletError> {
     let verifying_key = signing_key.verifying_key();
     let public_key_compressed: [u8; 33] = verifying_key.to_sec1_bytes()
         .as_ref()
         .try_into()
-        .map_err(|_| WalletError::DerivationFailed("Invalid public key length".into()))?;
+        .map_err(|_| WalletError::DerivationFailed {
+            message: "Invalid public key length".into(),
+        })?;
 
     let public_key_uncompressed: [u8; 65] = verifying_key
         .to_encoded_point(false)
         .as_bytes()
         .try_into()
-        .map_err(|_| WalletError::DerivationFailed("Invalid uncompressed public key".into()))?;
+        .map_err(|_| WalletError::DerivationFailed {
+            message: "Invalid uncompressed public key".into(),
+        })?;
 
     Ok(DerivedKey {
         private_key: private_key_bytes,
@@ -76,6 +205,35 @@ pub fn derive_secp256k1_key(
     })
 }
 
+/// `derivation_path` of keys built from raw bytes rather than derived from a seed
+pub const IMPORTED_KEY_PATH: &str = "imported";
+
+/// Build a secp256k1 key directly from a raw 32-byte scalar (imports, test fixtures)
+///
+/// Fails if the scalar is zero or not below the curve order. The path is
+/// [`IMPORTED_KEY_PATH`].
+pub fn secp256k1_from_raw(key: &[u8; 32]) -> Result<DerivedKey, WalletError> {
+    let signing_key =
+        SigningKey::from_bytes(&(*key).into()).map_err(|_| WalletError::InvalidPrivateKey {
+            message: "not a valid secp256k1 scalar".into(),
+        })?;
+    derived_key_from_signing_key(&signing_key, *key, IMPORTED_KEY_PATH.into())
+}
+
+/// Build an Ed25519 key directly from a raw 32-byte secret (imports, test fixtures)
+///
+/// Every 32-byte value is a valid Ed25519 secret. The path is [`IMPORTED_KEY_PATH`].
+pub fn ed25519_from_raw(key: &[u8; 32]) -> DerivedEd25519Key {
+    let public_key = ed25519_dalek::SigningKey::from_bytes(key)
+        .verifying_key()
+        .to_bytes();
+    DerivedEd25519Key {
+        private_key: *key,
+        public_key,
+        derivation_path: IMPORTED_KEY_PATH.into(),
+    }
+}
+
 /// Derive an Ed25519 private key from seed (for Solana)
 /// Uses SLIP-0010 derivation for Ed25519
 pub fn derive_ed25519_key(
@@ -84,7 +242,14 @@ pub fn derive_ed25519_key(
     account: u32,
 ) -> Result<DerivedEd25519Key, WalletError> {
     let path_str = derivation_path_for_chain(chain, account, 0)?;
+    derive_ed25519_key_at_path(seed, path_str)
+}
 
+/// SLIP-0010 Ed25519 derivation along an explicit path
+fn derive_ed25519_key_at_path(
+    seed: &[u8],
+    path_str: String,
+) -> Result<DerivedEd25519Key, WalletError> {
     // SLIP-0010 Ed25519 derivation
     // Master key: HMAC-SHA512(key="ed25519 seed", data=seed)
     use hmac::{Hmac, Mac};
@@ -92,8 +257,10 @@ pub fn derive_ed25519_key(
 
     type HmacSha512 = Hmac<Sha512>;
 
-    let mut mac = HmacSha512::new_from_slice(b"ed25519 seed")
-        .map_err(|e| WalletError::DerivationFailed(e.to_string()))?;
+    let mut mac =
+        HmacSha512::new_from_slice(b"ed25519 seed").map_err(|e| WalletError::DerivationFailed {
+            message: e.to_string(),
+        })?;
     mac.update(seed);
     let result = mac.finalize().into_bytes();
 
@@ -104,16 +271,26 @@ pub fn derive_ed25519_key(
 
     // Parse derivation path and derive child keys
     // For Solana: m/44'/501'/account'/0'
-    // All components are hardened for Ed25519
+    // SLIP-0010 only defines hardened children for Ed25519, so an unhardened
+    // component is an error rather than being silently hardened
     let components = parse_derivation_path(&path_str)?;
 
-    for child_index in components {
-        let mut mac = HmacSha512::new_from_slice(&chain_code)
-            .map_err(|e| WalletError::DerivationFailed(e.to_string()))?;
+    for (child_index, hardened) in components {
+        if !hardened {
+            chain_code.zeroize();
+            key.zeroize();
+            return Err(WalletError::DerivationFailed {
+                message: format!("Ed25519 path component {child_index} must be hardened"),
+            });
+        }
+        let mut mac =
+            HmacSha512::new_from_slice(&chain_code).map_err(|e| WalletError::DerivationFailed {
+                message: e.to_string(),
+            })?;
         // Hardened child: 0x00 || key || index (with hardened bit set)
         mac.update(&[0x00]);
         mac.update(&key);
-        mac.update(&(child_index | 0x80000000).to_be_bytes());
+        mac.update(&(child_index | HARDENED_BIT).to_be_bytes());
         let result = mac.finalize().into_bytes();
 
         key.copy_from_slice(&result[..32]);
@@ -137,22 +314,37 @@ pub fn derive_ed25519_key(
     Ok(derived)
 }
 
-/// Parse "m/44'/501'/0'/0'" into [44, 501, 0, 0]
-fn parse_derivation_path(path: &str) -> Result<Vec<u32>, WalletError> {
-    let path = path.strip_prefix("m/").ok_or_else(|| {
-        WalletError::DerivationFailed("Path must start with m/".into())
-    })?;
+/// BIP-32 hardened child offset (2^31)
+const HARDENED_BIT: u32 = 0x8000_0000;
+
+/// Parse "m/44'/501'/0'/0" into [(44, true), (501, true), (0, true), (0, false)]
+///
+/// Indices are returned without the hardened bit; the flag says whether the
+/// component carried a `'` or `h` suffix.
+fn parse_derivation_path(path: &str) -> Result<Vec<(u32, bool)>, WalletError> {
+    let path = path
+        .strip_prefix("m/")
+        .ok_or_else(|| WalletError::DerivationFailed {
+            message: "Path must start with m/".into(),
+        })?;
 
     path.split('/')
         .map(|component| {
-            let num_str = if component.ends_with('\'') || component.ends_with('h') {
-                &component[..component.len() - 1]
-            } else {
-                component
+            let (num_str, hardened) = match component.strip_suffix(['\'', 'h']) {
+                Some(num_str) => (num_str, true),
+                None => (component, false),
             };
-            num_str
+            let index = num_str
                 .parse::<u32>()
-                .map_err(|e| WalletError::DerivationFailed(format!("Invalid path component: {e}")))
+                .map_err(|e| WalletError::DerivationFailed {
+                    message: format!("Invalid path component: {e}"),
+                })?;
+            if index >= HARDENED_BIT {
+                return Err(WalletError::DerivationFailed {
+                    message: format!("Path component {index} out of range"),
+                });
+            }
+            Ok((index, hardened))
         })
         .collect()
 }
@@ -196,6 +388,67 @@ mod tests {
         mnemonic_to_seed(TEST_MNEMONIC, "").unwrap()
     }
 
+    #[test]
+    fn test_secp256k1_from_raw_key_one_is_generator() {
+        let mut raw = [0u8; 32];
+        raw[31] = 1;
+        let key = secp256k1_from_raw(&raw).unwrap();
+
+        assert_eq!(
+            hex::encode(key.public_key_compressed),
+            "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798"
+        );
+        assert_eq!(
+            hex::encode(&key.public_key_uncompressed[33..]),
+            "483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8"
+        );
+        assert_eq!(key.derivation_path, IMPORTED_KEY_PATH);
+    }
+
+    #[test]
+    fn test_secp256k1_from_raw_rejects_out_of_range() {
+        assert!(matches!(
+            secp256k1_from_raw(&[0u8; 32]),
+            Err(WalletError::InvalidPrivateKey { .. })
+        ));
+        // All-ones exceeds the curve order n.
+        assert!(secp256k1_from_raw(&[0xFF; 32]).is_err());
+    }
+
+    #[test]
+    fn test_secp256k1_from_raw_matches_derived_key() {
+        let derived = derive_secp256k1_key(&test_seed(), Chain::Ethereum, 0, 0).unwrap();
+        let imported = secp256k1_from_raw(&derived.private_key).unwrap();
+        assert_eq!(
+            imported.public_key_uncompressed,
+            derived.public_key_uncompressed
+        );
+    }
+
+    #[test]
+    fn test_ed25519_from_raw_rfc8032_vector() {
+        // RFC 8032 section 7.1, test 1.
+        let secret: [u8; 32] =
+            hex::decode("9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60")
+                .unwrap()
+                .try_into()
+                .unwrap();
+        let key = ed25519_from_raw(&secret);
+
+        assert_eq!(
+            hex::encode(key.public_key),
+            "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a"
+        );
+        assert_eq!(key.derivation_path, IMPORTED_KEY_PATH);
+    }
+
+    #[test]
+    fn test_master_fingerprint() {
+        // Well-known fingerprint of the "abandon ... about" master key.
+        let fingerprint = master_fingerprint(&test_seed()).unwrap();
+        assert_eq!(hex::encode(fingerprint), "73c5da0a");
+    }
+
     #[test]
     fn test_derive_eth_key() {
         let seed = test_seed();
@@ -226,6 +479,71 @@ mod tests {
         assert_eq!(key.public_key.len(), 32);
     }
 
+    #[test]
+    fn test_derive_sol_key_known_address() {
+        // Matches Phantom / Solflare for the all-`abandon` test mnemonic
+        let key = derive_ed25519_key(&test_seed(), Chain::Solana, 0).unwrap();
+        assert_eq!(
+            bs58::encode(key.public_key).into_string(),
+            "HAgk14JpMQLgt6rVgv7cBQFJWFto5Dqxi472uT3DKpqk"
+        );
+    }
+
+    #[test]
+    fn test_ed25519_path_honors_hardened_flag() {
+        let seed = test_seed();
+        let sol = derive_ed25519_key(&seed, Chain::Solana, 0).unwrap();
+        let explicit = derive_ed25519_key_at_path(&seed, "m/44h/501h/0h/0h".into()).unwrap();
+        assert_eq!(sol.private_key, explicit.private_key);
+
+        // SLIP-0010 has no unhardened Ed25519 children
+        assert!(derive_ed25519_key_at_path(&seed, "m/44'/501'/0'/0".into()).is_err());
+    }
+
+    #[test]
+    fn test_derive_btc_change_key() {
+        let seed = test_seed();
+        let key = derive_secp256k1_change_key(&seed, Chain::Bitcoin, 0, 3).unwrap();
+        assert_eq!(key.derivation_path, "m/84'/0'/0'/1/3");
+
+        let receive = derive_secp256k1_key(&seed, Chain::Bitcoin, 0, 3).unwrap();
+        assert_ne!(key.private_key, receive.private_key);
+    }
+
+    #[test]
+    fn test_derive_zec_change_key() {
+        let seed = test_seed();
+        let key = derive_secp256k1_change_key(&seed, Chain::Zcash, 2, 0).unwrap();
+        assert_eq!(key.derivation_path, "m/44'/133'/2'/1/0");
+    }
+
+    #[test]
+    fn test_derive_change_key_rejects_account_chains() {
+        let seed = test_seed();
+        assert!(derive_secp256k1_change_key(&seed, Chain::Ethereum, 0, 0).is_err());
+        assert!(derive_secp256k1_change_key(&seed, Chain::Solana, 0, 0).is_err());
+    }
+
+    #[test]
+    fn test_derive_keys_batch_matches_single() {
+        let seed = test_seed();
+        let batch = derive_secp256k1_keys(&seed, Chain::Bitcoin, 0, 3, 4).unwrap();
+
+        assert_eq!(batch.len(), 4);
+        for (offset, key) in batch.iter().enumerate() {
+            let single = derive_secp256k1_key(&seed, Chain::Bitcoin, 0, 3 + offset as u32).unwrap();
+            assert_eq!(key.private_key, single.private_key);
+            assert_eq!(key.derivation_path, single.derivation_path);
+        }
+    }
+
+    #[test]
+    fn test_derive_keys_batch_rejects_solana_and_overflow() {
+        let seed = test_seed();
+        assert!(derive_secp256k1_keys(&seed, Chain::Solana, 0, 0, 1).is_err());
+        assert!(derive_secp256k1_keys(&seed, Chain::Ethereum, 0, u32::MAX, 2).is_err());
+    }
+
     #[test]
     fn test_derivation_deterministic() {
         let seed = test_seed();
@@ -265,6 +583,22 @@ mod tests {
     #[test]
     fn test_parse_derivation_path() {
         let components = parse_derivation_path("m/44'/60'/0'/0/0").unwrap();
-        assert_eq!(components, vec![44, 60, 0, 0, 0]);
+        assert_eq!(
+            components,
+            vec![(44, true), (60, true), (0, true), (0, false), (0, false)]
+        );
+    }
+
+    #[test]
+    fn test_parse_derivation_path_mixed_suffixes() {
+        let components = parse_derivation_path("m/44h/501'/7/2147483647'").unwrap();
+        assert_eq!(
+            components,
+            vec![(44, true), (501, true), (7, false), (2147483647, true)]
+        );
+
+        assert!(parse_derivation_path("m/2147483648").is_err());
+        assert!(parse_derivation_path("44'/0'").is_err());
+        assert!(parse_derivation_path("m/44''").is_err());
     }
 }
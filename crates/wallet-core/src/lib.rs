@@ -1,4 +1,8 @@
+// The UniFFI-generated scaffolding leaves blank lines after its doc comments.
+#![allow(clippy::empty_line_after_doc_comments)]
+
 pub mod address;
+pub mod codec;
 pub mod error;
 pub mod hd_derivation;
 pub mod mnemonic;
@@ -10,22 +14,41 @@ mod ffi_eth;
 mod ffi_btc;
 mod ffi_sol;
 mod ffi_zec;
+mod ffi_wallet;
+mod ffi_intent;
 
 // Re-export all FFI types and functions so UniFFI sees them at crate root
-pub use ffi_common::{EncryptedSeedData, keccak256, validate_address};
+pub use ffi_common::{
+    base58_to_bytes, base64_to_bytes, bytes_to_base58, bytes_to_base64, bytes_to_hex, hex_to_bytes,
+    keccak256, signed_tx_to_json, validate_address, validate_addresses, validate_send_amount,
+    AddressEntry, EncryptedSeedData,
+};
 pub use ffi_eth::{
-    sign_eth_message, sign_eth_transaction, sign_erc20_transfer,
-    sign_eth_raw_hash, recover_eth_pubkey,
+    estimate_eth_send_cost, eth_checksum_address, recover_eth_pubkey, sign_erc20_approve_max,
+    sign_erc20_safe_approve, sign_erc20_transfer, sign_erc20_transfer_from, sign_eth_message,
+    sign_eth_raw_hash, sign_eth_transaction, sign_eth_transaction_with_key, sign_typed_data_v4,
+    validate_eth_transaction,
+};
+pub use ffi_btc::{
+    UtxoData, IndexedUtxoData, sign_btc_transaction, sign_btc_transaction_multi_index,
+    validate_btc_transaction, estimate_btc_send_cost,
 };
-pub use ffi_btc::{UtxoData, sign_btc_transaction};
 pub use ffi_sol::{
-    sign_sol_transfer, sign_spl_transfer, sign_sol_message,
-    sign_sol_raw_transaction, derive_sol_token_address,
+    sign_sol_transfer, sign_sol_transfer_with_imported_key, sign_sol_transfer_memo,
+    sign_sol_transfer_with_references, sign_spl_transfer, sign_spl_transfer_full,
+    sign_sol_message, sign_sol_raw_transaction, derive_sol_token_address, export_sol_keypair,
+    format_lamports, parse_sol, format_spl_token_amount, parse_spl_token_amount,
+    estimate_sol_send_cost,
 };
 pub use ffi_zec::{ZecUtxoData, sign_zec_transaction};
+pub use ffi_wallet::Wallet;
+pub use ffi_intent::{FeeHint, TransferIntent, sign_intent};
 
 use error::WalletError;
-use types::{Chain, DerivedAddress, EncryptedSeed};
+use types::{
+    Chain, DerivedAddress, EncryptedSeed, FeeQuote, SignedErc20Transfer, SignedUtxoTransaction,
+    TxBreakdown, WalletSummary,
+};
 use zeroize::Zeroize;
 
 // Include the UniFFI scaffolding
@@ -48,6 +71,16 @@ pub fn is_valid_bip39_word(word: String) -> bool {
     mnemonic::is_valid_word(&word)
 }
 
+/// Check each word of a phrase against the BIP-39 word list, by position
+pub fn validate_mnemonic_words(phrase: String) -> Vec<bool> {
+    mnemonic::validate_words(&phrase)
+}
+
+/// Check a phrase's BIP-39 checksum (false if any word is invalid)
+pub fn mnemonic_checksum_valid(phrase: String) -> bool {
+    mnemonic::checksum_valid(&phrase)
+}
+
 /// Derive an address for a specific chain from mnemonic
 pub fn derive_address_from_mnemonic(
     mnemonic_phrase: String,
@@ -56,10 +89,23 @@ pub fn derive_address_from_mnemonic(
     account: u32,
     index: u32,
 ) -> Result<DerivedAddress, WalletError> {
-    let mut seed = mnemonic::mnemonic_to_seed(&mnemonic_phrase, &passphrase)?;
-    let result = address::derive_address(&seed, chain, account, index);
-    seed.zeroize();
-    result
+    let seed = mnemonic::mnemonic_to_seed_zeroizing(&mnemonic_phrase, &passphrase)?;
+    address::derive_address(&seed, chain, account, index)
+}
+
+/// Derive a short fingerprint of the address a mnemonic + passphrase gives
+///
+/// Lets the user confirm they typed the same passphrase as before without
+/// revealing the address or any key material.
+pub fn derive_address_fingerprint(
+    mnemonic_phrase: String,
+    passphrase: String,
+    chain: Chain,
+    account: u32,
+    index: u32,
+) -> Result<String, WalletError> {
+    let derived = derive_address_from_mnemonic(mnemonic_phrase, passphrase, chain, account, index)?;
+    Ok(address::address_fingerprint(&derived.address))
 }
 
 /// Derive addresses for BTC, ETH, SOL from a mnemonic
@@ -68,10 +114,95 @@ pub fn derive_all_addresses_from_mnemonic(
     passphrase: String,
     account: u32,
 ) -> Result<Vec<DerivedAddress>, WalletError> {
-    let mut seed = mnemonic::mnemonic_to_seed(&mnemonic_phrase, &passphrase)?;
-    let result = address::derive_all_addresses(&seed, account);
-    seed.zeroize();
-    result
+    let seed = mnemonic::mnemonic_to_seed_zeroizing(&mnemonic_phrase, &passphrase)?;
+    address::derive_all_addresses(&seed, account)
+}
+
+/// Derive the (identical) EVM address tagged with each supported EVM chain
+///
+/// Returned as `DerivedAddress` records (chain + address + path), one per
+/// entry in `chain_eth::chains::supported_chains`, testnets included.
+pub fn evm_address_for_all_chains(
+    mnemonic_phrase: String,
+    passphrase: String,
+    account: u32,
+    index: u32,
+) -> Result<Vec<DerivedAddress>, WalletError> {
+    let seed = mnemonic::mnemonic_to_seed_zeroizing(&mnemonic_phrase, &passphrase)?;
+    address::derive_evm_addresses(&seed, account, index)
+}
+
+/// Derive the default addresses, paths and master fingerprint for an account
+///
+/// One call (and one seed derivation) instead of a round trip per chain.
+pub fn derive_wallet_summary(
+    mnemonic_phrase: String,
+    passphrase: String,
+    account: u32,
+) -> Result<WalletSummary, WalletError> {
+    let seed = mnemonic::mnemonic_to_seed_zeroizing(&mnemonic_phrase, &passphrase)?;
+    derive_wallet_summary_with_seed(seed, account)
+}
+
+/// [`derive_wallet_summary`] over any zeroizable seed container; the seed is
+/// wiped once the summary is derived or derivation fails.
+fn derive_wallet_summary_with_seed<S>(seed: S, account: u32) -> Result<WalletSummary, WalletError>
+where
+    S: std::ops::Deref<Target = [u8]> + Zeroize,
+{
+    ffi_common::with_zeroized_seed(seed, |s| address::derive_wallet_summary(s, account))
+}
+
+/// Derive receive addresses for a contiguous index range (wallet-restore gap scan)
+///
+/// The app queries each address's history and stops after the BIP-44 gap limit.
+pub fn derive_address_gap(
+    mnemonic_phrase: String,
+    passphrase: String,
+    chain: Chain,
+    account: u32,
+    start: u32,
+    count: u32,
+) -> Result<Vec<DerivedAddress>, WalletError> {
+    let seed = mnemonic::mnemonic_to_seed_zeroizing(&mnemonic_phrase, &passphrase)?;
+    address::derive_address_range(&seed, chain, account, start, count)
+}
+
+/// Derive the first receive address of accounts start..start+count (multi-account wallets)
+pub fn derive_accounts(
+    mnemonic_phrase: String,
+    passphrase: String,
+    chain: Chain,
+    start_account: u32,
+    count: u32,
+) -> Result<Vec<DerivedAddress>, WalletError> {
+    let seed = mnemonic::mnemonic_to_seed_zeroizing(&mnemonic_phrase, &passphrase)?;
+    address::derive_account_range(&seed, chain, start_account, count)
+}
+
+/// Find the first address index (Solana: account) whose address starts with `prefix`
+pub fn find_vanity_index(
+    mnemonic_phrase: String,
+    passphrase: String,
+    chain: Chain,
+    account: u32,
+    prefix: String,
+    max_index: u32,
+) -> Result<Option<u32>, WalletError> {
+    let seed = mnemonic::mnemonic_to_seed_zeroizing(&mnemonic_phrase, &passphrase)?;
+    address::find_vanity_index(&seed, chain, account, &prefix, max_index)
+}
+
+/// Derive the change address at the lowest index not in `used_indices` (BTC, ZEC)
+pub fn derive_next_change_address(
+    mnemonic_phrase: String,
+    passphrase: String,
+    chain: Chain,
+    account: u32,
+    used_indices: Vec<u32>,
+) -> Result<DerivedAddress, WalletError> {
+    let seed = mnemonic::mnemonic_to_seed_zeroizing(&mnemonic_phrase, &passphrase)?;
+    address::ChangeIndexTracker::new(used_indices).next_change_address(&seed, chain, account)
 }
 
 /// Encrypt seed with password (Argon2id + AES-256-GCM)
@@ -103,7 +234,38 @@ pub fn decrypt_seed_with_password(
     seed_encryption::decrypt_seed(&encrypted, password.as_bytes())
 }
 
+/// Map an EIP-155 chain id (e.g. from WalletConnect) to a supported chain
+pub fn chain_from_evm_chain_id(chain_id: u64) -> Option<Chain> {
+    Chain::from_evm_chain_id(chain_id)
+}
+
 /// Derive seed bytes from mnemonic + passphrase
 pub fn mnemonic_to_seed(mnemonic_phrase: String, passphrase: String) -> Result<Vec<u8>, WalletError> {
     mnemonic::mnemonic_to_seed(&mnemonic_phrase, &passphrase)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ffi_common::SeedProbe;
+
+    const TEST_MNEMONIC: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    #[test]
+    fn derive_wallet_summary_wipes_seed() {
+        let seed = mnemonic::mnemonic_to_seed(TEST_MNEMONIC, "").unwrap();
+        let (probe, wiped) = SeedProbe::new(seed);
+        let summary = derive_wallet_summary_with_seed(probe, 0).unwrap();
+        assert_eq!(summary.master_fingerprint, "73c5da0a");
+        assert_eq!(wiped.get(), Some(true));
+    }
+
+    #[test]
+    fn derive_wallet_summary_wipes_seed_on_error() {
+        // An account index with the hardened bit set cannot be hardened again.
+        let seed = mnemonic::mnemonic_to_seed(TEST_MNEMONIC, "").unwrap();
+        let (probe, wiped) = SeedProbe::new(seed);
+        assert!(derive_wallet_summary_with_seed(probe, 0x8000_0000).is_err());
+        assert_eq!(wiped.get(), Some(true));
+    }
+}
@@ -1,4 +1,5 @@
 use bip39::{Language, Mnemonic};
+use crypto_utils::zeroizing::ZeroizingBytes;
 use rand::RngCore;
 use zeroize::Zeroize;
 
@@ -9,8 +10,11 @@ pub fn generate_mnemonic() -> Result<String, WalletError> {
     // 24 words = 256 bits of entropy
     let mut entropy = [0u8; 32];
     rand::rngs::OsRng.fill_bytes(&mut entropy);
-    let mnemonic = Mnemonic::from_entropy_in(Language::English, &entropy)
-        .map_err(|e| WalletError::InvalidMnemonic(e.to_string()))?;
+    let mnemonic = Mnemonic::from_entropy_in(Language::English, &entropy).map_err(|e| {
+        WalletError::InvalidMnemonic {
+            message: e.to_string(),
+        }
+    })?;
     entropy.zeroize();
     Ok(mnemonic.to_string())
 }
@@ -26,8 +30,11 @@ pub fn validate_mnemonic(phrase: &str) -> Result<bool, WalletError> {
 /// Derive seed bytes from mnemonic + optional passphrase
 /// Returns 64-byte seed. Caller MUST zeroize the returned seed when done.
 pub fn mnemonic_to_seed(phrase: &str, passphrase: &str) -> Result<Vec<u8>, WalletError> {
-    let mnemonic = Mnemonic::parse_in_normalized(Language::English, phrase)
-        .map_err(|e| WalletError::InvalidMnemonic(e.to_string()))?;
+    let mnemonic = Mnemonic::parse_in_normalized(Language::English, phrase).map_err(|e| {
+        WalletError::InvalidMnemonic {
+            message: e.to_string(),
+        }
+    })?;
 
     let mut seed_arr = mnemonic.to_seed(passphrase);
     let seed_vec = seed_arr.to_vec();
@@ -35,6 +42,17 @@ pub fn mnemonic_to_seed(phrase: &str, passphrase: &str) -> Result<Vec<u8>, Walle
     Ok(seed_vec)
 }
 
+/// Derive seed bytes from mnemonic + optional passphrase, zeroized on drop
+///
+/// Same 64 bytes as [`mnemonic_to_seed`]; prefer this in Rust code so the seed
+/// cannot outlive its use by accident.
+pub fn mnemonic_to_seed_zeroizing(
+    phrase: &str,
+    passphrase: &str,
+) -> Result<ZeroizingBytes, WalletError> {
+    mnemonic_to_seed(phrase, passphrase).map(ZeroizingBytes::new)
+}
+
 /// Get the word list for autocomplete
 pub fn word_list() -> &'static [&'static str] {
     Language::English.word_list()
@@ -45,6 +63,23 @@ pub fn is_valid_word(word: &str) -> bool {
     Language::English.find_word(word).is_some()
 }
 
+/// Check each word of a phrase against the BIP-39 word list
+///
+/// One entry per whitespace-separated word, in order, so a UI can flag the
+/// exact positions that are misspelled. Says nothing about the checksum.
+pub fn validate_words(phrase: &str) -> Vec<bool> {
+    phrase.split_whitespace().map(is_valid_word).collect()
+}
+
+/// Whether a phrase passes the BIP-39 checksum
+///
+/// False if any word is unknown or the word count is not 12/15/18/21/24,
+/// since the checksum cannot be computed then; use [`validate_words`] to tell
+/// a misspelling apart from a wrong (but spelled correctly) word.
+pub fn checksum_valid(phrase: &str) -> bool {
+    Mnemonic::parse_in_normalized(Language::English, phrase).is_ok()
+}
+
 /// Zeroizable mnemonic wrapper
 pub struct ZeroizingMnemonic {
     phrase: String,
@@ -53,7 +88,9 @@ pub struct ZeroizingMnemonic {
 impl ZeroizingMnemonic {
     pub fn new(phrase: String) -> Result<Self, WalletError> {
         if !validate_mnemonic(&phrase)? {
-            return Err(WalletError::InvalidMnemonic("Invalid mnemonic phrase".into()));
+            return Err(WalletError::InvalidMnemonic {
+                message: "Invalid mnemonic phrase".into(),
+            });
         }
         Ok(Self { phrase })
     }
@@ -109,6 +146,16 @@ mod tests {
         assert_eq!(seed1.len(), 64);
     }
 
+    #[test]
+    fn test_mnemonic_to_seed_zeroizing_matches_plain() {
+        let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let plain = mnemonic_to_seed(phrase, "TREZOR").unwrap();
+        let wrapped = mnemonic_to_seed_zeroizing(phrase, "TREZOR").unwrap();
+        assert_eq!(&*wrapped, &plain[..]);
+        assert_eq!(wrapped.len(), 64);
+        // crypto_utils tests that dropping a ZeroizingBytes wipes its buffer.
+    }
+
     #[test]
     fn test_passphrase_changes_seed() {
         let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
@@ -139,6 +186,29 @@ mod tests {
         assert!(!is_valid_word(""));
     }
 
+    #[test]
+    fn test_validate_words_flags_misspelled_position() {
+        let phrase = "abandon abandon abandon abandon abandon abandn abandon abandon abandon abandon abandon about";
+        let validity = validate_words(phrase);
+        assert_eq!(validity.len(), 12);
+        for (i, valid) in validity.iter().enumerate() {
+            assert_eq!(*valid, i != 5, "word {i}");
+        }
+        assert!(!checksum_valid(phrase));
+    }
+
+    #[test]
+    fn test_checksum_invalid_with_valid_words() {
+        // Every word is in the list, but "abandon" x12 fails the checksum
+        let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon";
+        assert!(validate_words(phrase).iter().all(|&valid| valid));
+        assert!(!checksum_valid(phrase));
+
+        let good = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        assert!(checksum_valid(good));
+        assert!(validate_words("").is_empty());
+    }
+
     #[test]
     fn test_zeroizing_mnemonic() {
         let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
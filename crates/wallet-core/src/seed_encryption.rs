@@ -1,6 +1,8 @@
 use crypto_utils::encryption;
 use crypto_utils::kdf;
-use zeroize::Zeroize;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use zeroize::{Zeroize, Zeroizing};
 
 use crate::error::WalletError;
 use crate::types::EncryptedSeed;
@@ -36,18 +38,17 @@ pub fn encrypt_seed(seed: &[u8], password: &[u8]) -> Result<EncryptedSeed, Walle
 ///
 /// The caller must zeroize the returned seed bytes when done.
 pub fn decrypt_seed(encrypted: &EncryptedSeed, password: &[u8]) -> Result<Vec<u8>, WalletError> {
-    let salt: [u8; 16] = encrypted
-        .salt
-        .as_slice()
-        .try_into()
-        .map_err(|_| WalletError::DecryptionFailed("Invalid salt length".into()))?;
+    let salt = salt_of(encrypted)?;
 
     // Derive the same key from password + salt
     let mut key = kdf::derive_key(password, &salt)?;
 
     // Decrypt
-    let seed = encryption::decrypt(&encrypted.ciphertext, &key)
-        .map_err(|e| WalletError::DecryptionFailed(e.to_string()))?;
+    let seed = encryption::decrypt(&encrypted.ciphertext, &key).map_err(|e| {
+        WalletError::DecryptionFailed {
+            message: e.to_string(),
+        }
+    })?;
 
     // Zeroize the derived key
     key.zeroize();
@@ -55,16 +56,142 @@ pub fn decrypt_seed(encrypted: &EncryptedSeed, password: &[u8]) -> Result<Vec<u8
     Ok(seed)
 }
 
-/// Serialize EncryptedSeed to JSON for storage
+/// Encrypt several secrets (e.g. a seed and notes) under one password.
+///
+/// Argon2id runs once: every item shares one random salt and the key derived
+/// from it, and gets its own AES-256-GCM nonce. Each result is an ordinary
+/// EncryptedSeed, so [`decrypt_seed`] opens any one of them on its own.
+pub fn encrypt_seeds(items: &[&[u8]], password: &[u8]) -> Result<Vec<EncryptedSeed>, WalletError> {
+    let salt = kdf::generate_salt();
+    let mut key = kdf::derive_key(password, &salt)?;
+    let ciphertexts = encryption::encrypt_many(items, &key);
+    key.zeroize();
+
+    Ok(ciphertexts?
+        .into_iter()
+        .map(|ciphertext| EncryptedSeed {
+            ciphertext,
+            salt: salt.to_vec(),
+            se_ciphertext: None,
+        })
+        .collect())
+}
+
+/// Decrypt several EncryptedSeeds with one password, in order.
+///
+/// The key is derived once per distinct salt, so a batch from
+/// [`encrypt_seeds`] costs a single Argon2id run. The caller must zeroize the
+/// returned secrets when done. Keys and any seeds already decrypted are wiped
+/// if a later item fails.
+pub fn decrypt_seeds(
+    encrypted: &[EncryptedSeed],
+    password: &[u8],
+) -> Result<Vec<Vec<u8>>, WalletError> {
+    let mut keys: Vec<([u8; 16], Zeroizing<[u8; 32]>)> = Vec::new();
+    let mut seeds: Vec<Zeroizing<Vec<u8>>> = Vec::with_capacity(encrypted.len());
+    for item in encrypted {
+        let salt = salt_of(item)?;
+        let key_index = match keys.iter().position(|(s, _)| *s == salt) {
+            Some(index) => index,
+            None => {
+                keys.push((salt, Zeroizing::new(kdf::derive_key(password, &salt)?)));
+                keys.len() - 1
+            }
+        };
+        let seed = encryption::decrypt(&item.ciphertext, &keys[key_index].1).map_err(|e| {
+            WalletError::DecryptionFailed {
+                message: e.to_string(),
+            }
+        })?;
+        seeds.push(Zeroizing::new(seed));
+    }
+
+    // Moving the buffers out leaves nothing behind for the wrappers to wipe.
+    Ok(seeds
+        .iter_mut()
+        .map(|seed| std::mem::take(&mut **seed))
+        .collect())
+}
+
+/// The 16-byte Argon2id salt of `encrypted`.
+fn salt_of(encrypted: &EncryptedSeed) -> Result<[u8; 16], WalletError> {
+    encrypted
+        .salt
+        .as_slice()
+        .try_into()
+        .map_err(|_| WalletError::DecryptionFailed {
+            message: "Invalid salt length".into(),
+        })
+}
+
+/// Storage form of EncryptedSeed: the seed fields plus an integrity checksum
+#[derive(Serialize, Deserialize)]
+struct StoredEncryptedSeed {
+    #[serde(flatten)]
+    seed: EncryptedSeed,
+    /// Hex SHA-256 of the seed fields; absent in blobs written before it existed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    integrity: Option<String>,
+}
+
+/// SHA-256 over the canonical JSON of the seed fields, hex-encoded
+///
+/// The AEAD already authenticates the ciphertext; this also catches a
+/// corrupted salt, which would otherwise surface as a wrong-password error.
+fn integrity_checksum(encrypted: &EncryptedSeed) -> Result<String, WalletError> {
+    let canonical = serde_json::to_vec(encrypted).map_err(|e| WalletError::Internal {
+        message: format!("Serialization failed: {e}"),
+    })?;
+    Ok(hex::encode(Sha256::digest(canonical)))
+}
+
+/// Serialize EncryptedSeed to JSON for storage, with an `integrity` checksum
 pub fn serialize_encrypted_seed(encrypted: &EncryptedSeed) -> Result<String, WalletError> {
-    serde_json::to_string(encrypted)
-        .map_err(|e| WalletError::Internal(format!("Serialization failed: {e}")))
+    let stored = StoredEncryptedSeed {
+        seed: encrypted.clone(),
+        integrity: Some(integrity_checksum(encrypted)?),
+    };
+    serde_json::to_string(&stored).map_err(|e| WalletError::Internal {
+        message: format!("Serialization failed: {e}"),
+    })
 }
 
-/// Deserialize EncryptedSeed from JSON
+/// Deserialize EncryptedSeed from JSON, verifying its `integrity` checksum
+///
+/// Blobs without the field (written by older versions) are still accepted,
+/// so stripping the field from a current blob downgrades it to an unchecked
+/// one. Callers should pass stored blobs through [`upgrade_encrypted_seed`]
+/// and persist the result so the check applies from then on.
 pub fn deserialize_encrypted_seed(json: &str) -> Result<EncryptedSeed, WalletError> {
-    serde_json::from_str(json)
-        .map_err(|e| WalletError::Internal(format!("Deserialization failed: {e}")))
+    Ok(parse_stored_seed(json)?.seed)
+}
+
+/// Re-serialize a blob that has no `integrity` field
+///
+/// Returns `Ok(Some(json))` with the field added when `json` is a legacy
+/// blob, and `Ok(None)` when it already carries a valid checksum.
+pub fn upgrade_encrypted_seed(json: &str) -> Result<Option<String>, WalletError> {
+    let stored = parse_stored_seed(json)?;
+    match stored.integrity {
+        Some(_) => Ok(None),
+        None => serialize_encrypted_seed(&stored.seed).map(Some),
+    }
+}
+
+fn parse_stored_seed(json: &str) -> Result<StoredEncryptedSeed, WalletError> {
+    let stored: StoredEncryptedSeed =
+        serde_json::from_str(json).map_err(|e| WalletError::Internal {
+            message: format!("Deserialization failed: {e}"),
+        })?;
+
+    if let Some(expected) = &stored.integrity {
+        if !expected.eq_ignore_ascii_case(&integrity_checksum(&stored.seed)?) {
+            return Err(WalletError::Internal {
+                message: "Encrypted seed integrity check failed".into(),
+            });
+        }
+    }
+    Ok(stored)
 }
 
 #[cfg(test)]
@@ -114,6 +241,48 @@ mod tests {
         assert_eq!(dec1, seed);
     }
 
+    #[test]
+    fn test_encrypt_seeds_batch() {
+        let seed = vec![0x5E; 64];
+        let notes = b"backup is in the blue folder".to_vec();
+        let password = b"batch-password";
+
+        let encrypted = encrypt_seeds(&[&seed, &notes], password).unwrap();
+        assert_eq!(encrypted.len(), 2);
+        // One salt for the batch, hence one key derivation.
+        assert_eq!(encrypted[0].salt, encrypted[1].salt);
+        assert_ne!(encrypted[0].ciphertext[..12], encrypted[1].ciphertext[..12]);
+
+        // Each item decrypts on its own.
+        assert_eq!(decrypt_seed(&encrypted[0], password).unwrap(), seed);
+        assert_eq!(decrypt_seed(&encrypted[1], password).unwrap(), notes);
+        assert_eq!(decrypt_seeds(&encrypted, password).unwrap(), [seed, notes]);
+        assert!(decrypt_seeds(&encrypted, b"wrong").is_err());
+    }
+
+    #[test]
+    fn test_decrypt_seeds_mixed_salts() {
+        let password = b"pw";
+        let single = encrypt_seed(b"alone", password).unwrap();
+        let mut batch = encrypt_seeds(&[b"one", b"two"], password).unwrap();
+        batch.insert(1, single);
+
+        let plain = decrypt_seeds(&batch, password).unwrap();
+        assert_eq!(plain, [b"one".to_vec(), b"alone".to_vec(), b"two".to_vec()]);
+    }
+
+    #[test]
+    fn test_decrypt_seeds_fails_after_partial_success() {
+        let password = b"pw";
+        let mut batch = encrypt_seeds(&[b"one", b"two"], password).unwrap();
+        // The first item decrypts; the second is tampered with.
+        let last = batch[1].ciphertext.len() - 1;
+        batch[1].ciphertext[last] ^= 0x01;
+
+        let err = decrypt_seeds(&batch, password).unwrap_err();
+        assert!(matches!(err, WalletError::DecryptionFailed { .. }));
+    }
+
     #[test]
     fn test_serialize_deserialize() {
         let seed = vec![0xAB; 32];
@@ -130,4 +299,54 @@ mod tests {
         let decrypted = decrypt_seed(&deserialized, password).unwrap();
         assert_eq!(decrypted, seed);
     }
+
+    #[test]
+    fn test_deserialize_rejects_corrupted_salt() {
+        let encrypted = EncryptedSeed {
+            ciphertext: vec![0x11; 48],
+            salt: vec![0x22; 16],
+            se_ciphertext: None,
+        };
+        let json = serialize_encrypted_seed(&encrypted).unwrap();
+        assert!(json.contains("\"integrity\""));
+        assert!(deserialize_encrypted_seed(&json).is_ok());
+
+        // Flip the first salt byte: 0x22 = 34 becomes 35.
+        let tampered = json.replacen("\"salt\":[34,", "\"salt\":[35,", 1);
+        assert_ne!(tampered, json);
+        assert!(matches!(
+            deserialize_encrypted_seed(&tampered),
+            Err(WalletError::Internal { message }) if message.contains("integrity")
+        ));
+    }
+
+    #[test]
+    fn test_deserialize_accepts_legacy_blob_without_integrity() {
+        let legacy = r#"{"ciphertext":[1,2,3],"salt":[4,5,6],"se_ciphertext":null}"#;
+        let seed = deserialize_encrypted_seed(legacy).unwrap();
+        assert_eq!(seed.salt, vec![4, 5, 6]);
+    }
+
+    #[test]
+    fn test_upgrade_adds_integrity_to_stripped_blob() {
+        let encrypted = EncryptedSeed {
+            ciphertext: vec![0x11; 48],
+            salt: vec![0x22; 16],
+            se_ciphertext: None,
+        };
+        let json = serialize_encrypted_seed(&encrypted).unwrap();
+        assert_eq!(upgrade_encrypted_seed(&json).unwrap(), None);
+
+        let mut value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        value.as_object_mut().unwrap().remove("integrity");
+        let stripped = value.to_string();
+        assert!(!stripped.contains("\"integrity\""));
+
+        let upgraded = upgrade_encrypted_seed(&stripped).unwrap().unwrap();
+        assert_eq!(upgraded, json);
+
+        // Once upgraded, tampering is caught again.
+        let tampered = upgraded.replacen("\"salt\":[34,", "\"salt\":[35,", 1);
+        assert!(deserialize_encrypted_seed(&tampered).is_err());
+    }
 }
@@ -94,6 +94,48 @@ impl Chain {
             Chain::BitcoinTestnet | Chain::Sepolia | Chain::PolygonAmoy | Chain::SolanaDevnet | Chain::ZcashTestnet
         )
     }
+
+    /// Every EVM chain, in declaration order
+    pub(crate) const EVM_CHAINS: [Chain; 9] = [
+        Chain::Ethereum,
+        Chain::Polygon,
+        Chain::Arbitrum,
+        Chain::Base,
+        Chain::Optimism,
+        Chain::Bsc,
+        Chain::Avalanche,
+        Chain::Sepolia,
+        Chain::PolygonAmoy,
+    ];
+
+    /// EIP-155 chain id for EVM chains, `None` for BTC, SOL and ZEC
+    ///
+    /// Ids come from `chain_eth::chains`, so `get_chain` on the result gives the
+    /// RPC and explorer metadata.
+    pub fn evm_chain_id(&self) -> Option<u64> {
+        use chain_eth::chains;
+
+        let evm_chain = match self {
+            Chain::Ethereum => chains::ETHEREUM,
+            Chain::Polygon => chains::POLYGON,
+            Chain::Arbitrum => chains::ARBITRUM,
+            Chain::Base => chains::BASE,
+            Chain::Optimism => chains::OPTIMISM,
+            Chain::Bsc => chains::BSC,
+            Chain::Avalanche => chains::AVALANCHE,
+            Chain::Sepolia => chains::SEPOLIA,
+            Chain::PolygonAmoy => chains::POLYGON_AMOY,
+            _ => return None,
+        };
+        Some(evm_chain.chain_id)
+    }
+
+    /// The EVM chain with EIP-155 id `id` (e.g. from a WalletConnect request)
+    pub fn from_evm_chain_id(id: u64) -> Option<Chain> {
+        Self::EVM_CHAINS
+            .into_iter()
+            .find(|chain| chain.evm_chain_id() == Some(id))
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -110,6 +152,107 @@ pub struct DerivedAddress {
     pub derivation_path: String,
 }
 
+impl DerivedAddress {
+    /// The `(account, index)` this address was derived at, parsed back out of
+    /// `derivation_path`
+    ///
+    /// Handles the BIP-44 style `m/purpose'/coin'/account'/change/index` and
+    /// Solana's all-hardened `m/44'/501'/account'/0'`, which has no address
+    /// index and so reports index 0. Returns `None` for any other shape.
+    pub fn account_index(&self) -> Option<(u32, u32)> {
+        let hardened = |c: &str| c.strip_suffix('\'')?.parse::<u32>().ok();
+        let parts: Vec<&str> = self.derivation_path.split('/').collect();
+
+        match parts.as_slice() {
+            ["m", purpose, coin, account, change, index] => {
+                hardened(purpose)?;
+                hardened(coin)?;
+                change.parse::<u32>().ok()?;
+                Some((hardened(account)?, index.parse().ok()?))
+            }
+            ["m", purpose, coin, account, "0'"] => {
+                hardened(purpose)?;
+                hardened(coin)?;
+                Some((hardened(account)?, 0))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Everything the app needs to show a wallet account, from one seed derivation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalletSummary {
+    pub account: u32,
+    /// BIP-32 master key fingerprint, 8 lowercase hex chars
+    pub master_fingerprint: String,
+    /// Default receive address (index 0) per chain, with its derivation path
+    pub addresses: Vec<DerivedAddress>,
+}
+
+/// What a transaction would do, as reported by the `validate_*_transaction`
+/// dry runs. Amounts are decimal strings in the chain's base unit (sat, wei)
+/// because wei values do not fit in a u64.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TxBreakdown {
+    /// Amount delivered to the recipient
+    pub amount: String,
+    /// BTC: exact fee. ETH: worst case, `gas_limit * max_fee_per_gas`
+    pub fee: String,
+    /// Value returned to the wallet's change output ("0" if none)
+    pub change: String,
+    pub change_address: Option<String>,
+    /// `amount + fee`: what leaves the wallet
+    pub total: String,
+    /// Number of UTXOs spent (0 for account-based chains)
+    pub input_count: u32,
+    /// Non-fatal sanity findings (e.g. fee is a large share of the amount)
+    pub warnings: Vec<String>,
+}
+
+impl TxBreakdown {
+    /// Fee above this share of the amount (in percent) triggers a warning
+    pub(crate) const HIGH_FEE_PERCENT: u128 = 10;
+
+    /// Warning if `fee` exceeds [`Self::HIGH_FEE_PERCENT`] of `amount`
+    pub(crate) fn high_fee_warning(fee: u128, amount: u128) -> Option<String> {
+        (amount > 0 && fee.saturating_mul(100) > amount.saturating_mul(Self::HIGH_FEE_PERCENT))
+            .then(|| format!("fee is more than {}% of the amount", Self::HIGH_FEE_PERCENT))
+    }
+}
+
+/// A signed ERC-20 transfer and what building it turned up
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignedErc20Transfer {
+    /// Raw signed transaction, ready to broadcast
+    pub raw_tx: Vec<u8>,
+    /// Non-fatal findings (e.g. a gas limit below what transfers usually need)
+    pub warnings: Vec<String>,
+}
+
+/// A signed BTC or ZEC transaction and where its change went
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignedUtxoTransaction {
+    /// Raw signed transaction, ready to broadcast
+    pub raw_tx: Vec<u8>,
+    /// Address the change output pays, `None` if the change was dust and went
+    /// to the fee
+    pub change_address: Option<String>,
+}
+
+/// What a send would cost, quoted before anything is built or signed.
+/// Amounts are decimal strings in the chain's base unit (sat, wei, lamports),
+/// as in [`TxBreakdown`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeeQuote {
+    /// Network fee (ETH: worst case, `gas_limit * max_fee_per_gas`)
+    pub fee: String,
+    /// `amount + fee`: what leaves the wallet
+    pub total_spent: String,
+    /// Value returned to the wallet's change output ("0" if none)
+    pub change: String,
+}
+
 /// Encrypted seed data — stored in iOS Keychain
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EncryptedSeed {
@@ -129,3 +272,92 @@ pub struct WalletMetadata {
     pub chains: Vec<Chain>,
     pub has_passphrase: bool,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_MNEMONIC: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    fn derived(chain: Chain, path: &str) -> DerivedAddress {
+        DerivedAddress {
+            chain,
+            address: String::new(),
+            derivation_path: path.into(),
+        }
+    }
+
+    #[test]
+    fn account_index_parses_bip44_path() {
+        assert_eq!(
+            derived(Chain::Ethereum, "m/44'/60'/2'/0/5").account_index(),
+            Some((2, 5))
+        );
+        assert_eq!(
+            derived(Chain::Bitcoin, "m/84'/0'/0'/1/7").account_index(),
+            Some((0, 7))
+        );
+    }
+
+    #[test]
+    fn account_index_parses_solana_path() {
+        assert_eq!(
+            derived(Chain::Solana, "m/44'/501'/1'/0'").account_index(),
+            Some((1, 0))
+        );
+    }
+
+    #[test]
+    fn account_index_rejects_other_shapes() {
+        let paths = [
+            "",
+            "m",
+            "m/44'/60'/2'/0",
+            "m/44'/60'/2/0/5",
+            "m/44'/60'/2'/0/5'",
+            "m/44'/501'/1'/1'",
+            "44'/60'/2'/0/5",
+        ];
+        for path in paths {
+            assert_eq!(
+                derived(Chain::Ethereum, path).account_index(),
+                None,
+                "{path}"
+            );
+        }
+    }
+
+    #[test]
+    fn account_index_round_trips_derived_addresses() {
+        let seed = crate::mnemonic::mnemonic_to_seed(TEST_MNEMONIC, "").unwrap();
+        for chain in [Chain::Ethereum, Chain::Bitcoin, Chain::Zcash] {
+            let addr = crate::address::derive_address(&seed, chain, 3, 9).unwrap();
+            assert_eq!(addr.account_index(), Some((3, 9)));
+        }
+        let sol = crate::address::derive_address(&seed, Chain::Solana, 3, 0).unwrap();
+        assert_eq!(sol.account_index(), Some((3, 0)));
+    }
+
+    #[test]
+    fn from_evm_chain_id_known_ids() {
+        assert_eq!(Chain::from_evm_chain_id(1), Some(Chain::Ethereum));
+        assert_eq!(Chain::from_evm_chain_id(137), Some(Chain::Polygon));
+        assert_eq!(Chain::from_evm_chain_id(8453), Some(Chain::Base));
+        assert_eq!(Chain::from_evm_chain_id(999_999), None);
+    }
+
+    #[test]
+    fn evm_chain_id_round_trips_and_matches_chain_eth() {
+        for chain in Chain::EVM_CHAINS {
+            let id = chain.evm_chain_id().unwrap();
+            assert_eq!(Chain::from_evm_chain_id(id), Some(chain));
+            assert_eq!(
+                chain_eth::chains::get_chain(id).unwrap().symbol,
+                chain.symbol()
+            );
+        }
+        assert_eq!(Chain::Bitcoin.evm_chain_id(), None);
+        assert_eq!(Chain::Solana.evm_chain_id(), None);
+        assert_eq!(Chain::Zcash.evm_chain_id(), None);
+    }
+}
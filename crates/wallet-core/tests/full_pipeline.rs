@@ -5,6 +5,7 @@
 //! exposed to Swift) to catch regressions at crate boundaries.
 
 use wallet_core::*;
+use wallet_core::error::WalletError;
 use wallet_core::types::Chain;
 
 const TEST_MNEMONIC: &str =
@@ -45,6 +46,7 @@ fn eth_full_pipeline_native_transfer() {
         "0x3b9aca00".into(),        // 1 gwei priority fee
         "0xba43b7400".into(),       // 50 gwei max fee
         21_000,
+        false,
     )
     .unwrap();
 
@@ -60,7 +62,7 @@ fn eth_full_pipeline_erc20_transfer() {
     let recipient = "0x000000000000000000000000000000000000dEaD";
 
     // Sign ERC-20 transfer
-    let signed_tx = sign_erc20_transfer(
+    let signed = sign_erc20_transfer(
         seed,
         0,
         0,
@@ -75,8 +77,9 @@ fn eth_full_pipeline_erc20_transfer() {
     )
     .unwrap();
 
-    assert_eq!(signed_tx[0], 0x02);
-    assert!(signed_tx.len() > 100);
+    assert_eq!(signed.raw_tx[0], 0x02);
+    assert!(signed.raw_tx.len() > 100);
+    assert!(signed.warnings.is_empty());
 }
 
 #[test]
@@ -156,18 +159,97 @@ fn btc_full_pipeline() {
         seed,
         0,
         0,
+        0,
         vec![utxo],
         addr.address.clone(), // send to self for simplicity
         50_000,               // 0.0005 BTC
-        addr.address,         // change back to self
+        Some(addr.address),   // change back to self
         10,                   // 10 sat/vByte
         false,                // mainnet
     )
     .unwrap();
 
-    assert!(!signed.is_empty());
+    assert!(!signed.raw_tx.is_empty());
     // BTC wire format starts with version bytes
-    assert!(signed.len() > 50);
+    assert!(signed.raw_tx.len() > 50);
+}
+
+#[test]
+fn btc_insufficient_funds_reports_amounts() {
+    let seed = test_seed();
+    let utxo = UtxoData {
+        txid: "a".repeat(64),
+        vout: 0,
+        amount_sat: 1_000,
+        script_pubkey: vec![
+            0x00, 0x14, 0xAA, 0xBB, 0xCC, 0xDD, 0xEE, 0xFF, 0x00, 0x11, 0x22, 0x33, 0x44, 0x55,
+            0x66, 0x77, 0x88, 0x99, 0xAA, 0xBB, 0xCC, 0xDD,
+        ],
+    };
+
+    let err = sign_btc_transaction(
+        seed,
+        0,
+        0,
+        0,
+        vec![utxo],
+        "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4".into(),
+        50_000,
+        None,
+        10,
+        false,
+    )
+    .unwrap_err();
+
+    match err {
+        WalletError::InsufficientFunds { needed, available } => {
+            assert!(needed > 50_000); // amount + fee
+            assert_eq!(available, 1_000);
+        }
+        other => panic!("expected InsufficientFunds, got: {other}"),
+    }
+}
+
+// ─── ZEC: mnemonic -> derive -> sign ────────────────────────────────
+
+#[test]
+fn zec_insufficient_funds_reports_amounts() {
+    let seed = test_seed();
+    let addr =
+        derive_address_from_mnemonic(TEST_MNEMONIC.into(), String::new(), Chain::Zcash, 0, 0)
+            .unwrap();
+    let utxo = ZecUtxoData {
+        txid: "b".repeat(64),
+        vout: 0,
+        amount_zatoshi: 10_000,
+        script_pubkey: vec![
+            0x76, 0xA9, 0x14, 0xAA, 0xBB, 0xCC, 0xDD, 0xEE, 0xFF, 0x00, 0x11, 0x22, 0x33, 0x44,
+            0x55, 0x66, 0x77, 0x88, 0x99, 0xAA, 0xBB, 0xCC, 0xDD, 0x88, 0xAC,
+        ],
+    };
+
+    let err = sign_zec_transaction(
+        seed,
+        0,
+        0,
+        0,
+        vec![utxo],
+        addr.address,
+        1_000_000,
+        None,
+        1,
+        2_000_000,
+        false,
+    )
+    .unwrap_err();
+
+    match err {
+        WalletError::InsufficientFunds { needed, available } => {
+            assert!(needed > 1_000_000); // amount + fee
+            assert_eq!(available, 10_000);
+        }
+        other => panic!("expected InsufficientFunds, got: {other}"),
+    }
 }
 
 // ─── SOL: mnemonic -> derive -> sign ────────────────────────────────
@@ -264,6 +346,103 @@ fn same_mnemonic_produces_different_addresses_per_chain() {
     assert_ne!(btc.address, sol.address);
 }
 
+// ─── Passphrase fingerprint ─────────────────────────────────────────
+
+#[test]
+fn passphrase_fingerprint_is_stable_and_distinguishes_passphrases() {
+    let fingerprint = |passphrase: &str| {
+        derive_address_fingerprint(
+            TEST_MNEMONIC.into(),
+            passphrase.into(),
+            Chain::Bitcoin,
+            0,
+            0,
+        )
+        .unwrap()
+    };
+
+    assert_eq!(fingerprint("correct horse"), fingerprint("correct horse"));
+    assert_ne!(fingerprint("correct horse"), fingerprint("correct hors"));
+    assert_ne!(fingerprint(""), fingerprint("correct horse"));
+}
+
+#[test]
+fn passphrase_fingerprint_does_not_identify_the_address() {
+    let addr =
+        derive_address_from_mnemonic(TEST_MNEMONIC.into(), String::new(), Chain::Ethereum, 0, 0)
+            .unwrap();
+    let fp = derive_address_fingerprint(TEST_MNEMONIC.into(), String::new(), Chain::Ethereum, 0, 0)
+        .unwrap();
+
+    // Computed from the public address alone, so it carries no key material.
+    assert_eq!(fp, address::address_fingerprint(&addr.address));
+
+    // 16 bits cannot pin down a 160-bit address: other addresses share the
+    // fingerprint, so it cannot be used to recover or derive the wallet's.
+    assert_eq!(fp.len(), 4);
+    let others: Vec<String> = (0u64..)
+        .map(|i| format!("0x{i:040x}"))
+        .filter(|other| address::address_fingerprint(other) == fp)
+        .take(3)
+        .collect();
+    assert!(others
+        .iter()
+        .all(|other| !other.eq_ignore_ascii_case(&addr.address)));
+}
+
+// ─── Wallet summary ─────────────────────────────────────────────────
+
+#[test]
+fn wallet_summary_matches_individual_derivation() {
+    let summary = derive_wallet_summary(TEST_MNEMONIC.into(), String::new(), 0).unwrap();
+
+    assert_eq!(summary.account, 0);
+    assert_eq!(summary.master_fingerprint, "73c5da0a");
+    for chain in [Chain::Bitcoin, Chain::Ethereum, Chain::Solana] {
+        let single =
+            derive_address_from_mnemonic(TEST_MNEMONIC.into(), String::new(), chain, 0, 0).unwrap();
+        let entry = summary.addresses.iter().find(|a| a.chain == chain).unwrap();
+        assert_eq!(entry.address, single.address);
+        assert_eq!(entry.derivation_path, single.derivation_path);
+    }
+}
+
+// ─── Restore gap scan ───────────────────────────────────────────────
+
+#[test]
+fn address_gap_scan_matches_single_derivation() {
+    let gap = derive_address_gap(
+        TEST_MNEMONIC.into(),
+        String::new(),
+        Chain::Ethereum,
+        0,
+        0,
+        20,
+    )
+    .unwrap();
+    let first =
+        derive_address_from_mnemonic(TEST_MNEMONIC.into(), String::new(), Chain::Ethereum, 0, 0)
+            .unwrap();
+
+    assert_eq!(gap.len(), 20);
+    assert_eq!(gap[0].address, first.address);
+    let unique: std::collections::HashSet<_> = gap.iter().map(|a| a.address.clone()).collect();
+    assert_eq!(unique.len(), 20);
+}
+
+#[test]
+fn derive_accounts_differ_only_in_account() {
+    let accounts =
+        derive_accounts(TEST_MNEMONIC.into(), String::new(), Chain::Bitcoin, 0, 3).unwrap();
+
+    assert_eq!(accounts.len(), 3);
+    let unique: std::collections::HashSet<_> = accounts.iter().map(|a| a.address.clone()).collect();
+    assert_eq!(unique.len(), 3);
+    for (account, derived) in accounts.iter().enumerate() {
+        assert_eq!(derived.derivation_path, format!("m/84'/0'/{account}'/0/0"));
+    }
+}
+
 // ─── Seed encryption roundtrip ──────────────────────────────────────
 
 #[test]
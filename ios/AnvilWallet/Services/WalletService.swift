@@ -385,6 +385,7 @@ final class WalletService: ObservableObject {
                 seed: seedBytes,
                 account: accountIdx,
                 index: 0,
+                changeIndex: 0,
                 utxos: btcReq.utxos,
                 recipientAddress: btcReq.recipientAddress,
                 amountSat: btcReq.amountSat,
@@ -392,13 +393,14 @@ final class WalletService: ObservableObject {
                 feeRateSatVbyte: btcReq.feeRateSatVbyte,
                 isTestnet: btcReq.isTestnet
             )
-            signedTx = Data(result)
+            signedTx = Data(result.rawTx)
 
         case .zec(let zecReq):
             let result = try signZecTransaction(
                 seed: seedBytes,
                 account: accountIdx,
                 index: 0,
+                changeIndex: 0,
                 utxos: zecReq.utxos,
                 recipientAddress: zecReq.recipientAddress,
                 amountZatoshi: zecReq.amountZatoshi,
@@ -407,7 +409,7 @@ final class WalletService: ObservableObject {
                 expiryHeight: zecReq.expiryHeight,
                 isTestnet: zecReq.isTestnet
             )
-            signedTx = Data(result)
+            signedTx = Data(result.rawTx)
         }
 
         return signedTx
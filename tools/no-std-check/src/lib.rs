@@ -0,0 +1,47 @@
+//! `no_std` build check for the codecs shared with constrained targets.
+//!
+//! Nothing here is meant to be called; the crate exists so CI can build
+//! `chain-sol`'s compact-u16 codec and `chain-eth`'s ABI encoder for a target
+//! without `std`.
+
+#![cfg_attr(not(test), no_std)]
+
+extern crate alloc;
+
+pub use chain_eth::abi::{encode_function_call, AbiParam};
+pub use chain_sol::compact_u16::{decode_compact_u16, encode_compact_u16};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn compact_u16_roundtrips_every_value() {
+        for value in 0..=u16::MAX {
+            let encoded: Vec<u8> = encode_compact_u16(value);
+            let (decoded, consumed) = decode_compact_u16(&encoded).unwrap();
+            assert_eq!(decoded, value);
+            assert_eq!(consumed, encoded.len());
+        }
+    }
+
+    #[test]
+    fn abi_encodes_transfer_call() {
+        let selector = [0xa9, 0x05, 0x9c, 0xbb];
+        let mut amount = [0u8; 32];
+        amount[31] = 7;
+
+        let data = encode_function_call(
+            selector,
+            &[AbiParam::Address([0x11; 20]), AbiParam::Uint256(amount)],
+        );
+
+        let mut expected = selector.to_vec();
+        expected.extend_from_slice(&[0u8; 12]);
+        expected.extend_from_slice(&[0x11; 20]);
+        expected.extend_from_slice(&amount);
+        assert_eq!(data, expected);
+        assert_eq!(data.len(), 68);
+    }
+}